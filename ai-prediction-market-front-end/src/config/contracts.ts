@@ -23,6 +23,7 @@ export const contractConfig = {
     METADATA: 'metadata',
     WHITELIST: 'wl-seed', // Contract uses 'wl-seed' not 'whitelist'
     LP_POSITION: 'lp_position',
+    MARKET_METADATA: 'market_metadata',
   } as const,
 
   // Trading constants
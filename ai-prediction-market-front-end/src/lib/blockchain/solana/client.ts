@@ -45,6 +45,7 @@ import {
   getUserInfoPDA,
   getLPPositionPDA,
   getMarketUsdcVaultPDA,
+  getMarketMetadataPDA,
 } from './pda';
 import { formatMarketAccount, calculateEarlyExitPenalty } from './utils';
 
@@ -181,6 +182,10 @@ export class SolanaAdapter implements IBlockchainAdapter {
     return getMarketUsdcVaultPDA(market, this.dynamicConfig.programId);
   }
 
+  private getMarketMetadataPDA(market: PublicKey): [PublicKey, number] {
+    return getMarketMetadataPDA(market, this.dynamicConfig.programId);
+  }
+
   // Get config account data (for team wallet, USDC mint, etc.)
   async getConfigData(): Promise<{ teamWallet: PublicKey; usdcMint: PublicKey } | null> {
     try {
@@ -609,6 +614,7 @@ export class SolanaAdapter implements IBlockchainAdapter {
 
       // Derive market PDA from YES and NO token mints
       const [market] = this.getMarketPDAFromMints(yesToken.publicKey, noTokenPubkey);
+      const [marketMetadata] = this.getMarketMetadataPDA(market);
 
       // Derive metadata PDAs
       const [yesTokenMetadataAccount] = this.getMetadataPDA(yesToken.publicKey);
@@ -640,8 +646,33 @@ export class SolanaAdapter implements IBlockchainAdapter {
         endingSlot: params.endingSlot ? new BN(params.endingSlot) : null,
         displayName: params.displayName.slice(0, 64), // Max 64 chars
         initialYesProb: params.initialYesProb, // 2000-8000 basis points
+        scalarBounds: null, // Binary market
+        parentRequiredOutcome: null, // Not a conditional market
+        priceFeed: null, // Manually resolved
+        resolver: null, // Global authority resolves
+        resolutionGraceSlots: null, // Default resolution deadline (~7 days after end)
+        rules: {
+          rulesHash: Array.from(
+            new Uint8Array(
+              await crypto.subtle.digest(
+                'SHA-256',
+                new TextEncoder().encode(params.rulesText ?? params.displayName)
+              )
+            )
+          ),
+          resolutionSourceUri: params.resolutionSourceUri ?? params.yesUri,
+          category: params.category ?? '',
+          tags: params.tags ?? [],
+        },
+        startTime: params.startTime ? new BN(params.startTime) : null,
+        endTime: params.endTime ? new BN(params.endTime) : null,
       };
 
+      const config = await this.getConfigData();
+      if (!config) {
+        return { signature: '', success: false, error: 'Failed to get config' };
+      }
+
       const createMarketSignature = await (this.program.methods as any)
         .createMarket(contractParams)
         .accounts({
@@ -651,17 +682,22 @@ export class SolanaAdapter implements IBlockchainAdapter {
           creatorWhitelist,
           yesToken: yesToken.publicKey,
           noToken: noTokenPubkey,
+          usdcMint: config.usdcMint,
           market,
+          marketMetadata,
           yesTokenMetadataAccount,
           noTokenMetadataAccount,
           globalYesTokenAccount,
           globalNoTokenAccount,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          collateralTokenProgram: TOKEN_PROGRAM_ID,
+          outcomeTokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           mplTokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID,
           teamWallet,
+          parentMarket: null,
+          resolverCommittee: null,
         })
         .signers([yesToken])
         .rpc();
@@ -811,8 +847,20 @@ export class SolanaAdapter implements IBlockchainAdapter {
         { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // recipient (None)
         { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // recipient_yes_ata (None)
         { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // recipient_no_ata (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // order_book (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // book_usdc_ata (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // book_yes_ata (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // book_no_ata (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // referrer_stats (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // referrer_usdc_vault (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // fee_tier_table (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // user_fee_tier (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // session_key (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // session_signer (None)
+        { pubkey: new PublicKey(this.dynamicConfig.programId), isSigner: false, isWritable: false }, // delegate_authority (None)
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // collateral_token_program
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // outcome_token_program
         { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       ];
 
@@ -995,8 +1043,12 @@ export class SolanaAdapter implements IBlockchainAdapter {
           userUsdcAta,
           userInfo: userInfoPDA,
           user: this.wallet.publicKey,
+          referrerStats: null,
+          sessionKey: null,
+          sessionSigner: null,
           systemProgram: SystemProgram.programId,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          collateralTokenProgram: TOKEN_PROGRAM_ID,
+          outcomeTokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
         .instruction();
@@ -1070,8 +1122,11 @@ export class SolanaAdapter implements IBlockchainAdapter {
           userUsdcAta,
           userInfo: userInfoPDA,
           user: this.wallet.publicKey,
+          sessionKey: null,
+          sessionSigner: null,
           systemProgram: SystemProgram.programId,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          collateralTokenProgram: TOKEN_PROGRAM_ID,
+          outcomeTokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
         .rpc();
@@ -1154,7 +1209,8 @@ export class SolanaAdapter implements IBlockchainAdapter {
           userUsdcAta,
           lpPosition: lpPositionPDA,
           user: this.wallet.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          collateralTokenProgram: TOKEN_PROGRAM_ID,
+          outcomeTokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
@@ -1255,7 +1311,8 @@ export class SolanaAdapter implements IBlockchainAdapter {
           userUsdcAta,
           lpPosition: lpPositionPDA,
           user: this.wallet.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          collateralTokenProgram: TOKEN_PROGRAM_ID,
+          outcomeTokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
         .instruction();
@@ -60,6 +60,13 @@ export function getLPPositionPDA(market: PublicKey, user: PublicKey, programId:
   );
 }
 
+export function getMarketMetadataPDA(market: PublicKey, programId: string): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(solanaConfig.seeds.MARKET_METADATA), market.toBytes()],
+    new PublicKey(programId)
+  );
+}
+
 export function getMarketUsdcVaultPDA(market: PublicKey, programId: string): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [Buffer.from(solanaConfig.seeds.MARKET_USDC_VAULT), market.toBytes()],
@@ -20,6 +20,12 @@ export interface CreateMarketParams {
   initialYesProb: number; // Initial probability in basis points (2000-8000 = 20-80%)
   startSlot?: number; // Optional trading start slot
   endingSlot?: number; // Optional trading end slot
+  startTime?: number; // Optional trading start (unix seconds, enforced against on-chain clock)
+  endTime?: number; // Optional trading end (unix seconds, enforced against on-chain clock)
+  rulesText?: string; // Full resolution rules (SHA-256 committed on-chain; defaults to displayName)
+  resolutionSourceUri?: string; // Resolution source URI (defaults to yesUri)
+  category?: string; // Market category (max 32 bytes)
+  tags?: string[]; // Up to 5 tags (max 24 bytes each)
 }
 
 // Blockchain adapter interface
@@ -4,7 +4,8 @@ import {
   addLiquidity,
   configProject,
   createMarket,
-  resolution,
+  proposeResolution,
+  finalizeResolution,
   setClusterConfig,
   swap,
   withdrawLiquidity,
@@ -82,7 +83,53 @@ programCommand("swap")
     await swap(new PublicKey(yesToken), new PublicKey(noToken), amount, style, tokenType);
   });
 
-programCommand("resolution")
+programCommand("propose-resolution")
+  .option("-y, --yesToken <string>", "yesToken address")
+  .option("-n, --noToken <string>", "noToken address")
+  .option("--yesRatio <number>", "yes payout ratio")
+  .option("--noRatio <number>", "no payout ratio")
+  .option("-t, --tokenType <number>", "winning token type (0: no, 1: yes)")
+  .option("-e, --evidenceUri <string>", "resolution evidence uri")
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, yesToken, noToken, yesRatio, noRatio, tokenType, evidenceUri } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+
+    await setClusterConfig(env, keypair, rpc);
+
+    if (yesToken === undefined) {
+      console.log("Error yesToken address");
+      return;
+    }
+
+    if (noToken === undefined) {
+      console.log("Error noToken address");
+      return;
+    }
+
+    if (tokenType === undefined) {
+      console.log("Error token type");
+      return;
+    }
+
+    if (evidenceUri === undefined) {
+      console.log("Error evidence uri");
+      return;
+    }
+
+  await proposeResolution(
+    new PublicKey(yesToken),
+    new PublicKey(noToken),
+    Number(yesRatio ?? 0),
+    Number(noRatio ?? 0),
+    Number(tokenType),
+    evidenceUri,
+  );
+});
+
+programCommand("finalize-resolution")
   .option("-y, --yesToken <string>", "yesToken address")
   .option("-n, --noToken <string>", "noToken address")
   .action(async (directory, cmd) => {
@@ -104,7 +151,7 @@ programCommand("resolution")
       return;
     }
 
-  await resolution(new PublicKey(yesToken), new PublicKey(noToken));
+  await finalizeResolution(new PublicKey(yesToken), new PublicKey(noToken));
 });
 
 programCommand("ensure-team-ata").action(async (directory, cmd) => {
@@ -358,6 +405,7 @@ yarn script market
 yarn script addlp -y A5LmU5ZciBaJZKT9u59tasTDNrdMpGAo4WkGhmTkoBBA -n 9jnqZhBjuAjmj6qwmGBAm7ftok437prs6WkgcfLnKzrr -a 2000000000
 yarn script withdraw -y A5LmU5ZciBaJZKT9u59tasTDNrdMpGAo4WkGhmTkoBBA -n 9jnqZhBjuAjmj6qwmGBAm7ftok437prs6WkgcfLnKzrr -a 2000000000
 yarn script swap -y A5LmU5ZciBaJZKT9u59tasTDNrdMpGAo4WkGhmTkoBBA -n 9jnqZhBjuAjmj6qwmGBAm7ftok437prs6WkgcfLnKzrr -a 2000000000 -s 0 -t 1
-yarn script resolution -y A5LmU5ZciBaJZKT9u59tasTDNrdMpGAo4WkGhmTkoBBA -n 9jnqZhBjuAjmj6qwmGBAm7ftok437prs6WkgcfLnKzrr
+yarn script propose-resolution -y A5LmU5ZciBaJZKT9u59tasTDNrdMpGAo4WkGhmTkoBBA -n 9jnqZhBjuAjmj6qwmGBAm7ftok437prs6WkgcfLnKzrr -t 1 -e https://example.com/evidence.json
+yarn script finalize-resolution -y A5LmU5ZciBaJZKT9u59tasTDNrdMpGAo4WkGhmTkoBBA -n 9jnqZhBjuAjmj6qwmGBAm7ftok437prs6WkgcfLnKzrr
 
 */
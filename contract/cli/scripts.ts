@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { BN, Program, web3 } from "@coral-xyz/anchor";
 import fs from "fs";
+import { createHash } from "crypto";
 
 import { Keypair, Connection, PublicKey, Transaction } from "@solana/web3.js";
 
@@ -12,7 +13,8 @@ import {
   createMarketTx,
   mintNoTokenTx,
   swapTx,
-  resolutionTx,
+  proposeAdminResolutionTx,
+  finalizeResolutionTx,
   addLiquidityTx,
   withdrawLiquidityTx,
 } from "../lib/scripts";
@@ -34,6 +36,7 @@ import {
   SEED_CONFIG,
   SEED_MARKET,
   SEED_USERINFO,
+  SEED_RESOLUTION_PROPOSAL,
 } from "../lib/constant";
 
 let solConnection: Connection = null;
@@ -266,25 +269,68 @@ export const swap = async (
 };
 
 
-export const resolution = async (
+export const proposeResolution = async (
   yes_token: PublicKey,
   no_token: PublicKey,
+  yes_ratio: number,
+  no_ratio: number,
+  token_type: number,
+  evidence_uri: string,
 ) => {
+  const configPda = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_CONFIG)],
+    program.programId
+  )[0];
+  const cfg = await program.account.config.fetch(configPda);
+
+  // 证据哈希取证据 URI 的 sha256，链下可按 URI 取回原文复核
+  const evidenceHash = Array.from(createHash("sha256").update(evidence_uri).digest());
+
+  const tx = await proposeAdminResolutionTx(
+    payer.publicKey,
+    yes_token,
+    no_token,
+    cfg.usdcMint as PublicKey,
+    yes_ratio,
+    no_ratio,
+    token_type,
+    evidenceHash,
+    evidence_uri,
+
+    solConnection,
+    program
+  );
+
+  await execTx(tx, solConnection, payer);
 
   const marketPda = PublicKey.findProgramAddressSync(
     [Buffer.from(SEED_MARKET), yes_token.toBytes(), no_token.toBytes()],
     program.programId
   )[0];
+  const proposalPda = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_RESOLUTION_PROPOSAL), marketPda.toBytes()],
+    program.programId
+  )[0];
+  console.log("🚀 ~ proposeResolution ~ proposalPda:", proposalPda.toBase58())
+  const proposalAccount = await program.account.resolutionProposal.fetch(proposalPda);
+  console.log("🚀 ~ proposeResolution ~ proposalAccount:", proposalAccount)
+};
 
-  console.log("🚀 ~ createMarket ~ marketPda:", marketPda.toBase58())
-  const marketAccount = await program.account.market.fetch(marketPda);
-  console.log("🚀 ~ createMarket ~ marketAccount:", marketAccount)
+export const finalizeResolution = async (
+  yes_token: PublicKey,
+  no_token: PublicKey,
+) => {
+  const configPda = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_CONFIG)],
+    program.programId
+  )[0];
+  const cfg = await program.account.config.fetch(configPda);
 
-  const tx = await resolutionTx(
-    payer.publicKey,
+  const tx = await finalizeResolutionTx(
     payer.publicKey,
     yes_token,
     no_token,
+    cfg.usdcMint as PublicKey,
 
     solConnection,
     program
@@ -292,6 +338,13 @@ export const resolution = async (
 
   await execTx(tx, solConnection, payer);
 
+  const marketPda = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_MARKET), yes_token.toBytes(), no_token.toBytes()],
+    program.programId
+  )[0];
+  console.log("🚀 ~ finalizeResolution ~ marketPda:", marketPda.toBase58())
+  const marketAccount = await program.account.market.fetch(marketPda);
+  console.log("🚀 ~ finalizeResolution ~ marketAccount:", marketAccount)
 };
 
 export const addLiquidity = async (
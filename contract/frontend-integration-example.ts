@@ -314,41 +314,33 @@ export class PredictionMarketClient {
   }
 
   /**
-   * 市场结算（仅管理员）
+   * 市场结算提案（仅管理员，挑战窗口结束后调用 finalizeResolution 最终确认）
    */
-  async resolution(params: {
+  async proposeResolution(params: {
     yesAmount: number;
     noAmount: number;
     tokenType: number;
-    isCompleted: boolean;
+    evidenceHash: number[];
+    evidenceUri: string;
     yesToken: PublicKey;
     noToken: PublicKey;
   }): Promise<string> {
     const [configPda] = await this.getConfigPDA();
-    const [globalVaultPda] = await this.getGlobalVaultPDA();
     const [marketPda] = await this.getMarketPDA(params.yesToken, params.noToken);
-    const [userInfoPda] = await this.getUserInfoPDA(
-      this.provider.wallet.publicKey,
-      marketPda
-    );
 
     const tx = await this.program.methods
-      .resolution(
+      .proposeAdminResolution(
         new BN(params.yesAmount),
         new BN(params.noAmount),
         params.tokenType,
-        params.isCompleted
+        params.evidenceHash,
+        params.evidenceUri
       )
       .accounts({
         globalConfig: configPda,
         market: marketPda,
-        globalVault: globalVaultPda,
-        yesToken: params.yesToken,
-        noToken: params.noToken,
-        userInfo: userInfoPda,
-        user: this.provider.wallet.publicKey,
         authority: this.provider.wallet.publicKey,
-        // ... 其他必要账户
+        // ... 其他必要账户（oracleConfig、proposal、bondVault 等）
       })
       .rpc();
 
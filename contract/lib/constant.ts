@@ -18,3 +18,6 @@ export const SEED_MARKET = "market";
 export const SEED_USERINFO = "userinfo";
 export const SEED_GLOBAL = "global";
 export const SEED_METADATA = "metadata";
+export const SEED_ORACLE_CONFIG = "oracle_config";
+export const SEED_RESOLUTION_PROPOSAL = "resolution_proposal";
+export const SEED_MARKET_METADATA = "market_metadata";
@@ -1,5 +1,6 @@
 // 交易创建函数
-import { Connection, PublicKey, Transaction, Keypair } from "@solana/web3.js";
+import { Connection, PublicKey, Transaction, Keypair, SystemProgram } from "@solana/web3.js";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
 import { BN, Program } from "@coral-xyz/anchor";
 import { PredictionMarket } from "../target/types/prediction_market";
 import { 
@@ -10,7 +11,10 @@ import {
   SEED_MARKET,
   SEED_USERINFO,
   SEED_GLOBAL,
-  SEED_METADATA
+  SEED_METADATA,
+  SEED_ORACLE_CONFIG,
+  SEED_RESOLUTION_PROPOSAL,
+  SEED_MARKET_METADATA
 } from "./constant";
 
 // 创建配置交易
@@ -191,12 +195,17 @@ export async function swapTx(
   return tx;
 }
 
-// 市场结算
-export async function resolutionTx(
-  user: PublicKey,
+// 管理员结算提案（两阶段结算第一步：提交结果与证据并开启挑战窗口）
+export async function proposeAdminResolutionTx(
   authority: PublicKey,
   yesToken: PublicKey,
   noToken: PublicKey,
+  usdcMint: PublicKey,
+  yesRatio: number,
+  noRatio: number,
+  tokenType: number,
+  evidenceHash: number[],
+  evidenceUri: string,
   connection: Connection,
   program: Program<PredictionMarket>
 ): Promise<Transaction> {
@@ -204,7 +213,58 @@ export async function resolutionTx(
     [Buffer.from(SEED_CONFIG)],
     program.programId
   );
-  
+
+  const [oracleConfigPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_ORACLE_CONFIG)],
+    program.programId
+  );
+
+  const [marketPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_MARKET), yesToken.toBytes(), noToken.toBytes()],
+    program.programId
+  );
+
+  const [proposalPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_RESOLUTION_PROPOSAL), marketPda.toBytes()],
+    program.programId
+  );
+
+  const bondVault = getAssociatedTokenAddressSync(usdcMint, proposalPda, true, TOKEN_PROGRAM_ID);
+
+  const tx = await program.methods
+    .proposeAdminResolution(new BN(yesRatio), new BN(noRatio), tokenType, evidenceHash, evidenceUri)
+    .accounts({
+      globalConfig: configPda,
+      oracleConfig: oracleConfigPda,
+      market: marketPda,
+      proposal: proposalPda,
+      resolutionVotes: null,
+      usdcMint: usdcMint,
+      bondVault: bondVault,
+      authority: authority,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+
+  return tx;
+}
+
+// 最终确认结算（两阶段结算第二步：挑战窗口结束后任何人可调用，保证金退还提案人）
+export async function finalizeResolutionTx(
+  caller: PublicKey,
+  yesToken: PublicKey,
+  noToken: PublicKey,
+  usdcMint: PublicKey,
+  connection: Connection,
+  program: Program<PredictionMarket>
+): Promise<Transaction> {
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_CONFIG)],
+    program.programId
+  );
+
   const [globalVaultPda] = PublicKey.findProgramAddressSync(
     [Buffer.from(SEED_GLOBAL)],
     program.programId
@@ -215,20 +275,40 @@ export async function resolutionTx(
     program.programId
   );
 
+  const [proposalPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_RESOLUTION_PROPOSAL), marketPda.toBytes()],
+    program.programId
+  );
+
+  const [marketMetadataPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_MARKET_METADATA), marketPda.toBytes()],
+    program.programId
+  );
+
+  const proposal = await program.account.resolutionProposal.fetch(proposalPda);
+  const proposer = proposal.proposer as PublicKey;
+
   const tx = await program.methods
-    .resolution(new BN(0), new BN(0), 0, true)
+    .finalizeResolution()
     .accounts({
       globalConfig: configPda,
       market: marketPda,
+      proposal: proposalPda,
+      proposer: proposer,
       globalVault: globalVaultPda,
       yesToken: yesToken,
       noToken: noToken,
-      userInfo: PublicKey.default,
-      user: user,
-      authority: authority,
-      systemProgram: PublicKey.default,
-      tokenProgram: PublicKey.default,
-      associatedTokenProgram: PublicKey.default,
+      globalYesAta: getAssociatedTokenAddressSync(yesToken, globalVaultPda, true, TOKEN_PROGRAM_ID),
+      globalNoAta: getAssociatedTokenAddressSync(noToken, globalVaultPda, true, TOKEN_PROGRAM_ID),
+      usdcMint: usdcMint,
+      bondVault: getAssociatedTokenAddressSync(usdcMint, proposalPda, true, TOKEN_PROGRAM_ID),
+      bondRecipientUsdcAta: getAssociatedTokenAddressSync(usdcMint, proposer, true, TOKEN_PROGRAM_ID),
+      eventGroup: null,
+      parentMarket: null,
+      marketMetadata: marketMetadataPda,
+      caller: caller,
+      collateralTokenProgram: TOKEN_PROGRAM_ID,
+      outcomeTokenProgram: TOKEN_PROGRAM_ID,
     })
     .transaction();
 
@@ -40,6 +40,12 @@ pub const NO_NAME: &str = "disagree";
 /// ✅ v3.0.1: 从 1 周增加到 30 天，避免过度限制长期市场
 pub const MAX_START_SLOT_DELAY: u64 = 6_480_000; // ~30 days in slots (400ms each)
 
+/// ✅ v3.14.0: 最大开始时间延迟（30天，以秒计算，用于 Unix 时间戳排期）
+pub const MAX_START_TIME_DELAY_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// ✅ v3.14.0: 每个 slot 的估算时长（毫秒），仅用于将 Unix 时间戳排期换算为结算截止 slot
+pub const ESTIMATED_SLOT_MS: i64 = 400;
+
 /// ✅ LMSR b参数最大值
 /// ✅ v1.1.0: 更新为 USDC 单位（6 位精度）
 /// 1M USDC = 1,000,000 USDC * 10^6
@@ -302,3 +308,251 @@ pub const MAX_SINGLE_TRADE_BPS: u16 = 1000;
 ///
 /// **历史**: v2.4 从1%放宽到2%，平衡安全性和用户体验
 pub const LIQUIDITY_RATIO_TOLERANCE_BPS: u16 = 200;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.3.0: 分类市场（N 结果）常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 分类市场PDA种子（[CATEGORICAL_MARKET, creator, market_id]）
+pub const CATEGORICAL_MARKET: &str = "categorical_market";
+
+/// 分类市场最少结果数量（2 个结果请使用二元 Market）
+pub const MIN_CATEGORICAL_OUTCOMES: usize = 3;
+
+/// 分类市场最多结果数量（受账户大小和单笔交易账户数限制）
+pub const MAX_CATEGORICAL_OUTCOMES: usize = 16;
+
+/// 分类市场按权重拆分结算时的 winning_outcome 标记
+pub const CATEGORICAL_SPLIT_OUTCOME: u8 = u8::MAX;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.5.0: 负风险事件组常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 事件组PDA种子（[EVENT_GROUP, group_id]）
+/// 事件组 PDA 同时作为事件组 USDC 金库 ATA 的 authority
+pub const EVENT_GROUP: &str = "event_group";
+
+/// 事件组最少成员数量（结算时校验）
+pub const MIN_EVENT_GROUP_MARKETS: usize = 2;
+
+/// 事件组最多成员数量
+/// 转换指令每个成员需要 3-5 个 remaining_accounts，8 个成员已接近单笔交易上限（需配合 ALT）
+pub const MAX_EVENT_GROUP_MARKETS: usize = 8;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.7.0: 乐观预言机常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 乐观预言机配置PDA种子
+pub const ORACLE_CONFIG: &str = "oracle_config";
+
+/// 结算提案PDA种子（[RESOLUTION_PROPOSAL, market]）
+/// 提案 PDA 同时作为保证金托管 ATA 的 authority
+pub const RESOLUTION_PROPOSAL: &str = "resolution_proposal";
+
+/// 挑战窗口下限：10 分钟
+pub const MIN_ORACLE_LIVENESS_SECS: i64 = 600;
+
+/// 挑战窗口上限：7 天
+pub const MAX_ORACLE_LIVENESS_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// ✅ v3.12.0: 管理员两阶段结算挑战窗口下限：5 分钟（上限同 MAX_ORACLE_LIVENESS_SECS）
+pub const MIN_ADMIN_CHALLENGE_SECS: i64 = 300;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.8.0: 价格源自动结算常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 价格最大允许延迟上限（秒）
+pub const MAX_PRICE_FEED_STALENESS_SECS: u32 = 3600;
+
+/// 价格置信区间上限（基点，10% = 1000）
+pub const MAX_PRICE_FEED_CONF_BPS: u16 = 1000;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.9.0: 市场级结算人 / 结算委员会常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 结算委员会PDA种子（[RESOLVER_COMMITTEE, creator, committee_id]）
+pub const RESOLVER_COMMITTEE: &str = "resolver_committee";
+
+/// 委员会投票记录PDA种子（[RESOLUTION_VOTES, market]）
+pub const RESOLUTION_VOTES: &str = "resolution_votes";
+
+/// 结算委员会最多成员数量（投票掩码使用 u16）
+pub const MAX_COMMITTEE_MEMBERS: usize = 10;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.11.0: 结算截止（活性保证）常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 结算宽限期默认值（ending_slot 之后，约 7 天 @ 400ms/slot）
+pub const DEFAULT_RESOLUTION_GRACE_SLOTS: u64 = 1_512_000;
+
+/// 结算宽限期下限（约 1 天）
+pub const MIN_RESOLUTION_GRACE_SLOTS: u64 = 216_000;
+
+/// 结算宽限期上限（约 90 天）
+pub const MAX_RESOLUTION_GRACE_SLOTS: u64 = 19_440_000;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.13.0: 规则承诺与结算证据常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 市场元数据PDA种子（[MARKET_METADATA, market]）
+pub const MARKET_METADATA: &str = "market_metadata";
+
+/// 结算来源 / 证据 URI 最大长度（字节）
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// 分类最大长度（字节）
+pub const MAX_CATEGORY_LEN: usize = 32;
+
+/// 标签最多数量
+pub const MAX_MARKET_TAGS: usize = 5;
+
+/// 单个标签最大长度（字节）
+pub const MAX_TAG_LEN: usize = 24;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.17.0: 限价订单簿常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 订单簿PDA种子（[ORDER_BOOK, market]）
+pub const ORDER_BOOK: &str = "order_book";
+
+/// 每个订单簿的挂单槽位数量
+pub const MAX_BOOK_ORDERS: usize = 32;
+
+/// ✅ v3.17.1: 最小挂单名义金额（5 USDC，按限价计算，买卖单均适用）
+/// 代币数量下限无法阻止低价粉尘挂单（1 个代币 @ 1bp ≈ 0.0001 USDC）占满槽位
+pub const MIN_LIMIT_ORDER_NOTIONAL: u64 = 5_000_000;
+
+/// ✅ v3.17.1: 单个挂单人在同一订单簿中最多占用的槽位数量（含待领取的已成交挂单）
+pub const MAX_ORDERS_PER_OWNER: usize = 4;
+
+/// 单次 swap 最多吃掉的挂单数量（计算预算限制）
+pub const MAX_BOOK_FILLS_PER_SWAP: usize = 8;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.18.0: 多市场组合交易常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 单笔组合交易最多交易腿数量
+/// 每条腿需要 7 个 remaining_accounts，10 条腿需配合 ALT 且接近计算预算上限
+pub const MAX_BUNDLE_LEGS: usize = 10;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.20.0: TWAP 价格累加器常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 每个市场的价格观测环形缓冲区容量
+pub const TWAP_OBSERVATION_COUNT: usize = 24;
+
+/// 相邻观测的最小间隔（秒）
+/// 24 个观测 × 300 秒 ≈ 2 小时可查询窗口
+pub const TWAP_MIN_OBSERVATION_INTERVAL_SECS: i64 = 300;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.21.0: 条件订单（止损 / 止盈）常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 条件订单PDA种子（[CONDITIONAL_ORDER, market, owner, order_id]）
+pub const CONDITIONAL_ORDER: &str = "conditional_order";
+
+/// Keeper 执行费上限（基点，按卖出所得计算）
+/// 1% 足以覆盖 keeper 的交易费与优先费，同时限制对用户所得的侵蚀
+pub const MAX_KEEPER_FEE_BPS: u16 = 100;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.22.0: 引荐分成常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 引荐人统计PDA种子（[REFERRER, referrer]）
+/// 引荐分成托管在该 PDA 的 USDC ATA 中，由 claim_referral_fees 领取
+pub const REFERRER: &str = "referrer";
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.23.0: 成交量分级费率与做市返佣常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 全局费率分级表PDA种子（[FEE_TIER_TABLE]）
+pub const FEE_TIER_TABLE: &str = "fee_tier_table";
+
+/// 用户费率分级账户PDA种子（[FEE_TIER, user]）
+pub const FEE_TIER: &str = "fee_tier";
+
+/// 费率分级表最多档位数
+pub const MAX_FEE_TIERS: usize = 8;
+
+/// 滚动成交量窗口（天），按日分桶
+pub const FEE_TIER_WINDOW_DAYS: usize = 30;
+
+/// 每日秒数（成交量分桶粒度）
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// 做市返佣上限（基点，即 LP 费率最低为 -0.5%）
+/// 返佣由该笔交易的团队份额出资，实际返佣同时受团队份额截断
+pub const MAX_MAKER_REBATE_BPS: u16 = 50;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.24.0: 动态 LP 费率常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 动态 LP 费率上限（基点，市场级 max_bps 不得超过此值）
+pub const MAX_DYNAMIC_LP_FEE_BPS: u16 = 1_000;
+
+/// 不平衡度满刻度：get_imbalance_ratio() - 100 达到该值时不平衡溢价满额
+/// 300 对应池子储备 4:1
+pub const DYNAMIC_FEE_IMBALANCE_FULL_SCALE: u128 = 300;
+
+/// 波动率 EWMA 平滑系数（基点，新样本权重 20%）
+pub const DYNAMIC_FEE_EWMA_ALPHA_BPS: u64 = 2_000;
+
+/// 波动率 EWMA 半衰期（秒）：无成交时每经过一个半衰期波动率减半
+pub const DYNAMIC_FEE_VOLATILITY_HALF_LIFE_SECS: i64 = 3_600;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.25.0: 签名交易意图常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 用户意图序号PDA种子（[INTENT_NONCE, user]）
+/// 该 PDA 同时作为用户 ATA 的唯一 delegate，在 execute_intent 与会话模式（✅ v3.26.1）中代用户签名转账
+pub const INTENT_NONCE: &str = "intent_nonce";
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.26.0: 会话密钥常量
+// ═══════════════════════════════════════════════════════════════
+
+/// 会话密钥PDA种子（[SESSION_KEY, owner, session_signer]）
+/// ✅ v3.26.1: 仅记录会话作用域与支出上限，不再作为 ATA 的 delegate（由 INTENT_NONCE PDA 代签）
+pub const SESSION_KEY: &str = "session_key";
+
+/// 会话密钥可限定的最大市场数量（0 个表示不限市场）
+pub const MAX_SESSION_MARKETS: usize = 8;
+
+/// 会话密钥最长有效期（秒）
+pub const MAX_SESSION_KEY_DURATION_SECS: i64 = 7 * SECONDS_PER_DAY;
+
+/// 会话允许的操作（位掩码）：交易
+pub const SESSION_ACTION_SWAP: u8 = 1 << 0;
+
+/// 会话允许的操作（位掩码）：铸造完整集合
+pub const SESSION_ACTION_MINT: u8 = 1 << 1;
+
+/// 会话允许的操作（位掩码）：赎回完整集合
+pub const SESSION_ACTION_REDEEM: u8 = 1 << 2;
+
+/// 全部可授权操作（提现类操作不可授权给会话密钥）
+pub const SESSION_ACTIONS_ALL: u8 = SESSION_ACTION_SWAP | SESSION_ACTION_MINT | SESSION_ACTION_REDEEM;
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.27.0: Token-2022 兼容常量
+// ═══════════════════════════════════════════════════════════════
+
+/// Token-2022 结果代币元数据：symbol 最大长度（与 Metaplex 限制一致）
+pub const MAX_OUTCOME_SYMBOL_LEN: usize = 10;
+
+/// Token-2022 结果代币元数据：URI 最大长度（与 Metaplex 限制一致）
+pub const MAX_OUTCOME_URI_LEN: usize = 200;
@@ -457,4 +457,436 @@ pub enum PredictionMarketError {
     /// 市场级暂停
     #[msg("Market is paused")]
     MarketPaused,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.3.0: 分类市场相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 结果数量无效
+    /// 分类市场结果数量必须在 3-16 之间（v3.3.0）
+    #[msg("Invalid outcome count for categorical market")]
+    InvalidOutcomeCount,
+
+    /// 结果索引越界（v3.3.0）
+    #[msg("Outcome index out of range")]
+    InvalidOutcomeIndex,
+
+    /// 赔付向量无效
+    /// 长度必须等于结果数量，且总和为 10000 bps（v3.3.0）
+    #[msg("Invalid payout vector: must cover every outcome and sum to 10000 bps")]
+    InvalidPayoutVector,
+
+    /// 结果代币账户与市场记录不匹配
+    /// remaining_accounts 中的 mint/ATA 顺序或归属错误（v3.3.0）
+    #[msg("Outcome token account does not match market outcome mints")]
+    OutcomeAccountMismatch,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.4.0: 标量市场相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 标量区间无效
+    /// 下界必须严格小于上界（v3.4.0）
+    #[msg("Invalid scalar bounds: lower must be less than upper")]
+    InvalidScalarBounds,
+
+    /// 非标量市场
+    /// 标量结算提案只能用于创建时设置了区间的市场（v3.4.0）
+    #[msg("Market is not a scalar market")]
+    NotScalarMarket,
+
+    /// 标量市场必须按数值结算（v3.4.0；✅ v3.12.1: propose_admin_scalar_resolution）
+    #[msg("Scalar market must be resolved with a numeric value")]
+    ScalarMarketRequiresValue,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.5.0: 负风险事件组相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 转换掩码无效
+    /// 至少选择一个成员，且不得引用不存在的成员（v3.5.0）
+    #[msg("Invalid neg-risk convert mask")]
+    InvalidConvertMask,
+
+    /// 事件组成员已满（v3.5.0）
+    #[msg("Event group is full")]
+    EventGroupFull,
+
+    /// 市场已属于某个事件组，或不满足入组条件（已结算/标量/条件市场）（v3.5.0, v3.6.2）
+    #[msg("Market cannot be added to an event group")]
+    MarketNotGroupable,
+
+    /// 事件组已发生转换，成员列表已冻结（v3.5.0）
+    #[msg("Event group members are frozen after the first conversion")]
+    EventGroupLocked,
+
+    /// 事件组尚未结算（v3.5.0）
+    #[msg("Event group is not resolved yet")]
+    EventGroupNotResolved,
+
+    /// 事件组已结算（v3.5.0）
+    #[msg("Event group is already resolved")]
+    EventGroupAlreadyResolved,
+
+    /// 市场与事件组不匹配
+    /// 传入的事件组不是该市场所属的事件组，或成员账户顺序错误（v3.5.0）
+    #[msg("Market does not belong to this event group")]
+    EventGroupMismatch,
+
+    /// 事件组成员的结算结果必须与事件组胜者一致：胜者 YES，其余 NO（v3.5.0）
+    #[msg("Resolution conflicts with event group outcome")]
+    EventGroupOutcomeMismatch,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.6.0: 条件市场相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 父市场无效
+    /// 父市场必须为未结算的二元市场，且 parent_required_outcome 只能为 0 或 1（v3.6.0）
+    #[msg("Invalid parent market or required parent outcome")]
+    InvalidParentMarket,
+
+    /// 传入的父市场与条件市场记录的不一致（v3.6.0）
+    #[msg("Parent market does not match")]
+    ParentMarketMismatch,
+
+    /// 该市场不是条件市场（v3.6.0）
+    #[msg("Market is not a conditional market")]
+    NotConditionalMarket,
+
+    /// 父市场尚未结算（v3.6.0）
+    #[msg("Parent market is not resolved yet")]
+    ParentMarketNotResolved,
+
+    /// 父市场结果满足条件，条件市场不可作废（v3.6.0）
+    #[msg("Parent condition is met; market cannot be voided")]
+    ParentConditionMet,
+
+    /// 父市场结果不满足条件，条件市场只能作废（v3.6.0）
+    #[msg("Parent condition is not met; market must be voided")]
+    ParentConditionNotMet,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.7.0: 乐观预言机相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 挑战窗口超出允许范围（10 分钟 - 7 天）（v3.7.0）
+    #[msg("Invalid oracle liveness window")]
+    InvalidLiveness,
+
+    /// 保证金低于最低要求（v3.7.0）
+    #[msg("Bond is below the required minimum")]
+    BondTooLow,
+
+    /// 乐观预言机未启用（v3.7.0）
+    #[msg("Optimistic oracle is disabled")]
+    OracleDisabled,
+
+    /// 当前提案状态不允许该操作（v3.7.0）
+    #[msg("Invalid resolution proposal status")]
+    InvalidProposalStatus,
+
+    /// 挑战窗口已结束，不能再发起争议（v3.7.0）
+    #[msg("Liveness window has expired")]
+    LivenessExpired,
+
+    /// 挑战窗口尚未结束，不能最终确认（v3.7.0）
+    #[msg("Liveness window has not expired yet")]
+    LivenessNotExpired,
+
+    /// 提案人不能挑战自己的提案（v3.7.0）
+    #[msg("Proposer cannot dispute their own proposal")]
+    SelfDispute,
+
+    /// 保证金接收账户不属于胜方（v3.7.0）
+    #[msg("Bond recipient does not match the winning party")]
+    InvalidBondRecipient,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.8.0: 价格源自动结算相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 价格源配置无效（阈值、结算时间、时效或置信区间超出范围）（v3.8.0）
+    #[msg("Invalid price feed configuration")]
+    InvalidPriceFeedConfig,
+
+    /// 该市场未配置价格源（v3.8.0）
+    #[msg("Market has no price feed")]
+    NotPriceFeedMarket,
+
+    /// 价格源账户无效（owner、discriminator、布局或验证级别不符）（v3.8.0）
+    #[msg("Invalid price feed account")]
+    InvalidPriceFeed,
+
+    /// 价格已过期（v3.8.0）
+    #[msg("Price feed is stale")]
+    PriceFeedStale,
+
+    /// 价格置信区间过宽（v3.8.0）
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+
+    /// 价格发布时间早于市场约定的结算时间（v3.8.0）
+    #[msg("Price was published before the resolution time")]
+    PriceFeedTooEarly,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.8.1: 价格源结算窗口相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 价格发布时间晚于结算窗口（resolve_after + max_staleness_secs）（v3.8.1）
+    #[msg("Price was published outside the resolution window")]
+    PriceFeedOutsideWindow,
+
+    /// 市场由指定结算人或价格源结算，不接受公开提案（v3.8.1, v3.9.0）
+    #[msg("Market resolves through its designated resolver or price feed")]
+    MarketHasDesignatedResolver,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.9.0: 市场级结算人 / 结算委员会相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 委员会配置无效：需满足 1 ≤ M ≤ N ≤ 10，成员不重复（v3.9.0）
+    #[msg("Invalid resolver committee")]
+    InvalidCommittee,
+
+    /// 调用者不是该市场指定的结算人，或结算人类型不符（v3.9.0）
+    #[msg("Signer is not the market resolver")]
+    InvalidResolver,
+
+    /// 投票者不是委员会成员（v3.9.0）
+    #[msg("Signer is not a committee member")]
+    NotCommitteeMember,
+
+    /// 该成员已投票（v3.9.0）
+    #[msg("Committee member has already voted")]
+    AlreadyVoted,
+
+    /// 委员会投票冲突，已升级至全局管理员（v3.9.0）
+    #[msg("Resolution escalated to the global authority")]
+    ResolutionEscalated,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.5.1: 事件组作废相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 事件组已作废，不可再转换、加入成员或按胜者结算（v3.5.1）
+    #[msg("Event group is voided")]
+    EventGroupVoided,
+
+    /// 事件组成员只有在事件组作废后才能按 Invalid 结算（v3.5.1）
+    #[msg("Event group is not voided")]
+    EventGroupNotVoided,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.11.0: 结算截止（活性保证）相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 结算宽限期超出允许范围（v3.11.0）
+    #[msg("Resolution grace period out of range")]
+    InvalidResolutionDeadline,
+
+    /// 市场未设置结算截止（无 ending_slot）（v3.11.0）
+    #[msg("Market has no resolution deadline")]
+    NoResolutionDeadline,
+
+    /// 尚未到达结算截止（v3.11.0）
+    #[msg("Resolution deadline not reached")]
+    ResolutionDeadlineNotReached,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.12.0: 管理员两阶段结算相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 管理员提案不接受公开挑战（v3.12.0）
+    #[msg("Admin resolution proposals cannot be disputed")]
+    AdminProposalNotDisputable,
+
+    /// 仅管理员提案可被修改或撤回（v3.12.0）
+    #[msg("Only admin resolution proposals can be amended or retracted")]
+    NotAdminProposal,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.13.0: 规则承诺与结算证据相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 市场规则参数无效（规则哈希为空、URI/分类/标签超长）（v3.13.0）
+    #[msg("Invalid market rules")]
+    InvalidMarketRules,
+
+    /// 结算证据无效（证据哈希为空或 URI 为空/超长）（v3.13.0）
+    #[msg("Invalid resolution evidence")]
+    InvalidResolutionEvidence,
+
+    /// 市场元数据账户缺失或与市场不匹配（v3.13.0）
+    #[msg("Market metadata mismatch")]
+    MarketMetadataMismatch,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.17.0: 限价订单簿相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 挂单价格无效（必须在 1-9999 基点之间）（v3.17.0）
+    #[msg("Invalid limit order price")]
+    InvalidOrderPrice,
+
+    /// 订单簿槽位已满（v3.17.0）
+    #[msg("Order book is full")]
+    OrderBookFull,
+
+    /// 挂单不存在（v3.17.0）
+    #[msg("Limit order not found")]
+    OrderNotFound,
+
+    /// 买卖单价格不交叉或不可撮合（v3.17.0）
+    #[msg("Orders do not cross")]
+    OrdersDoNotCross,
+
+    /// 订单簿托管账户无效（v3.17.0）
+    #[msg("Invalid order book escrow account")]
+    InvalidOrderBookEscrow,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.17.1: 订单簿粉尘挂单限制相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 挂单名义金额低于 MIN_LIMIT_ORDER_NOTIONAL（v3.17.1）
+    #[msg("Limit order notional is below the minimum")]
+    LimitOrderTooSmall,
+
+    /// 挂单人占用的槽位已达 MAX_ORDERS_PER_OWNER（v3.17.1）
+    #[msg("Too many open orders for this owner")]
+    TooManyOpenOrders,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.17.2: 订单簿挤出相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 被挤出挂单人的退款账户缺失或不是其规范 ATA（v3.17.2）
+    #[msg("Evicted order refund account is missing or not the owner's ATA")]
+    InvalidEvictionAccount,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.18.0: 多市场组合交易相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 交易腿数量无效（必须在 1 - MAX_BUNDLE_LEGS 之间）（v3.18.0）
+    #[msg("Invalid bundle leg count")]
+    InvalidBundleLegCount,
+
+    /// 交易腿的 remaining_accounts 数量、顺序或归属错误（v3.18.0）
+    #[msg("Bundle leg accounts mismatch")]
+    BundleAccountMismatch,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.20.0: TWAP 相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// TWAP 查询窗口无效（必须大于 0）（v3.20.0）
+    #[msg("Invalid TWAP window")]
+    InvalidTwapWindow,
+
+    /// 观测历史不足以覆盖查询窗口（v3.20.0）
+    #[msg("Insufficient TWAP observation history")]
+    InsufficientTwapHistory,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.21.0: 条件订单相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 条件订单参数无效（触发方向、触发价或 keeper 费率越界）（v3.21.0）
+    #[msg("Invalid conditional order parameters")]
+    InvalidConditionalOrder,
+
+    /// 当前价格尚未穿越触发价（v3.21.0）
+    #[msg("Conditional order trigger not reached")]
+    TriggerNotReached,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.22.0: 引荐分成相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 引荐人账户与托管账户不匹配或未成对提供（v3.22.0）
+    #[msg("Invalid referrer accounts")]
+    InvalidReferrer,
+
+    /// 不能引荐自己（v3.22.0）
+    #[msg("Self-referral is not allowed")]
+    SelfReferral,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.23.0: 分级费率相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 费率分级表参数无效（档位未按成交量递增、费率越界或返佣超过上限）（v3.23.0）
+    #[msg("Invalid fee tier table")]
+    InvalidFeeTierTable,
+
+    /// 费率分级账户不属于交易用户或未与分级表成对提供（v3.23.0）
+    #[msg("Invalid fee tier account")]
+    InvalidFeeTierAccount,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.24.0: 动态 LP 费率相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 动态费率参数无效（下限高于上限或超过 MAX_DYNAMIC_LP_FEE_BPS）（v3.24.0）
+    #[msg("Invalid dynamic fee configuration")]
+    InvalidDynamicFeeConfig,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.25.0: 签名交易意图相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 意图参数无效（市场不匹配、方向/代币类型越界或数量为 0）（v3.25.0）
+    #[msg("Invalid swap intent")]
+    InvalidIntent,
+
+    /// 缺少 Ed25519 预编译指令，或签名者/消息与意图不一致（v3.25.0）
+    #[msg("Invalid intent signature")]
+    InvalidIntentSignature,
+
+    /// 意图序号不等于下一个可执行序号（已执行或跳号）（v3.25.0）
+    #[msg("Invalid intent nonce")]
+    InvalidIntentNonce,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.26.0: 会话密钥相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 会话密钥参数无效，或会话账户与签名者不匹配 / 未签名（v3.26.0）
+    #[msg("Invalid session key")]
+    InvalidSessionKey,
+
+    /// 会话密钥已过期（v3.26.0）
+    #[msg("Session key expired")]
+    SessionKeyExpired,
+
+    /// 会话密钥未授权该操作或该市场（v3.26.0）
+    #[msg("Session key not allowed for this action or market")]
+    SessionKeyNotAllowed,
+
+    /// 会话累计支出超过 USDC 上限（v3.26.0）
+    #[msg("Session key spend cap exceeded")]
+    SessionSpendCapExceeded,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.27.0: Token-2022 兼容相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// mint 含不支持的 Token-2022 扩展（转账钩子、永久委托等）（v3.27.0）
+    #[msg("Unsupported Token-2022 mint extension")]
+    UnsupportedMintExtension,
+
+    /// 代币账户不属于预期的代币程序（v3.27.0；v3.27.1 起抵押品与 YES/NO 可分属不同程序）
+    #[msg("Token account owned by an unexpected token program")]
+    TokenProgramMismatch,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.20.1: 旧市场账户扩容相关错误
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 账户不是本程序的市场 PDA（v3.20.1）
+    #[msg("Invalid market account")]
+    InvalidMarketAccount,
 }
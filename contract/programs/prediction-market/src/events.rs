@@ -11,7 +11,7 @@
 //! - 字段精简：移除可从其他来源获取的冗余数据
 //!
 //! **使用方法**：
-//! ```rust
+//! ```ignore
 //! // 关键事件：始终发射
 //! emit!(SwapExecuted { ... });
 //!
@@ -80,6 +80,12 @@ pub struct CreateEvent {
     
     /// 结束槽位
     pub ending_slot: u64,
+
+    /// ✅ v3.14.0: 开始时间（Unix 时间戳，0 = 未设置）
+    pub start_time: i64,
+
+    /// ✅ v3.14.0: 结束时间（Unix 时间戳，0 = 未设置）
+    pub end_time: i64,
 }
 
 /// 提取事件
@@ -294,7 +300,7 @@ pub struct ResolutionEvent {
     /// 市场账户
     pub market: Pubkey,
 
-    /// 获胜方代币类型 (0=NO, 1=YES, 2=平局)
+    /// 获胜方代币类型 (0=NO, 1=YES, 2=平局, 3=无效)
     pub winner_token_type: u8,
 
     /// YES方比例
@@ -305,6 +311,12 @@ pub struct ResolutionEvent {
 
     /// 时间戳
     pub timestamp: i64,
+
+    /// ✅ v3.13.0: 结算证据哈希（✅ v3.13.1: 所有路径均非全 0，自动结算路径由链上数据派生）
+    pub evidence_hash: [u8; 32],
+
+    /// ✅ v3.13.0: 结算证据 URI（✅ v3.13.1: 自动结算路径描述派生来源）
+    pub evidence_uri: String,
 }
 
 /// 用户领取奖励事件
@@ -719,6 +731,7 @@ pub trait IntoEvent<T: anchor_lang::Event> {
     ///
     /// # 返回
     /// * `T` - 转换后的事件
+    #[allow(clippy::wrong_self_convention)]
     fn into_event(&self) -> T;
 }
 
@@ -762,6 +775,12 @@ pub struct SwapExecuted {
 
     /// 时间戳
     pub timestamp: i64,
+
+    /// ✅ v3.19.0: 成交路由（0=直接, 1=铸造后卖出, 2=买入后赎回，见 `types::SwapRoute`）
+    pub route: u8,
+
+    /// ✅ v3.24.0: 本次适用的 LP 费率（基点，含动态溢价）
+    pub lp_fee_bps: u16,
 }
 
 /// ✅ v3.0.1: 调试模式详细交易事件
@@ -830,7 +849,7 @@ pub struct LiquidityChanged {
 /// 根据编译特性选择发射哪个事件
 ///
 /// **用法**:
-/// ```rust
+/// ```ignore
 /// emit_optimized!(
 ///     SwapExecuted { ... },           // 生产版本
 ///     SwapDebugInfo { ... }           // 调试版本（需 feature="event-debug"）
@@ -964,3 +983,1181 @@ pub struct InternalSwapEvent {
     /// 时间戳
     pub timestamp: i64,
 }
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.3.0: 分类市场（N 结果）事件
+// ═══════════════════════════════════════════════════════════════
+
+/// 分类市场创建事件
+#[event]
+pub struct CategoricalMarketCreatedEvent {
+    /// 创建者
+    pub creator: Pubkey,
+
+    /// 分类市场账户
+    pub market: Pubkey,
+
+    /// 结果数量（3-16）
+    pub outcome_count: u8,
+
+    /// 各结果代币 mint（按结果索引排列）
+    pub outcome_mints: Vec<Pubkey>,
+
+    /// LMSR 流动性参数
+    pub lmsr_b: u64,
+
+    /// 创建者注入的做市补贴（b × ln(N)，LMSR 最大亏损）
+    pub subsidy: u64,
+
+    pub start_slot: u64,
+    pub ending_slot: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 分类市场交易事件
+#[event]
+pub struct CategoricalTradeEvent {
+    /// 交易用户
+    pub user: Pubkey,
+
+    /// 分类市场账户
+    pub market: Pubkey,
+
+    /// 交易的结果索引
+    pub outcome_index: u8,
+
+    /// true=买入, false=卖出
+    pub is_buy: bool,
+
+    /// USDC 数量（买=支付含费，卖=收到税后）
+    pub usdc_amount: u64,
+
+    /// 代币数量（买=收到，卖=支付）
+    pub token_amount: u64,
+
+    /// 总手续费（USDC）
+    pub fee_usdc: u64,
+
+    /// 交易后各结果价格（基点，10000=100%）
+    pub prices_bps: Vec<u16>,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 分类市场完整集合铸造/赎回事件
+#[event]
+pub struct CategoricalCompleteSetEvent {
+    /// 用户
+    pub user: Pubkey,
+
+    /// 分类市场账户
+    pub market: Pubkey,
+
+    /// true=铸造, false=赎回
+    pub is_mint: bool,
+
+    /// 集合数量（= USDC 数量 = 每个结果代币数量）
+    pub amount: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 分类市场结算事件
+#[event]
+pub struct CategoricalResolutionEvent {
+    /// 管理员
+    pub authority: Pubkey,
+
+    /// 分类市场账户
+    pub market: Pubkey,
+
+    /// 获胜结果索引（CATEGORICAL_SPLIT_OUTCOME = 按权重拆分）
+    pub winning_outcome: u8,
+
+    /// 各结果赔付比例（基点，总和 10000）
+    pub payout_bps: Vec<u16>,
+
+    /// 结算时的总赔付负债（USDC）
+    pub total_liability: u64,
+
+    /// 退还给创建者的剩余补贴（USDC）
+    pub creator_refund: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 分类市场领取奖励事件
+#[event]
+pub struct CategoricalClaimEvent {
+    /// 用户
+    pub user: Pubkey,
+
+    /// 分类市场账户
+    pub market: Pubkey,
+
+    /// 结果索引
+    pub outcome_index: u8,
+
+    /// 销毁的结果代币数量
+    pub tokens_burned: u64,
+
+    /// 用户收到的 USDC
+    pub usdc_payout: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.4.0: 标量市场事件
+// ═══════════════════════════════════════════════════════════════
+
+/// 标量市场结算事件（与 ResolutionEvent 同时发射）
+#[event]
+pub struct ScalarResolutionEvent {
+    /// 管理员
+    pub authority: Pubkey,
+
+    /// 市场账户
+    pub market: Pubkey,
+
+    /// 报告的原始结算值（未截断）
+    pub resolved_value: i64,
+
+    /// 区间下界
+    pub lower_bound: i64,
+
+    /// 区间上界
+    pub upper_bound: i64,
+
+    /// LONG(YES) 赔付比例（基点）
+    pub long_ratio: u64,
+
+    /// SHORT(NO) 赔付比例（基点）
+    pub short_ratio: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.5.0: 负风险事件组事件
+// ═══════════════════════════════════════════════════════════════
+
+/// 事件组创建事件
+#[event]
+pub struct EventGroupCreatedEvent {
+    /// 管理员
+    pub authority: Pubkey,
+
+    /// 事件组账户
+    pub event_group: Pubkey,
+
+    /// 事件组编号
+    pub group_id: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 事件组新增成员事件
+#[event]
+pub struct EventGroupMarketAddedEvent {
+    /// 事件组账户
+    pub event_group: Pubkey,
+
+    /// 成员市场
+    pub market: Pubkey,
+
+    /// 成员索引
+    pub index: u8,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 负风险转换事件
+#[event]
+pub struct NegRiskConvertEvent {
+    /// 用户
+    pub user: Pubkey,
+
+    /// 事件组账户
+    pub event_group: Pubkey,
+
+    /// 被销毁 NO 的成员掩码（第 i 位 = 成员 i）
+    pub no_mask: u16,
+
+    /// 每个成员的转换数量
+    pub amount: u64,
+
+    /// 用户获得的 USDC（(k - 1) × amount）
+    pub usdc_out: u64,
+
+    /// 用户获得的 YES 成员数量（N - k）
+    pub yes_markets_minted: u8,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 事件组结算事件
+#[event]
+pub struct EventGroupResolvedEvent {
+    /// 管理员
+    pub authority: Pubkey,
+
+    /// 事件组账户
+    pub event_group: Pubkey,
+
+    /// 胜出成员索引
+    pub winning_index: u8,
+
+    /// 胜出市场
+    pub winning_market: Pubkey,
+
+    /// 注入胜出市场的担保抵押品
+    pub collateral_transferred: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// ✅ v3.5.1: 事件组作废事件
+#[event]
+pub struct EventGroupVoidedEvent {
+    /// 调用者
+    pub caller: Pubkey,
+
+    /// 事件组账户
+    pub event_group: Pubkey,
+
+    /// 累计转换量（成员 Invalid 赔付缺口基数）
+    pub total_converted: u64,
+
+    /// 各成员归一化 YES 退款价格（基点）
+    pub void_yes_price_bps: Vec<u16>,
+
+    /// 注入各成员市场的担保抵押品合计
+    pub collateral_distributed: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.6.0: 条件市场
+// ═══════════════════════════════════════════════════════════════
+
+/// 条件市场作废事件
+///
+/// 父市场未按要求结算时由任何人触发，市场按 50/50 平局结算以退还完整集合抵押品
+#[event]
+pub struct ConditionalMarketVoidedEvent {
+    /// 触发者
+    pub caller: Pubkey,
+
+    /// 条件市场
+    pub market: Pubkey,
+
+    /// 父市场
+    pub parent_market: Pubkey,
+
+    /// 要求的父市场结果（0=NO, 1=YES）
+    pub required_outcome: u8,
+
+    /// 父市场实际结果（0=NO, 1=YES, 2=平局）
+    pub parent_winner_token_type: u8,
+
+    /// 父市场是否已作废
+    pub parent_voided: bool,
+
+    /// 作废时锁定的完整集合抵押品
+    pub collateral_locked: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.7.0: 乐观预言机
+// ═══════════════════════════════════════════════════════════════
+
+/// 结算提案事件
+#[event]
+pub struct ResolutionProposedEvent {
+    /// 提案人
+    pub proposer: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 提案结果（0=NO, 1=YES, 2=平局）
+    pub token_type: u8,
+
+    /// 提案 YES/NO 赎回比例（基点）
+    pub yes_ratio: u64,
+    pub no_ratio: u64,
+
+    /// 保证金
+    pub bond: u64,
+
+    /// 挑战窗口结束时间
+    pub liveness_ends_at: i64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 结算争议事件（升级至管理员裁决）
+#[event]
+pub struct ResolutionDisputedEvent {
+    /// 挑战人
+    pub disputer: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 提案人
+    pub proposer: Pubkey,
+
+    /// 挑战保证金
+    pub dispute_bond: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 结算提案最终确认事件
+#[event]
+pub struct ResolutionFinalizedEvent {
+    /// 调用者（无争议时为任何人，有争议时为管理员）
+    pub caller: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 最终结果（0=NO, 1=YES, 2=平局）
+    pub token_type: u8,
+
+    /// 最终 YES/NO 赎回比例（基点）
+    pub yes_ratio: u64,
+    pub no_ratio: u64,
+
+    /// 是否经过争议裁决
+    pub disputed: bool,
+
+    /// 保证金接收方（提案人或挑战人）
+    pub bond_recipient: Pubkey,
+
+    /// 支付给接收方的保证金总额（含罚没的败方保证金）
+    pub bond_paid: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.8.0: 价格源自动结算
+// ═══════════════════════════════════════════════════════════════
+
+/// 价格源结算事件
+#[event]
+pub struct PriceFeedResolutionEvent {
+    /// 调用者（任何人）
+    pub caller: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 价格源账户
+    pub price_feed: Pubkey,
+
+    /// 读取的价格 / 置信区间 / 精度指数 / 发布时间
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+
+    /// 阈值（threshold × 10^threshold_expo）
+    pub threshold: i64,
+    pub threshold_expo: i32,
+
+    /// 结算结果（1=YES 高于阈值, 0=NO）
+    pub winner_token_type: u8,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.9.0: 市场级结算人 / 结算委员会
+// ═══════════════════════════════════════════════════════════════
+
+/// 结算委员会创建事件
+#[event]
+pub struct ResolverCommitteeCreatedEvent {
+    /// 创建者
+    pub creator: Pubkey,
+
+    /// 委员会账户
+    pub committee: Pubkey,
+
+    /// 成员
+    pub members: Vec<Pubkey>,
+
+    /// 结算所需一致票数 M
+    pub threshold: u8,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 委员会投票事件
+#[event]
+pub struct ResolverVoteCastEvent {
+    /// 投票成员
+    pub voter: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 委员会
+    pub committee: Pubkey,
+
+    /// 投票内容
+    pub token_type: u8,
+    pub yes_ratio: u64,
+    pub no_ratio: u64,
+
+    /// 与本票一致的票数
+    pub matching_votes: u8,
+
+    /// 结算所需一致票数 M
+    pub threshold: u8,
+
+    /// 本票是否触发结算
+    pub resolved: bool,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 委员会投票冲突事件（✅ v3.9.1: 没有结果还能达到阈值时发出，升级至全局管理员）
+#[event]
+pub struct ResolverVoteConflictEvent {
+    /// 触发冲突的成员
+    pub voter: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 委员会
+    pub committee: Pubkey,
+
+    /// 冲突投票内容
+    pub token_type: u8,
+    pub yes_ratio: u64,
+    pub no_ratio: u64,
+
+    /// 已投票成员掩码
+    pub voted_mask: u16,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.10.0: 无效结果退款
+// ═══════════════════════════════════════════════════════════════
+
+/// 市场以 Invalid 结果结算事件
+#[event]
+pub struct MarketInvalidatedEvent {
+    /// 结算发起者
+    pub authority: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 最后成交 YES 价格快照（基点）
+    pub snapshot_yes_price_bps: u16,
+
+    /// YES/NO 退款比例（基点）
+    pub yes_refund_ratio: u64,
+    pub no_refund_ratio: u64,
+
+    /// 池内代币按快照价格退还至 Pool Ledger 的 USDC
+    pub pool_refund_credited: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 无效市场交易者退款事件
+#[event]
+pub struct InvalidRefundClaimedEvent {
+    /// 用户
+    pub user: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 销毁的 YES/NO 数量
+    pub yes_burned: u64,
+    pub no_burned: u64,
+
+    /// 退款 USDC
+    pub usdc_refund: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 无效市场 LP 按本金退出事件
+#[event]
+pub struct LpInvalidUnwindEvent {
+    /// LP 用户
+    pub user: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 销毁的 LP 份额
+    pub lp_shares_burned: u64,
+
+    /// 对应的投入本金
+    pub invested_usdc: u64,
+
+    /// 按份额可得的池内 USDC
+    pub pool_share_usdc: u64,
+
+    /// 实际退还 USDC（= min(本金, 池内份额)）
+    pub usdc_out: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.11.0: 结算截止（活性保证）
+// ═══════════════════════════════════════════════════════════════
+
+/// 超过结算截止未结算、市场被作废事件
+#[event]
+pub struct MarketDeadlineVoidedEvent {
+    /// 触发者（任何人）
+    pub caller: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 结算截止 slot
+    pub resolution_deadline_slot: u64,
+
+    /// YES/NO 退款比例（基点，来自价格快照）
+    pub yes_refund_ratio: u64,
+    pub no_refund_ratio: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.12.0: 管理员两阶段结算
+// （提案复用 ResolutionProposedEvent（bond = 0），最终确认复用 ResolutionFinalizedEvent）
+// ═══════════════════════════════════════════════════════════════
+
+/// 管理员修改结算提案事件（挑战窗口重新计时）
+#[event]
+pub struct ResolutionAmendedEvent {
+    /// 管理员
+    pub authority: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 修改前结果
+    pub previous_token_type: u8,
+    pub previous_yes_ratio: u64,
+    pub previous_no_ratio: u64,
+
+    /// 修改后结果
+    pub token_type: u8,
+    pub yes_ratio: u64,
+    pub no_ratio: u64,
+
+    /// 新的挑战窗口结束时间
+    pub liveness_ends_at: i64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 管理员撤回结算提案事件
+#[event]
+pub struct ResolutionRetractedEvent {
+    /// 管理员
+    pub authority: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 被撤回的结果
+    pub token_type: u8,
+    pub yes_ratio: u64,
+    pub no_ratio: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// ✅ v3.7.1: 未最终确认提案的保证金退还事件
+#[event]
+pub struct ResolutionBondsRefundedEvent {
+    /// 调用者（任何人）
+    pub caller: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 提案人及退还金额
+    pub proposer: Pubkey,
+    pub proposer_refund: u64,
+
+    /// 挑战人及退还金额（无挑战时为 Pubkey::default() / 0）
+    pub disputer: Pubkey,
+    pub dispute_bond: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.13.0: 规则承诺与结算证据
+// （证据随 ResolutionEvent 发出）
+// ═══════════════════════════════════════════════════════════════
+
+/// 市场元数据创建事件（规则承诺）
+#[event]
+pub struct MarketMetadataCreatedEvent {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 元数据账户
+    pub metadata: Pubkey,
+
+    /// 规则文本哈希
+    pub rules_hash: [u8; 32],
+
+    /// 结算来源 URI
+    pub resolution_source_uri: String,
+
+    /// 分类
+    pub category: String,
+
+    /// 标签
+    pub tags: Vec<String>,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.17.0: 限价订单簿
+// （每笔成交同时发出 TradeEvent，兼容现有索引器）
+// ═══════════════════════════════════════════════════════════════
+
+/// 挂单事件
+#[event]
+pub struct LimitOrderPostedEvent {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 挂单编号
+    pub order_id: u64,
+
+    /// 挂单人
+    pub owner: Pubkey,
+
+    /// 方向（0=买单, 1=卖单）
+    pub side: u8,
+
+    /// 代币类型（0=NO, 1=YES）
+    pub token_type: u8,
+
+    /// 限价（基点）
+    pub price_bps: u16,
+
+    /// 挂单数量
+    pub amount: u64,
+
+    /// 托管数量（买单为 USDC，卖单为代币）
+    pub escrowed: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 挂单成交事件
+#[event]
+pub struct LimitOrderFilledEvent {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 被成交的挂单编号
+    pub order_id: u64,
+
+    /// 挂单人（maker）
+    pub maker: Pubkey,
+
+    /// 对手方（swap 的 taker 或撮合中的另一挂单人）
+    pub taker: Pubkey,
+
+    /// 代币类型（0=NO, 1=YES）
+    pub token_type: u8,
+
+    /// 成交价格（基点）
+    pub price_bps: u16,
+
+    /// 成交代币数量
+    pub token_amount: u64,
+
+    /// 成交 USDC
+    pub usdc_amount: u64,
+
+    /// 挂单剩余数量
+    pub remaining: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 挂单领取 / 撤单事件
+#[event]
+pub struct LimitOrderSettledEvent {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 挂单编号
+    pub order_id: u64,
+
+    /// 挂单人
+    pub owner: Pubkey,
+
+    /// 支付给挂单人的 USDC
+    pub usdc_amount: u64,
+
+    /// 支付给挂单人的代币
+    pub token_amount: u64,
+
+    /// 是否为撤单
+    pub cancelled: bool,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.18.0: 多市场组合交易
+// （每条腿同时发出 SwapExecuted，兼容现有索引器）
+// ═══════════════════════════════════════════════════════════════
+
+/// 组合交易汇总事件
+#[event]
+pub struct SwapBundleExecutedEvent {
+    /// 交易用户
+    pub user: Pubkey,
+
+    /// 交易腿数量
+    pub leg_count: u8,
+
+    /// 买入腿支付的 USDC 总额（含手续费）
+    pub total_usdc_in: u64,
+
+    /// 卖出腿收到的 USDC 总额（税后）
+    pub total_usdc_out: u64,
+
+    /// 用户 USDC 净变化（收到 - 支付）
+    pub net_usdc_out: i64,
+
+    /// 手续费总额（USDC）
+    pub total_fee: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.21.0: 条件订单（止损 / 止盈）
+// （执行时同时发出 SwapExecuted，兼容现有索引器）
+// ═══════════════════════════════════════════════════════════════
+
+/// 条件订单创建事件
+#[event]
+pub struct ConditionalOrderCreatedEvent {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 条件订单账户
+    pub order: Pubkey,
+
+    /// 下单人
+    pub owner: Pubkey,
+
+    /// 订单编号（下单人自选）
+    pub order_id: u64,
+
+    /// 代币类型（0=NO, 1=YES）
+    pub token_type: u8,
+
+    /// 触发方向（0=YES 价格 ≤ 触发价, 1=YES 价格 ≥ 触发价）
+    pub trigger_direction: u8,
+
+    /// 触发价（YES 价格，基点）
+    pub trigger_price_bps: u16,
+
+    /// 托管代币数量
+    pub amount: u64,
+
+    /// 扣除 keeper 费后的最少所得（USDC）
+    pub min_usdc_out: u64,
+
+    /// Keeper 执行费（基点）
+    pub keeper_fee_bps: u16,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 条件订单执行事件
+#[event]
+pub struct ConditionalOrderExecutedEvent {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 条件订单账户
+    pub order: Pubkey,
+
+    /// 下单人
+    pub owner: Pubkey,
+
+    /// 执行的 keeper
+    pub keeper: Pubkey,
+
+    /// 执行时的 YES 价格（基点）
+    pub yes_price_bps: u16,
+
+    /// 卖出的代币数量
+    pub token_amount: u64,
+
+    /// 卖出所得（USDC，已扣交易手续费）
+    pub usdc_proceeds: u64,
+
+    /// 支付给 keeper 的执行费（USDC）
+    pub keeper_fee: u64,
+
+    /// 下单人实际收到的 USDC
+    pub owner_usdc_out: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 条件订单撤销事件
+#[event]
+pub struct ConditionalOrderCancelledEvent {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 条件订单账户
+    pub order: Pubkey,
+
+    /// 下单人
+    pub owner: Pubkey,
+
+    /// 退还的代币数量
+    pub token_amount: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.22.0: 引荐分成
+// ═══════════════════════════════════════════════════════════════
+
+/// 引荐人配置事件
+#[event]
+pub struct ReferrerConfiguredEvent {
+    /// 引荐人
+    pub referrer: Pubkey,
+
+    /// 引荐人统计账户
+    pub referrer_stats: Pubkey,
+
+    /// 平台费分成比例（基点，实际受 Config.max_referral_share_bps 限制）
+    pub share_bps: u16,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 引荐成交事件（swap / mint_complete_set）
+#[event]
+pub struct ReferralFeeAccruedEvent {
+    /// 引荐人
+    pub referrer: Pubkey,
+
+    /// 被引荐用户
+    pub user: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 成交量（USDC，含手续费）
+    pub volume: u64,
+
+    /// 本次分成（USDC，mint_complete_set 无平台费时为 0）
+    pub referral_fee: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 引荐分成领取事件
+#[event]
+pub struct ReferralFeesClaimedEvent {
+    /// 引荐人
+    pub referrer: Pubkey,
+
+    /// 领取数量（USDC）
+    pub amount: u64,
+
+    /// 累计领取（USDC）
+    pub total_claimed: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.23.0: 成交量分级费率与做市返佣
+// ═══════════════════════════════════════════════════════════════
+
+/// 费率分级表更新事件
+#[event]
+pub struct FeeTierTableConfiguredEvent {
+    /// 档位数量
+    pub tier_count: u8,
+
+    /// 做市商平台费率（基点）
+    pub maker_platform_fee_bps: u16,
+
+    /// 做市商 LP 费率（基点，负数为返佣）
+    pub maker_lp_fee_bps: i16,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 做市商白名单变更事件
+#[event]
+pub struct MakerRebateUpdatedEvent {
+    /// 用户
+    pub user: Pubkey,
+
+    /// 是否适用做市返佣档
+    pub is_maker: bool,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 分级费率生效事件（swap 携带费率分级账户时发出）
+#[event]
+pub struct FeeTierAppliedEvent {
+    /// 用户
+    pub user: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 交易前 30 天滚动成交量（USDC）
+    pub rolling_volume_30d: u64,
+
+    /// 档位平台费率（基点，实际费率 = min(基础费率, 档位费率)）
+    pub platform_fee_bps: u16,
+
+    /// 档位 LP 费率（基点，负数为返佣）
+    pub lp_fee_bps: i16,
+
+    /// 本次做市返佣（USDC）
+    pub maker_rebate: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.24.0: 动态 LP 费率
+// ═══════════════════════════════════════════════════════════════
+
+/// 市场动态 LP 费率配置事件
+#[event]
+pub struct MarketDynamicFeeConfiguredEvent {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 是否启用
+    pub enabled: bool,
+
+    /// LP 费率下限（基点）
+    pub min_bps: u16,
+
+    /// LP 费率上限（基点）
+    pub max_bps: u16,
+
+    /// 不平衡溢价满额（基点）
+    pub imbalance_bps: u16,
+
+    /// 波动率溢价系数（基点）
+    pub volatility_multiplier_bps: u16,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.25.0: 签名交易意图
+// ═══════════════════════════════════════════════════════════════
+
+/// 意图执行事件（同时发出 SwapExecuted，兼容现有索引器）
+#[event]
+pub struct IntentExecutedEvent {
+    /// 签名用户
+    pub user: Pubkey,
+
+    /// 提交交易的 relayer
+    pub relayer: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 意图序号
+    pub nonce: u64,
+
+    /// 0 = 买入, 1 = 卖出
+    pub direction: u8,
+
+    /// 输入数量
+    pub amount: u64,
+
+    /// 支付给 relayer 的 USDC
+    pub relayer_fee: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.26.0: 会话密钥
+// ═══════════════════════════════════════════════════════════════
+
+/// 会话密钥创建事件
+#[event]
+pub struct SessionKeyCreatedEvent {
+    /// 授权人
+    pub owner: Pubkey,
+
+    /// 会话签名者（临时热钥）
+    pub session_signer: Pubkey,
+
+    /// 过期时间
+    pub expires_at: i64,
+
+    /// USDC 支出上限
+    pub spend_cap: u64,
+
+    /// 允许的操作（位掩码）
+    pub allowed_actions: u8,
+
+    /// 限定市场数量（0 = 不限）
+    pub market_count: u8,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 会话密钥撤销事件
+#[event]
+pub struct SessionKeyRevokedEvent {
+    /// 授权人
+    pub owner: Pubkey,
+
+    /// 会话签名者
+    pub session_signer: Pubkey,
+
+    /// 累计支出（USDC）
+    pub spent: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+/// 会话密钥使用事件（swap / mint_complete_set / redeem_complete_set）
+#[event]
+pub struct SessionKeyUsedEvent {
+    /// 授权人
+    pub owner: Pubkey,
+
+    /// 会话签名者
+    pub session_signer: Pubkey,
+
+    /// 市场
+    pub market: Pubkey,
+
+    /// 操作（SESSION_ACTION_*）
+    pub action: u8,
+
+    /// 本次支出（USDC）
+    pub spend: u64,
+
+    /// 累计支出（USDC）
+    pub total_spent: u64,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.27.0: Token-2022 结果代币
+// ═══════════════════════════════════════════════════════════════
+
+/// Token-2022 结果代币创建事件（元数据指针指向 mint 自身）
+#[event]
+pub struct OutcomeMintCreatedEvent {
+    /// 结果代币 mint
+    pub mint: Pubkey,
+
+    /// 创建者
+    pub creator: Pubkey,
+
+    /// 元数据名称
+    pub name: String,
+
+    /// 元数据符号
+    pub symbol: String,
+
+    /// 元数据 URI
+    pub uri: String,
+
+    /// 时间戳
+    pub timestamp: i64,
+}
@@ -0,0 +1,88 @@
+//! ✅ v3.5.0: 管理员指令：将市场加入负风险事件组
+
+use crate::{
+    constants::{CONFIG, EVENT_GROUP, MARKET},
+    errors::PredictionMarketError,
+    events::EventGroupMarketAddedEvent,
+    state::{config::*, event_group::*, market::*},
+};
+use anchor_lang::prelude::*;
+
+/// 加入事件组的账户集合
+#[derive(Accounts)]
+pub struct AddMarketToEventGroup<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 事件组
+    #[account(
+        mut,
+        seeds = [EVENT_GROUP.as_bytes(), &event_group.group_id.to_le_bytes()],
+        bump = event_group.bump,
+    )]
+    pub event_group: Box<Account<'info, EventGroup>>,
+
+    /// 目标市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// YES/NO mint（用于推导 market PDA）
+    /// CHECK: only used as PDA seed
+    pub yes_token: AccountInfo<'info>,
+    /// CHECK: only used as PDA seed
+    pub no_token: AccountInfo<'info>,
+
+    /// 管理员（必须为全局 authority）
+    #[account(
+        constraint = authority.key() == global_config.authority @ PredictionMarketError::IncorrectAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+impl AddMarketToEventGroup<'_> {
+    /// 将市场加入事件组
+    ///
+    /// # 前置条件
+    /// - 市场未结算、非标量、非条件市场、未属于其他事件组
+    /// - 事件组未结算、未发生过转换（成员冻结前）、成员未满
+    pub fn handler(&mut self) -> Result<()> {
+        let group = &mut self.event_group;
+        let market = &mut self.market;
+
+        require!(!group.is_resolved, PredictionMarketError::EventGroupAlreadyResolved);
+        require!(!group.is_voided, PredictionMarketError::EventGroupVoided);
+        require!(group.total_converted == 0, PredictionMarketError::EventGroupLocked);
+        require!(
+            (group.market_count as usize) < crate::constants::MAX_EVENT_GROUP_MARKETS,
+            PredictionMarketError::EventGroupFull
+        );
+        // ✅ v3.6.2: 条件市场不可入组（void_conditional_market 不携带事件组账户）
+        require!(market.is_groupable(), PredictionMarketError::MarketNotGroupable);
+
+        let index = group.market_count;
+        group.markets[index as usize] = market.key();
+        group.market_count = index
+            .checked_add(1)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.event_group = group.key();
+
+        msg!("✅ Market {} added to event group at index {}", market.key(), index);
+
+        emit!(EventGroupMarketAddedEvent {
+            event_group: group.key(),
+            market: market.key(),
+            index,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
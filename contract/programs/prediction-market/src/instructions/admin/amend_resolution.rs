@@ -0,0 +1,157 @@
+//! ✅ v3.12.0: 管理员指令：两阶段结算 - 修改 / 撤回结算提案
+//!
+//! 仅适用于管理员提案（保证金为 0），且必须在挑战窗口内：
+//! - `amend`：修改结果，挑战窗口重新计时
+//! - `retract`：撤回提案并关闭提案账户，之后可重新提案
+//!
+//! ✅ v3.12.1: 标量市场提案按结算值计算比例，不支持修改，需撤回后重新提案。
+//!
+//! ✅ v3.13.1: 修改时须重新提交结算证据。
+
+use crate::{
+    constants::{CONFIG, ORACLE_CONFIG, RESOLUTION_PROPOSAL},
+    errors::PredictionMarketError,
+    events::{ResolutionAmendedEvent, ResolutionRetractedEvent},
+    instructions::market::resolution::validate_resolution_ratios,
+    state::{config::*, market_metadata::ResolutionEvidence, oracle::*},
+};
+use anchor_lang::prelude::*;
+
+/// 账户集合：修改结算提案
+#[derive(Accounts)]
+pub struct AmendResolution<'info> {
+    /// 全局配置（用于权限验证）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 乐观预言机配置（提供管理员挑战窗口）
+    #[account(
+        seeds = [ORACLE_CONFIG.as_bytes()],
+        bump = oracle_config.bump,
+    )]
+    pub oracle_config: Box<Account<'info, OracleConfig>>,
+
+    /// 结算提案
+    #[account(
+        mut,
+        seeds = [RESOLUTION_PROPOSAL.as_bytes(), proposal.market.as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// 管理员（必须为全局 authority）
+    #[account(
+        constraint = authority.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+impl AmendResolution<'_> {
+    /// 修改结算提案（挑战窗口重新计时）
+    pub fn handler(
+        &mut self,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        evidence: ResolutionEvidence,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.proposal.scalar_value.is_none(),
+            PredictionMarketError::ScalarMarketRequiresValue
+        );
+        validate_resolution_ratios(yes_ratio, no_ratio, token_type)?;
+        evidence.validate()?;
+
+        let proposal = &mut self.proposal;
+        let (previous_token_type, previous_yes_ratio, previous_no_ratio) =
+            (proposal.token_type, proposal.yes_ratio, proposal.no_ratio);
+
+        proposal.amend(
+            yes_ratio,
+            no_ratio,
+            token_type,
+            evidence,
+            now,
+            self.oracle_config.admin_challenge_secs,
+        )?;
+        let liveness_ends_at = proposal.liveness_ends_at;
+
+        msg!(
+            "✅ Admin resolution amended: token_type {} -> {}, challenge_ends_at={}",
+            previous_token_type,
+            token_type,
+            liveness_ends_at
+        );
+
+        emit!(ResolutionAmendedEvent {
+            authority: self.authority.key(),
+            market: proposal.market,
+            previous_token_type,
+            previous_yes_ratio,
+            previous_no_ratio,
+            token_type,
+            yes_ratio,
+            no_ratio,
+            liveness_ends_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+/// 账户集合：撤回结算提案
+#[derive(Accounts)]
+pub struct RetractResolution<'info> {
+    /// 全局配置（用于权限验证）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 结算提案（撤回后关闭，租金退还管理员）
+    #[account(
+        mut,
+        close = authority,
+        seeds = [RESOLUTION_PROPOSAL.as_bytes(), proposal.market.as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// 管理员（必须为全局 authority）
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+impl RetractResolution<'_> {
+    /// 撤回结算提案
+    pub fn handler(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.proposal.check_admin_revisable(now)?;
+
+        msg!(
+            "✅ Admin resolution retracted: market={}, token_type={}",
+            self.proposal.market,
+            self.proposal.token_type
+        );
+
+        emit!(ResolutionRetractedEvent {
+            authority: self.authority.key(),
+            market: self.proposal.market,
+            token_type: self.proposal.token_type,
+            yes_ratio: self.proposal.yes_ratio,
+            no_ratio: self.proposal.no_ratio,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -6,8 +6,7 @@ use crate::{
     state::config::*,
 };
 use anchor_lang::{prelude::*, system_program, Discriminator};
-use anchor_spl::{associated_token::AssociatedToken, token::Token};
-use anchor_lang::prelude::borsh::BorshDeserialize;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::TokenInterface};
 
 /// 配置账户集合
 #[derive(Accounts)]
@@ -39,7 +38,7 @@ pub struct Configure<'info> {
     system_program: Program<'info, System>,
 
     /// 代币程序
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
 
     /// 关联代币账户程序
     associated_token_program: Program<'info, AssociatedToken>,
@@ -316,9 +315,15 @@ impl<'info> Configure<'info> {
             new_config.insurance_max_compensation_bps
         );
 
+        // ✅ v3.22.0: 校验引荐分成上限（0-10000）
+        require!(
+            new_config.max_referral_share_bps as u64 <= crate::constants::BASIS_POINTS_DIVISOR,
+            PredictionMarketError::ValueTooLarge
+        );
+
         // 计算空间与租金
         let serialized_config =
-            [&Config::DISCRIMINATOR, new_config.try_to_vec()?.as_slice()].concat();
+            [Config::DISCRIMINATOR, new_config.try_to_vec()?.as_slice()].concat();
         let serialized_config_len = serialized_config.len();
         let config_cost = Rent::get()?.minimum_balance(serialized_config_len);
 
@@ -354,11 +359,15 @@ impl<'info> Configure<'info> {
             if data.len() < 8 || &data[0..8] != Config::DISCRIMINATOR {
                 return err!(PredictionMarketError::IncorrectConfigAccount);
             }
-            let config = Config::deserialize(&mut &data[8..])?;
+            // ✅ v3.22.0: 仅读取 authority（首个字段）
+            // 升级前创建的配置账户短于当前结构，完整反序列化会失败，导致无法通过本指令扩容
+            require!(data.len() >= 8 + 32, PredictionMarketError::IncorrectConfigAccount);
+            let current_authority = Pubkey::try_from(&data[8..40])
+                .map_err(|_| PredictionMarketError::IncorrectConfigAccount)?;
 
             // 只有当前 authority 可以更新配置
             require!(
-                config.authority == self.payer.key(),
+                current_authority == self.payer.key(),
                 PredictionMarketError::IncorrectAuthority
             );
 
@@ -0,0 +1,91 @@
+//! ✅ v3.24.0: 管理员指令：配置市场级动态 LP 费率（可启用/关闭）
+
+use crate::{
+    constants::{BASIS_POINTS_DIVISOR, CONFIG, MARKET, MAX_DYNAMIC_LP_FEE_BPS},
+    errors::PredictionMarketError,
+    events::MarketDynamicFeeConfiguredEvent,
+    state::{config::Config, market::Market},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureDynamicFee<'info> {
+    /// 全局配置（用于权限验证）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Account<'info, Config>,
+
+    /// 目标市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// 市场 YES/NO mint（用于推导 PDA）
+    /// CHECK: only used as PDA seeds
+    pub yes_token: AccountInfo<'info>,
+    /// CHECK: only used as PDA seeds
+    pub no_token: AccountInfo<'info>,
+
+    /// 管理员
+    #[account(
+        constraint = admin.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DynamicFeeParams {
+    pub enabled: bool,
+    /// LP 费率下限（基点）
+    pub min_bps: u16,
+    /// LP 费率上限（基点，≤ MAX_DYNAMIC_LP_FEE_BPS）
+    pub max_bps: u16,
+    /// 不平衡溢价满额（基点）
+    pub imbalance_bps: u16,
+    /// 波动率溢价系数（基点）
+    pub volatility_multiplier_bps: u16,
+}
+
+impl ConfigureDynamicFee<'_> {
+    pub fn handler(&mut self, params: DynamicFeeParams) -> Result<()> {
+        require!(
+            params.min_bps <= params.max_bps
+                && params.max_bps <= MAX_DYNAMIC_LP_FEE_BPS
+                && params.imbalance_bps <= MAX_DYNAMIC_LP_FEE_BPS
+                && params.volatility_multiplier_bps as u64 <= BASIS_POINTS_DIVISOR,
+            PredictionMarketError::InvalidDynamicFeeConfig
+        );
+
+        let market = &mut self.market;
+        market.dynamic_fee_enabled = params.enabled;
+        market.dynamic_fee_min_bps = params.min_bps;
+        market.dynamic_fee_max_bps = params.max_bps;
+        market.dynamic_fee_imbalance_bps = params.imbalance_bps;
+        market.dynamic_fee_volatility_multiplier_bps = params.volatility_multiplier_bps;
+
+        let clock = Clock::get()?;
+        emit!(MarketDynamicFeeConfiguredEvent {
+            market: market.key(),
+            enabled: params.enabled,
+            min_bps: params.min_bps,
+            max_bps: params.max_bps,
+            imbalance_bps: params.imbalance_bps,
+            volatility_multiplier_bps: params.volatility_multiplier_bps,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "✅ v3.24.0: Dynamic LP fee {} for market {}: [{}, {}]bps",
+            if params.enabled { "enabled" } else { "disabled" },
+            market.key(),
+            params.min_bps,
+            params.max_bps
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! ✅ v3.23.0: 管理员指令：配置成交量分级费率表与做市返佣档
+
+use crate::{
+    constants::{CONFIG, FEE_TIER_TABLE, MAX_FEE_TIERS},
+    errors::PredictionMarketError,
+    events::FeeTierTableConfiguredEvent,
+    state::{
+        config::Config,
+        fee_tier::{FeeTier, FeeTierTable},
+    },
+};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct ConfigureFeeTiers<'info> {
+    /// 全局配置（用于权限验证）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 费率分级表（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FeeTierTable::INIT_SPACE,
+        seeds = [FEE_TIER_TABLE.as_bytes()],
+        bump
+    )]
+    pub fee_tier_table: Box<Account<'info, FeeTierTable>>,
+
+    /// 管理员
+    #[account(
+        mut,
+        constraint = admin.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl ConfigureFeeTiers<'_> {
+    /// 整表替换档位与做市返佣档
+    ///
+    /// # 参数
+    /// * `tiers` - 档位（按 min_volume_30d 严格递增，空表 = 关闭成交量分级）
+    /// * `maker_platform_fee_bps` - 做市返佣档平台费率
+    /// * `maker_lp_fee_bps` - 做市返佣档 LP 费率（负数为返佣）
+    pub fn handler(
+        &mut self,
+        tiers: Vec<FeeTier>,
+        maker_platform_fee_bps: u16,
+        maker_lp_fee_bps: i16,
+        bump: u8,
+    ) -> Result<()> {
+        require!(tiers.len() <= MAX_FEE_TIERS, PredictionMarketError::InvalidFeeTierTable);
+
+        let now = Clock::get()?.unix_timestamp;
+        let table = &mut self.fee_tier_table;
+        table.tier_count = tiers.len() as u8;
+        table.tiers = [FeeTier::default(); MAX_FEE_TIERS];
+        table.tiers[..tiers.len()].copy_from_slice(&tiers);
+        table.maker_platform_fee_bps = maker_platform_fee_bps;
+        table.maker_lp_fee_bps = maker_lp_fee_bps;
+        table.updated_at = now;
+        table.bump = bump;
+        table.validate()?;
+
+        emit!(FeeTierTableConfiguredEvent {
+            tier_count: table.tier_count,
+            maker_platform_fee_bps,
+            maker_lp_fee_bps,
+            timestamp: now,
+        });
+
+        msg!(
+            "✅ v3.23.0: Fee tier table configured: {} tiers, maker platform={}bps lp={}bps",
+            table.tier_count,
+            maker_platform_fee_bps,
+            maker_lp_fee_bps
+        );
+        Ok(())
+    }
+}
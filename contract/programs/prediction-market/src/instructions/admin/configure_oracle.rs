@@ -0,0 +1,70 @@
+//! ✅ v3.7.0: 管理员指令：配置乐观预言机（挑战窗口与最低保证金）
+//! ✅ v3.12.0: 同时配置管理员两阶段结算的挑战窗口
+
+use crate::{
+    constants::{CONFIG, ORACLE_CONFIG},
+    errors::PredictionMarketError,
+    state::{config::*, oracle::*},
+};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct ConfigureOracle<'info> {
+    /// 全局配置（用于权限验证）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 乐观预言机配置（首次调用时创建）
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + OracleConfig::INIT_SPACE,
+        seeds = [ORACLE_CONFIG.as_bytes()],
+        bump
+    )]
+    pub oracle_config: Box<Account<'info, OracleConfig>>,
+
+    /// 管理员（必须为全局 authority）
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    /// 系统程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl ConfigureOracle<'_> {
+    /// 设置乐观预言机参数
+    ///
+    /// # 参数
+    /// * `liveness_secs` - 挑战窗口（秒，600 - 604800）
+    /// * `min_bond` - 最低提案保证金（USDC 最小单位）
+    /// * `enabled` - 是否启用（关闭后不再接受新提案，已有提案不受影响）
+    /// * `admin_challenge_secs` - 管理员两阶段结算挑战窗口（秒，300 - 604800，不受 enabled 影响）
+    pub fn handler(&mut self, liveness_secs: i64, min_bond: u64, enabled: bool, admin_challenge_secs: i64, bump: u8) -> Result<()> {
+        OracleConfig::validate(liveness_secs, min_bond, admin_challenge_secs)?;
+
+        let oracle_config = &mut self.oracle_config;
+        oracle_config.liveness_secs = liveness_secs;
+        oracle_config.min_bond = min_bond;
+        oracle_config.enabled = enabled;
+        oracle_config.admin_challenge_secs = admin_challenge_secs;
+        oracle_config.bump = bump;
+
+        msg!(
+            "✅ Oracle configured: liveness={}s, min_bond={}, enabled={}, admin_challenge={}s",
+            liveness_secs,
+            min_bond,
+            enabled,
+            admin_challenge_secs
+        );
+
+        Ok(())
+    }
+}
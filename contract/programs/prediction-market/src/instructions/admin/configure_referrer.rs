@@ -0,0 +1,101 @@
+//! ✅ v3.22.0: 管理员指令：创建/更新引荐人账户及分成比例
+
+use crate::{
+    constants::{BASIS_POINTS_DIVISOR, CONFIG, REFERRER},
+    errors::PredictionMarketError,
+    events::ReferrerConfiguredEvent,
+    state::{config::Config, referrer::ReferrerStats},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct ConfigureReferrer<'info> {
+    /// 全局配置（用于权限验证）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 引荐人钱包
+    /// CHECK: any wallet can be registered as a referrer
+    pub referrer: AccountInfo<'info>,
+
+    /// 引荐人统计账户（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + ReferrerStats::INIT_SPACE,
+        seeds = [REFERRER.as_bytes(), referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_stats: Box<Account<'info, ReferrerStats>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 引荐分成托管 ATA（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = referrer_stats,
+        associated_token::token_program = token_program,
+    )]
+    pub referrer_usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 管理员
+    #[account(
+        mut,
+        constraint = admin.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl ConfigureReferrer<'_> {
+    /// 设置引荐人分成比例（基点，0 = 仅统计成交量不分成）
+    pub fn handler(&mut self, share_bps: u16, bump: u8) -> Result<()> {
+        require!(
+            share_bps as u64 <= BASIS_POINTS_DIVISOR,
+            PredictionMarketError::InvalidParameter
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let stats = &mut self.referrer_stats;
+        if stats.referrer == Pubkey::default() {
+            stats.referrer = self.referrer.key();
+            stats.created_at = now;
+            stats.bump = bump;
+        }
+        stats.share_bps = share_bps;
+
+        emit!(ReferrerConfiguredEvent {
+            referrer: stats.referrer,
+            referrer_stats: stats.key(),
+            share_bps,
+            timestamp: now,
+        });
+
+        msg!(
+            "✅ v3.22.0: Referrer {} configured: share={}bps (cap {}bps)",
+            stats.referrer,
+            share_bps,
+            self.global_config.max_referral_share_bps
+        );
+        Ok(())
+    }
+}
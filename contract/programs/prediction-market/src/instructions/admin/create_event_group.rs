@@ -0,0 +1,101 @@
+//! ✅ v3.5.0: 管理员指令：创建负风险事件组
+
+use crate::{
+    constants::{CONFIG, EVENT_GROUP},
+    errors::PredictionMarketError,
+    events::EventGroupCreatedEvent,
+    state::{config::*, event_group::*},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/// 创建事件组的账户集合
+#[derive(Accounts)]
+#[instruction(group_id: u64)]
+pub struct CreateEventGroup<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 事件组 PDA（将被创建）
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EventGroup::INIT_SPACE,
+        seeds = [EVENT_GROUP.as_bytes(), &group_id.to_le_bytes()],
+        bump
+    )]
+    pub event_group: Box<Account<'info, EventGroup>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 事件组 USDC 金库（authority = 事件组 PDA，存放转换产生的担保抵押品）
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = event_group,
+        associated_token::token_program = token_program,
+    )]
+    pub group_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 管理员（必须为全局 authority）
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.authority @ PredictionMarketError::IncorrectAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl CreateEventGroup<'_> {
+    /// 创建事件组（成员通过 add_market_to_event_group 逐个加入）
+    pub fn handler(&mut self, group_id: u64, display_name: String, bump: u8) -> Result<()> {
+        require!(
+            !display_name.is_empty() && display_name.len() <= EventGroup::MAX_DISPLAY_NAME_LEN,
+            PredictionMarketError::InvalidParameter
+        );
+
+        let clock = Clock::get()?;
+        let group = &mut self.event_group;
+        group.authority = self.authority.key();
+        group.group_id = group_id;
+        group.market_count = 0;
+        group.markets = [Pubkey::default(); crate::constants::MAX_EVENT_GROUP_MARKETS];
+        group.total_converted = 0;
+        group.is_resolved = false;
+        group.winning_index = 0;
+        group.display_name = display_name;
+        group.created_at = clock.unix_timestamp;
+        group.bump = bump;
+        group.is_voided = false;
+        group.void_yes_price_bps = [0; crate::constants::MAX_EVENT_GROUP_MARKETS];
+
+        msg!("✅ Event group created: id={}", group_id);
+
+        emit!(EventGroupCreatedEvent {
+            authority: self.authority.key(),
+            event_group: group.key(),
+            group_id,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -11,7 +11,7 @@ use crate::state::config::Config;
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 #[derive(Accounts)]
@@ -34,7 +34,7 @@ pub struct EnsureTeamUsdcAta<'info> {
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 团队钱包（必须与配置一致）
     /// CHECK: Validated against global_config.team_wallet
@@ -49,12 +49,13 @@ pub struct EnsureTeamUsdcAta<'info> {
         payer = admin,
         associated_token::mint = usdc_mint,
         associated_token::authority = team_wallet,
+        associated_token::token_program = token_program,
     )]
-    pub team_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub team_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(address = anchor_lang::system_program::ID)]
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -32,3 +32,30 @@ pub use configure_market_fees::*;
 
 pub mod pause_market;
 pub use pause_market::*;
+
+pub mod create_event_group;        // ✅ v3.5.0: 负风险事件组
+pub use create_event_group::*;
+
+pub mod add_market_to_event_group; // ✅ v3.5.0: 事件组新增成员
+pub use add_market_to_event_group::*;
+
+pub mod configure_oracle;          // ✅ v3.7.0: 乐观预言机参数
+pub use configure_oracle::*;
+
+pub mod propose_admin_resolution;  // ✅ v3.12.0: 两阶段结算 - 管理员提案
+pub use propose_admin_resolution::*;
+
+pub mod amend_resolution;          // ✅ v3.12.0: 两阶段结算 - 修改/撤回提案
+pub use amend_resolution::*;
+
+pub mod configure_referrer;        // ✅ v3.22.0: 引荐人账户与分成比例
+pub use configure_referrer::*;
+
+pub mod configure_fee_tiers;       // ✅ v3.23.0: 成交量分级费率表
+pub use configure_fee_tiers::*;
+
+pub mod set_maker_rebate;          // ✅ v3.23.0: 做市商白名单
+pub use set_maker_rebate::*;
+
+pub mod configure_dynamic_fee;     // ✅ v3.24.0: 市场级动态 LP 费率
+pub use configure_dynamic_fee::*;
@@ -0,0 +1,225 @@
+//! ✅ v3.12.0: 管理员指令：两阶段结算 - 提交结算提案
+//!
+//! 提案只记录结果并开启挑战窗口（oracle_config.admin_challenge_secs）：
+//! - 窗口内管理员可通过 amend_resolution 修改（重新计时）或 retract_resolution 撤回
+//! - 窗口结束后任何人调用 finalize_resolution 最终确认，此时才销毁 global_yes_ata/global_no_ata
+//!   中的代币并标记 is_completed，claim_rewards 与 settle_pool 随之开放
+//!
+//! 提案复用乐观预言机的 ResolutionProposal 账户（每个市场一个，保证金为 0）。
+//!
+//! ✅ v3.12.1: 本指令是唯一的管理员结算入口（一步生效的 resolution / resolve_scalar 已移除），
+//! 标量市场通过 `handler_scalar` 提交结算值，按区间计算 LONG/SHORT 比例。
+//!
+//! ✅ v3.13.1: 提案须附带结算证据（哈希 + URI），最终确认时写入 MarketMetadata
+//!
+//! ✅ v3.9.2: 指定结算人 / 价格源市场只在委员会僵局升级（提供 resolution_votes）
+//! 或超过 resolution_deadline_slot 后才接受管理员提案
+
+use crate::{
+    constants::{CONFIG, MARKET, ORACLE_CONFIG, RESOLUTION_PROPOSAL, RESOLUTION_VOTES},
+    errors::PredictionMarketError,
+    events::ResolutionProposedEvent,
+    instructions::market::resolution::validate_resolution_ratios,
+    state::{config::*, market::*, market_metadata::ResolutionEvidence, oracle::*, resolver::ResolutionVotes},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：管理员提交结算提案
+#[derive(Accounts)]
+pub struct ProposeAdminResolution<'info> {
+    /// 全局配置（用于权限验证）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 乐观预言机配置（提供管理员挑战窗口）
+    #[account(
+        seeds = [ORACLE_CONFIG.as_bytes()],
+        bump = oracle_config.bump,
+    )]
+    pub oracle_config: Box<Account<'info, OracleConfig>>,
+
+    /// 市场
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 结算提案（每个市场仅一个，与公开提案共用）
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ResolutionProposal::INIT_SPACE,
+        seeds = [RESOLUTION_PROPOSAL.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// ✅ v3.9.2: 委员会投票记录（委员会市场僵局升级后由管理员接手时提供）
+    #[account(
+        seeds = [RESOLUTION_VOTES.as_bytes(), market.key().as_ref()],
+        bump = resolution_votes.bump,
+    )]
+    pub resolution_votes: Option<Box<Account<'info, ResolutionVotes>>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 保证金托管 ATA（管理员提案无保证金，创建以兼容 finalize_resolution 账户结构）
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = usdc_mint,
+        associated_token::token_program = token_program,
+        associated_token::authority = proposal,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 管理员（必须为全局 authority）
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl ProposeAdminResolution<'_> {
+    /// 提交结算提案
+    ///
+    /// # 参数
+    /// * `yes_ratio` / `no_ratio` / `token_type` - YES/NO 赎回比例与获胜方（0=NO, 1=YES, 2=平局, 3=无效）
+    /// * `evidence` - ✅ v3.13.1: 结算证据（哈希 + URI）
+    pub fn handler(
+        &mut self,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        evidence: ResolutionEvidence,
+        bump: u8,
+    ) -> Result<()> {
+        // 标量市场的赔付比例由结算值决定，不适用于按比例提案
+        require!(!self.market.is_scalar, PredictionMarketError::ScalarMarketRequiresValue);
+        self.open(yes_ratio, no_ratio, token_type, None, evidence, bump)
+    }
+
+    /// ✅ v3.12.1: 提交标量市场结算提案：按创建时设置的区间线性计算 LONG(YES)/SHORT(NO) 比例
+    ///
+    /// 比例为 (10000, 0) / (0, 10000) 时按单边胜出记录，否则按平局（比例拆分）记录
+    pub fn handler_scalar(&mut self, resolved_value: i64, evidence: ResolutionEvidence, bump: u8) -> Result<()> {
+        let bounds = self
+            .market
+            .scalar_bounds()
+            .ok_or(PredictionMarketError::NotScalarMarket)?;
+        let (yes_ratio, no_ratio) = bounds.payout_ratios(resolved_value)?;
+        let token_type = if yes_ratio == crate::constants::BASIS_POINTS_DIVISOR {
+            1
+        } else if no_ratio == crate::constants::BASIS_POINTS_DIVISOR {
+            0
+        } else {
+            2
+        };
+
+        msg!(
+            "✅ v3.12.1 Scalar resolution proposed: value={}, range=[{}, {}], LONG={}bps, SHORT={}bps",
+            resolved_value,
+            bounds.lower,
+            bounds.upper,
+            yes_ratio,
+            no_ratio
+        );
+
+        self.open(yes_ratio, no_ratio, token_type, Some(resolved_value), evidence, bump)
+    }
+
+    /// 记录提案并开启挑战窗口
+    fn open(
+        &mut self,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        scalar_value: Option<i64>,
+        evidence: ResolutionEvidence,
+        bump: u8,
+    ) -> Result<()> {
+        let market = &self.market;
+        require!(!market.is_completed, PredictionMarketError::MarketIsCompleted);
+
+        let clock = Clock::get()?;
+        // ✅ v3.14.0: 同时校验 slot 排期与 Unix 时间戳排期
+        require!(
+            market.has_ended(clock.slot, clock.unix_timestamp),
+            PredictionMarketError::MarketNotEnded
+        );
+        // ✅ v3.9.2: 指定结算人 / 价格源市场仅在僵局升级或超过结算截止后才接受管理员提案
+        let escalated = self.resolution_votes.as_ref().is_some_and(|votes| votes.escalated);
+        require!(
+            market.admin_resolution_allowed(escalated, clock.slot),
+            PredictionMarketError::MarketHasDesignatedResolver
+        );
+
+        validate_resolution_ratios(yes_ratio, no_ratio, token_type)?;
+        // ✅ v3.13.1: 管理员结算必须提交证据
+        evidence.validate()?;
+
+        let liveness_ends_at = clock
+            .unix_timestamp
+            .checked_add(self.oracle_config.admin_challenge_secs)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let proposal = &mut self.proposal;
+        proposal.market = market.key();
+        proposal.proposer = self.authority.key();
+        proposal.token_type = token_type;
+        proposal.yes_ratio = yes_ratio;
+        proposal.no_ratio = no_ratio;
+        proposal.bond = 0;
+        proposal.proposed_at = clock.unix_timestamp;
+        proposal.liveness_ends_at = liveness_ends_at;
+        proposal.disputer = Pubkey::default();
+        proposal.dispute_bond = 0;
+        proposal.disputed_at = 0;
+        proposal.status = ProposalStatus::Proposed;
+        proposal.bump = bump;
+        proposal.evidence_hash = evidence.hash;
+        proposal.evidence_uri = evidence.uri;
+        proposal.scalar_value = scalar_value;
+
+        msg!(
+            "✅ Admin resolution proposed: token_type={}, YES={}, NO={}, challenge_ends_at={}",
+            token_type,
+            yes_ratio,
+            no_ratio,
+            liveness_ends_at
+        );
+
+        emit!(ResolutionProposedEvent {
+            proposer: proposal.proposer,
+            market: proposal.market,
+            token_type,
+            yes_ratio,
+            no_ratio,
+            bond: 0,
+            liveness_ends_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
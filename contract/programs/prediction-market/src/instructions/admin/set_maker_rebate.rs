@@ -0,0 +1,66 @@
+//! ✅ v3.23.0: 管理员指令：设置做市商白名单（适用做市返佣档）
+
+use crate::{
+    constants::{CONFIG, FEE_TIER},
+    errors::PredictionMarketError,
+    events::MakerRebateUpdatedEvent,
+    state::{config::Config, fee_tier::UserFeeTier},
+};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct SetMakerRebate<'info> {
+    /// 全局配置（用于权限验证）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 做市商钱包
+    /// CHECK: any wallet can be whitelisted as a maker
+    pub user: AccountInfo<'info>,
+
+    /// 用户费率分级账户（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + UserFeeTier::INIT_SPACE,
+        seeds = [FEE_TIER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_fee_tier: Box<Account<'info, UserFeeTier>>,
+
+    /// 管理员
+    #[account(
+        mut,
+        constraint = admin.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl SetMakerRebate<'_> {
+    /// 加入/移出做市商白名单
+    pub fn handler(&mut self, is_maker: bool, bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let account = &mut self.user_fee_tier;
+        if account.user == Pubkey::default() {
+            account.user = self.user.key();
+            account.created_at = now;
+            account.bump = bump;
+        }
+        account.is_maker = is_maker;
+
+        emit!(MakerRebateUpdatedEvent {
+            user: account.user,
+            is_maker,
+            timestamp: now,
+        });
+
+        msg!("✅ v3.23.0: Maker rebate for {} set to {}", account.user, is_maker);
+        Ok(())
+    }
+}
@@ -20,12 +20,13 @@ use crate::{
     errors::PredictionMarketError,
     events::AddLiquidityEvent,
     state::{config::Config, market::{LPPosition, Market}},
+    token_compat,
     utils::ReentrancyGuard,  // ✅ v3.1.4: 重入保护
 };
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, MintTo, Token, TokenAccount},
+    token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：添加LP所需账户（v3.0 单币LP）
@@ -52,7 +53,7 @@ pub struct AddLiquidity<'info> {
         constraint = yes_token.mint_authority == anchor_lang::solana_program::program_option::COption::Some(market.key())
             @ PredictionMarketError::MintAuthorityNotTransferred
     )]
-    pub yes_token: Account<'info, Mint>,
+    pub yes_token: InterfaceAccount<'info, Mint>,
 
     /// NO Token Mint（✅ v3.0: authority 必须是 market PDA）
     #[account(
@@ -60,7 +61,7 @@ pub struct AddLiquidity<'info> {
         constraint = no_token.mint_authority == anchor_lang::solana_program::program_option::COption::Some(market.key())
             @ PredictionMarketError::MintAuthorityNotTransferred
     )]
-    pub no_token: Account<'info, Mint>,
+    pub no_token: InterfaceAccount<'info, Mint>,
 
     /// ✅ v3.0.2: Global Vault PDA（用于签名代币操作）
     /// CHECK: global vault pda used as authority
@@ -75,30 +76,33 @@ pub struct AddLiquidity<'info> {
         mut,
         associated_token::mint = yes_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub global_yes_ata: Account<'info, TokenAccount>,
+    pub global_yes_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// ✅ v3.0.2: Global 的 NO Token ATA（统一代币托管）
     #[account(
         mut,
         associated_token::mint = no_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub global_no_ata: Account<'info, TokenAccount>,
+    pub global_no_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// USDC Mint
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Market USDC ATA（市场专用金库）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub market_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Market USDC Vault PDA（签名权限）
     /// CHECK: market-specific usdc vault pda
@@ -113,8 +117,9 @@ pub struct AddLiquidity<'info> {
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub user_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// LP Position（如不存在则创建）
     #[account(
@@ -130,8 +135,9 @@ pub struct AddLiquidity<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
-    /// SPL Token 程序
-    pub token_program: Program<'info, Token>,
+    /// 代币程序（✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序）
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
 
     /// 系统程序
     pub system_program: Program<'info, System>,
@@ -161,12 +167,22 @@ pub fn handler(ctx: Context<AddLiquidity>, usdc_amount: u64) -> Result<()> {
     );
 
     require!(!market.is_completed, PredictionMarketError::MarketIsCompleted);
+    // ✅ v3.14.0: Unix 时间戳排期的市场结束后不再接受新的流动性
+    if market.end_time > 0 {
+        require!(
+            current_timestamp < market.end_time,
+            PredictionMarketError::MarketEnded
+        );
+    }
     // ✅ v3.1.2: 市场级暂停保护
     require!(
         !market.market_paused,
         PredictionMarketError::MarketPaused
     );
 
+    // ✅ v3.20.0: 流动性变动可能改变 b 值与现价，先按变动前价格推进 TWAP
+    market.update_twap(current_timestamp)?;
+
     // ═══════════════════════════════════════════════════════════════
     // 2. 计算 LP 份额
     // ═══════════════════════════════════════════════════════════════
@@ -256,11 +272,7 @@ pub fn handler(ctx: Context<AddLiquidity>, usdc_amount: u64) -> Result<()> {
             0
         };
 
-        let ratio_diff = if actual_yes_ratio > yes_ratio_bps as u128 {
-            actual_yes_ratio - yes_ratio_bps as u128
-        } else {
-            yes_ratio_bps as u128 - actual_yes_ratio
-        };
+        let ratio_diff = actual_yes_ratio.abs_diff(yes_ratio_bps as u128);
 
         require!(
             ratio_diff <= 200, // 允许 ±2% 误差（200 bps），v3.0.3安全加强
@@ -370,16 +382,18 @@ pub fn handler(ctx: Context<AddLiquidity>, usdc_amount: u64) -> Result<()> {
     // 3. 转移用户 USDC 到市场金库
     // ═══════════════════════════════════════════════════════════════
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            ctx.accounts.collateral_token_program.to_account_info(),
+            token_interface::TransferChecked {
                 from: ctx.accounts.user_usdc_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
                 to: ctx.accounts.market_usdc_ata.to_account_info(),
                 authority: user.to_account_info(),
             },
         ),
-        usdc_amount,
+        token_compat::gross_up_transfer_fee(&ctx.accounts.usdc_mint.to_account_info(), usdc_amount)?,
+        ctx.accounts.usdc_mint.decimals,
     )?;
 
     msg!("✅ Transferred {} USDC from user to market vault", usdc_amount);
@@ -399,9 +413,9 @@ pub fn handler(ctx: Context<AddLiquidity>, usdc_amount: u64) -> Result<()> {
 
     if yes_to_mint > 0 {
         // ✅ v3.0.2: 铸币到全局ATA，统一代币托管
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.outcome_token_program.to_account_info(),
                 MintTo {
                     mint: ctx.accounts.yes_token.to_account_info(),
                     to: ctx.accounts.global_yes_ata.to_account_info(),
@@ -417,9 +431,9 @@ pub fn handler(ctx: Context<AddLiquidity>, usdc_amount: u64) -> Result<()> {
 
     if no_to_mint > 0 {
         // ✅ v3.0.2: 铸币到全局ATA，统一代币托管
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.outcome_token_program.to_account_info(),
                 MintTo {
                     mint: ctx.accounts.no_token.to_account_info(),
                     to: ctx.accounts.global_no_ata.to_account_info(),
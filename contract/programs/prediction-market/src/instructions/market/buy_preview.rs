@@ -0,0 +1,230 @@
+//! ✅ v3.15.0: 买入预览（前端辅助，只读）
+//!
+//! 与 swap 买入路径使用同一套链上计算（费率覆盖、动态 b 值、LMSR 定点二分），
+//! 返回可获得的代币数量、费用拆分（平台/LP/保险）、成交均价与成交后边际价格、价格冲击，
+//! 以及 MAX_SINGLE_TRADE_BPS / min_trading_liquidity / 持仓上限 / 动态 b 值是否会改变或拒绝本次交易，
+//! 避免前端在 TypeScript 中重新实现 LMSR 导致舍入偏差。
+
+use crate::{
+    constants::{BASIS_POINTS_DIVISOR, CONFIG, MARKET, MAX_POSITION_IMBALANCE_MULTIPLIER, MAX_SINGLE_TRADE_BPS},
+    errors::PredictionMarketError,
+    math::LmsrCalculator,
+    state::{config::Config, market::Market},
+};
+use anchor_lang::prelude::*;
+
+/// 账户集合：买入预览
+#[derive(Accounts)]
+pub struct BuyPreview<'info> {
+    /// 全局配置（只读）
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Account<'info, Config>,
+
+    /// 市场账户（只读）
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+/// 买入预览结果
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BuyPreviewResult {
+    pub token_type: u8,                    // 0=NO, 1=YES
+    pub usdc_amount_in: u64,               // 用户支付的 USDC（含费用）
+    pub platform_fee: u64,                 // 平台费（USDC）
+    pub lp_fee: u64,                       // LP 费（USDC）
+    pub total_fee: u64,                    // 总费用
+    pub team_fee: u64,                     // 平台费中分配给团队的份额
+    pub insurance_allocation: u64,         // 平台费中分配给保险池的份额
+    pub amount_after_fee: u64,             // 进入 LMSR 的 USDC
+    pub tokens_out: u64,                   // 可获得的代币数量
+    pub price_before_bps: u16,             // 交易前该方向的边际价格（基点）
+    pub average_price_bps: u64,            // 成交均价（amount_after_fee / tokens_out，基点）
+    pub marginal_price_after_bps: u16,     // 交易后该方向的边际价格（基点）
+    pub price_impact_bps: u64,             // 价格冲击：(均价 - 交易前价格) / 交易前价格（基点）
+    pub base_b: u64,                       // 市场原始 b 值
+    pub effective_b: u64,                  // 本次交易使用的动态 b 值
+    pub b_multiplier_bps: u64,             // 动态 b 值乘数（10000 = 1.0x）
+    pub max_trade_size: u64,               // MAX_SINGLE_TRADE_BPS 对应的单笔上限（USDC）
+    pub exceeds_max_trade_size: bool,      // 是否超过单笔上限（swap 将拒绝）
+    pub min_trading_liquidity: u64,        // 配置的最小交易流动性
+    pub below_min_trading_liquidity: bool, // 市场储备是否低于最小交易流动性（swap 将拒绝）
+    pub exceeds_position_cap: bool,        // 是否触发持仓不平衡上限（swap 将拒绝）
+    pub insufficient_pool_reserve: bool,   // 池内代币储备是否不足（swap 将拒绝）
+    pub trading_open: bool,                // 市场当前是否可交易（未结算/未暂停/在交易时间窗口内）
+    pub will_reject: bool,                 // swap 是否会拒绝本次交易
+}
+
+/// 买入预览：计算当前状态下支付 `usdc_amount` 可获得的代币与各项限制
+pub fn handler(
+    ctx: Context<BuyPreview>,
+    usdc_amount: u64,
+    token_type: u8, // 0=NO, 1=YES
+) -> Result<BuyPreviewResult> {
+    let market = &ctx.accounts.market;
+    let cfg = &ctx.accounts.global_config;
+
+    require!(usdc_amount > 0, PredictionMarketError::InvalidAmount);
+    require!(token_type <= 1, PredictionMarketError::InvalidTokenType);
+    let is_yes = token_type == 1;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 1. 交易状态与限额（与 swap 的校验顺序一致）
+    // ═══════════════════════════════════════════════════════════════
+    let clock = Clock::get()?;
+    let trading_open = !cfg.is_paused
+        && !market.market_paused
+        && !market.is_completed
+        && market.check_trading_window(clock.slot, clock.unix_timestamp).is_ok();
+
+    let below_min_trading_liquidity = market.pool_collateral_reserve < cfg.min_trading_liquidity;
+
+    let max_trade_size = (market.pool_collateral_reserve as u128)
+        .checked_mul(MAX_SINGLE_TRADE_BPS as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR as u128)
+        .ok_or(PredictionMarketError::MathOverflow)? as u64;
+    let exceeds_max_trade_size = usdc_amount > max_trade_size;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 2. 动态 b 值（距离结算越近 b 越大，价格变动越平缓）
+    // ═══════════════════════════════════════════════════════════════
+    let base_b = market.lmsr_b;
+    let effective_b = market.calculate_effective_lmsr_b()?;
+    let b_multiplier_bps = if base_b > 0 {
+        (effective_b as u128)
+            .checked_mul(BASIS_POINTS_DIVISOR as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_div(base_b as u128)
+            .ok_or(PredictionMarketError::MathOverflow)? as u64
+    } else {
+        BASIS_POINTS_DIVISOR
+    };
+
+    // ═══════════════════════════════════════════════════════════════
+    // 3. 费用（支持市场级覆盖）
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.24.0: 包含动态 LP 费率（与 Market::swap 一致）
+    let (platform_buy_bps, lp_buy_bps, _) = market.swap_fee_bps(cfg, 0, token_type, None, clock.unix_timestamp);
+
+    let platform_fee = usdc_amount
+        .checked_mul(platform_buy_bps)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let lp_fee = usdc_amount
+        .checked_mul(lp_buy_bps)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let total_fee = platform_fee
+        .checked_add(lp_fee)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let amount_after_fee = usdc_amount
+        .checked_sub(total_fee)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let insurance_allocation = (platform_fee as u128)
+        .checked_mul(cfg.lp_insurance_allocation_bps as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR as u128)
+        .ok_or(PredictionMarketError::MathOverflow)? as u64;
+    let team_fee = platform_fee
+        .checked_sub(insurance_allocation)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 4. LMSR 成交（使用动态 b 值，与 swap 一致）
+    // ═══════════════════════════════════════════════════════════════
+    let calc = LmsrCalculator::with_effective_b(market, effective_b);
+    let tokens_out = if is_yes {
+        calc.tokens_for_usdc_yes(amount_after_fee)?
+    } else {
+        calc.tokens_for_usdc_no(amount_after_fee)?
+    };
+
+    // 持仓不平衡上限：交易前多数方已达 2b 上限时禁止继续买入多数方；交易后不平衡度不得超过上限
+    let hard_cap = effective_b
+        .checked_mul(MAX_POSITION_IMBALANCE_MULTIPLIER)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let imbalance_before = market.lmsr_q_yes.abs_diff(market.lmsr_q_no);
+    let buying_majority = (market.lmsr_q_yes > market.lmsr_q_no) == is_yes;
+    let (new_q_yes, new_q_no) = calc.new_positions_after_buy(tokens_out, is_yes)?;
+    let exceeds_position_cap = (imbalance_before >= hard_cap && buying_majority)
+        || new_q_yes.abs_diff(new_q_no) > hard_cap;
+
+    let pool_reserve = if is_yes { market.pool_yes_reserve } else { market.pool_no_reserve };
+    let insufficient_pool_reserve = tokens_out > pool_reserve;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 5. 价格（边际价格按市场原始 b 值报告，与 calculate_yes_price_bps 一致）
+    // ═══════════════════════════════════════════════════════════════
+    let side_price_bps = |yes_bps: u16| if is_yes { yes_bps } else { (BASIS_POINTS_DIVISOR as u16) - yes_bps };
+
+    let price_before_bps = side_price_bps(market.calculate_yes_price_bps()?);
+
+    let mut after = (**market).clone();
+    after.lmsr_q_yes = new_q_yes;
+    after.lmsr_q_no = new_q_no;
+    let marginal_price_after_bps = side_price_bps(after.calculate_yes_price_bps()?);
+
+    let average_price_bps = if tokens_out > 0 {
+        (amount_after_fee as u128)
+            .checked_mul(BASIS_POINTS_DIVISOR as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_div(tokens_out as u128)
+            .ok_or(PredictionMarketError::MathOverflow)? as u64
+    } else {
+        0
+    };
+
+    let price_impact_bps = if price_before_bps > 0 {
+        average_price_bps
+            .saturating_sub(price_before_bps as u64)
+            .checked_mul(BASIS_POINTS_DIVISOR)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_div(price_before_bps as u64)
+            .ok_or(PredictionMarketError::MathOverflow)?
+    } else {
+        0
+    };
+
+    let will_reject = !trading_open
+        || below_min_trading_liquidity
+        || exceeds_max_trade_size
+        || exceeds_position_cap
+        || insufficient_pool_reserve
+        || tokens_out == 0;
+
+    Ok(BuyPreviewResult {
+        token_type,
+        usdc_amount_in: usdc_amount,
+        platform_fee,
+        lp_fee,
+        total_fee,
+        team_fee,
+        insurance_allocation,
+        amount_after_fee,
+        tokens_out,
+        price_before_bps,
+        average_price_bps,
+        marginal_price_after_bps,
+        price_impact_bps,
+        base_b,
+        effective_b,
+        b_multiplier_bps,
+        max_trade_size,
+        exceeds_max_trade_size,
+        min_trading_liquidity: cfg.min_trading_liquidity,
+        below_min_trading_liquidity,
+        exceeds_position_cap,
+        insufficient_pool_reserve,
+        trading_open,
+        will_reject,
+    })
+}
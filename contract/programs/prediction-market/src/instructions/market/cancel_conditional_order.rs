@@ -0,0 +1,202 @@
+//! ✅ v3.21.0: 条件订单：撤单（退还托管代币并关闭订单）
+//!
+//! 不受暂停与市场结算状态限制，下单人随时可以取回托管代币。
+
+use crate::{
+    constants::{CONDITIONAL_ORDER, CONFIG, MARKET},
+    errors::PredictionMarketError,
+    events::ConditionalOrderCancelledEvent,
+    state::{conditional_order::*, config::*, market::*},
+    token_compat,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：撤销条件订单
+#[derive(Accounts)]
+pub struct CancelConditionalOrder<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 条件订单（撤单后关闭，租金退还下单人）
+    #[account(
+        mut,
+        seeds = [
+            CONDITIONAL_ORDER.as_bytes(),
+            market.key().as_ref(),
+            owner.key().as_ref(),
+            &conditional_order.order_id.to_le_bytes(),
+        ],
+        bump = conditional_order.bump,
+        has_one = market @ PredictionMarketError::InvalidParameter,
+        has_one = owner @ PredictionMarketError::IncorrectAuthority,
+        close = owner,
+    )]
+    pub conditional_order: Box<Account<'info, ConditionalOrder>>,
+
+    /// 托管代币的 mint
+    #[account(
+        constraint = token_mint.key() == if conditional_order.token_type == 1 {
+            market.yes_token_mint
+        } else {
+            market.no_token_mint
+        } @ PredictionMarketError::InvalidMint,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// USDC Mint（✅ v3.27.0: 可写，关闭托管账户前归集扣留的转账手续费）
+    #[account(
+        mut,
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 订单托管代币 ATA
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = conditional_order,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub escrow_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 订单 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = conditional_order,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub escrow_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 下单人代币 ATA（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = token_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub owner_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 下单人 USDC ATA（不存在则创建，接收外部转入订单 USDC 账户的余额）
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub owner_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 下单人
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl CancelConditionalOrder<'_> {
+    /// 撤销条件订单：退还托管账户全部余额并关闭托管账户
+    pub fn handler(&mut self) -> Result<()> {
+        let market_key = self.market.key();
+        let owner_key = self.owner.key();
+        let order_key = self.conditional_order.key();
+        let order_id_bytes = self.conditional_order.order_id.to_le_bytes();
+        let order_seeds: &[&[&[u8]]] = &[&[
+            CONDITIONAL_ORDER.as_bytes(),
+            market_key.as_ref(),
+            owner_key.as_ref(),
+            &order_id_bytes,
+            &[self.conditional_order.bump],
+        ]];
+        let order_info = self.conditional_order.to_account_info();
+
+        // 退还全部余额（外部转入的粉尘一并退还，保证托管账户可关闭）
+        let token_amount = self.escrow_token_ata.amount;
+        for (from, to, mint, token_program, amount) in [
+            (
+                &self.escrow_token_ata,
+                &self.owner_token_ata,
+                &self.token_mint,
+                &self.outcome_token_program,
+                token_amount,
+            ),
+            (
+                &self.escrow_usdc_ata,
+                &self.owner_usdc_ata,
+                &self.usdc_mint,
+                &self.collateral_token_program,
+                self.escrow_usdc_ata.amount,
+            ),
+        ] {
+            if amount > 0 {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        token_interface::TransferChecked {
+                            from: from.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: to.to_account_info(),
+                            authority: order_info.clone(),
+                        },
+                        order_seeds,
+                    ),
+                    amount,
+                    mint.decimals,
+                )?;
+            }
+
+            token_compat::harvest_withheld_fees(
+                &token_program.to_account_info(),
+                &mint.to_account_info(),
+                &from.to_account_info(),
+            )?;
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_interface::CloseAccount {
+                    account: from.to_account_info(),
+                    destination: self.owner.to_account_info(),
+                    authority: order_info.clone(),
+                },
+                order_seeds,
+            ))?;
+        }
+
+        emit!(ConditionalOrderCancelledEvent {
+            market: market_key,
+            order: order_key,
+            owner: owner_key,
+            token_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "✅ v3.21.0: Conditional order #{} cancelled, {} tokens returned",
+            self.conditional_order.order_id,
+            token_amount
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+//! ✅ v3.9.0: 结算委员会投票
+//!
+//! 市场结算人为 M-of-N 委员会时，每个成员调用本指令投票一次：
+//! - 任一结果累计 M 票 → 自动结算（所有结算路径共用 `SettleContext::settle`）
+//! - ✅ v3.9.1: 按结果分别计票，少数反对票不再触发升级；仅当没有任何结果还能达到 M 票时，
+//!   记录僵局、发出 ResolverVoteConflictEvent 并升级（escalated），
+//!   之后委员会投票不再触发结算，由全局管理员裁决
+//! - ✅ v3.13.1: 每张投票须附带结算证据；达到阈值的那张投票的证据写入 MarketMetadata
+
+use crate::{
+    constants::{GLOBAL, MARKET, MARKET_METADATA, RESOLUTION_VOTES},
+    errors::PredictionMarketError,
+    events::{ResolverVoteCastEvent, ResolverVoteConflictEvent},
+    instructions::market::resolution::{validate_resolution_ratios, SettleContext},
+    state::{event_group::*, market::*, market_metadata::*, resolver::*},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：委员会投票
+#[derive(Accounts)]
+pub struct CastResolutionVote<'info> {
+    /// 市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// 结算委员会（必须为 market.resolver）
+    #[account(
+        constraint = market.resolver_is_committee
+            && market.resolver == committee.key() @ PredictionMarketError::InvalidResolver
+    )]
+    pub committee: Box<Account<'info, ResolverCommittee>>,
+
+    /// 投票记录（首次投票时创建）
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + ResolutionVotes::INIT_SPACE,
+        seeds = [RESOLUTION_VOTES.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub votes: Box<Account<'info, ResolutionVotes>>,
+
+    /// 全局金库（PDA，清算池内代币的签名者）
+    /// CHECK: global vault pda
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    #[account(mut)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库持有的 YES/NO（结算时清算）
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 所属事件组（仅事件组成员市场需要提供）
+    pub event_group: Option<Box<Account<'info, EventGroup>>>,
+
+    /// 父市场（仅条件市场需要提供）
+    pub parent_market: Option<Box<Account<'info, Market>>>,
+
+    /// ✅ v3.13.1: 市场元数据（记录结算证据；market.has_metadata 为 true 时必须提供）
+    #[account(
+        mut,
+        seeds = [MARKET_METADATA.as_bytes(), market.key().as_ref()],
+        bump = market_metadata.bump,
+    )]
+    pub market_metadata: Option<Box<Account<'info, MarketMetadata>>>,
+
+    /// 投票成员（首次投票时支付投票记录租金）
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// 系统/代币程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl CastResolutionVote<'_> {
+    /// 记录成员投票，达到阈值时结算市场
+    ///
+    /// # 参数
+    /// * `yes_amount` - YES代币的赎回比例（0-10000）
+    /// * `no_amount` - NO代币的赎回比例（0-10000）
+    /// * `token_type` - 获胜方（0=NO, 1=YES, 2=平局, 3=无效：比例传 0/0，按价格快照退款）
+    /// * `evidence` - ✅ v3.13.1: 结算证据（哈希非全 0，URI 非空）
+    pub fn handler(
+        &mut self,
+        yes_amount: u64,
+        no_amount: u64,
+        token_type: u8,
+        evidence: ResolutionEvidence,
+        votes_bump: u8,
+        global_vault_bump: u8,
+    ) -> Result<()> {
+        require!(!self.market.is_completed, PredictionMarketError::MarketIsCompleted);
+        require!(
+            !self.market.is_scalar,
+            PredictionMarketError::ScalarMarketRequiresValue
+        );
+        validate_resolution_ratios(yes_amount, no_amount, token_type)?;
+        evidence.validate()?;
+
+        let member_index = self
+            .committee
+            .member_index(&self.voter.key())
+            .ok_or(PredictionMarketError::NotCommitteeMember)?;
+
+        // 首次投票：初始化投票记录
+        if self.votes.market == Pubkey::default() {
+            self.votes.market = self.market.key();
+            self.votes.committee = self.committee.key();
+            self.votes.bump = votes_bump;
+        }
+        require!(!self.votes.escalated, PredictionMarketError::ResolutionEscalated);
+
+        // ═══════════════════════════════════════════════════════════════
+        // 记录投票
+        // ═══════════════════════════════════════════════════════════════
+        let vote = ResolverVote { token_type, yes_ratio: yes_amount, no_ratio: no_amount };
+        let matching_votes = self.votes.record(member_index, vote)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        // ═══════════════════════════════════════════════════════════════
+        // 达到阈值 → 结算（所有结算路径共用核心逻辑）
+        // ═══════════════════════════════════════════════════════════════
+        let threshold = self.committee.threshold;
+        let resolved = matching_votes >= threshold;
+        if resolved {
+            SettleContext {
+                market: &mut self.market,
+                global_vault: &self.global_vault,
+                yes_token: &self.yes_token,
+                no_token: &self.no_token,
+                global_yes_ata: &self.global_yes_ata,
+                global_no_ata: &self.global_no_ata,
+                token_program: &self.token_program,
+                event_group: self.event_group.as_deref(),
+                parent_market: self.parent_market.as_deref(),
+                authority: self.committee.key(),
+                evidence,
+                market_metadata: self.market_metadata.as_deref_mut(),
+            }
+            .settle(yes_amount, no_amount, token_type, true, global_vault_bump)?;
+        } else if !self.votes.can_reach_threshold(self.committee.member_count, threshold) {
+            // ✅ v3.9.1: 僵局 → 升级至全局管理员
+            self.votes.escalated = true;
+            msg!(
+                "⚠️ Resolver vote deadlock on market {}: escalated to global authority",
+                self.market.key()
+            );
+            emit!(ResolverVoteConflictEvent {
+                voter: self.voter.key(),
+                market: self.market.key(),
+                committee: self.committee.key(),
+                token_type,
+                yes_ratio: yes_amount,
+                no_ratio: no_amount,
+                voted_mask: self.votes.voted_mask,
+                timestamp: now,
+            });
+        }
+
+        msg!(
+            "✅ Resolver vote: member={}, token_type={}, matching={}/{}, resolved={}",
+            self.voter.key(),
+            token_type,
+            matching_votes,
+            threshold,
+            resolved
+        );
+
+        emit!(ResolverVoteCastEvent {
+            voter: self.voter.key(),
+            market: self.market.key(),
+            committee: self.committee.key(),
+            token_type,
+            yes_ratio: yes_amount,
+            no_ratio: no_amount,
+            matching_votes,
+            threshold,
+            resolved,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,428 @@
+//! ✅ v3.3.0: 分类市场交易：按 N 维 LMSR 买入/卖出单个结果代币
+//!
+//! - 买入：用户支付 USDC（含手续费），做市商铸造结果代币，q_i 增加
+//! - 卖出：用户销毁结果代币，做市商从金库支付 USDC，q_i 减少
+//! - 平台费转入团队钱包；LP 费留在金库并计入 pool_collateral_reserve，
+//!   结算时作为盈余随补贴一起退还给创建者
+
+use crate::{
+    constants::{CATEGORICAL_MARKET, CONFIG, MARKET_USDC_VAULT},
+    errors::PredictionMarketError,
+    events::CategoricalTradeEvent,
+    state::{categorical_market::*, config::*},
+    token_compat,
+    utils::ReentrancyGuard,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：分类市场交易所需账户
+#[derive(Accounts)]
+#[instruction(outcome_index: u8)]
+pub struct CategoricalSwap<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 团队钱包（仅用于验证 team_usdc_ata 的 authority）
+    /// CHECK: Verified against global_config.team_wallet
+    #[account(
+        constraint = global_config.team_wallet == team_wallet.key() @ PredictionMarketError::IncorrectAuthority
+    )]
+    pub team_wallet: AccountInfo<'info>,
+
+    /// 分类市场账户
+    #[account(
+        mut,
+        seeds = [CATEGORICAL_MARKET.as_bytes(), market.creator.as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, CategoricalMarket>>,
+
+    /// 交易的结果代币 mint（必须与 market.outcome_mints[outcome_index] 一致）
+    #[account(
+        mut,
+        constraint = market.outcome_index(outcome_index).is_ok()
+            && market.outcome_mints[outcome_index as usize] == outcome_mint.key()
+            @ PredictionMarketError::OutcomeAccountMismatch
+    )]
+    pub outcome_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 用户的结果代币 ATA（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = outcome_mint,
+        associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub user_outcome_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场USDC金库PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 团队钱包 USDC ATA（接收平台手续费）
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = team_wallet,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub team_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl CategoricalSwap<'_> {
+    /// 处理分类市场交易
+    ///
+    /// # 参数
+    /// * `outcome_index` - 结果索引（0..outcome_count）
+    /// * `direction` - 0 = 买入（amount 为 USDC），1 = 卖出（amount 为结果代币）
+    /// * `minimum_receive_amount` - 买入时最少获得代币数 / 卖出时最少获得 USDC（扣费后）
+    /// * `deadline` - 交易过期时间戳，0 表示不检查
+    pub fn handler(
+        &mut self,
+        outcome_index: u8,
+        direction: u8,
+        amount: u64,
+        minimum_receive_amount: u64,
+        deadline: i64,
+        market_usdc_vault_bump: u8,
+    ) -> Result<()> {
+        use crate::types::TradeDirection;
+
+        require!(
+            !self.global_config.is_paused,
+            PredictionMarketError::ContractPaused
+        );
+        require!(!self.market.market_paused, PredictionMarketError::MarketPaused);
+        require!(!self.market.is_completed, PredictionMarketError::CurveAlreadyCompleted);
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+        let trade_direction = TradeDirection::from_u8(direction)
+            .ok_or(PredictionMarketError::InvalidTradeDirection)?;
+
+        let clock = Clock::get()?;
+        if deadline > 0 {
+            require!(
+                clock.unix_timestamp <= deadline,
+                PredictionMarketError::TransactionExpired
+            );
+        }
+        self.market.check_trading_window(clock.slot)?;
+
+        let index = self.market.outcome_index(outcome_index)?;
+        let market_key = self.market.key();
+        let user_key = self.user.key();
+
+        // 🔒 重入保护
+        let _guard = ReentrancyGuard::new(&mut self.market.swap_in_progress)?;
+
+        let (usdc_amount, token_amount, fee_usdc) = match trade_direction {
+            TradeDirection::Buy => self.buy(index, amount, minimum_receive_amount)?,
+            TradeDirection::Sell => {
+                self.sell(index, amount, minimum_receive_amount, market_usdc_vault_bump)?
+            }
+        };
+
+        let prices_bps = self.market.prices_bps()?;
+
+        msg!(
+            "✅ Categorical swap: outcome={}, buy={}, usdc={}, tokens={}, fee={}",
+            outcome_index,
+            direction == 0,
+            usdc_amount,
+            token_amount,
+            fee_usdc
+        );
+
+        emit!(CategoricalTradeEvent {
+            user: user_key,
+            market: market_key,
+            outcome_index,
+            is_buy: direction == 0,
+            usdc_amount,
+            token_amount,
+            fee_usdc,
+            prices_bps,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// 计算平台费与 LP 费（基点）
+    fn fees(&self, amount: u64, is_buy: bool) -> Result<(u64, u64)> {
+        let (platform_bps, lp_bps) = if is_buy {
+            (self.global_config.platform_buy_fee, self.global_config.lp_buy_fee)
+        } else {
+            (self.global_config.platform_sell_fee, self.global_config.lp_sell_fee)
+        };
+
+        let platform_fee = crate::utils::bps_mul(platform_bps, amount, crate::constants::BASIS_POINTS_DIVISOR)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        let lp_fee = crate::utils::bps_mul(lp_bps, amount, crate::constants::BASIS_POINTS_DIVISOR)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        Ok((platform_fee, lp_fee))
+    }
+
+    /// 买入：返回 (支付 USDC, 获得代币, 手续费)
+    fn buy(&mut self, index: usize, amount: u64, minimum_receive_amount: u64) -> Result<(u64, u64, u64)> {
+        require!(
+            self.user_usdc_ata.amount >= amount,
+            PredictionMarketError::InsufficientBalance
+        );
+
+        let (platform_fee, lp_fee) = self.fees(amount, true)?;
+        let total_fee = platform_fee
+            .checked_add(lp_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        let amount_after_fee = amount
+            .checked_sub(total_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let token_amount = crate::math::lmsr::lmsr_tokens_for_usdc_multi(
+            self.market.lmsr_b,
+            self.market.active_q(),
+            index,
+            amount_after_fee,
+        )?;
+        require!(token_amount > 0, PredictionMarketError::InvalidAmount);
+        require!(
+            token_amount >= minimum_receive_amount,
+            PredictionMarketError::SlippageExceeded
+        );
+
+        // 1. 用户转 USDC：净额 + LP 费进入市场金库，平台费进入团队钱包
+        let usdc_to_vault = amount_after_fee
+            .checked_add(lp_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.user_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.market_usdc_ata.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            token_compat::gross_up_transfer_fee(&self.usdc_mint.to_account_info(), usdc_to_vault)?,
+            self.usdc_mint.decimals,
+        )?;
+        if platform_fee > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    self.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.user_usdc_ata.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: self.team_usdc_ata.to_account_info(),
+                        authority: self.user.to_account_info(),
+                    },
+                ),
+                platform_fee,
+                self.usdc_mint.decimals,
+            )?;
+        }
+
+        // 2. 以市场 PDA 签名铸造结果代币
+        let creator = self.market.creator;
+        let market_id_bytes = self.market.market_id.to_le_bytes();
+        let bump = [self.market.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CATEGORICAL_MARKET.as_bytes(),
+            creator.as_ref(),
+            &market_id_bytes,
+            &bump,
+        ]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.outcome_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: self.outcome_mint.to_account_info(),
+                    to: self.user_outcome_ata.to_account_info(),
+                    authority: self.market.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_amount,
+        )?;
+
+        // 3. 更新 LMSR 账本
+        let market = &mut self.market;
+        market.lmsr_q[index] = market.lmsr_q[index]
+            .checked_add(token_amount as i64)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.pool_collateral_reserve = market
+            .pool_collateral_reserve
+            .checked_add(usdc_to_vault)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        Ok((amount, token_amount, total_fee))
+    }
+
+    /// 卖出：返回 (获得 USDC, 卖出代币, 手续费)
+    fn sell(
+        &mut self,
+        index: usize,
+        amount: u64,
+        minimum_receive_amount: u64,
+        market_usdc_vault_bump: u8,
+    ) -> Result<(u64, u64, u64)> {
+        require!(
+            self.user_outcome_ata.amount >= amount,
+            PredictionMarketError::InsufficientBalance
+        );
+
+        let payout = crate::math::lmsr::lmsr_sell_payout_multi(
+            self.market.lmsr_b,
+            self.market.active_q(),
+            index,
+            amount,
+        )?;
+
+        let (platform_fee, lp_fee) = self.fees(payout, false)?;
+        let total_fee = platform_fee
+            .checked_add(lp_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        let amount_after_fee = payout
+            .checked_sub(total_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(
+            amount_after_fee >= minimum_receive_amount,
+            PredictionMarketError::SlippageExceeded
+        );
+
+        // LP 费留在金库，实际流出 = 用户净额 + 平台费
+        let outflow = amount_after_fee
+            .checked_add(platform_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(
+            self.market.pool_collateral_reserve >= outflow,
+            PredictionMarketError::InsufficientLiquidity
+        );
+
+        // 🔒 市场金库最小余额保护（与二元 swap 一致）
+        let projected_remaining = self
+            .market_usdc_ata
+            .amount
+            .checked_sub(outflow)
+            .ok_or(PredictionMarketError::InsufficientLiquidity)?;
+        require!(
+            projected_remaining >= self.global_config.usdc_vault_min_balance,
+            PredictionMarketError::InsufficientBalance
+        );
+
+        // 1. 销毁用户的结果代币
+        token_interface::burn(
+            CpiContext::new(
+                self.outcome_token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: self.outcome_mint.to_account_info(),
+                    from: self.user_outcome_ata.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        // 2. 从市场金库支付用户与团队
+        let market_key = self.market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MARKET_USDC_VAULT.as_bytes(),
+            market_key.as_ref(),
+            &[market_usdc_vault_bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.market_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.user_usdc_ata.to_account_info(),
+                    authority: self.market_usdc_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount_after_fee,
+            self.usdc_mint.decimals,
+        )?;
+        if platform_fee > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.market_usdc_ata.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: self.team_usdc_ata.to_account_info(),
+                        authority: self.market_usdc_vault.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                platform_fee,
+                self.usdc_mint.decimals,
+            )?;
+        }
+
+        // 3. 更新 LMSR 账本
+        let market = &mut self.market;
+        market.lmsr_q[index] = market.lmsr_q[index]
+            .checked_sub(amount as i64)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.pool_collateral_reserve = market
+            .pool_collateral_reserve
+            .checked_sub(outflow)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        Ok((amount_after_fee, amount, total_fee))
+    }
+}
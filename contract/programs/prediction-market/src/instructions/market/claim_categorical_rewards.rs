@@ -0,0 +1,208 @@
+//! ✅ v3.3.0: 分类市场领取奖励（结算后）
+//!
+//! 用户销毁某一结果的全部代币，按 `余额 × payout_bps[i] / 10000` 领取 USDC。
+//! 与二元 claim_rewards 一致：先从 total_collateral_locked 扣减，不足部分从 pool_collateral_reserve 扣减；
+//! 结算后即使合约暂停也允许领取，确保用户资金不会被锁定。
+
+use crate::{
+    constants::{CATEGORICAL_MARKET, CONFIG, MARKET_USDC_VAULT},
+    errors::PredictionMarketError,
+    events::CategoricalClaimEvent,
+    state::{categorical_market::*, config::*},
+    utils::ReentrancyGuard,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：分类市场领取奖励所需账户
+#[derive(Accounts)]
+#[instruction(outcome_index: u8)]
+pub struct ClaimCategoricalRewards<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 分类市场账户
+    #[account(
+        mut,
+        seeds = [CATEGORICAL_MARKET.as_bytes(), market.creator.as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, CategoricalMarket>>,
+
+    /// 领取的结果代币 mint
+    #[account(
+        mut,
+        constraint = market.outcome_index(outcome_index).is_ok()
+            && market.outcome_mints[outcome_index as usize] == outcome_mint.key()
+            @ PredictionMarketError::OutcomeAccountMismatch
+    )]
+    pub outcome_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 用户的结果代币 ATA
+    #[account(
+        mut,
+        associated_token::mint = outcome_mint,
+        associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub user_outcome_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场USDC金库PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户 USDC ATA（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl ClaimCategoricalRewards<'_> {
+    /// 领取分类市场奖励
+    ///
+    /// # 参数
+    /// * `outcome_index` - 领取的结果索引
+    pub fn handler(&mut self, outcome_index: u8, market_usdc_vault_bump: u8) -> Result<()> {
+        require!(self.market.is_completed, PredictionMarketError::MarketNotCompleted);
+
+        let index = self.market.outcome_index(outcome_index)?;
+        let market_key = self.market.key();
+
+        // 🔒 重入保护
+        let _guard = ReentrancyGuard::new(&mut self.market.claim_in_progress)?;
+
+        let tokens = self.user_outcome_ata.amount;
+        require!(tokens > 0, PredictionMarketError::InsufficientBalance);
+
+        let usdc_payout: u64 = (tokens as u128)
+            .checked_mul(self.market.payout_bps[index] as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_div(crate::constants::BASIS_POINTS_DIVISOR as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .try_into()
+            .map_err(|_| PredictionMarketError::MathOverflow)?;
+
+        // 账本：优先扣减完整集合抵押品
+        let from_locked = usdc_payout.min(self.market.total_collateral_locked);
+        let from_pool = usdc_payout
+            .checked_sub(from_locked)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(
+            self.market.pool_collateral_reserve >= from_pool,
+            PredictionMarketError::InsufficientLiquidity
+        );
+        require!(
+            self.market_usdc_ata.amount >= usdc_payout,
+            PredictionMarketError::InsufficientLiquidity
+        );
+
+        // 1. 销毁用户全部结果代币（包括赔付为 0 的结果，便于清理）
+        token_interface::burn(
+            CpiContext::new(
+                self.outcome_token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: self.outcome_mint.to_account_info(),
+                    from: self.user_outcome_ata.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            tokens,
+        )?;
+
+        // 2. 支付 USDC
+        if usdc_payout > 0 {
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                MARKET_USDC_VAULT.as_bytes(),
+                market_key.as_ref(),
+                &[market_usdc_vault_bump],
+            ]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.market_usdc_ata.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: self.user_usdc_ata.to_account_info(),
+                        authority: self.market_usdc_vault.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                usdc_payout,
+                self.usdc_mint.decimals,
+            )?;
+        }
+
+        // 3. 更新账本
+        let market = &mut self.market;
+        market.total_collateral_locked = market
+            .total_collateral_locked
+            .checked_sub(from_locked)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.pool_collateral_reserve = market
+            .pool_collateral_reserve
+            .checked_sub(from_pool)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        msg!(
+            "✅ Categorical claim: outcome={}, tokens_burned={}, usdc_payout={}",
+            outcome_index,
+            tokens,
+            usdc_payout
+        );
+
+        emit!(CategoricalClaimEvent {
+            user: self.user.key(),
+            market: market_key,
+            outcome_index,
+            tokens_burned: tokens,
+            usdc_payout,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use crate::constants::{CONFIG, MARKET, MARKET_USDC_VAULT, LPPOSITION, FEE_PER_SH
 use crate::errors::PredictionMarketError;
 use crate::state::{config::Config, market::{Market, LPPosition}};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 #[derive(Accounts)]
 pub struct ClaimFeesPreview<'info> {
@@ -33,7 +33,7 @@ pub struct ClaimFeesPreview<'info> {
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     /// 市场 USDC 金库 PDA + ATA（只读余额）
     /// CHECK: derived PDA
@@ -47,7 +47,7 @@ pub struct ClaimFeesPreview<'info> {
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
     )]
-    pub market_usdc_ata: Account<'info, TokenAccount>,
+    pub market_usdc_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// LP Position（只读）
     #[account(
@@ -33,7 +33,7 @@ use crate::{
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：LP 费用领取
@@ -84,29 +84,30 @@ pub struct ClaimLpFees<'info> {
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 🔒 v1.2.7: 市场专用 USDC 金库（支付 LP 费用）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = token_program,
     )]
-    pub market_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v1.1.0: LP 的 USDC ATA（接收费用）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = lp,
+        associated_token::token_program = token_program,
     )]
-    pub lp_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub lp_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 系统程序
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     #[account(address = anchor_spl::associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -244,17 +245,19 @@ impl<'info> ClaimLpFees<'info> {
             &[market_usdc_vault_bump],
         ]];
 
-        anchor_spl::token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                anchor_spl::token::Transfer {
+                token_interface::TransferChecked {
                     from: self.market_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
                     to: self.lp_usdc_ata.to_account_info(),
                     authority: self.market_usdc_vault.to_account_info(),
                 },
                 signer_seeds,
             ),
             fees_amount,
+            self.usdc_mint.decimals,
         )?;
 
         msg!(
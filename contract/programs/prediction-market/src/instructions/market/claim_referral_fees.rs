@@ -0,0 +1,108 @@
+//! ✅ v3.22.0: 引荐人领取累计分成
+//!
+//! 不受暂停限制：分成已从平台费中划出，属于引荐人的资金。
+
+use crate::{
+    constants::{CONFIG, REFERRER},
+    errors::PredictionMarketError,
+    events::ReferralFeesClaimedEvent,
+    state::{config::Config, referrer::ReferrerStats},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：领取引荐分成
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 引荐人统计账户
+    #[account(
+        mut,
+        seeds = [REFERRER.as_bytes(), referrer.key().as_ref()],
+        bump = referrer_stats.bump,
+        has_one = referrer @ PredictionMarketError::IncorrectAuthority,
+    )]
+    pub referrer_stats: Box<Account<'info, ReferrerStats>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 引荐分成托管 ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = referrer_stats,
+        associated_token::token_program = token_program,
+    )]
+    pub referrer_usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 引荐人 USDC 账户
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = referrer,
+        token::token_program = token_program,
+    )]
+    pub referrer_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 引荐人
+    pub referrer: Signer<'info>,
+
+    /// 抵押品（USDC）代币程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl ClaimReferralFees<'_> {
+    /// 领取托管账户中的全部分成
+    pub fn handler(&mut self) -> Result<()> {
+        let amount = self.referrer_usdc_vault.amount;
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+        let referrer_key = self.referrer.key();
+        let stats_seeds: &[&[&[u8]]] = &[&[
+            REFERRER.as_bytes(),
+            referrer_key.as_ref(),
+            &[self.referrer_stats.bump],
+        ]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.referrer_usdc_vault.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.referrer_usdc_ata.to_account_info(),
+                    authority: self.referrer_stats.to_account_info(),
+                },
+                stats_seeds,
+            ),
+            amount,
+            self.usdc_mint.decimals,
+        )?;
+
+        let stats = &mut self.referrer_stats;
+        stats.total_claimed = stats
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        emit!(ReferralFeesClaimedEvent {
+            referrer: referrer_key,
+            amount,
+            total_claimed: stats.total_claimed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("✅ v3.22.0: Referrer {} claimed {} USDC", referrer_key, amount);
+        Ok(())
+    }
+}
@@ -3,14 +3,14 @@
 use crate::{
     constants::{CONFIG, GLOBAL, MARKET, MARKET_USDC_VAULT, USERINFO},
     errors::PredictionMarketError,
-    events::ClaimRewardsEvent,
+    events::{ClaimRewardsEvent, InvalidRefundClaimedEvent},
     state::{config::*, market::*},
     utils::ReentrancyGuard,  // ✅ v1.2.3: RAII 重入保护
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：领取奖励所需账户
@@ -44,15 +44,16 @@ pub struct ClaimRewards<'info> {
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 🔒 v1.2.7: 市场专用 USDC 金库（隔离流动性）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub market_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 🔒 v1.2.7: 市场USDC金库PDA（签名权限）
     /// CHECK: market-specific usdc vault pda
@@ -64,35 +65,38 @@ pub struct ClaimRewards<'info> {
 
     /// YES 代币 mint
     #[account(mut)]
-    pub yes_token: Box<Account<'info, Mint>>,
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// NO 代币 mint
     #[account(mut)]
-    pub no_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// 用户的 YES ATA
     #[account(
         mut,
         associated_token::mint = yes_token,
         associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub user_yes_ata: Box<Account<'info, TokenAccount>>,
+    pub user_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 用户的 NO ATA
     #[account(
         mut,
         associated_token::mint = no_token,
         associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub user_no_ata: Box<Account<'info, TokenAccount>>,
+    pub user_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v1.1.0: 用户 USDC ATA（接收奖励）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub user_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 用户信息（自动创建以支持被动持有者）
     /// 🔒 修复：使用 init_if_needed 允许仅通过链上转账收到代币的用户领取奖励
@@ -112,8 +116,9 @@ pub struct ClaimRewards<'info> {
     /// 系统/代币/ATA程序
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -355,10 +360,10 @@ impl<'info> ClaimRewards<'info> {
 
         // 6.1 销毁用户的 YES 代币（如果有）
         if yes_balance > 0 {
-            token::burn(
+            token_interface::burn(
                 CpiContext::new(
-                    self.token_program.to_account_info(),
-                    token::Burn {
+                    self.outcome_token_program.to_account_info(),
+                    token_interface::Burn {
                         mint: self.yes_token.to_account_info(),
                         from: self.user_yes_ata.to_account_info(),
                         authority: self.user.to_account_info(),
@@ -371,10 +376,10 @@ impl<'info> ClaimRewards<'info> {
 
         // 6.2 销毁用户的 NO 代币（如果有）
         if no_balance > 0 {
-            token::burn(
+            token_interface::burn(
                 CpiContext::new(
-                    self.token_program.to_account_info(),
-                    token::Burn {
+                    self.outcome_token_program.to_account_info(),
+                    token_interface::Burn {
                         mint: self.no_token.to_account_info(),
                         from: self.user_no_ata.to_account_info(),
                         authority: self.user.to_account_info(),
@@ -394,17 +399,19 @@ impl<'info> ClaimRewards<'info> {
                 &[market_usdc_vault_bump],
             ]];
 
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    token::Transfer {
+                    self.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
                         from: self.market_usdc_ata.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
                         to: self.user_usdc_ata.to_account_info(),
                         authority: self.market_usdc_vault.to_account_info(),
                     },
                     signer_seeds,
                 ),
                 total_payout,
+                self.usdc_mint.decimals,
             )?;
 
             msg!("✅ Transferred {} USDC to user from market vault", total_payout);
@@ -436,6 +443,18 @@ impl<'info> ClaimRewards<'info> {
             timestamp: clock.unix_timestamp,
         });
 
+        // ✅ v3.10.0: 无效市场按价格快照退款，单独发射退款事件
+        if self.market.is_invalid() {
+            emit!(InvalidRefundClaimedEvent {
+                user: self.user.key(),
+                market: self.market.key(),
+                yes_burned: yes_balance,
+                no_burned: no_balance,
+                usdc_refund: total_payout,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
         // ✅ v1.2.3: 不需要手动清除锁 - ReentrancyGuard 的 Drop 会自动清除
 
         Ok(())
@@ -13,7 +13,7 @@ use crate::{
     state::{config::*, market::*},
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 /// 账户集合：预览领取奖励（只读）
 #[derive(Accounts)]
@@ -33,30 +33,30 @@ pub struct ClaimRewardsPreview<'info> {
     pub market: Account<'info, Market>,
 
     /// YES 代币 mint
-    pub yes_token: Account<'info, Mint>,
+    pub yes_token: InterfaceAccount<'info, Mint>,
 
     /// NO 代币 mint
-    pub no_token: Account<'info, Mint>,
+    pub no_token: InterfaceAccount<'info, Mint>,
 
     /// ✅ v1.1.0: USDC Mint
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     /// 用户的 YES ATA
     #[account(
         associated_token::mint = yes_token,
         associated_token::authority = user,
     )]
-    pub user_yes_ata: Account<'info, TokenAccount>,
+    pub user_yes_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// 用户的 NO ATA
     #[account(
         associated_token::mint = no_token,
         associated_token::authority = user,
     )]
-    pub user_no_ata: Account<'info, TokenAccount>,
+    pub user_no_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// 用户（只用来推导 ATA，不需要签名）
     /// CHECK: used to derive ATA addresses only
@@ -0,0 +1,366 @@
+//! ✅ v3.5.0: 负风险转换：k 个 NO → (k - 1) USDC + 其余成员的 YES
+//!
+//! 在恰好一个成员胜出的事件组中，持有 k 个成员各 a 个 NO 等价于：
+//! - 至少 k - 1 个 NO 必然兑现 → (k - 1) × a USDC
+//! - 若胜者不在这 k 个成员中，剩余那个 NO 也兑现 → 等价于其余 N - k 个成员各 a 个 YES
+//!
+//! 资金流与担保模型见 `state::event_group` 模块文档。
+
+use crate::{
+    constants::{CONFIG, EVENT_GROUP, GLOBAL, MARKET, MARKET_USDC_VAULT},
+    errors::PredictionMarketError,
+    events::NegRiskConvertEvent,
+    state::{config::*, event_group::*, market::*},
+    token_compat,
+};
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：负风险转换所需账户
+#[derive(Accounts)]
+pub struct ConvertNegRiskPositions<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 事件组
+    #[account(
+        mut,
+        seeds = [EVENT_GROUP.as_bytes(), &event_group.group_id.to_le_bytes()],
+        bump = event_group.bump,
+    )]
+    pub event_group: Box<Account<'info, EventGroup>>,
+
+    /// 全局金库（YES mint authority 未转移时的签名者）
+    /// CHECK: global vault pda
+    #[account(
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 事件组 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = event_group,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub group_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户 USDC ATA（接收 (k - 1) × amount）
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 代币程序（✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序）
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+}
+
+/// 负风险转换
+///
+/// # 参数
+/// * `no_mask` - 被销毁 NO 的成员掩码（第 i 位 = 成员 i）
+/// * `amount` - 每个成员的转换数量
+///
+/// # remaining_accounts（按成员索引顺序）
+/// - 掩码内成员（销毁 NO）：`[market, no_mint, user_no_ata, market_usdc_vault, market_usdc_ata]`
+/// - 掩码外成员（铸造 YES）：`[market, yes_mint, user_yes_ata]`
+///
+/// 用户的 YES/NO ATA 需预先创建
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConvertNegRiskPositions<'info>>,
+    no_mask: u16,
+    amount: u64,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    require!(
+        !accounts.global_config.is_paused,
+        PredictionMarketError::ContractPaused
+    );
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        !accounts.event_group.is_resolved,
+        PredictionMarketError::EventGroupAlreadyResolved
+    );
+    require!(
+        !accounts.event_group.is_voided,
+        PredictionMarketError::EventGroupVoided
+    );
+    require!(
+        accounts.event_group.market_count as usize >= crate::constants::MIN_EVENT_GROUP_MARKETS,
+        PredictionMarketError::EventGroupMismatch
+    );
+
+    let k = accounts.event_group.validate_convert_mask(no_mask)?;
+    let member_count = accounts.event_group.market_count as usize;
+    let members = accounts.event_group.members().to_vec();
+
+    let user_key = accounts.user.key();
+    let group_key = accounts.event_group.key();
+    let global_vault_bump = ctx.bumps.global_vault;
+
+    // ✅ v3.27.0: 事件组金库实收 USDC（抵押品带转账手续费时小于 k × amount）
+    let mut group_received: u64 = 0;
+
+    let mut remaining = ctx.remaining_accounts.iter();
+    let mut next_account = || -> Result<&'info AccountInfo<'info>> {
+        remaining
+            .next()
+            .ok_or_else(|| PredictionMarketError::EventGroupMismatch.into())
+    };
+
+    for (i, member) in members.iter().enumerate() {
+        let market_info = next_account()?;
+        require!(market_info.key() == *member, PredictionMarketError::EventGroupMismatch);
+
+        let mut market = Account::<Market>::try_from(market_info)?;
+        require!(market.event_group == group_key, PredictionMarketError::EventGroupMismatch);
+        require!(!market.is_completed, PredictionMarketError::MarketIsCompleted);
+        require!(!market.market_paused, PredictionMarketError::MarketPaused);
+
+        if no_mask & (1 << i) != 0 {
+            // ═══════════════════════════════════════════════════════
+            // 掩码内：销毁 NO，抵押品转入事件组金库
+            // ═══════════════════════════════════════════════════════
+            let no_mint_info = next_account()?;
+            let user_no_ata_info = next_account()?;
+            let vault_info = next_account()?;
+            let vault_ata_info = next_account()?;
+
+            require!(
+                no_mint_info.key() == market.no_token_mint,
+                PredictionMarketError::InvalidMint
+            );
+
+            let user_no_ata = InterfaceAccount::<TokenAccount>::try_from(user_no_ata_info)?;
+            require!(user_no_ata.mint == market.no_token_mint, PredictionMarketError::InvalidMint);
+            require!(user_no_ata.owner == user_key, PredictionMarketError::IncorrectAuthority);
+            require!(user_no_ata.amount >= amount, PredictionMarketError::InsufficientBalance);
+
+            let (expected_vault, vault_bump) = Pubkey::find_program_address(
+                &[MARKET_USDC_VAULT.as_bytes(), market_info.key.as_ref()],
+                &crate::ID,
+            );
+            require!(vault_info.key() == expected_vault, PredictionMarketError::InvalidAuthority);
+            require!(
+                vault_ata_info.key()
+                    == get_associated_token_address_with_program_id(
+                        &expected_vault,
+                        &accounts.usdc_mint.key(),
+                        &accounts.collateral_token_program.key(),
+                    ),
+                PredictionMarketError::InvalidMint
+            );
+
+            require!(
+                market.total_collateral_locked >= amount && market.total_no_minted >= amount,
+                PredictionMarketError::InsufficientLiquidity
+            );
+
+            token_interface::burn(
+                CpiContext::new(
+                    accounts.outcome_token_program.to_account_info(),
+                    token_interface::Burn {
+                        mint: no_mint_info.clone(),
+                        from: user_no_ata_info.clone(),
+                        authority: accounts.user.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+
+            let vault_seeds: &[&[&[u8]]] = &[&[
+                MARKET_USDC_VAULT.as_bytes(),
+                market_info.key.as_ref(),
+                &[vault_bump],
+            ]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    accounts.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: vault_ata_info.clone(),
+                        mint: accounts.usdc_mint.to_account_info(),
+                        to: accounts.group_usdc_ata.to_account_info(),
+                        authority: vault_info.clone(),
+                    },
+                    vault_seeds,
+                ),
+                amount,
+                accounts.usdc_mint.decimals,
+            )?;
+            group_received = amount
+                .checked_sub(token_compat::transfer_fee(&accounts.usdc_mint.to_account_info(), amount)?)
+                .and_then(|net| group_received.checked_add(net))
+                .ok_or(PredictionMarketError::MathOverflow)?;
+
+            market.total_collateral_locked = market
+                .total_collateral_locked
+                .checked_sub(amount)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            market.total_no_minted = market
+                .total_no_minted
+                .checked_sub(amount)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            market.token_no_total_supply = market.token_no_total_supply.saturating_sub(amount);
+        } else {
+            // ═══════════════════════════════════════════════════════
+            // 掩码外：铸造 YES（由事件组金库担保）
+            // ═══════════════════════════════════════════════════════
+            let yes_mint_info = next_account()?;
+            let user_yes_ata_info = next_account()?;
+
+            require!(
+                yes_mint_info.key() == market.yes_token_mint,
+                PredictionMarketError::InvalidMint
+            );
+            let yes_mint = InterfaceAccount::<Mint>::try_from(yes_mint_info)?;
+
+            let user_yes_ata = InterfaceAccount::<TokenAccount>::try_from(user_yes_ata_info)?;
+            require!(user_yes_ata.mint == market.yes_token_mint, PredictionMarketError::InvalidMint);
+            require!(user_yes_ata.owner == user_key, PredictionMarketError::IncorrectAuthority);
+
+            // ✅ 与 mint_complete_set 一致：支持 global_vault 或 market PDA 作为 mint authority
+            let market_key = market_info.key();
+            if yes_mint.mint_authority == COption::Some(market_key) {
+                let (_, market_bump) = Pubkey::find_program_address(
+                    &[
+                        MARKET.as_bytes(),
+                        &market.yes_token_mint.to_bytes(),
+                        &market.no_token_mint.to_bytes(),
+                    ],
+                    &crate::ID,
+                );
+                let yes_bytes = market.yes_token_mint.to_bytes();
+                let no_bytes = market.no_token_mint.to_bytes();
+                let market_seeds: &[&[&[u8]]] =
+                    &[&[MARKET.as_bytes(), &yes_bytes, &no_bytes, &[market_bump]]];
+                token_interface::mint_to(
+                    CpiContext::new_with_signer(
+                        accounts.outcome_token_program.to_account_info(),
+                        token_interface::MintTo {
+                            mint: yes_mint_info.clone(),
+                            to: user_yes_ata_info.clone(),
+                            authority: market_info.clone(),
+                        },
+                        market_seeds,
+                    ),
+                    amount,
+                )?;
+            } else {
+                let global_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+                token_interface::mint_to(
+                    CpiContext::new_with_signer(
+                        accounts.outcome_token_program.to_account_info(),
+                        token_interface::MintTo {
+                            mint: yes_mint_info.clone(),
+                            to: user_yes_ata_info.clone(),
+                            authority: accounts.global_vault.to_account_info(),
+                        },
+                        global_seeds,
+                    ),
+                    amount,
+                )?;
+            }
+
+            market.total_yes_minted = market
+                .total_yes_minted
+                .checked_add(amount)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            market.token_yes_total_supply = market
+                .token_yes_total_supply
+                .checked_add(amount)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        }
+
+        // 持久化成员市场账本（remaining_accounts 不会被 Anchor 自动写回）
+        market.exit(&crate::ID)?;
+    }
+
+    require!(
+        remaining.next().is_none(),
+        PredictionMarketError::EventGroupMismatch
+    );
+
+    // ═══════════════════════════════════════════════════════════════
+    // 事件组金库向用户支付 (k - 1) × amount USDC
+    // ✅ v3.27.0: 金库恰好留存 amount 作为胜者担保，转入手续费由用户承担
+    // ═══════════════════════════════════════════════════════════════
+    let usdc_out = group_received
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::InsufficientLiquidity)?;
+
+    if usdc_out > 0 {
+        let group_id_bytes = accounts.event_group.group_id.to_le_bytes();
+        let group_seeds: &[&[&[u8]]] = &[&[
+            EVENT_GROUP.as_bytes(),
+            &group_id_bytes,
+            &[accounts.event_group.bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: accounts.group_usdc_ata.to_account_info(),
+                    mint: accounts.usdc_mint.to_account_info(),
+                    to: accounts.user_usdc_ata.to_account_info(),
+                    authority: accounts.event_group.to_account_info(),
+                },
+                group_seeds,
+            ),
+            usdc_out,
+            accounts.usdc_mint.decimals,
+        )?;
+    }
+
+    let group = &mut accounts.event_group;
+    group.total_converted = group
+        .total_converted
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let yes_markets_minted = (member_count as u32 - k) as u8;
+
+    msg!(
+        "✅ Neg-risk convert: mask={:#b}, amount={}, usdc_out={}, yes_markets={}, total_converted={}",
+        no_mask,
+        amount,
+        usdc_out,
+        yes_markets_minted,
+        group.total_converted
+    );
+
+    emit!(NegRiskConvertEvent {
+        user: user_key,
+        event_group: group_key,
+        no_mask,
+        amount,
+        usdc_out,
+        yes_markets_minted,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,295 @@
+//! ✅ v3.3.0: 市场指令：创建分类市场（N 结果）
+//!
+//! 结果代币 mint 由客户端预先创建，并通过 remaining_accounts 按结果索引顺序传入：
+//! - mint authority 必须是本分类市场 PDA（地址可由 [CATEGORICAL_MARKET, creator, market_id] 推导）
+//! - supply 必须为 0、无 freeze authority、精度与 token_decimals_config 一致
+//!
+//! 由于 mint authority 绑定到唯一的市场 PDA，同一 mint 无法被其他市场复用
+//! （等价于二元市场 NO 代币的哨兵机制，但无需额外铸造）。
+
+use crate::{
+    constants::{CATEGORICAL_MARKET, CONFIG, MARKET_USDC_VAULT},
+    errors::PredictionMarketError,
+    events::CategoricalMarketCreatedEvent,
+    state::{categorical_market::*, config::*, whitelist::*},
+    token_compat,
+};
+use anchor_lang::{prelude::*, solana_program::program_option::COption, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：创建分类市场所需账户
+#[derive(Accounts)]
+#[instruction(params: CreateCategoricalMarketParams)]
+pub struct CreateCategoricalMarket<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 创建者（支付租金和做市补贴）
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// 白名单账户（可选，取决于 global_config.whitelist_enabled）
+    #[account(
+        seeds = [Whitelist::SEED_PREFIX.as_bytes(), creator.key().as_ref()],
+        bump,
+    )]
+    pub creator_whitelist: Option<Account<'info, Whitelist>>,
+
+    /// 分类市场账户
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CategoricalMarket::INIT_SPACE,
+        seeds = [CATEGORICAL_MARKET.as_bytes(), creator.key().as_ref(), &params.market_id.to_le_bytes()],
+        bump
+    )]
+    pub market: Box<Account<'info, CategoricalMarket>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场USDC金库PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 市场专用 USDC 金库（存放补贴、交易收入和完整集合抵押品）
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 创建者 USDC ATA（支付做市补贴）
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub creator_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// 创建分类市场
+///
+/// # remaining_accounts
+/// `[outcome_mint_0, outcome_mint_1, ..., outcome_mint_{N-1}]`
+///
+/// # 流程
+/// 1. 暂停/白名单/参数校验
+/// 2. 校验每个结果 mint（authority = 市场 PDA、supply = 0、无 freeze、精度一致、互不重复）
+/// 3. 计算补贴 = ceil(b × ln(N))，由创建者转入市场金库
+/// 4. 初始化市场状态并发射事件
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateCategoricalMarket<'info>>,
+    params: CreateCategoricalMarketParams,
+) -> Result<()> {
+    msg!("CreateCategoricalMarket start: outcomes={}", params.outcome_count);
+
+    let accounts = ctx.accounts;
+
+    require!(
+        !accounts.global_config.is_paused,
+        PredictionMarketError::ContractPaused
+    );
+
+    // ✅ 白名单验证（与 create_market 一致）
+    if accounts.global_config.whitelist_enabled {
+        let whitelist = accounts
+            .creator_whitelist
+            .as_ref()
+            .ok_or(PredictionMarketError::CreatorNotWhitelisted)?;
+        require!(
+            whitelist.creator == accounts.creator.key(),
+            PredictionMarketError::IncorrectAuthority
+        );
+    }
+
+    require!(
+        accounts.usdc_mint.decimals == crate::constants::USDC_DECIMALS,
+        PredictionMarketError::InvalidParameter
+    );
+    // ✅ v3.27.0: 抵押品 mint 扩展白名单（转账钩子等扩展会破坏金库记账）
+    token_compat::validate_collateral_mint(&accounts.usdc_mint.to_account_info())?;
+
+    CategoricalMarket::validate_outcome_count(params.outcome_count)?;
+    let outcome_count = params.outcome_count as usize;
+
+    require!(
+        !params.display_name.is_empty()
+            && params.display_name.len() <= CategoricalMarket::MAX_DISPLAY_NAME_LEN,
+        PredictionMarketError::InvalidParameter
+    );
+
+    // ═══════════════════════════════════════════════════════════════
+    // 时间槽校验（与 create_market 一致）
+    // ═══════════════════════════════════════════════════════════════
+    let clock = Clock::get()?;
+
+    if let Some(start) = params.start_slot {
+        let max_allowed_start = clock.slot
+            .checked_add(crate::constants::MAX_START_SLOT_DELAY)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(
+            start > clock.slot && start <= max_allowed_start,
+            PredictionMarketError::InvalidStartTime
+        );
+    }
+    if let Some(end) = params.ending_slot {
+        require!(end > clock.slot, PredictionMarketError::InvalidEndTime);
+    }
+    if let (Some(start), Some(end)) = (params.start_slot, params.ending_slot) {
+        require!(start < end, PredictionMarketError::InvalidEndTime);
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // LMSR 参数与补贴
+    // ═══════════════════════════════════════════════════════════════
+    let lmsr_b = if params.lmsr_b == 0 {
+        accounts.global_config.initial_real_token_reserves_config
+    } else {
+        params.lmsr_b
+    };
+    require!(
+        lmsr_b > 0 && lmsr_b <= crate::constants::MAX_LMSR_B,
+        PredictionMarketError::InvalidParameter
+    );
+
+    // LMSR 最大亏损 = C(0) = b × ln(N)，+1 抵消定点数向下取整
+    let zero_q = vec![0i64; outcome_count];
+    let subsidy = (crate::math::lmsr::lmsr_cost_multi(lmsr_b, &zero_q)? as u64)
+        .checked_add(1)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 结果 mint 校验
+    // ═══════════════════════════════════════════════════════════════
+    require!(
+        ctx.remaining_accounts.len() == outcome_count,
+        PredictionMarketError::OutcomeAccountMismatch
+    );
+
+    let market_key = accounts.market.key();
+    let mut outcome_mints = [Pubkey::default(); crate::constants::MAX_CATEGORICAL_OUTCOMES];
+
+    for (i, mint_info) in ctx.remaining_accounts.iter().enumerate() {
+        // ✅ v3.27.1: 结果 mint 须属于 outcome_token_program（可与抵押品代币程序不同）
+        require_keys_eq!(
+            *mint_info.owner,
+            accounts.outcome_token_program.key(),
+            PredictionMarketError::TokenProgramMismatch
+        );
+        let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+
+        require!(
+            mint.mint_authority == COption::Some(market_key),
+            PredictionMarketError::InvalidAuthority
+        );
+        require!(mint.supply == 0, PredictionMarketError::TokenAlreadyInUse);
+        require!(mint.freeze_authority.is_none(), PredictionMarketError::InvalidParameter);
+        require!(
+            mint.decimals == accounts.global_config.token_decimals_config,
+            PredictionMarketError::InvalidParameter
+        );
+        require!(
+            !outcome_mints[..i].contains(&mint_info.key()),
+            PredictionMarketError::OutcomeAccountMismatch
+        );
+
+        outcome_mints[i] = mint_info.key();
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // 创建者注入补贴
+    // ═══════════════════════════════════════════════════════════════
+    token_interface::transfer_checked(
+        CpiContext::new(
+            accounts.collateral_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: accounts.creator_usdc_ata.to_account_info(),
+                mint: accounts.usdc_mint.to_account_info(),
+                to: accounts.market_usdc_ata.to_account_info(),
+                authority: accounts.creator.to_account_info(),
+            },
+        ),
+        token_compat::gross_up_transfer_fee(&accounts.usdc_mint.to_account_info(), subsidy)?,
+        accounts.usdc_mint.decimals,
+    )?;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 初始化市场账户
+    // ═══════════════════════════════════════════════════════════════
+    let market = &mut accounts.market;
+    market.creator = accounts.creator.key();
+    market.market_id = params.market_id;
+    market.outcome_count = params.outcome_count;
+    market.outcome_mints = outcome_mints;
+
+    market.total_collateral_locked = 0;
+    market.total_sets_minted = 0;
+
+    market.pool_collateral_reserve = subsidy;
+    market.subsidy = subsidy;
+    market.lmsr_b = lmsr_b;
+    market.lmsr_q = [0; crate::constants::MAX_CATEGORICAL_OUTCOMES];
+
+    market.start_slot = params.start_slot;
+    market.ending_slot = params.ending_slot;
+    market.is_completed = false;
+    market.payout_bps = [0; crate::constants::MAX_CATEGORICAL_OUTCOMES];
+    market.winning_outcome = 0;
+
+    market.swap_in_progress = false;
+    market.claim_in_progress = false;
+    market.market_paused = false;
+
+    market.display_name = params.display_name;
+    market.created_at = clock.unix_timestamp;
+    market.bump = ctx.bumps.market;
+
+    msg!(
+        "✅ Categorical market initialized: outcomes={}, lmsr_b={}, subsidy={}",
+        outcome_count,
+        lmsr_b,
+        subsidy
+    );
+
+    emit!(CategoricalMarketCreatedEvent {
+        creator: market.creator,
+        market: market_key,
+        outcome_count: params.outcome_count,
+        outcome_mints: market.active_mints().to_vec(),
+        lmsr_b,
+        subsidy,
+        start_slot: params.start_slot.unwrap_or(0),
+        ending_slot: params.ending_slot.unwrap_or(0),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
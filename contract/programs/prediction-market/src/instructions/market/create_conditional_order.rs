@@ -0,0 +1,186 @@
+//! ✅ v3.21.0: 条件订单：创建（托管代币并设置 YES 价格触发条件）
+
+use crate::{
+    constants::{CONDITIONAL_ORDER, CONFIG, MARKET},
+    errors::PredictionMarketError,
+    events::ConditionalOrderCreatedEvent,
+    state::{conditional_order::*, config::*, market::*},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：创建条件订单
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateConditionalOrder<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 条件订单
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ConditionalOrder::INIT_SPACE,
+        seeds = [CONDITIONAL_ORDER.as_bytes(), market.key().as_ref(), owner.key().as_ref(), &order_id.to_le_bytes()],
+        bump
+    )]
+    pub conditional_order: Box<Account<'info, ConditionalOrder>>,
+
+    /// 托管代币的 mint（YES 或 NO，与 token_type 一致）
+    #[account(
+        constraint = token_mint.key() == market.yes_token_mint
+            || token_mint.key() == market.no_token_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 订单托管代币 ATA
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = token_mint,
+        associated_token::authority = conditional_order,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub escrow_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 订单 USDC ATA（执行时暂存卖出所得，随后分配给下单人与 keeper）
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = conditional_order,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub escrow_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 下单人代币 ATA
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub owner_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 下单人
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl CreateConditionalOrder<'_> {
+    /// 创建条件订单
+    ///
+    /// # 参数
+    /// * `order_id` - 订单编号（下单人自选，同一市场内不可重复）
+    /// * `token_type` - 代币类型（0=NO, 1=YES）
+    /// * `trigger_direction` - 触发方向（0=YES 价格 ≤ 触发价, 1=YES 价格 ≥ 触发价）
+    /// * `trigger_price_bps` - 触发价（YES 价格，基点，1-9999）
+    /// * `amount` - 托管并在触发后卖出的代币数量
+    /// * `min_usdc_out` - 扣除 keeper 费后的最少所得（USDC）
+    /// * `keeper_fee_bps` - Keeper 执行费（基点，≤ MAX_KEEPER_FEE_BPS）
+    #[allow(clippy::too_many_arguments)]
+    pub fn handler(
+        &mut self,
+        order_id: u64,
+        token_type: u8,
+        trigger_direction: u8,
+        trigger_price_bps: u16,
+        amount: u64,
+        min_usdc_out: u64,
+        keeper_fee_bps: u16,
+        bump: u8,
+    ) -> Result<()> {
+        require!(!self.global_config.is_paused, PredictionMarketError::ContractPaused);
+        require!(!self.market.is_completed, PredictionMarketError::CurveAlreadyCompleted);
+
+        let expected_mint = if token_type == 1 {
+            self.market.yes_token_mint
+        } else {
+            self.market.no_token_mint
+        };
+        require!(self.token_mint.key() == expected_mint, PredictionMarketError::InvalidMint);
+
+        let now = Clock::get()?.unix_timestamp;
+        let order = &mut self.conditional_order;
+        order.market = self.market.key();
+        order.owner = self.owner.key();
+        order.order_id = order_id;
+        order.token_type = token_type;
+        order.trigger_direction = trigger_direction;
+        order.trigger_price_bps = trigger_price_bps;
+        order.amount = amount;
+        order.min_usdc_out = min_usdc_out;
+        order.keeper_fee_bps = keeper_fee_bps;
+        order.created_at = now;
+        order.bump = bump;
+        order.validate()?;
+
+        // 托管代币
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.outcome_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.owner_token_ata.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.escrow_token_ata.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            amount,
+            self.token_mint.decimals,
+        )?;
+
+        emit!(ConditionalOrderCreatedEvent {
+            market: self.market.key(),
+            order: self.conditional_order.key(),
+            owner: self.owner.key(),
+            order_id,
+            token_type,
+            trigger_direction,
+            trigger_price_bps,
+            amount,
+            min_usdc_out,
+            keeper_fee_bps,
+            timestamp: now,
+        });
+
+        msg!(
+            "✅ v3.21.0: Conditional order #{} created: token_type={}, trigger_direction={}, trigger={}bps, amount={}",
+            order_id,
+            token_type,
+            trigger_direction,
+            trigger_price_bps,
+            amount
+        );
+        Ok(())
+    }
+}
@@ -1,16 +1,17 @@
 //! 市场指令：创建市场（含YES mint、元数据、金库ATA等）
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET, METADATA},
+    constants::{CONFIG, GLOBAL, MARKET, MARKET_METADATA, METADATA},
     errors::*,
-    events::CreateEvent,
-    state::{config::*, market::*, whitelist::*},
+    events::{CreateEvent, MarketMetadataCreatedEvent},
+    state::{config::*, market::*, market_metadata::*, resolver::*, whitelist::*},
+    token_compat,
 };
 use anchor_lang::{prelude::*, solana_program::sysvar::SysvarId, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
     metadata::{self, Metadata},
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：创建市场所需账户
@@ -46,22 +47,32 @@ pub struct CreateMarket<'info> {
     creator_whitelist: Option<Account<'info, Whitelist>>,
 
     /// YES代币mint（由全局金库作为mint authority）
+    /// ✅ v3.27.0: init_if_needed —— Token-2022 部署中可传入 create_outcome_mint 预先创建的 mint
     #[account(
-        init,
+        init_if_needed,
         payer = creator,
         mint::decimals = global_config.token_decimals_config,
         mint::authority = global_vault.key(),
+        mint::token_program = outcome_token_program,
     )]
-    yes_token: Box<Account<'info, Mint>>,
+    yes_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// NO代币mint（需在mint_no_token指令中创建）
     /// ✅ FIX: 验证 mint authority 是全局金库，防止恶意铸造
     #[account(
         mut,
         constraint = no_token.mint_authority == anchor_lang::solana_program::program_option::COption::Some(global_vault.key())
-            @ PredictionMarketError::InvalidAuthority
+            @ PredictionMarketError::InvalidAuthority,
+        mint::token_program = outcome_token_program,
+    )]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// ✅ v3.27.0: 抵押品 mint（校验扩展白名单；✅ v3.27.1: 须属于 collateral_token_program）
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint,
+        mint::token_program = collateral_token_program,
     )]
-    pub no_token: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 市场账户（以YES/NO mint作为种子）
     /// 🔒 v1.2.2: 使用 Market::INIT_SPACE 正确分配空间（包含 display_name String）
@@ -74,6 +85,16 @@ pub struct CreateMarket<'info> {
     )]
     market: Box<Account<'info, Market>>,
 
+    /// ✅ v3.13.0: 市场元数据账户（规则承诺与结算证据）
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketMetadata::INIT_SPACE,
+        seeds = [MARKET_METADATA.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    market_metadata: Box<Account<'info, MarketMetadata>>,
+
     /// YES元数据账户（传递给 Metadata 程序）
     /// CHECK: passed to token metadata program
     #[account(mut,
@@ -107,7 +128,7 @@ pub struct CreateMarket<'info> {
         mut,
         seeds = [
             global_vault.key().as_ref(),
-            token::spl_token::ID.as_ref(),
+            outcome_token_program.key().as_ref(),
             yes_token.key().as_ref(),
         ],
         bump,
@@ -122,16 +143,18 @@ pub struct CreateMarket<'info> {
         payer = creator,
         associated_token::mint = no_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    global_no_token_account: Box<Account<'info, TokenAccount>>,
+    global_no_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 系统/租金/代币/ATA/元数据程序
     #[account(address = system_program::ID)]
     system_program: Program<'info, System>,
     #[account(address = Rent::id())]
     rent: Sysvar<'info, Rent>,
-    #[account(address = token::ID)]
-    token_program: Program<'info, Token>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    collateral_token_program: Interface<'info, TokenInterface>,
+    outcome_token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     associated_token_program: Program<'info, AssociatedToken>,
     #[account(address = metadata::ID)]
@@ -144,11 +167,17 @@ pub struct CreateMarket<'info> {
         constraint = global_config.team_wallet == team_wallet.key() @PredictionMarketError::IncorrectAuthority
     )]
     pub team_wallet: UncheckedAccount<'info>,
+
+    /// ✅ v3.6.0: 父市场（可选，仅创建条件市场时传入）
+    pub parent_market: Option<Box<Account<'info, Market>>>,
+
+    /// ✅ v3.9.0: 结算委员会（可选，指定 M-of-N 委员会作为结算人时传入）
+    pub resolver_committee: Option<Box<Account<'info, ResolverCommittee>>>,
 }
 
 impl<'info> CreateMarket<'info> {
     /// 处理创建市场：初始化市场状态和代币元数据
-    pub fn handler(&mut self, params: CreateMarketParams, _global_vault_bump: u8) -> Result<()> {
+    pub fn handler(&mut self, params: CreateMarketParams, _global_vault_bump: u8, market_metadata_bump: u8) -> Result<()> {
         msg!("CreateMarket start");
 
         // ═══════════════════════════════════════════════════════════════
@@ -247,6 +276,22 @@ impl<'info> CreateMarket<'info> {
             PredictionMarketError::TokenAlreadyInUse
         );
 
+        // ═══════════════════════════════════════════════════════════════
+        // ✅ v3.27.0: Token-2022 兼容校验
+        // ═══════════════════════════════════════════════════════════════
+        // ✅ v3.27.1: 代币程序归属由账户约束校验（YES/NO → outcome_token_program，
+        // 抵押品 → collateral_token_program），两者可以不同
+        token_compat::validate_collateral_mint(&self.usdc_mint.to_account_info())?;
+        token_compat::validate_outcome_mint(&self.yes_token.to_account_info())?;
+        token_compat::validate_outcome_mint(&self.no_token.to_account_info())?;
+
+        // 预先创建的 YES mint 唯一性：供应为 0 且全局 YES ATA 尚未创建
+        // （每个市场创建时都会创建全局 YES ATA，已被使用过的 YES mint 必然不满足）
+        require!(
+            self.yes_token.supply == 0 && self.global_yes_token_account.data_is_empty(),
+            PredictionMarketError::TokenAlreadyInUse
+        );
+
         msg!(
             "✅ Token validation passed: YES/NO decimals={}, freeze_authority=None, NO supply=0",
             self.no_token.decimals
@@ -259,10 +304,10 @@ impl<'info> CreateMarket<'info> {
             &[_global_vault_bump],
         ]];
 
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::MintTo {
+                self.outcome_token_program.to_account_info(),
+                token_interface::MintTo {
                     mint: self.no_token.to_account_info(),
                     to: self.global_no_token_account.to_account_info(),
                     authority: self.global_vault.to_account_info(),
@@ -389,6 +434,23 @@ impl<'info> CreateMarket<'info> {
         market.start_slot = params.start_slot;
         market.ending_slot = params.ending_slot;
 
+        // ✅ v3.14.0: Unix 时间戳排期（与 slot 排期相同的校验规则，以 Clock::unix_timestamp 为准）
+        if let Some(start) = params.start_time {
+            require!(start > clock.unix_timestamp, PredictionMarketError::InvalidStartTime);
+            let max_allowed_start = clock.unix_timestamp
+                .checked_add(crate::constants::MAX_START_TIME_DELAY_SECS)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            require!(start <= max_allowed_start, PredictionMarketError::InvalidStartTime);
+        }
+        if let (Some(start), Some(end)) = (params.start_time, params.end_time) {
+            require!(start < end, PredictionMarketError::InvalidEndTime);
+        }
+        if let Some(end) = params.end_time {
+            require!(end > clock.unix_timestamp, PredictionMarketError::InvalidEndTime);
+        }
+        market.start_time = params.start_time.unwrap_or(0);
+        market.end_time = params.end_time.unwrap_or(0);
+
         // ✅ 初始化Resolution参数
         market.resolution_yes_ratio = 0;
         market.resolution_no_ratio = 0;
@@ -457,6 +519,140 @@ impl<'info> CreateMarket<'info> {
         // ✅ v3.1.3: 初始化哨兵代币追踪（防 NO mint 复用）
         market.sentinel_no_minted = true;  // create_market 总是铸造 1 个哨兵
 
+        // ✅ v3.4.0: 标量市场区间（YES = LONG，NO = SHORT）
+        if let Some(bounds) = params.scalar_bounds {
+            bounds.validate()?;
+            market.is_scalar = true;
+            market.scalar_lower_bound = bounds.lower;
+            market.scalar_upper_bound = bounds.upper;
+            msg!("✅ v3.4.0 Scalar market: range=[{}, {}]", bounds.lower, bounds.upper);
+        } else {
+            market.is_scalar = false;
+            market.scalar_lower_bound = 0;
+            market.scalar_upper_bound = 0;
+        }
+        market.scalar_resolved_value = 0;
+
+        // ✅ v3.5.0: 新市场不属于任何事件组（通过 add_market_to_event_group 加入）
+        market.event_group = Pubkey::default();
+
+        // ✅ v3.6.0: 条件市场（父市场未按要求结算时可由任何人作废并退款）
+        match (&self.parent_market, params.parent_required_outcome) {
+            (Some(parent), Some(required_outcome)) => {
+                require!(
+                    required_outcome <= 1
+                        && !parent.is_completed
+                        && !parent.is_scalar
+                        && parent.key() != market.key(),
+                    PredictionMarketError::InvalidParentMarket
+                );
+                market.parent_market = parent.key();
+                market.parent_required_outcome = required_outcome;
+                msg!(
+                    "✅ v3.6.0 Conditional market: parent={}, required_outcome={}",
+                    parent.key(),
+                    required_outcome
+                );
+            }
+            (None, None) => {
+                market.parent_market = Pubkey::default();
+                market.parent_required_outcome = 0;
+            }
+            _ => return Err(PredictionMarketError::InvalidParentMarket.into()),
+        }
+        market.is_voided = false;
+
+        // ✅ v3.8.0: 价格源自动结算（任何人可通过 resolve_from_feed 结算）
+        if let Some(feed) = params.price_feed {
+            feed.validate()?;
+            require!(!market.is_scalar, PredictionMarketError::InvalidPriceFeedConfig);
+            market.price_feed = feed.feed;
+            market.price_feed_id = feed.feed_id;
+            market.price_threshold = feed.threshold;
+            market.price_threshold_expo = feed.threshold_expo;
+            market.price_resolve_after = feed.resolve_after;
+            market.price_max_staleness_secs = feed.max_staleness_secs;
+            market.price_max_conf_bps = feed.max_conf_bps;
+            msg!(
+                "✅ v3.8.0 Price feed market: feed={}, threshold={}e{}, resolve_after={}",
+                feed.feed,
+                feed.threshold,
+                feed.threshold_expo,
+                feed.resolve_after
+            );
+        } else {
+            market.price_feed = Pubkey::default();
+            market.price_feed_id = [0u8; 32];
+            market.price_threshold = 0;
+            market.price_threshold_expo = 0;
+            market.price_resolve_after = 0;
+            market.price_max_staleness_secs = 0;
+            market.price_max_conf_bps = 0;
+        }
+
+        // ✅ v3.9.0: 市场级结算人（单一密钥或 M-of-N 委员会；全局管理员始终保留结算权）
+        match (params.resolver, &self.resolver_committee) {
+            (Some(resolver), None) => {
+                require!(resolver != Pubkey::default(), PredictionMarketError::InvalidResolver);
+                market.resolver = resolver;
+                market.resolver_is_committee = false;
+                msg!("✅ v3.9.0 Market resolver: {}", resolver);
+            }
+            (None, Some(committee)) => {
+                market.resolver = committee.key();
+                market.resolver_is_committee = true;
+                msg!(
+                    "✅ v3.9.0 Market resolver committee: {} ({}-of-{})",
+                    committee.key(),
+                    committee.threshold,
+                    committee.member_count
+                );
+            }
+            (None, None) => {
+                market.resolver = Pubkey::default();
+                market.resolver_is_committee = false;
+            }
+            (Some(_), Some(_)) => return Err(PredictionMarketError::InvalidResolver.into()),
+        }
+
+        // ✅ v3.10.0: 价格快照初始为初始概率（无成交时 Invalid 按初始概率退款）
+        market.last_trade_yes_price_bps = initial_yes_prob;
+
+        // ✅ v3.11.0: 结算截止 = ending_slot + 宽限期（无结束时间的市场不设截止）
+        let grace_slots = params
+            .resolution_grace_slots
+            .unwrap_or(crate::constants::DEFAULT_RESOLUTION_GRACE_SLOTS);
+        require!(
+            (crate::constants::MIN_RESOLUTION_GRACE_SLOTS..=crate::constants::MAX_RESOLUTION_GRACE_SLOTS)
+                .contains(&grace_slots),
+            PredictionMarketError::InvalidResolutionDeadline
+        );
+        // ✅ v3.14.0: 仅有 end_time 的市场按估算 slot 时长换算结束 slot（void_unresolved_market 另行校验 end_time 已过）
+        let end_slot = match (params.ending_slot, params.end_time) {
+            (Some(end), _) => Some(end),
+            (None, Some(end_time)) => {
+                let secs_to_end = end_time.saturating_sub(clock.unix_timestamp) as u64;
+                Some(
+                    secs_to_end
+                        .checked_mul(1000)
+                        .and_then(|ms| ms.checked_div(crate::constants::ESTIMATED_SLOT_MS as u64))
+                        .and_then(|slots| clock.slot.checked_add(slots))
+                        .ok_or(PredictionMarketError::MathOverflow)?,
+                )
+            }
+            (None, None) => None,
+        };
+        market.resolution_deadline_slot = match end_slot {
+            Some(end) => end
+                .checked_add(grace_slots)
+                .ok_or(PredictionMarketError::MathOverflow)?,
+            None => 0,
+        };
+
+        // ✅ v3.13.0: 规则承诺（规则文本哈希、结算来源、分类标签）
+        params.rules.validate()?;
+        market.has_metadata = true;
+
         msg!("Market initialized: LMSR_b={}, initial_yes_prob={}% ({}bps), created_at={}, insurance_contribution=0",
             market.lmsr_b,
             initial_yes_prob / 100,
@@ -465,6 +661,8 @@ impl<'info> CreateMarket<'info> {
         );
 
         // ⚠️ 设计决策：元数据创建在链下或后续交易中完成
+        // ✅ v3.27.0: Token-2022 部署中 YES/NO 元数据已由 create_outcome_mint 写入 mint（metadata-pointer），
+        //    yes/no_token_metadata_account 仅作为 Metaplex PDA 记录在事件中
         //
         // 原因：
         // 1. 计算单元限制：Metaplex create_metadata_accounts_v3 CPI 消耗大量CU
@@ -483,23 +681,21 @@ impl<'info> CreateMarket<'info> {
         // 注意：CreateMarketParams 中包含 yes_symbol 和 yes_uri 供前端使用
         msg!("✅ Market tokens created. Metadata creation delegated to client for CU optimization");
 
-        // 创建全局金库的YES代币账户（如果不存在）
-        if self.global_yes_token_account.data_is_empty() {
-            anchor_spl::associated_token::create(
-                CpiContext::new(
-                    self.associated_token_program.to_account_info(),
-                    anchor_spl::associated_token::Create {
-                        payer: self.creator.to_account_info(),
-                        associated_token: self.global_yes_token_account.to_account_info(),
-                        authority: self.global_vault.to_account_info(),
-                        mint: self.yes_token.to_account_info(),
-                        system_program: self.system_program.to_account_info(),
-                        token_program: self.token_program.to_account_info(),
-                    },
-                ),
-            )?;
-            msg!("Global YES token account created");
-        }
+        // 创建全局金库的YES代币账户（✅ v3.27.0: 上方已校验其尚不存在）
+        anchor_spl::associated_token::create(
+            CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: self.creator.to_account_info(),
+                    associated_token: self.global_yes_token_account.to_account_info(),
+                    authority: self.global_vault.to_account_info(),
+                    mint: self.yes_token.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.outcome_token_program.to_account_info(),
+                },
+            ),
+        )?;
+        msg!("Global YES token account created");
 
         // ═══════════════════════════════════════════════════════════════
         // ✅ 发射市场创建事件
@@ -515,6 +711,31 @@ impl<'info> CreateMarket<'info> {
             token_no_total_supply: market.token_no_total_supply,
             start_slot: params.start_slot.unwrap_or(0),
             ending_slot: params.ending_slot.unwrap_or(0),
+            start_time: market.start_time,
+            end_time: market.end_time,
+        });
+
+        // ✅ v3.13.0: 写入市场元数据（结算证据在 resolution 时补充）
+        let metadata = &mut self.market_metadata;
+        metadata.market = market_key;
+        metadata.rules_hash = params.rules.rules_hash;
+        metadata.resolution_source_uri = params.rules.resolution_source_uri;
+        metadata.category = params.rules.category;
+        metadata.tags = params.rules.tags;
+        metadata.evidence_hash = [0u8; 32];
+        metadata.evidence_uri = String::new();
+        metadata.resolved_by = Pubkey::default();
+        metadata.resolved_at = 0;
+        metadata.bump = market_metadata_bump;
+
+        emit!(MarketMetadataCreatedEvent {
+            market: market_key,
+            metadata: metadata.key(),
+            rules_hash: metadata.rules_hash,
+            resolution_source_uri: metadata.resolution_source_uri.clone(),
+            category: metadata.category.clone(),
+            tags: metadata.tags.clone(),
+            timestamp: clock.unix_timestamp,
         });
 
         msg!("CreateMarket completed successfully");
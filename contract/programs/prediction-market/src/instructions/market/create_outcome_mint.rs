@@ -0,0 +1,120 @@
+//! ✅ v3.27.0: 市场指令：创建 Token-2022 结果代币 mint（metadata-pointer 扩展，不经过 Metaplex）
+//!
+//! 元数据（name / symbol / uri）写入 mint 自身的 TokenMetadata 扩展，metadata pointer 指向 mint。
+//! 创建后将 YES mint 传入 create_market（YES 为 init_if_needed）、NO mint 直接传入 create_market，
+//! 替代 SPL Token 部署中的 mint_no_token + Metaplex 元数据流程。
+
+use crate::{
+    constants::{CONFIG, GLOBAL, MAX_OUTCOME_SYMBOL_LEN, MAX_OUTCOME_URI_LEN, NO_NAME, YES_NAME},
+    errors::PredictionMarketError,
+    events::OutcomeMintCreatedEvent,
+    state::config::*,
+    token_compat,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    token_2022::Token2022,
+    token_interface::{self, Mint},
+};
+
+/// 账户集合：创建 Token-2022 结果代币 mint
+#[derive(Accounts)]
+pub struct CreateOutcomeMint<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 全局金库（PDA，作为铸造、元数据更新权限）
+    /// CHECK: global vault pda which stores USDC
+    #[account(
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// 创建者
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    /// 结果代币 mint（metadata pointer 指向 mint 自身）
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = global_config.token_decimals_config,
+        mint::authority = global_vault.key(),
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = global_vault,
+        extensions::metadata_pointer::metadata_address = outcome_mint,
+    )]
+    pub outcome_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = system_program::ID)]
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token2022>,
+}
+
+impl<'info> CreateOutcomeMint<'info> {
+    /// 写入 TokenMetadata（is_yes 决定名称 YES_NAME / NO_NAME）
+    pub fn handler(&mut self, is_yes: bool, symbol: String, uri: String, global_vault_bump: u8) -> Result<()> {
+        require!(
+            !symbol.is_empty() && symbol.len() <= MAX_OUTCOME_SYMBOL_LEN && uri.len() <= MAX_OUTCOME_URI_LEN,
+            PredictionMarketError::InvalidParameter
+        );
+        let name = if is_yes { YES_NAME } else { NO_NAME };
+
+        // TokenMetadata 初始化会 realloc mint 账户，需预先补足租金
+        let mint_info = self.outcome_mint.to_account_info();
+        let required_len = mint_info
+            .data_len()
+            .checked_add(token_compat::token_metadata_space(name, &symbol, &uri))
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        let shortfall = Rent::get()?
+            .minimum_balance(required_len)
+            .saturating_sub(mint_info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.creator.to_account_info(),
+                        to: mint_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+        token_interface::token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TokenMetadataInitialize {
+                    program_id: self.token_program.to_account_info(),
+                    mint: mint_info.clone(),
+                    metadata: mint_info,
+                    mint_authority: self.global_vault.to_account_info(),
+                    update_authority: self.global_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            name.to_string(),
+            symbol.clone(),
+            uri.clone(),
+        )?;
+
+        emit!(OutcomeMintCreatedEvent {
+            mint: self.outcome_mint.key(),
+            creator: self.creator.key(),
+            name: name.to_string(),
+            symbol,
+            uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("✅ Token-2022 outcome mint created: {} ({})", self.outcome_mint.key(), name);
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+//! ✅ v3.9.0: 创建 M-of-N 结算委员会（任何人）
+//!
+//! 委员会创建后可在 create_market 时通过 `resolver_committee` 账户指定为市场结算人。
+//! 成员列表与阈值创建后不可修改。
+
+use crate::{
+    constants::{MAX_COMMITTEE_MEMBERS, RESOLVER_COMMITTEE},
+    events::ResolverCommitteeCreatedEvent,
+    state::resolver::*,
+};
+use anchor_lang::{prelude::*, system_program};
+
+/// 账户集合：创建结算委员会
+#[derive(Accounts)]
+#[instruction(committee_id: u64)]
+pub struct CreateResolverCommittee<'info> {
+    /// 结算委员会（PDA：[resolver_committee, creator, committee_id]）
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ResolverCommittee::INIT_SPACE,
+        seeds = [RESOLVER_COMMITTEE.as_bytes(), creator.key().as_ref(), &committee_id.to_le_bytes()],
+        bump
+    )]
+    pub committee: Box<Account<'info, ResolverCommittee>>,
+
+    /// 创建者（支付租金）
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// 系统程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl CreateResolverCommittee<'_> {
+    /// 创建结算委员会
+    ///
+    /// # 参数
+    /// * `committee_id` - 委员会编号（同一创建者下唯一）
+    /// * `members` - 成员列表（1 - 10 个，不可重复）
+    /// * `threshold` - 结算所需一致票数 M（1 ≤ M ≤ N）
+    pub fn handler(&mut self, committee_id: u64, members: Vec<Pubkey>, threshold: u8, bump: u8) -> Result<()> {
+        ResolverCommittee::validate(&members, threshold)?;
+
+        let committee = &mut self.committee;
+        committee.creator = self.creator.key();
+        committee.committee_id = committee_id;
+        committee.member_count = members.len() as u8;
+        committee.threshold = threshold;
+        committee.members = [Pubkey::default(); MAX_COMMITTEE_MEMBERS];
+        committee.members[..members.len()].copy_from_slice(&members);
+        committee.bump = bump;
+
+        msg!(
+            "✅ Resolver committee created: {} ({}-of-{})",
+            committee.key(),
+            threshold,
+            members.len()
+        );
+
+        emit!(ResolverCommitteeCreatedEvent {
+            creator: self.creator.key(),
+            committee: self.committee.key(),
+            members,
+            threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+//! ✅ v3.26.0: 用户指令：创建会话密钥（委托临时热钥交易）
+//!
+//! ✅ v3.26.1: ATA 的 delegate 统一授权给用户委托权限 PDA（`[INTENT_NONCE, owner]`，
+//! 与 execute_intent 共用，不存在时由本指令创建），会话之间、会话与意图之间不再互相覆盖授权。
+//! 单个会话的支出以 SessionKey.spend_cap 为界（程序内记账），与 SPL 授权额度无关；
+//! 授权额度为所有会话与意图共享的总上限。
+
+use crate::{
+    constants::{INTENT_NONCE, MAX_SESSION_MARKETS, SESSION_KEY},
+    errors::PredictionMarketError,
+    events::SessionKeyCreatedEvent,
+    state::{intent_nonce::IntentNonce, session_key::SessionKey},
+};
+use anchor_lang::{prelude::*, system_program};
+
+/// 会话密钥参数
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SessionKeyParams {
+    /// 过期时间（Unix 时间戳，不超过 MAX_SESSION_KEY_DURATION_SECS）
+    pub expires_at: i64,
+    /// USDC 支出上限
+    pub spend_cap: u64,
+    /// 允许的操作（SESSION_ACTION_* 位掩码）
+    pub allowed_actions: u8,
+    /// 限定市场（为空表示不限市场）
+    pub allowed_markets: Vec<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSessionKey<'info> {
+    /// 会话密钥
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SessionKey::INIT_SPACE,
+        seeds = [SESSION_KEY.as_bytes(), owner.key().as_ref(), session_signer.key().as_ref()],
+        bump
+    )]
+    pub session_key: Box<Account<'info, SessionKey>>,
+
+    /// 会话签名者（临时热钥）
+    /// CHECK: any key can be delegated as a session signer
+    pub session_signer: AccountInfo<'info>,
+
+    /// ✅ v3.26.1: 用户委托权限（IntentNonce PDA，不存在则创建）：会话与意图共用的唯一 delegate
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + IntentNonce::INIT_SPACE,
+        seeds = [INTENT_NONCE.as_bytes(), owner.key().as_ref()],
+        bump
+    )]
+    pub delegate_authority: Box<Account<'info, IntentNonce>>,
+
+    /// 授权人
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl CreateSessionKey<'_> {
+    pub fn handler(&mut self, params: SessionKeyParams, bump: u8, delegate_bump: u8) -> Result<()> {
+        require!(
+            params.allowed_markets.len() <= MAX_SESSION_MARKETS,
+            PredictionMarketError::InvalidSessionKey
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let delegate = &mut self.delegate_authority;
+        if delegate.user == Pubkey::default() {
+            delegate.user = self.owner.key();
+            delegate.bump = delegate_bump;
+        }
+
+        let session = &mut self.session_key;
+        session.owner = self.owner.key();
+        session.session_signer = self.session_signer.key();
+        session.expires_at = params.expires_at;
+        session.spend_cap = params.spend_cap;
+        session.spent = 0;
+        session.allowed_actions = params.allowed_actions;
+        session.market_count = params.allowed_markets.len() as u8;
+        session.allowed_markets[..params.allowed_markets.len()].copy_from_slice(&params.allowed_markets);
+        session.created_at = now;
+        session.bump = bump;
+        session.validate(now)?;
+
+        emit!(SessionKeyCreatedEvent {
+            owner: session.owner,
+            session_signer: session.session_signer,
+            expires_at: session.expires_at,
+            spend_cap: session.spend_cap,
+            allowed_actions: session.allowed_actions,
+            market_count: session.market_count,
+            timestamp: now,
+        });
+
+        msg!(
+            "✅ v3.26.0: Session key {} created for {} (expires {}, cap {})",
+            session.session_signer,
+            session.owner,
+            session.expires_at,
+            session.spend_cap
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+//! ✅ v3.7.0: 乐观预言机：挑战结算提案（质押等额反向保证金，升级至管理员裁决）
+
+use crate::{
+    constants::{CONFIG, RESOLUTION_PROPOSAL},
+    errors::PredictionMarketError,
+    events::ResolutionDisputedEvent,
+    state::{config::*, oracle::*},
+    token_compat,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：挑战结算提案
+#[derive(Accounts)]
+pub struct DisputeResolution<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 结算提案
+    #[account(
+        mut,
+        seeds = [RESOLUTION_PROPOSAL.as_bytes(), proposal.market.as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 保证金托管 ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 挑战人 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = disputer,
+        associated_token::token_program = token_program,
+    )]
+    pub disputer_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 挑战人
+    pub disputer: Signer<'info>,
+
+    /// 抵押品（USDC）代币程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl DisputeResolution<'_> {
+    /// 挑战结算提案：质押与提案相同的保证金
+    pub fn handler(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let proposal = &self.proposal;
+        // ✅ v3.12.0: 管理员两阶段结算提案由管理员在窗口内自行修改/撤回，不接受公开挑战
+        proposal.check_disputable(now, &self.disputer.key())?;
+
+        let dispute_bond = proposal.bond;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.disputer_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.bond_vault.to_account_info(),
+                    authority: self.disputer.to_account_info(),
+                },
+            ),
+            token_compat::gross_up_transfer_fee(&self.usdc_mint.to_account_info(), dispute_bond)?,
+            self.usdc_mint.decimals,
+        )?;
+
+        let proposal = &mut self.proposal;
+        proposal.disputer = self.disputer.key();
+        proposal.dispute_bond = dispute_bond;
+        proposal.disputed_at = now;
+        proposal.status = ProposalStatus::Disputed;
+
+        msg!(
+            "✅ Resolution disputed: market={}, disputer={}, bond={}",
+            proposal.market,
+            proposal.disputer,
+            dispute_bond
+        );
+
+        emit!(ResolutionDisputedEvent {
+            disputer: proposal.disputer,
+            market: proposal.market,
+            proposer: proposal.proposer,
+            dispute_bond,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
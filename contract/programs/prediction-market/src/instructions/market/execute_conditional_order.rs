@@ -0,0 +1,330 @@
+//! ✅ v3.21.0: 条件订单：keeper 执行
+//!
+//! 任何 keeper 在 YES 价格穿越触发价后调用，托管代币经 `Market::swap` 卖出路径成交
+//! （订单 PDA 作为卖方签名），所得按订单费率分给 keeper，其余转给下单人，
+//! 随后关闭托管账户与订单账户，租金退还下单人。
+
+use crate::{
+    constants::{CONDITIONAL_ORDER, CONFIG, GLOBAL, MARKET, MARKET_USDC_VAULT},
+    errors::PredictionMarketError,
+    events::{ConditionalOrderExecutedEvent, SwapExecuted},
+    state::{conditional_order::*, config::*, market::*},
+    token_compat,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：执行条件订单
+#[derive(Accounts)]
+pub struct ExecuteConditionalOrder<'info> {
+    /// 全局配置
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 全局金库（YES/NO 池子代币的 authority）
+    /// CHECK: global vault pda used as authority
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币 mint
+    #[account(address = market.yes_token_mint @ PredictionMarketError::InvalidMint)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = market.no_token_mint @ PredictionMarketError::InvalidMint)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库的 YES/NO ATA
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// USDC Mint（✅ v3.27.0: 可写，关闭托管账户前归集扣留的转账手续费）
+    #[account(
+        mut,
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 市场 USDC 金库 PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 团队钱包 USDC ATA（接收平台手续费）
+    #[account(
+        mut,
+        constraint = team_usdc_ata.mint == usdc_mint.key() @ PredictionMarketError::InvalidMint,
+        constraint = team_usdc_ata.owner == global_config.team_wallet @ PredictionMarketError::IncorrectAuthority,
+    )]
+    pub team_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 条件订单（执行后关闭，租金退还下单人）
+    #[account(
+        mut,
+        seeds = [
+            CONDITIONAL_ORDER.as_bytes(),
+            market.key().as_ref(),
+            owner.key().as_ref(),
+            &conditional_order.order_id.to_le_bytes(),
+        ],
+        bump = conditional_order.bump,
+        has_one = market @ PredictionMarketError::InvalidParameter,
+        has_one = owner @ PredictionMarketError::IncorrectAuthority,
+        close = owner,
+    )]
+    pub conditional_order: Box<Account<'info, ConditionalOrder>>,
+
+    /// 订单托管代币账户
+    #[account(
+        mut,
+        token::authority = conditional_order,
+        token::token_program = outcome_token_program,
+        constraint = escrow_token_ata.mint == if conditional_order.token_type == 1 {
+            market.yes_token_mint
+        } else {
+            market.no_token_mint
+        } @ PredictionMarketError::InvalidMint,
+    )]
+    pub escrow_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 订单 USDC ATA（暂存卖出所得）
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = conditional_order,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub escrow_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 下单人（接收所得与租金）
+    /// CHECK: verified by conditional_order.has_one
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    /// 下单人 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub owner_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Keeper 的 USDC 账户（接收执行费）
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = keeper,
+        token::token_program = collateral_token_program,
+    )]
+    pub keeper_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Keeper（任何人）
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// 代币程序（✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序）
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExecuteConditionalOrder<'info> {
+    /// 执行条件订单
+    pub fn handler(&mut self, global_vault_bump: u8, market_usdc_vault_bump: u8) -> Result<()> {
+        require!(
+            self.global_vault.owner == &crate::ID,
+            PredictionMarketError::InvalidAuthority
+        );
+        require!(!self.global_config.is_paused, PredictionMarketError::ContractPaused);
+        require!(!self.market.market_paused, PredictionMarketError::MarketPaused);
+        require!(
+            self.market.pool_collateral_reserve >= self.global_config.min_trading_liquidity,
+            PredictionMarketError::MarketBelowMinLiquidity
+        );
+
+        // 触发条件：按成交前的 LMSR 现价判断
+        let yes_price_bps = self.market.calculate_yes_price_bps()?;
+        let order: ConditionalOrder = (**self.conditional_order).clone();
+        require!(order.is_triggered(yes_price_bps), PredictionMarketError::TriggerNotReached);
+
+        let now = Clock::get()?.unix_timestamp;
+        let market_key = self.market.key();
+        let order_key = self.conditional_order.key();
+        let order_id_bytes = order.order_id.to_le_bytes();
+        let order_seeds: &[&[&[u8]]] = &[&[
+            CONDITIONAL_ORDER.as_bytes(),
+            market_key.as_ref(),
+            order.owner.as_ref(),
+            &order_id_bytes,
+            &[order.bump],
+        ]];
+        let global_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        // 卖出托管账户的全部余额（≥ order.amount，外部转入的粉尘一并卖出归下单人）
+        let token_amount = self.escrow_token_ata.amount;
+        let order_info = self.conditional_order.to_account_info();
+        let mut source = self.global_vault.to_account_info();
+        let mut global_yes_ata = self.global_yes_ata.to_account_info();
+        let mut global_no_ata = self.global_no_ata.to_account_info();
+        let mut escrow_yes = self.escrow_token_ata.to_account_info();
+        let mut escrow_no = self.escrow_token_ata.to_account_info();
+
+        let result = self.market.swap(
+            &mut self.global_config,
+            &mut global_yes_ata,
+            &mut escrow_yes,
+            &mut global_no_ata,
+            &mut escrow_no,
+            &mut source,
+            token_amount,
+            1, // 卖出
+            order.token_type,
+            0, // 滑点按扣除 keeper 费后的所得统一校验
+            None,
+            &order_info,
+            order_seeds,
+            global_seeds,
+            &self.collateral_token_program,
+            &self.outcome_token_program,
+            &self.usdc_mint,
+            &self.yes_token.to_account_info(),
+            &self.no_token.to_account_info(),
+            &self.market_usdc_ata,
+            &self.market_usdc_vault,
+            market_usdc_vault_bump,
+            &self.escrow_usdc_ata,
+            &self.team_usdc_ata,
+            None,
+            None,
+        )?;
+
+        let (keeper_fee, owner_proceeds) = order.split_proceeds(result.usdc_amount)?;
+        require!(owner_proceeds >= order.min_usdc_out, PredictionMarketError::SlippageExceeded);
+
+        // ✅ v3.10.0: 记录最后成交价格快照（Invalid 结果按此退款）
+        self.market.last_trade_yes_price_bps = self.market.calculate_yes_price_bps()?;
+
+        // 分配所得：keeper 执行费，其余（含外部转入的粉尘）归下单人
+        self.escrow_usdc_ata.reload()?;
+        let owner_usdc_out = self
+            .escrow_usdc_ata
+            .amount
+            .checked_sub(keeper_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        for (to, amount) in [
+            (self.keeper_usdc_ata.to_account_info(), keeper_fee),
+            (self.owner_usdc_ata.to_account_info(), owner_usdc_out),
+        ] {
+            if amount > 0 {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        self.collateral_token_program.to_account_info(),
+                        token_interface::TransferChecked {
+                            from: self.escrow_usdc_ata.to_account_info(),
+                            mint: self.usdc_mint.to_account_info(),
+                            to,
+                            authority: order_info.clone(),
+                        },
+                        order_seeds,
+                    ),
+                    amount,
+                    self.usdc_mint.decimals,
+                )?;
+            }
+        }
+
+        // 关闭托管账户，租金退还下单人（✅ v3.27.0: 先归集 USDC 托管中扣留的转账手续费）
+        token_compat::harvest_withheld_fees(
+            &self.collateral_token_program.to_account_info(),
+            &self.usdc_mint.to_account_info(),
+            &self.escrow_usdc_ata.to_account_info(),
+        )?;
+        for (account, token_program) in [
+            (self.escrow_token_ata.to_account_info(), &self.outcome_token_program),
+            (self.escrow_usdc_ata.to_account_info(), &self.collateral_token_program),
+        ] {
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_interface::CloseAccount {
+                    account,
+                    destination: self.owner.to_account_info(),
+                    authority: order_info.clone(),
+                },
+                order_seeds,
+            ))?;
+        }
+
+        emit!(SwapExecuted {
+            market: market_key,
+            user: order.owner,
+            is_yes: order.token_type == 1,
+            amount_in: result.token_amount,
+            amount_out: result.usdc_amount,
+            fee: result.fee_usdc,
+            timestamp: now,
+            route: crate::types::SwapRoute::Direct.to_u8(),
+            lp_fee_bps: result.lp_fee_bps,
+        });
+
+        emit!(ConditionalOrderExecutedEvent {
+            market: market_key,
+            order: order_key,
+            owner: order.owner,
+            keeper: self.keeper.key(),
+            yes_price_bps,
+            token_amount: result.token_amount,
+            usdc_proceeds: result.usdc_amount,
+            keeper_fee,
+            owner_usdc_out,
+            timestamp: now,
+        });
+
+        msg!(
+            "✅ v3.21.0: Conditional order #{} executed at {}bps: sold {} tokens for {} USDC (keeper fee {})",
+            order.order_id,
+            yes_price_bps,
+            result.token_amount,
+            result.usdc_amount,
+            keeper_fee
+        );
+        Ok(())
+    }
+}
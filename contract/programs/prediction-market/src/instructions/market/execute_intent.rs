@@ -0,0 +1,338 @@
+//! ✅ v3.25.0: 市场指令：执行用户签名的交易意图（relayer 代付 SOL）
+//!
+//! 交易结构：`[Ed25519 预编译(user 对 intent 签名), execute_intent]`
+//!
+//! - relayer 签名并支付交易费与账户租金，成交后从用户 USDC 中获得 intent.relayer_fee
+//! - 用户无需签名交易：资金转出由用户事先授权给 `IntentNonce` PDA 的 delegate 额度完成
+//! - 成交走 `Market::swap`（与 swap 相同的手续费、滑点与账本逻辑），不走订单簿 / 引荐
+
+use crate::{
+    constants::{CONFIG, GLOBAL, INTENT_NONCE, MARKET, MARKET_USDC_VAULT, USERINFO},
+    errors::PredictionMarketError,
+    events::{IntentExecutedEvent, SwapExecuted},
+    intent::{verify_ed25519_signed, SwapIntent},
+    state::{config::*, intent_nonce::IntentNonce, market::*},
+};
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as ix_sysvar, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct ExecuteIntent<'info> {
+    /// 全局配置
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 团队钱包（仅用于验证 team_usdc_ata 的 authority）
+    /// CHECK: Verified against global_config.team_wallet
+    #[account(
+        constraint = global_config.team_wallet == team_wallet.key() @ PredictionMarketError::IncorrectAuthority
+    )]
+    pub team_wallet: AccountInfo<'info>,
+
+    /// 市场账户
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 全局金库 PDA
+    /// CHECK: global vault pda used as authority
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库的 YES/NO ATA
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 签名用户（不签名交易，身份由 Ed25519 预编译指令证明）
+    /// CHECK: verified against the ed25519 precompile instruction in handler
+    pub user: AccountInfo<'info>,
+
+    /// 用户意图序号（不存在则由 relayer 创建），同时是用户 ATA 的 delegate
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + IntentNonce::INIT_SPACE,
+        seeds = [INTENT_NONCE.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub intent_nonce: Box<Account<'info, IntentNonce>>,
+
+    /// 用户的 YES/NO ATA（不存在则由 relayer 创建）
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = yes_token,
+        associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub user_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = no_token,
+        associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub user_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户信息（按需初始化）
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<UserInfo>(),
+        seeds = [USERINFO.as_bytes(), &user.key().to_bytes(), &market.key().to_bytes()],
+        bump
+    )]
+    pub user_info: Box<Account<'info, UserInfo>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 市场USDC金库PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 用户 USDC ATA（delegate = intent_nonce）
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 团队钱包 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = team_wallet,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub team_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// relayer（交易费与租金支付者）
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// relayer 的 USDC 账户（接收 relayer_fee）
+    #[account(
+        mut,
+        constraint = relayer_usdc_ata.mint == usdc_mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub relayer_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 指令 sysvar（读取 Ed25519 预编译指令）
+    /// CHECK: address checked
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl ExecuteIntent<'_> {
+    pub fn handler(
+        &mut self,
+        intent: SwapIntent,
+        intent_nonce_bump: u8,
+        global_vault_bump: u8,
+        market_usdc_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            self.usdc_mint.decimals == crate::constants::USDC_DECIMALS,
+            PredictionMarketError::InvalidParameter
+        );
+        require!(
+            self.global_vault.owner == &crate::ID,
+            PredictionMarketError::InvalidAuthority
+        );
+        require!(!self.global_config.is_paused, PredictionMarketError::ContractPaused);
+        require!(!self.market.market_paused, PredictionMarketError::MarketPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= intent.deadline, PredictionMarketError::TransactionExpired);
+
+        let market_key = self.market.key();
+        let user_key = self.user.key();
+        require!(
+            intent.market == market_key
+                && intent.direction <= 1
+                && intent.token_type <= 1
+                && intent.amount > 0,
+            PredictionMarketError::InvalidIntent
+        );
+
+        // 1. 签名校验：上一条指令为 user 对意图消息的 Ed25519 签名
+        verify_ed25519_signed(&self.instructions, &user_key, &intent.message(&crate::ID)?)?;
+
+        // 2. 防重放：消费意图序号
+        let intent_nonce = &mut self.intent_nonce;
+        if intent_nonce.user == Pubkey::default() {
+            intent_nonce.user = user_key;
+            intent_nonce.bump = intent_nonce_bump;
+        }
+        intent_nonce.consume(intent.nonce, now)?;
+
+        require!(
+            self.market.pool_collateral_reserve >= self.global_config.min_trading_liquidity,
+            PredictionMarketError::MarketBelowMinLiquidity
+        );
+
+        let user_info = &mut self.user_info;
+        if !user_info.is_initialized {
+            user_info.user = user_key;
+            user_info.is_lp = false;
+            user_info.is_initialized = true;
+        }
+
+        // 3. 成交：以 IntentNonce PDA（用户 ATA 的 delegate）作为转出权限
+        let delegate_info = self.intent_nonce.to_account_info();
+        let delegate_bump = self.intent_nonce.bump;
+        let delegate_seeds: &[&[&[u8]]] = &[&[
+            INTENT_NONCE.as_bytes(),
+            user_key.as_ref(),
+            &[delegate_bump],
+        ]];
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        let mut source = self.global_vault.to_account_info();
+        let mut global_yes_ata = self.global_yes_ata.to_account_info();
+        let mut user_yes_ata = self.user_yes_ata.to_account_info();
+        let mut global_no_ata = self.global_no_ata.to_account_info();
+        let mut user_no_ata = self.user_no_ata.to_account_info();
+
+        let result = self.market.swap(
+            &mut self.global_config,
+            &mut global_yes_ata,
+            &mut user_yes_ata,
+            &mut global_no_ata,
+            &mut user_no_ata,
+            &mut source,
+            intent.amount,
+            intent.direction,
+            intent.token_type,
+            intent.min_out,
+            None,
+            &delegate_info,
+            delegate_seeds,
+            signer_seeds,
+            &self.collateral_token_program,
+            &self.outcome_token_program,
+            &self.usdc_mint,
+            &self.yes_token.to_account_info(),
+            &self.no_token.to_account_info(),
+            &self.market_usdc_ata,
+            &self.market_usdc_vault,
+            market_usdc_vault_bump,
+            &self.user_usdc_ata,
+            &self.team_usdc_ata,
+            None,
+            None,
+        )?;
+
+        // 4. relayer 报酬：从用户 USDC 中支付（卖出时来自本次所得）
+        if intent.relayer_fee > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.user_usdc_ata.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: self.relayer_usdc_ata.to_account_info(),
+                        authority: delegate_info.clone(),
+                    },
+                    delegate_seeds,
+                ),
+                intent.relayer_fee,
+                self.usdc_mint.decimals,
+            )?;
+        }
+
+        // ✅ v3.10.0: 记录最后成交价格快照（Invalid 结果按此退款）
+        self.market.last_trade_yes_price_bps = self.market.calculate_yes_price_bps()?;
+
+        emit!(SwapExecuted {
+            market: market_key,
+            user: user_key,
+            is_yes: intent.token_type == 1,
+            amount_in: if intent.direction == 0 { result.usdc_amount } else { result.token_amount },
+            amount_out: if intent.direction == 0 { result.token_amount } else { result.usdc_amount },
+            fee: result.fee_usdc,
+            timestamp: now,
+            route: crate::types::SwapRoute::Direct.to_u8(),
+            lp_fee_bps: result.lp_fee_bps,
+        });
+
+        emit!(IntentExecutedEvent {
+            user: user_key,
+            relayer: self.relayer.key(),
+            market: market_key,
+            nonce: intent.nonce,
+            direction: intent.direction,
+            amount: intent.amount,
+            relayer_fee: intent.relayer_fee,
+            timestamp: now,
+        });
+
+        msg!(
+            "✅ v3.25.0: Intent #{} executed for {} by relayer {} (fee {})",
+            intent.nonce,
+            user_key,
+            self.relayer.key(),
+            intent.relayer_fee
+        );
+        Ok(())
+    }
+}
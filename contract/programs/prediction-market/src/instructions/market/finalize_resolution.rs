@@ -0,0 +1,319 @@
+//! ✅ v3.7.0: 乐观预言机：最终确认结算提案
+//!
+//! - `finalize`：无争议且挑战窗口结束后，任何人可按提案结果结算市场，保证金退还提案人
+//! - `settle_dispute`：有争议时由全局管理员给出最终结果；
+//!   结果与提案一致则提案人胜出，否则挑战人胜出，胜方取回双方全部保证金
+//!
+//! 两条路径都通过 `SettleContext::settle` 完成结算。
+//!
+//! ✅ v3.12.1: 提案附带的结算证据写入 MarketMetadata（已承诺规则的市场必须提供该账户）
+//! 并随 ResolutionEvent 发出；标量市场提案最终确认时记录结算值并发出 ScalarResolutionEvent。
+//!
+//! ✅ v3.13.1: 公开提案同样附带证据；争议裁决由管理员提交裁决证据（记录在元数据中，
+//! resolved_by 为证据提交者：无争议时为提案人，争议裁决时为管理员）。
+//!
+//! ✅ v3.7.1: 最终确认后关闭保证金托管 ATA 与提案账户（租金退还提案人）。
+//! 市场经其他路径结算（管理员、价格源、作废等）而提案未最终确认时，
+//! 通过 refund_resolution_bonds 原路退还双方保证金并关闭提案。
+
+use crate::{
+    constants::{CONFIG, GLOBAL, MARKET, MARKET_METADATA, RESOLUTION_PROPOSAL},
+    errors::PredictionMarketError,
+    events::{ResolutionFinalizedEvent, ScalarResolutionEvent},
+    instructions::market::resolution::SettleContext,
+    state::{config::*, event_group::*, market::*, market_metadata::*, oracle::*},
+    token_compat,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：最终确认结算提案
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// 结算提案（✅ v3.7.1: 最终确认后关闭，租金退还提案人）
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [RESOLUTION_PROPOSAL.as_bytes(), market.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// ✅ v3.7.1: 提案人（接收提案账户与托管 ATA 的租金）
+    /// CHECK: address checked against proposal.proposer
+    #[account(
+        mut,
+        address = proposal.proposer @ PredictionMarketError::InvalidBondRecipient
+    )]
+    pub proposer: AccountInfo<'info>,
+
+    /// 全局金库（PDA，清算池内代币的签名者）
+    /// CHECK: global vault pda
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    #[account(mut)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库持有的 YES/NO（结算时清算）
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 保证金托管 ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 保证金接收方 USDC 账户（须属于胜方，在 handler 中校验）
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+    )]
+    pub bond_recipient_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 所属事件组（仅事件组成员市场需要提供）
+    pub event_group: Option<Box<Account<'info, EventGroup>>>,
+
+    /// 父市场（仅条件市场需要提供）
+    pub parent_market: Option<Box<Account<'info, Market>>>,
+
+    /// ✅ v3.12.1: 市场元数据（记录结算证据；market.has_metadata 为 true 时必须提供）
+    #[account(
+        mut,
+        seeds = [MARKET_METADATA.as_bytes(), market.key().as_ref()],
+        bump = market_metadata.bump,
+    )]
+    pub market_metadata: Option<Box<Account<'info, MarketMetadata>>>,
+
+    /// 调用者（finalize 任何人；settle_dispute 必须为全局管理员）
+    pub caller: Signer<'info>,
+
+    /// 代币程序（✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序）
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+}
+
+impl FinalizeResolution<'_> {
+    /// 无争议最终确认：挑战窗口结束后按提案结果结算
+    pub fn finalize(&mut self, global_vault_bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.proposal.check_finalizable(now)?;
+
+        let (yes_ratio, no_ratio, token_type) =
+            (self.proposal.yes_ratio, self.proposal.no_ratio, self.proposal.token_type);
+        let proposer = self.proposal.proposer;
+        let evidence = self.proposal.evidence();
+
+        self.complete(yes_ratio, no_ratio, token_type, proposer, false, evidence, proposer, global_vault_bump)
+    }
+
+    /// 争议裁决：由管理员给出最终结果与裁决证据，败方保证金罚没给胜方
+    pub fn settle_dispute(
+        &mut self,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        evidence: ResolutionEvidence,
+        global_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            self.caller.key() == self.global_config.authority,
+            PredictionMarketError::InvalidAuthority
+        );
+        require!(
+            self.proposal.status == ProposalStatus::Disputed,
+            PredictionMarketError::InvalidProposalStatus
+        );
+
+        let winner = if self.proposal.matches(yes_ratio, no_ratio, token_type) {
+            self.proposal.proposer
+        } else {
+            self.proposal.disputer
+        };
+
+        let authority = self.caller.key();
+
+        self.complete(yes_ratio, no_ratio, token_type, winner, true, evidence, authority, global_vault_bump)
+    }
+
+    /// 结算市场并向胜方支付托管中的全部保证金
+    ///
+    /// `evidence_by` 为证据提交者（写入 ResolutionEvent 与 MarketMetadata）
+    #[allow(clippy::too_many_arguments)]
+    fn complete(
+        &mut self,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        bond_recipient: Pubkey,
+        disputed: bool,
+        evidence: ResolutionEvidence,
+        evidence_by: Pubkey,
+        global_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            self.bond_recipient_usdc_ata.owner == bond_recipient,
+            PredictionMarketError::InvalidBondRecipient
+        );
+        // 争议裁决改判时提案中的标量结算值不再适用
+        let scalar_value = self
+            .proposal
+            .scalar_value
+            .filter(|_| self.proposal.matches(yes_ratio, no_ratio, token_type));
+        if let Some(value) = scalar_value {
+            self.market.scalar_resolved_value = value;
+        }
+
+        // 1. 结算市场（所有结算路径共用核心逻辑）
+        SettleContext {
+            market: &mut self.market,
+            global_vault: &self.global_vault,
+            yes_token: &self.yes_token,
+            no_token: &self.no_token,
+            global_yes_ata: &self.global_yes_ata,
+            global_no_ata: &self.global_no_ata,
+            token_program: &self.outcome_token_program,
+            event_group: self.event_group.as_deref(),
+            parent_market: self.parent_market.as_deref(),
+            authority: evidence_by,
+            evidence,
+            market_metadata: self.market_metadata.as_deref_mut(),
+        }
+        .settle(yes_ratio, no_ratio, token_type, true, global_vault_bump)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if let (Some(value), Some(bounds)) = (scalar_value, self.market.scalar_bounds()) {
+            emit!(ScalarResolutionEvent {
+                authority: self.proposal.proposer,
+                market: self.market.key(),
+                resolved_value: value,
+                lower_bound: bounds.lower,
+                upper_bound: bounds.upper,
+                long_ratio: yes_ratio,
+                short_ratio: no_ratio,
+                timestamp: now,
+            });
+        }
+
+        // 2. 支付保证金并关闭托管（✅ v3.7.1: 外部转入的粉尘一并支付，保证托管账户可关闭）
+        let bond_paid = self.bond_vault.amount.max(self.proposal.total_bond()?);
+        let market_key = self.market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            RESOLUTION_PROPOSAL.as_bytes(),
+            market_key.as_ref(),
+            &[self.proposal.bump],
+        ]];
+        let proposal_info = self.proposal.to_account_info();
+        if bond_paid > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.bond_vault.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: self.bond_recipient_usdc_ata.to_account_info(),
+                        authority: proposal_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                bond_paid,
+                self.usdc_mint.decimals,
+            )?;
+        }
+        close_bond_vault(
+            &self.collateral_token_program.to_account_info(),
+            &self.usdc_mint.to_account_info(),
+            &self.bond_vault.to_account_info(),
+            &proposal_info,
+            &self.proposer,
+            signer_seeds,
+        )?;
+
+        msg!(
+            "✅ Resolution finalized: token_type={}, disputed={}, bond_recipient={}, bond_paid={}",
+            token_type,
+            disputed,
+            bond_recipient,
+            bond_paid
+        );
+
+        emit!(ResolutionFinalizedEvent {
+            caller: self.caller.key(),
+            market: self.market.key(),
+            token_type,
+            yes_ratio,
+            no_ratio,
+            disputed,
+            bond_recipient,
+            bond_paid,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+/// ✅ v3.7.1: 归集扣留手续费后关闭保证金托管 ATA（租金退还提案人，余额须已清空）
+pub(crate) fn close_bond_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    usdc_mint: &AccountInfo<'info>,
+    bond_vault: &AccountInfo<'info>,
+    proposal: &AccountInfo<'info>,
+    proposer: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_compat::harvest_withheld_fees(token_program, usdc_mint, bond_vault)?;
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        token_interface::CloseAccount {
+            account: bond_vault.clone(),
+            destination: proposer.clone(),
+            authority: proposal.clone(),
+        },
+        signer_seeds,
+    ))
+}
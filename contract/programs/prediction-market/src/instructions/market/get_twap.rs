@@ -0,0 +1,52 @@
+//! ✅ v3.20.0: TWAP 价格查询（只读，供其他程序 CPI 组合使用）
+//!
+//! 市场在每次 swap / 添加 / 移除流动性前按变动前的现价推进累加器，
+//! 本指令返回最近 `window_secs` 秒的 YES/NO 时间加权平均价格。
+//! 与现价相比，TWAP 无法在单笔交易内被闪电操纵，适合作为借贷抵押估值或衍生品结算参考。
+
+use crate::{
+    constants::{BASIS_POINTS_DIVISOR, MARKET},
+    state::market::Market,
+};
+use anchor_lang::prelude::*;
+
+/// 账户集合：TWAP 查询
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    /// 市场账户（只读）
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+/// TWAP 查询结果
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TwapResult {
+    pub market: Pubkey,              // 市场地址
+    pub twap_yes_price_bps: u16,     // YES 时间加权平均价格（基点）
+    pub twap_no_price_bps: u16,      // NO 时间加权平均价格（基点）
+    pub spot_yes_price_bps: u16,     // YES 当前现价（基点）
+    pub window_secs: u32,            // 请求的窗口（秒）
+    pub observed_window_secs: i64,   // 实际覆盖的窗口（秒，≥ 请求窗口）
+    pub timestamp: i64,              // 查询时间
+}
+
+/// 查询最近 `window_secs` 秒的 TWAP
+pub fn handler(ctx: Context<GetTwap>, window_secs: u32) -> Result<TwapResult> {
+    let market = &ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+
+    let (twap_yes, observed) = market.twap_yes_price_bps(window_secs, now)?;
+
+    Ok(TwapResult {
+        market: market.key(),
+        twap_yes_price_bps: twap_yes,
+        twap_no_price_bps: (BASIS_POINTS_DIVISOR as u16).saturating_sub(twap_yes),
+        spot_yes_price_bps: market.calculate_yes_price_bps()?,
+        window_secs,
+        observed_window_secs: observed,
+        timestamp: now,
+    })
+}
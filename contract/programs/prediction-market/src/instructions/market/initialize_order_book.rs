@@ -0,0 +1,108 @@
+//! ✅ v3.17.0: 限价订单簿：创建市场订单簿及托管账户（任何人，每个市场一次）
+
+use crate::{
+    constants::{CONFIG, MARKET, ORDER_BOOK},
+    errors::PredictionMarketError,
+    state::{config::*, market::*, order_book::*},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：创建订单簿
+#[derive(Accounts)]
+pub struct InitializeOrderBook<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 订单簿（每个市场仅一个）
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OrderBook::INIT_SPACE,
+        seeds = [ORDER_BOOK.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    /// YES/NO 代币 mint
+    #[account(address = market.yes_token_mint @ PredictionMarketError::InvalidMint)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = market.no_token_mint @ PredictionMarketError::InvalidMint)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 托管 ATA（authority = 订单簿 PDA）
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = order_book,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub book_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = yes_token,
+        associated_token::authority = order_book,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub book_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = no_token,
+        associated_token::authority = order_book,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub book_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 付款人
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl InitializeOrderBook<'_> {
+    /// 创建订单簿
+    pub fn handler(&mut self, bump: u8) -> Result<()> {
+        require!(!self.global_config.is_paused, PredictionMarketError::ContractPaused);
+        require!(!self.market.is_completed, PredictionMarketError::MarketIsCompleted);
+
+        let order_book = &mut self.order_book;
+        order_book.market = self.market.key();
+        order_book.next_order_id = 1;
+        order_book.orders = [LimitOrder::default(); crate::constants::MAX_BOOK_ORDERS];
+        order_book.bump = bump;
+
+        msg!("✅ v3.17.0: Order book initialized for market {}", order_book.market);
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+//! ✅ v3.10.0: 无效结果退款预览（只读）
+//!
+//! 预览市场以 Invalid 结算时的退款：
+//! - 交易者：YES 按最后成交价格快照 p、NO 按 1-p 退款
+//! - LP：按份额退还 min(投入本金, 池内 USDC 份额)
+//!
+//! 市场已以 Invalid 结算时使用已记录的退款比例与池子状态；
+//! 尚未结算时按当前快照估算（池内代币按快照价格折算）。
+
+use crate::{
+    constants::{BASIS_POINTS_DIVISOR, LPPOSITION, MARKET},
+    errors::PredictionMarketError,
+    state::market::{LPPosition, Market},
+};
+use anchor_lang::prelude::*;
+
+/// 账户集合：无效结果退款预览
+#[derive(Accounts)]
+pub struct InvalidRefundPreview<'info> {
+    /// 市场账户（只读）
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// LP 仓位（可选，只读）
+    #[account(
+        seeds = [LPPOSITION.as_bytes(), market.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub lp_position: Option<Account<'info, LPPosition>>,
+
+    /// 用户（无需签名，只读查询）
+    /// CHECK: read-only query, no signature required
+    pub user: AccountInfo<'info>,
+}
+
+/// 无效结果退款预览结果
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InvalidRefundPreviewResult {
+    /// 市场是否已以 Invalid 结算
+    pub is_invalid: bool,
+
+    /// 最后成交 YES 价格快照（基点）
+    pub snapshot_yes_price_bps: u16,
+
+    /// YES/NO 退款比例（基点）
+    pub yes_refund_ratio: u64,
+    pub no_refund_ratio: u64,
+
+    /// 交易者退款（按传入的 YES/NO 数量）
+    pub trader_refund_usdc: u64,
+
+    /// LP 投入本金
+    pub lp_invested_usdc: u64,
+
+    /// LP 按份额可得的池内 USDC（估算）
+    pub lp_pool_share_usdc: u64,
+
+    /// LP 预计退还 USDC
+    pub lp_refund_usdc: u64,
+}
+
+/// 预览无效结果退款
+///
+/// # 参数
+/// * `yes_amount` - 持有的 YES 数量
+/// * `no_amount` - 持有的 NO 数量
+pub fn handler(
+    ctx: Context<InvalidRefundPreview>,
+    yes_amount: u64,
+    no_amount: u64,
+) -> Result<InvalidRefundPreviewResult> {
+    let market = &ctx.accounts.market;
+    let is_invalid = market.is_invalid();
+
+    // 已结算：使用记录的比例；未结算：按当前快照估算
+    let (yes_refund_ratio, no_refund_ratio) = if is_invalid {
+        (market.resolution_yes_ratio, market.resolution_no_ratio)
+    } else {
+        market.invalid_refund_ratios()
+    };
+
+    let value_at = |amount: u64, ratio: u64| -> Result<u64> {
+        Ok((amount as u128)
+            .checked_mul(ratio as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR as u128)
+            .ok_or(PredictionMarketError::MathOverflow)? as u64)
+    };
+
+    let trader_refund_usdc = value_at(yes_amount, yes_refund_ratio)?
+        .checked_add(value_at(no_amount, no_refund_ratio)?)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // 池子价值：已结算时池内代币已折算计入 pool_collateral_reserve
+    let pool_value = if is_invalid {
+        market.pool_collateral_reserve
+    } else {
+        market
+            .pool_collateral_reserve
+            .checked_add(value_at(market.pool_yes_reserve, yes_refund_ratio)?)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_add(value_at(market.pool_no_reserve, no_refund_ratio)?)
+            .ok_or(PredictionMarketError::MathOverflow)?
+    };
+
+    let (lp_invested_usdc, lp_pool_share_usdc) = match &ctx.accounts.lp_position {
+        Some(position) if position.lp_shares > 0 && market.total_lp_shares > 0 => (
+            position.invested_usdc,
+            crate::utils::calculate_proportional_share(
+                pool_value,
+                position.lp_shares as u128,
+                market.total_lp_shares as u128,
+            )?,
+        ),
+        _ => (0, 0),
+    };
+    let lp_refund_usdc = lp_invested_usdc.min(lp_pool_share_usdc);
+
+    msg!(
+        "✅ InvalidRefundPreview: snapshot={}bps, ratios YES={} NO={}, trader_refund={}, lp_refund={} (invested={}, pool_share={})",
+        market.last_trade_yes_price_bps,
+        yes_refund_ratio,
+        no_refund_ratio,
+        trader_refund_usdc,
+        lp_refund_usdc,
+        lp_invested_usdc,
+        lp_pool_share_usdc
+    );
+
+    Ok(InvalidRefundPreviewResult {
+        is_invalid,
+        snapshot_yes_price_bps: market.last_trade_yes_price_bps,
+        yes_refund_ratio,
+        no_refund_ratio,
+        trader_refund_usdc,
+        lp_invested_usdc,
+        lp_pool_share_usdc,
+        lp_refund_usdc,
+    })
+}
@@ -0,0 +1,105 @@
+//! ✅ v3.17.0: 限价订单簿：撮合价格交叉的买卖单（任何人可调用）
+//!
+//! 成交价格取先挂单的一方，资金在订单簿托管账户内部划转，
+//! 双方通过 settle_limit_order 领取成交所得。
+
+use crate::{
+    constants::{CONFIG, MARKET, ORDER_BOOK},
+    errors::PredictionMarketError,
+    events::{LimitOrderFilledEvent, TradeEvent},
+    state::{config::*, market::*, order_book::*},
+};
+use anchor_lang::prelude::*;
+
+/// 账户集合：撮合挂单
+#[derive(Accounts)]
+pub struct MatchLimitOrders<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 订单簿
+    #[account(
+        mut,
+        seeds = [ORDER_BOOK.as_bytes(), market.key().as_ref()],
+        bump = order_book.bump,
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    /// 调用者（撮合人）
+    pub cranker: Signer<'info>,
+}
+
+impl MatchLimitOrders<'_> {
+    /// 撮合买单 `bid_order_id` 与卖单 `ask_order_id`
+    pub fn handler(&mut self, bid_order_id: u64, ask_order_id: u64) -> Result<()> {
+        require!(!self.global_config.is_paused, PredictionMarketError::ContractPaused);
+        require!(!self.market.market_paused, PredictionMarketError::MarketPaused);
+        require!(!self.market.is_completed, PredictionMarketError::CurveAlreadyCompleted);
+
+        let clock = Clock::get()?;
+        self.market.check_trading_window(clock.slot, clock.unix_timestamp)?;
+
+        let book = &mut self.order_book;
+        let bid_index = book.index_of(bid_order_id)?;
+        let ask_index = book.index_of(ask_order_id)?;
+        let fill = book.match_orders(bid_index, ask_index)?;
+
+        let bid = book.orders[bid_index];
+        let ask = book.orders[ask_index];
+        let market = &self.market;
+        let market_key = market.key();
+
+        emit!(TradeEvent {
+            user: bid.owner,
+            recipient: bid.owner,
+            token_yes: market.yes_token_mint,
+            token_no: market.no_token_mint,
+            market_info: market_key,
+            usdc_amount: fill.usdc,
+            token_amount: fill.tokens,
+            fee_usdc: 0,
+            is_buy: true,
+            is_yes_no: bid.token_type == 1,
+            real_usdc_reserves: market.pool_collateral_reserve,
+            real_token_yes_reserves: market.pool_yes_reserve,
+            real_token_no_reserves: market.pool_no_reserve,
+            timestamp: clock.unix_timestamp,
+        });
+
+        for (order, counterparty) in [(bid, ask.owner), (ask, bid.owner)] {
+            emit!(LimitOrderFilledEvent {
+                market: market_key,
+                order_id: order.order_id,
+                maker: order.owner,
+                taker: counterparty,
+                token_type: order.token_type,
+                price_bps: fill.price_bps,
+                token_amount: fill.tokens,
+                usdc_amount: fill.usdc,
+                remaining: order.remaining,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        msg!(
+            "✅ v3.17.0: Matched bid #{} with ask #{}: {} tokens @ {}bps ({} USDC)",
+            bid_order_id,
+            ask_order_id,
+            fill.tokens,
+            fill.price_bps,
+            fill.usdc
+        );
+        Ok(())
+    }
+}
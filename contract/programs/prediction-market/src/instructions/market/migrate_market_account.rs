@@ -0,0 +1,92 @@
+//! ✅ v3.20.1: 旧市场账户扩容（无需许可）
+//!
+//! Market 新字段均追加在结构体末尾（TWAP 观测缓冲区、动态费率、feed_id 等），
+//! 升级前创建的市场账户按旧版 `Market::INIT_SPACE` 分配，剩余空间不足以容纳新字段，
+//! 反序列化会失败，导致市场上的所有指令（包括结算与退款）无法执行。
+//!
+//! 本指令将旧账户扩容至当前 `8 + Market::INIT_SPACE`：
+//! - 新增字节以 0 填充，新字段均以 0 表示"未设置"（TWAP 未初始化、动态费率关闭、无价格源等）
+//! - 只校验 owner、鉴别器与 PDA 地址，不反序列化完整结构（与 configure 扩容 Config 的做法一致）
+//! - 租金差额由调用者支付；账户已满足当前大小时直接返回
+//!
+//! ✅ v3.20.2: 名称被缩短过的旧账户在实际序列化内容之后残留旧字节，扩容后会被解码为追加字段；
+//! 扩容时将旧版内容（`Market::legacy_serialized_len`）之后的全部字节清零。
+
+use crate::{constants::MARKET, errors::PredictionMarketError, state::market::*};
+use anchor_lang::{prelude::*, system_program};
+
+/// 账户集合：扩容旧市场账户
+#[derive(Accounts)]
+pub struct MigrateMarketAccount<'info> {
+    /// 市场（可能为旧版布局，无法按当前结构反序列化）
+    /// CHECK: owner, discriminator and PDA address are checked in the handler
+    #[account(mut, owner = crate::ID @ PredictionMarketError::InvalidMarketAccount)]
+    pub market: UncheckedAccount<'info>,
+
+    /// 支付扩容租金（任何人）
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// 系统程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateMarketAccount<'_> {
+    /// 扩容市场账户
+    pub fn handler(&mut self) -> Result<()> {
+        let market_info = self.market.to_account_info();
+
+        // 仅读取鉴别器与前两个字段（yes/no mint），校验 PDA 地址
+        {
+            let data = market_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 64 && data[..8] == *Market::DISCRIMINATOR,
+                PredictionMarketError::InvalidMarketAccount
+            );
+            let (expected, _) = Pubkey::find_program_address(
+                &[MARKET.as_bytes(), &data[8..40], &data[40..72]],
+                &crate::ID,
+            );
+            require!(market_info.key() == expected, PredictionMarketError::InvalidMarketAccount);
+        }
+
+        let current_len = market_info.data_len();
+        let target_len = 8 + Market::INIT_SPACE;
+        if current_len >= target_len {
+            msg!("Market account already migrated: {} bytes", current_len);
+            return Ok(());
+        }
+        let legacy_len = Market::legacy_serialized_len(&market_info.try_borrow_data()?)?;
+
+        // 补足租金后扩容（新增字节以 0 填充）
+        let required = Rent::get()?.minimum_balance(target_len);
+        let lamport_delta = required.saturating_sub(market_info.lamports());
+        if lamport_delta > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.payer.to_account_info(),
+                        to: market_info.clone(),
+                    },
+                ),
+                lamport_delta,
+            )?;
+        }
+        // realloc 被标记为 deprecated 但是 Anchor 0.32.1 中唯一可用的方法
+        #[allow(deprecated)]
+        market_info.realloc(target_len, true)?;
+        // ✅ v3.20.2: 清零旧版内容之后的残留字节（realloc 只清零新增部分）
+        market_info.try_borrow_mut_data()?[legacy_len..].fill(0);
+
+        msg!(
+            "✅ v3.20.1 Market account migrated: {} -> {} bytes, +{} lamports",
+            current_len,
+            target_len,
+            lamport_delta
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+//! ✅ v3.3.0: 分类市场铸造完整集合：用户存入 X USDC，获得每个结果各 X 个代币
+//!
+//! 与二元 mint_complete_set 对齐：完整集合在任意赔付向量下都恰好价值 X USDC，
+//! 因此抵押品进入 Settlement Ledger（total_collateral_locked），不影响 LMSR 价格。
+
+use crate::{
+    constants::{CATEGORICAL_MARKET, CONFIG, MARKET_USDC_VAULT},
+    errors::PredictionMarketError,
+    events::CategoricalCompleteSetEvent,
+    state::{categorical_market::*, config::*},
+    token_compat,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：分类市场铸造完整集合所需账户
+#[derive(Accounts)]
+pub struct MintCategoricalSet<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 分类市场账户
+    #[account(
+        mut,
+        seeds = [CATEGORICAL_MARKET.as_bytes(), market.creator.as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, CategoricalMarket>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场USDC金库PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// 校验 remaining_accounts 中的 `[mint_i, user_ata_i]` 结果账户对
+///
+/// - 数量必须为 2 × outcome_count，且按结果索引排序
+/// - mint_i 必须等于 market.outcome_mints[i]
+/// - user_ata_i 必须是 mint_i 的代币账户，且 owner = user
+pub(crate) fn validate_outcome_account_pairs<'info>(
+    market: &CategoricalMarket,
+    user: &Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<(&'info AccountInfo<'info>, &'info AccountInfo<'info>)>> {
+    require!(
+        remaining_accounts.len() == market.outcomes() * 2,
+        PredictionMarketError::OutcomeAccountMismatch
+    );
+
+    let mut pairs = Vec::with_capacity(market.outcomes());
+    for (i, pair) in remaining_accounts.chunks_exact(2).enumerate() {
+        let (mint_info, ata_info) = (&pair[0], &pair[1]);
+
+        require!(
+            mint_info.key() == market.outcome_mints[i],
+            PredictionMarketError::OutcomeAccountMismatch
+        );
+        require!(mint_info.is_writable && ata_info.is_writable, PredictionMarketError::OutcomeAccountMismatch);
+
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(ata_info)?;
+        require!(token_account.mint == mint_info.key(), PredictionMarketError::InvalidMint);
+        require!(token_account.owner == *user, PredictionMarketError::IncorrectAuthority);
+
+        pairs.push((mint_info, ata_info));
+    }
+
+    Ok(pairs)
+}
+
+/// 铸造分类完整集合
+///
+/// # remaining_accounts
+/// `[mint_0, user_ata_0, mint_1, user_ata_1, ...]`（用户 ATA 需预先创建）
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintCategoricalSet<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    require!(
+        !accounts.global_config.is_paused,
+        PredictionMarketError::ContractPaused
+    );
+    require!(!accounts.market.market_paused, PredictionMarketError::MarketPaused);
+    require!(!accounts.market.is_completed, PredictionMarketError::MarketIsCompleted);
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        accounts.user_usdc_ata.amount >= amount,
+        PredictionMarketError::InsufficientBalance
+    );
+
+    let user_key = accounts.user.key();
+    let pairs = validate_outcome_account_pairs(&accounts.market, &user_key, ctx.remaining_accounts)?;
+
+    // 1. 用户转 USDC 到市场金库
+    token_interface::transfer_checked(
+        CpiContext::new(
+            accounts.collateral_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: accounts.user_usdc_ata.to_account_info(),
+                mint: accounts.usdc_mint.to_account_info(),
+                to: accounts.market_usdc_ata.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        ),
+        token_compat::gross_up_transfer_fee(&accounts.usdc_mint.to_account_info(), amount)?,
+        accounts.usdc_mint.decimals,
+    )?;
+
+    // 2. 以市场 PDA 签名，为每个结果铸造 amount 个代币
+    let market_info = accounts.market.to_account_info();
+    let creator = accounts.market.creator;
+    let market_id_bytes = accounts.market.market_id.to_le_bytes();
+    let bump = [accounts.market.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        CATEGORICAL_MARKET.as_bytes(),
+        creator.as_ref(),
+        &market_id_bytes,
+        &bump,
+    ]];
+
+    for (mint_info, ata_info) in pairs {
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                accounts.outcome_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: mint_info.clone(),
+                    to: ata_info.clone(),
+                    authority: market_info.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    // 3. 更新 Settlement Ledger
+    let market = &mut accounts.market;
+    market.total_collateral_locked = market
+        .total_collateral_locked
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.total_sets_minted = market
+        .total_sets_minted
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    msg!(
+        "✅ Minted categorical set: amount={}, total_locked={}",
+        amount,
+        market.total_collateral_locked
+    );
+
+    emit!(CategoricalCompleteSetEvent {
+        user: user_key,
+        market: market.key(),
+        is_mint: true,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -6,14 +6,24 @@
 //! - 确保 YES + NO 价值 = X USDC
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET, MARKET_USDC_VAULT, USERINFO},  // 🔒 v1.2.7: 添加 MARKET_USDC_VAULT
+    constants::{
+        CONFIG, GLOBAL, INTENT_NONCE, MARKET, MARKET_USDC_VAULT, REFERRER, SESSION_ACTION_MINT, SESSION_KEY,
+        USERINFO,
+    },  // 🔒 v1.2.7: 添加 MARKET_USDC_VAULT
     errors::PredictionMarketError,
-    state::{config::*, market::*},
+    state::{
+        config::*,
+        intent_nonce::IntentNonce,
+        market::*,
+        referrer::ReferrerStats,
+        session_key::{authorize_session, SessionKey},
+    },
+    token_compat,
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：铸造完整集合所需账户
@@ -55,7 +65,7 @@ pub struct MintCompleteSet<'info> {
             auth == COption::Some(global_vault.key()) || auth == COption::Some(market.key())
         } @ PredictionMarketError::InvalidAuthority
     )]
-    pub yes_token: Box<Account<'info, Mint>>,
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// NO 代币 mint
     /// ✅ v3.0.10: 支持两种 authority（global_vault 或 market PDA）
@@ -67,7 +77,7 @@ pub struct MintCompleteSet<'info> {
             auth == COption::Some(global_vault.key()) || auth == COption::Some(market.key())
         } @ PredictionMarketError::InvalidAuthority
     )]
-    pub no_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// 用户的 YES ATA
     #[account(
@@ -75,8 +85,9 @@ pub struct MintCompleteSet<'info> {
         payer = user,
         associated_token::mint = yes_token,
         associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub user_yes_ata: Box<Account<'info, TokenAccount>>,
+    pub user_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 用户的 NO ATA
     #[account(
@@ -84,14 +95,15 @@ pub struct MintCompleteSet<'info> {
         payer = user,
         associated_token::mint = no_token,
         associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub user_no_ata: Box<Account<'info, TokenAccount>>,
+    pub user_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v1.1.0: USDC Mint
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 🔒 v1.2.7: 市场USDC金库PDA（签名权限）
     /// CHECK: market-specific usdc vault pda
@@ -106,16 +118,18 @@ pub struct MintCompleteSet<'info> {
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub market_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v1.1.0: 用户 USDC ATA
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub user_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 用户信息
     #[account(
@@ -127,15 +141,45 @@ pub struct MintCompleteSet<'info> {
     )]
     pub user_info: Box<Account<'info, UserInfo>>,
 
-    /// 用户签名者
+    /// 用户（资金所有者）
+    /// ✅ v3.26.0: 须签名，或提供有效的 session_key + session_signer
+    /// CHECK: signer or valid session key, verified by authorize_session in handler
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub user: AccountInfo<'info>,
+
+    /// ✅ v3.22.0: 引荐人统计账户（可选，铸造完整集合不收平台费，仅累计引荐成交量）
+    #[account(
+        mut,
+        seeds = [REFERRER.as_bytes(), referrer_stats.referrer.as_ref()],
+        bump = referrer_stats.bump,
+    )]
+    pub referrer_stats: Option<Box<Account<'info, ReferrerStats>>>,
+
+    /// ✅ v3.26.0: 会话密钥（可选，与 session_signer 一同提供）：作用域、有效期与支出上限
+    #[account(
+        mut,
+        seeds = [SESSION_KEY.as_bytes(), user.key().as_ref(), session_key.session_signer.as_ref()],
+        bump = session_key.bump,
+    )]
+    pub session_key: Option<Box<Account<'info, SessionKey>>>,
+
+    /// ✅ v3.26.0: 会话签名者（临时热钥，代替 user 签名）
+    pub session_signer: Option<Signer<'info>>,
+
+    /// ✅ v3.26.1: 用户委托权限（IntentNonce PDA，会话模式必须提供）：
+    /// 会话与意图共用的唯一 delegate，资金由该 PDA 从 user ATA 转出
+    #[account(
+        seeds = [INTENT_NONCE.as_bytes(), user.key().as_ref()],
+        bump = delegate_authority.bump,
+    )]
+    pub delegate_authority: Option<Box<Account<'info, IntentNonce>>>,
 
     /// 系统/代币/ATA程序
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -188,6 +232,30 @@ impl<'info> MintCompleteSet<'info> {
             PredictionMarketError::CurveAlreadyCompleted
         );
 
+        // ✅ v3.26.0: 交易授权（user 签名，或会话密钥签名）
+        let now = Clock::get()?.unix_timestamp;
+        let user_key = self.user.key();
+        let is_session = authorize_session(
+            &self.user,
+            self.session_key.as_deref().map(|s| &**s),
+            self.session_signer.as_deref(),
+            &self.market.key(),
+            SESSION_ACTION_MINT,
+            now,
+        )?;
+        // ✅ v3.26.1: 会话模式由用户委托权限 PDA（与 execute_intent 共用）签名转出
+        let delegate_bump = [self.delegate_authority.as_ref().map_or(0, |d| d.bump)];
+        let delegate_seeds: &[&[&[u8]]] = &[&[INTENT_NONCE.as_bytes(), user_key.as_ref(), &delegate_bump]];
+        let (authority, authority_seeds): (AccountInfo<'info>, &[&[&[u8]]]) = if is_session {
+            let delegate = self
+                .delegate_authority
+                .as_ref()
+                .ok_or(PredictionMarketError::InvalidSessionKey)?;
+            (delegate.to_account_info(), delegate_seeds)
+        } else {
+            (self.user.to_account_info(), &[])
+        };
+
         // 初始化用户信息（如果需要）
         if !self.user_info.is_initialized {
             self.user_info.user = self.user.key();
@@ -197,16 +265,19 @@ impl<'info> MintCompleteSet<'info> {
         }
 
         // 🔒 v1.2.7: 1. 用户转 USDC 到市场专用 USDC 金库（修复金库错误）
-        token::transfer(
-            CpiContext::new(
-                self.token_program.to_account_info(),
-                token::Transfer {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
                     from: self.user_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
                     to: self.market_usdc_ata.to_account_info(),
-                    authority: self.user.to_account_info(),
+                    authority,
                 },
+                authority_seeds,
             ),
-            amount,
+            token_compat::gross_up_transfer_fee(&self.usdc_mint.to_account_info(), amount)?,
+            self.usdc_mint.decimals,
         )?;
         msg!("✅ Locked {} USDC as collateral in market vault", amount);
 
@@ -234,10 +305,10 @@ impl<'info> MintCompleteSet<'info> {
             };
 
         // 2. 铸造等量的 YES 代币给用户
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::MintTo {
+                self.outcome_token_program.to_account_info(),
+                token_interface::MintTo {
                     mint: self.yes_token.to_account_info(),
                     to: self.user_yes_ata.to_account_info(),
                     authority: signer_pda_info.clone(),
@@ -249,10 +320,10 @@ impl<'info> MintCompleteSet<'info> {
         msg!("✅ Minted {} YES tokens", amount);
 
         // 3. 铸造等量的 NO 代币给用户
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::MintTo {
+                self.outcome_token_program.to_account_info(),
+                token_interface::MintTo {
                     mint: self.no_token.to_account_info(),
                     to: self.user_no_ata.to_account_info(),
                     authority: signer_pda_info.clone(),
@@ -318,6 +389,36 @@ impl<'info> MintCompleteSet<'info> {
             timestamp: clock.unix_timestamp,
         });
 
+        // ✅ v3.26.0: 会话支出计入 USDC 上限
+        if let Some(session) = self.session_key.as_mut() {
+            session.record_spend(amount)?;
+
+            emit!(crate::events::SessionKeyUsedEvent {
+                owner: user_key,
+                session_signer: session.session_signer,
+                market: market_key,
+                action: SESSION_ACTION_MINT,
+                spend: amount,
+                total_spent: session.spent,
+                timestamp: now,
+            });
+        }
+
+        // ✅ v3.22.0: 引荐统计（无平台费可分，分成为 0）
+        if let Some(referrer_stats) = self.referrer_stats.as_mut() {
+            require!(referrer_stats.referrer != user_key, PredictionMarketError::SelfReferral);
+            referrer_stats.record(amount, 0)?;
+
+            emit!(crate::events::ReferralFeeAccruedEvent {
+                referrer: referrer_stats.referrer,
+                user: user_key,
+                market: market_key,
+                volume: amount,
+                referral_fee: 0,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
         // 🔒 v1.2.7: 发射金库余额快照事件（监控账本-金库一致性）
         // ⚠️ PRODUCT DECISION: reload() 必须保留
         // 用途：获取转账后的实时余额，用于计算准确的 balance_discrepancy
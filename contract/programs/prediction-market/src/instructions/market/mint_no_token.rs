@@ -1,4 +1,6 @@
 //! 市场指令：铸造 NO 代币（创建NO mint、全局ATA与元数据，并撤销铸造权限）
+//!
+//! ✅ v3.27.0: Token-2022 部署可改用 create_outcome_mint（metadata-pointer 元数据，不经过 Metaplex）
 
 use crate::{
     constants::{CONFIG, GLOBAL, METADATA, NO_NAME},
@@ -8,7 +10,7 @@ use anchor_lang::{prelude::*, solana_program::sysvar::SysvarId, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
     metadata::{self, mpl_token_metadata::types::DataV2, Metadata},
-    token::{self, Mint, Token},
+    token_interface::{Mint, TokenInterface},
 };
 
 /// 账户集合：铸造NO代币所需账户
@@ -41,8 +43,9 @@ pub struct MintNoToken<'info> {
         payer = creator,
         mint::decimals = global_config.token_decimals_config ,
         mint::authority = global_vault.key(),
+        mint::token_program = token_program,
     )]
-    no_token: Box<Account<'info, Mint>>,
+    no_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// NO元数据账户（传递给 Metadata 程序）
     /// CHECK: passed to token metadata program
@@ -64,7 +67,7 @@ pub struct MintNoToken<'info> {
         mut,
         seeds = [
             global_vault.key().as_ref(),
-            token::spl_token::ID.as_ref(),
+            token_program.key().as_ref(),
             no_token.key().as_ref(),
         ],
         bump,
@@ -77,8 +80,7 @@ pub struct MintNoToken<'info> {
     system_program: Program<'info, System>,
     #[account(address = Rent::id())]
     rent: Sysvar<'info, Rent>,
-    #[account(address = token::ID)]
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     associated_token_program: Program<'info, AssociatedToken>,
     #[account(address = metadata::ID)]
@@ -95,7 +97,7 @@ impl<'info> MintNoToken<'info> {
         global_vault_bump: u8,
     ) -> Result<()> {
         let creator = &self.creator;
-        let no_token: &Box<Account<'info, Mint>> = &self.no_token;
+        let no_token = &self.no_token;
         let global_no_token_account = &self.global_no_token_account;
         let global_vault = &self.global_vault;
         let no_name = NO_NAME;
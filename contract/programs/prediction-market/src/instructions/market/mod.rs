@@ -59,3 +59,116 @@ pub use sell_preview::*;
 
 pub mod claim_fees_preview;       // ✅ v3.1.1: LP 手续费领取预览（只读）
 pub use claim_fees_preview::*;
+
+pub mod create_categorical_market;      // ✅ v3.3.0: 分类市场（N 结果）
+pub use create_categorical_market::*;
+
+pub mod mint_categorical_set;           // ✅ v3.3.0: 分类市场铸造完整集合
+pub use mint_categorical_set::*;
+
+pub mod redeem_categorical_set;         // ✅ v3.3.0: 分类市场赎回完整集合
+pub use redeem_categorical_set::*;
+
+pub mod categorical_swap;               // ✅ v3.3.0: 分类市场 LMSR 交易
+pub use categorical_swap::*;
+
+pub mod resolve_categorical_market;     // ✅ v3.3.0: 分类市场结算
+pub use resolve_categorical_market::*;
+
+pub mod claim_categorical_rewards;      // ✅ v3.3.0: 分类市场领取奖励
+pub use claim_categorical_rewards::*;
+
+pub mod convert_neg_risk_positions;     // ✅ v3.5.0: 负风险事件组转换
+pub use convert_neg_risk_positions::*;
+
+pub mod resolve_event_group;            // ✅ v3.5.0: 事件组结算
+pub use resolve_event_group::*;
+
+pub mod void_event_group;               // ✅ v3.5.1: 作废事件组
+pub use void_event_group::*;
+
+pub mod void_conditional_market;        // ✅ v3.6.0: 作废条件市场
+pub use void_conditional_market::*;
+
+pub mod propose_resolution;             // ✅ v3.7.0: 乐观预言机提案
+pub use propose_resolution::*;
+
+pub mod dispute_resolution;             // ✅ v3.7.0: 乐观预言机争议
+pub use dispute_resolution::*;
+
+pub mod finalize_resolution;            // ✅ v3.7.0: 乐观预言机最终确认/争议裁决
+pub use finalize_resolution::*;
+
+pub mod resolve_from_feed;              // ✅ v3.8.0: 价格源自动结算
+pub use resolve_from_feed::*;
+
+pub mod create_resolver_committee;      // ✅ v3.9.0: 创建 M-of-N 结算委员会
+pub use create_resolver_committee::*;
+
+pub mod resolve_by_resolver;            // ✅ v3.9.0: 指定结算人结算
+pub use resolve_by_resolver::*;
+
+pub mod cast_resolution_vote;           // ✅ v3.9.0: 结算委员会投票
+pub use cast_resolution_vote::*;
+
+pub mod invalid_refund_preview;         // ✅ v3.10.0: 无效结果退款预览（只读）
+pub use invalid_refund_preview::*;
+
+pub mod void_unresolved_market;         // ✅ v3.11.0: 超过结算截止作废市场
+pub use void_unresolved_market::*;
+pub mod refund_resolution_bonds;        // ✅ v3.7.1: 退还未最终确认提案的保证金
+pub use refund_resolution_bonds::*;
+
+pub mod buy_preview;                    // ✅ v3.15.0: 买入预览（只读）
+pub use buy_preview::*;
+
+pub mod initialize_order_book;          // ✅ v3.17.0: 创建限价订单簿
+pub use initialize_order_book::*;
+
+pub mod post_limit_order;               // ✅ v3.17.0: 挂单
+pub use post_limit_order::*;
+
+pub mod match_limit_orders;             // ✅ v3.17.0: 撮合交叉挂单
+pub use match_limit_orders::*;
+
+pub mod settle_limit_order;             // ✅ v3.17.0: 领取成交所得 / 撤单
+pub use settle_limit_order::*;
+
+pub mod swap_bundle;                    // ✅ v3.18.0: 多市场组合交易
+pub use swap_bundle::*;
+
+pub mod routed_swap;                    // ✅ v3.19.0: 智能路由交易（完整集合路径）
+pub use routed_swap::*;
+
+pub mod get_twap;                       // ✅ v3.20.0: TWAP 价格查询（只读）
+pub use get_twap::*;
+
+pub mod create_conditional_order;       // ✅ v3.21.0: 创建条件订单（止损/止盈）
+pub use create_conditional_order::*;
+
+pub mod execute_conditional_order;      // ✅ v3.21.0: keeper 执行条件订单
+pub use execute_conditional_order::*;
+
+pub mod cancel_conditional_order;       // ✅ v3.21.0: 撤销条件订单
+pub use cancel_conditional_order::*;
+
+pub mod claim_referral_fees;            // ✅ v3.22.0: 引荐人领取分成
+pub use claim_referral_fees::*;
+
+pub mod open_fee_tier_account;          // ✅ v3.23.0: 创建用户费率分级账户
+pub use open_fee_tier_account::*;
+
+pub mod execute_intent;                 // ✅ v3.25.0: 执行签名交易意图（relayer 代付）
+pub use execute_intent::*;
+
+pub mod create_session_key;             // ✅ v3.26.0: 创建会话密钥
+pub use create_session_key::*;
+
+pub mod revoke_session_key;             // ✅ v3.26.0: 撤销会话密钥
+pub use revoke_session_key::*;
+
+pub mod create_outcome_mint;            // ✅ v3.27.0: 创建 Token-2022 结果代币 mint（metadata-pointer）
+pub use create_outcome_mint::*;
+
+pub mod migrate_market_account;         // ✅ v3.20.1: 旧市场账户扩容
+pub use migrate_market_account::*;
@@ -0,0 +1,36 @@
+//! ✅ v3.23.0: 用户指令：创建费率分级账户（开始累计 30 天滚动成交量）
+
+use crate::{constants::FEE_TIER, state::fee_tier::UserFeeTier};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct OpenFeeTierAccount<'info> {
+    /// 用户费率分级账户
+    #[account(
+        init,
+        payer = user,
+        space = 8 + UserFeeTier::INIT_SPACE,
+        seeds = [FEE_TIER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_fee_tier: Box<Account<'info, UserFeeTier>>,
+
+    /// 用户
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl OpenFeeTierAccount<'_> {
+    pub fn handler(&mut self, bump: u8) -> Result<()> {
+        let account = &mut self.user_fee_tier;
+        account.user = self.user.key();
+        account.created_at = Clock::get()?.unix_timestamp;
+        account.bump = bump;
+
+        msg!("✅ v3.23.0: Fee tier account opened for {}", account.user);
+        Ok(())
+    }
+}
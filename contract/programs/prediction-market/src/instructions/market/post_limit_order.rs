@@ -0,0 +1,302 @@
+//! ✅ v3.17.0: 限价订单簿：挂单（买单托管 USDC，卖单托管代币）
+//!
+//! ✅ v3.17.2: 订单簿已满时按价格优先挤出最劣挂单（见 `OrderBook::eviction_candidate`），
+//! 调用者须提供被挤出挂单人的 USDC 与对应代币 ATA（不存在时可先用 ATA 程序无需许可地创建）。
+
+use crate::{
+    constants::{CONFIG, MARKET, ORDER_BOOK},
+    errors::PredictionMarketError,
+    events::{LimitOrderPostedEvent, LimitOrderSettledEvent},
+    state::{config::*, market::*, order_book::*},
+    token_compat,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：挂单
+#[derive(Accounts)]
+pub struct PostLimitOrder<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 订单簿
+    #[account(
+        mut,
+        seeds = [ORDER_BOOK.as_bytes(), market.key().as_ref()],
+        bump = order_book.bump,
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    /// YES/NO 代币 mint
+    #[account(address = market.yes_token_mint @ PredictionMarketError::InvalidMint)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = market.no_token_mint @ PredictionMarketError::InvalidMint)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 订单簿托管 ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = order_book,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub book_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = order_book,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub book_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = order_book,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub book_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 挂单人 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub owner_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 挂单人 YES/NO ATA（不存在则创建，用于卖单托管与后续领取）
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = yes_token,
+        associated_token::authority = owner,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub owner_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = no_token,
+        associated_token::authority = owner,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub owner_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// ✅ v3.17.2: 被挤出挂单人的 USDC / 代币 ATA（仅订单簿已满时需要，在 evict 中校验）
+    #[account(mut)]
+    pub evicted_usdc_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub evicted_token_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// 挂单人
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl PostLimitOrder<'_> {
+    /// 挂单
+    ///
+    /// # 参数
+    /// * `side` - 方向（0=买单, 1=卖单）
+    /// * `token_type` - 代币类型（0=NO, 1=YES）
+    /// * `price_bps` - 限价（基点，1-9999）
+    /// * `amount` - 代币数量
+    pub fn handler(&mut self, side: u8, token_type: u8, price_bps: u16, amount: u64) -> Result<()> {
+        require!(!self.global_config.is_paused, PredictionMarketError::ContractPaused);
+        require!(!self.market.market_paused, PredictionMarketError::MarketPaused);
+        require!(!self.market.is_completed, PredictionMarketError::CurveAlreadyCompleted);
+
+        let clock = Clock::get()?;
+        self.market.check_trading_window(clock.slot, clock.unix_timestamp)?;
+
+        // ✅ v3.17.2: 订单簿已满时按价格优先挤出最劣挂单
+        if !self.order_book.orders.iter().any(LimitOrder::is_empty) {
+            let pool_yes_price_bps = self.market.calculate_yes_price_bps()?;
+            let index = self
+                .order_book
+                .eviction_candidate(side, token_type, price_bps, pool_yes_price_bps)
+                .ok_or(PredictionMarketError::OrderBookFull)?;
+            self.evict(index, clock.unix_timestamp)?;
+        }
+
+        let owner = self.owner.key();
+        let order_id = self
+            .order_book
+            .post(owner, side, token_type, price_bps, amount, clock.unix_timestamp)?;
+        let escrowed = {
+            let order = &self.order_book.orders[self.order_book.index_of(order_id)?];
+            if side == ORDER_SIDE_BID { order.escrow_usdc } else { order.remaining }
+        };
+
+        // 托管：买单转入 USDC，卖单转入对应代币
+        let (from, to, mint, token_program) = match (side, token_type) {
+            (ORDER_SIDE_BID, _) => (
+                &self.owner_usdc_ata,
+                &self.book_usdc_ata,
+                &self.usdc_mint,
+                &self.collateral_token_program,
+            ),
+            (_, 1) => (&self.owner_yes_ata, &self.book_yes_ata, &self.yes_token, &self.outcome_token_program),
+            _ => (&self.owner_no_ata, &self.book_no_ata, &self.no_token, &self.outcome_token_program),
+        };
+        // ✅ v3.27.0: 转账手续费由挂单人承担，托管账户恰好收到 escrowed（YES/NO 无手续费扩展）
+        let transfer_amount = token_compat::gross_up_transfer_fee(&mint.to_account_info(), escrowed)?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: from.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            transfer_amount,
+            mint.decimals,
+        )?;
+
+        emit!(LimitOrderPostedEvent {
+            market: self.market.key(),
+            order_id,
+            owner,
+            side,
+            token_type,
+            price_bps,
+            amount,
+            escrowed,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "✅ v3.17.0: Limit order #{} posted: side={}, token_type={}, price={}bps, amount={}",
+            order_id,
+            side,
+            token_type,
+            price_bps,
+            amount
+        );
+        Ok(())
+    }
+
+    /// ✅ v3.17.2: 挤出挂单：按撤单结算，成交所得与未成交部分退还至挂单人的规范 ATA
+    fn evict(&mut self, index: usize, now: i64) -> Result<()> {
+        let order = self.order_book.orders[index];
+        let (Some(evicted_usdc_ata), Some(evicted_token_ata)) =
+            (self.evicted_usdc_ata.as_ref(), self.evicted_token_ata.as_ref())
+        else {
+            return err!(PredictionMarketError::InvalidEvictionAccount);
+        };
+        let (book_token_ata, token_mint) = if order.token_type == 1 {
+            (&self.book_yes_ata, &self.yes_token)
+        } else {
+            (&self.book_no_ata, &self.no_token)
+        };
+        require!(
+            evicted_usdc_ata.key()
+                == associated_token::get_associated_token_address_with_program_id(
+                    &order.owner,
+                    &self.usdc_mint.key(),
+                    &self.collateral_token_program.key(),
+                )
+                && evicted_token_ata.key()
+                    == associated_token::get_associated_token_address_with_program_id(
+                        &order.owner,
+                        &token_mint.key(),
+                        &self.outcome_token_program.key(),
+                    ),
+            PredictionMarketError::InvalidEvictionAccount
+        );
+
+        let (usdc_amount, token_amount) = self.order_book.settle(index, true)?;
+
+        let market_key = self.market.key();
+        let book_seeds: &[&[&[u8]]] = &[&[
+            ORDER_BOOK.as_bytes(),
+            market_key.as_ref(),
+            &[self.order_book.bump],
+        ]];
+
+        if usdc_amount > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.book_usdc_ata.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: evicted_usdc_ata.to_account_info(),
+                        authority: self.order_book.to_account_info(),
+                    },
+                    book_seeds,
+                ),
+                usdc_amount,
+                self.usdc_mint.decimals,
+            )?;
+        }
+
+        if token_amount > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.outcome_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: book_token_ata.to_account_info(),
+                        mint: token_mint.to_account_info(),
+                        to: evicted_token_ata.to_account_info(),
+                        authority: self.order_book.to_account_info(),
+                    },
+                    book_seeds,
+                ),
+                token_amount,
+                token_mint.decimals,
+            )?;
+        }
+
+        emit!(LimitOrderSettledEvent {
+            market: market_key,
+            order_id: order.order_id,
+            owner: order.owner,
+            usdc_amount,
+            token_amount,
+            cancelled: true,
+            timestamp: now,
+        });
+
+        msg!(
+            "✅ v3.17.2: Limit order #{} evicted at {}bps: refunded {} USDC, {} tokens",
+            order.order_id,
+            order.price_bps,
+            usdc_amount,
+            token_amount
+        );
+        Ok(())
+    }
+}
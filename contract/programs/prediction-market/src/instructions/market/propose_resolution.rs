@@ -0,0 +1,191 @@
+//! ✅ v3.7.0: 乐观预言机：提交结算提案（任何人，需质押保证金）
+//!
+//! ✅ v3.13.1: 提案须附带结算证据（哈希 + URI），最终确认时写入 MarketMetadata
+
+use crate::{
+    constants::{CONFIG, MARKET, ORACLE_CONFIG, RESOLUTION_PROPOSAL},
+    errors::PredictionMarketError,
+    events::ResolutionProposedEvent,
+    instructions::market::resolution::validate_resolution_ratios,
+    state::{config::*, market::*, market_metadata::ResolutionEvidence, oracle::*},
+    token_compat,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：提交结算提案
+#[derive(Accounts)]
+pub struct ProposeResolution<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 乐观预言机配置
+    #[account(
+        seeds = [ORACLE_CONFIG.as_bytes()],
+        bump = oracle_config.bump,
+    )]
+    pub oracle_config: Box<Account<'info, OracleConfig>>,
+
+    /// 市场
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 结算提案（每个市场仅一个）
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + ResolutionProposal::INIT_SPACE,
+        seeds = [RESOLUTION_PROPOSAL.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 保证金托管 ATA（authority = 提案 PDA）
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 提案人 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = proposer,
+        associated_token::token_program = token_program,
+    )]
+    pub proposer_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 提案人
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl ProposeResolution<'_> {
+    /// 提交结算提案
+    ///
+    /// # 参数
+    /// * `yes_ratio` / `no_ratio` / `token_type` - YES/NO 赎回比例与获胜方（0=NO, 1=YES, 2=平局, 3=无效）
+    /// * `bond` - 保证金（≥ oracle_config.min_bond）
+    /// * `evidence` - ✅ v3.13.1: 结算证据（哈希非全 0，URI 非空）
+    pub fn handler(
+        &mut self,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        bond: u64,
+        evidence: ResolutionEvidence,
+        bump: u8,
+    ) -> Result<()> {
+        require!(!self.global_config.is_paused, PredictionMarketError::ContractPaused);
+        require!(self.oracle_config.enabled, PredictionMarketError::OracleDisabled);
+        require!(bond >= self.oracle_config.min_bond, PredictionMarketError::BondTooLow);
+
+        let market = &self.market;
+        require!(!market.is_completed, PredictionMarketError::MarketIsCompleted);
+        // 标量市场的赔付比例由结算值决定，不适用于按比例提案
+        require!(!market.is_scalar, PredictionMarketError::ScalarMarketRequiresValue);
+        // ✅ v3.8.1: 指定结算人 / 价格源市场只能按约定方式结算，公开提案会绕过该约定
+        require!(
+            !market.has_resolver() && market.price_feed_config().is_none(),
+            PredictionMarketError::MarketHasDesignatedResolver
+        );
+
+        let clock = Clock::get()?;
+        // ✅ v3.14.0: 同时校验 slot 排期与 Unix 时间戳排期
+        require!(
+            market.has_ended(clock.slot, clock.unix_timestamp),
+            PredictionMarketError::MarketNotEnded
+        );
+
+        // 提前校验比例，避免无效提案占用挑战窗口
+        validate_resolution_ratios(yes_ratio, no_ratio, token_type)?;
+        evidence.validate()?;
+
+        // 质押保证金
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.proposer_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.bond_vault.to_account_info(),
+                    authority: self.proposer.to_account_info(),
+                },
+            ),
+            token_compat::gross_up_transfer_fee(&self.usdc_mint.to_account_info(), bond)?,
+            self.usdc_mint.decimals,
+        )?;
+
+        let liveness_ends_at = clock
+            .unix_timestamp
+            .checked_add(self.oracle_config.liveness_secs)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let proposal = &mut self.proposal;
+        proposal.market = market.key();
+        proposal.proposer = self.proposer.key();
+        proposal.token_type = token_type;
+        proposal.yes_ratio = yes_ratio;
+        proposal.no_ratio = no_ratio;
+        proposal.bond = bond;
+        proposal.proposed_at = clock.unix_timestamp;
+        proposal.liveness_ends_at = liveness_ends_at;
+        proposal.disputer = Pubkey::default();
+        proposal.dispute_bond = 0;
+        proposal.disputed_at = 0;
+        proposal.status = ProposalStatus::Proposed;
+        proposal.bump = bump;
+        proposal.evidence_hash = evidence.hash;
+        proposal.evidence_uri = evidence.uri;
+        proposal.scalar_value = None;
+
+        msg!(
+            "✅ Resolution proposed: token_type={}, YES={}, NO={}, bond={}, liveness_ends_at={}",
+            token_type,
+            yes_ratio,
+            no_ratio,
+            bond,
+            liveness_ends_at
+        );
+
+        emit!(ResolutionProposedEvent {
+            proposer: proposal.proposer,
+            market: proposal.market,
+            token_type,
+            yes_ratio,
+            no_ratio,
+            bond,
+            liveness_ends_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
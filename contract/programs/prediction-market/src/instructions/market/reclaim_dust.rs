@@ -10,7 +10,7 @@ use crate::{
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：回收尾款所需账户
@@ -50,15 +50,16 @@ pub struct ReclaimDust<'info> {
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     /// 🔒 v1.2.7: 市场专用 USDC 金库（回收来源）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = token_program,
     )]
-    pub market_usdc_ata: Account<'info, TokenAccount>,
+    pub market_usdc_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// 团队钱包
     /// CHECK: Verified against global_config.team_wallet
@@ -72,11 +73,12 @@ pub struct ReclaimDust<'info> {
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = team_wallet,
+        associated_token::token_program = token_program,
     )]
-    pub team_usdc_ata: Account<'info, TokenAccount>,
+    pub team_usdc_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// Token Program
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -146,17 +148,19 @@ impl<'info> ReclaimDust<'info> {
             &[market_usdc_vault_bump],
         ]];
 
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                token::Transfer {
+                token_interface::TransferChecked {
                     from: self.market_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
                     to: self.team_usdc_ata.to_account_info(),
                     authority: self.market_usdc_vault.to_account_info(),
                 },
                 signer_seeds,
             ),
             reclaimable_amount,
+            self.usdc_mint.decimals,
         )?;
 
         msg!(
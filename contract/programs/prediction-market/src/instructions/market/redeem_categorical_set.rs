@@ -0,0 +1,191 @@
+//! ✅ v3.3.0: 分类市场赎回完整集合：用户销毁每个结果各 X 个代币，取回 X USDC
+//!
+//! 完整集合可能一部分来自 mint_categorical_set、一部分来自 LMSR 买入，
+//! 因此优先从 total_collateral_locked 支付，不足部分从 pool_collateral_reserve 支付。
+//! 任意赔付向量下负债恰好减少 X，偿付能力不变量保持成立。
+
+use crate::{
+    constants::{CATEGORICAL_MARKET, CONFIG, MARKET_USDC_VAULT},
+    errors::PredictionMarketError,
+    events::CategoricalCompleteSetEvent,
+    instructions::market::mint_categorical_set::validate_outcome_account_pairs,
+    state::{categorical_market::*, config::*},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：分类市场赎回完整集合所需账户
+#[derive(Accounts)]
+pub struct RedeemCategoricalSet<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 分类市场账户
+    #[account(
+        mut,
+        seeds = [CATEGORICAL_MARKET.as_bytes(), market.creator.as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, CategoricalMarket>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场USDC金库PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// 赎回分类完整集合
+///
+/// # remaining_accounts
+/// `[mint_0, user_ata_0, mint_1, user_ata_1, ...]`
+///
+/// 与二元 redeem_complete_set 一致：市场结算后不允许赎回，应使用 claim_categorical_rewards
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RedeemCategoricalSet<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        !accounts.market.is_completed,
+        PredictionMarketError::CurveAlreadyCompleted
+    );
+    require!(
+        !accounts.global_config.is_paused,
+        PredictionMarketError::ContractPaused
+    );
+
+    let user_key = accounts.user.key();
+    let pairs = validate_outcome_account_pairs(&accounts.market, &user_key, ctx.remaining_accounts)?;
+
+    // 账本：优先使用完整集合抵押品，不足部分由做市商储备承担
+    let market = &accounts.market;
+    let from_locked = amount.min(market.total_collateral_locked);
+    let from_pool = amount
+        .checked_sub(from_locked)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    require!(
+        market.pool_collateral_reserve >= from_pool,
+        PredictionMarketError::InsufficientLiquidity
+    );
+    require!(
+        accounts.market_usdc_ata.amount >= amount,
+        PredictionMarketError::InsufficientLiquidity
+    );
+
+    // 1. 销毁用户每个结果的代币（余额不足时由 token 程序拒绝）
+    for (mint_info, ata_info) in pairs {
+        token_interface::burn(
+            CpiContext::new(
+                accounts.outcome_token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: mint_info.clone(),
+                    from: ata_info.clone(),
+                    authority: accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    // 2. 从市场金库返还 USDC
+    let market_key = accounts.market.key();
+    let vault_bump = ctx.bumps.market_usdc_vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        MARKET_USDC_VAULT.as_bytes(),
+        market_key.as_ref(),
+        &[vault_bump],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.collateral_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: accounts.market_usdc_ata.to_account_info(),
+                mint: accounts.usdc_mint.to_account_info(),
+                to: accounts.user_usdc_ata.to_account_info(),
+                authority: accounts.market_usdc_vault.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        accounts.usdc_mint.decimals,
+    )?;
+
+    // 3. 更新账本
+    let market = &mut accounts.market;
+    market.total_collateral_locked = market
+        .total_collateral_locked
+        .checked_sub(from_locked)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.total_sets_minted = market.total_sets_minted.saturating_sub(from_locked);
+    market.pool_collateral_reserve = market
+        .pool_collateral_reserve
+        .checked_sub(from_pool)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    msg!(
+        "✅ Redeemed categorical set: amount={}, from_locked={}, from_pool={}",
+        amount,
+        from_locked,
+        from_pool
+    );
+
+    emit!(CategoricalCompleteSetEvent {
+        user: user_key,
+        market: market_key,
+        is_mint: false,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -6,14 +6,21 @@
 //! - 返还 X USDC 给用户
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET, MARKET_USDC_VAULT, USERINFO},  // 🔒 v1.2.7: 添加 MARKET_USDC_VAULT
+    constants::{
+        CONFIG, GLOBAL, INTENT_NONCE, MARKET, MARKET_USDC_VAULT, SESSION_ACTION_REDEEM, SESSION_KEY, USERINFO,
+    },  // 🔒 v1.2.7: 添加 MARKET_USDC_VAULT
     errors::PredictionMarketError,
-    state::{config::*, market::*},
+    state::{
+        config::*,
+        intent_nonce::IntentNonce,
+        market::*,
+        session_key::{authorize_session, SessionKey},
+    },
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：赎回完整集合所需账户
@@ -54,15 +61,16 @@ pub struct RedeemCompleteSet<'info> {
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 🔒 v1.2.7: 市场专用 USDC 金库（存放市场抵押品）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub market_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// YES 代币 mint
     /// ✅ v3.0.10: 支持两种 authority（global_vault 或 market PDA）
@@ -76,7 +84,7 @@ pub struct RedeemCompleteSet<'info> {
             auth == COption::Some(global_vault.key()) || auth == COption::Some(market.key())
         } @ PredictionMarketError::InvalidAuthority
     )]
-    pub yes_token: Box<Account<'info, Mint>>,
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// NO 代币 mint
     /// ✅ v3.0.10: 支持两种 authority（global_vault 或 market PDA）
@@ -88,31 +96,34 @@ pub struct RedeemCompleteSet<'info> {
             auth == COption::Some(global_vault.key()) || auth == COption::Some(market.key())
         } @ PredictionMarketError::InvalidAuthority
     )]
-    pub no_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// 用户的 YES ATA
     #[account(
         mut,
         associated_token::mint = yes_token,
         associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub user_yes_ata: Box<Account<'info, TokenAccount>>,
+    pub user_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 用户的 NO ATA
     #[account(
         mut,
         associated_token::mint = no_token,
         associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub user_no_ata: Box<Account<'info, TokenAccount>>,
+    pub user_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v1.1.0: 用户 USDC ATA（接收赎回的抵押品）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub user_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 用户信息（自动创建以支持被动持有者）
     /// 🔒 修复：使用 init_if_needed 允许仅通过链上转账收到代币的用户赎回
@@ -125,15 +136,36 @@ pub struct RedeemCompleteSet<'info> {
     )]
     pub user_info: Box<Account<'info, UserInfo>>,
 
-    /// 用户签名者
+    /// 用户（资金所有者）
+    /// ✅ v3.26.0: 须签名，或提供有效的 session_key + session_signer
+    /// CHECK: signer or valid session key, verified by authorize_session in handler
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub user: AccountInfo<'info>,
+
+    /// ✅ v3.26.0: 会话密钥（可选，与 session_signer 一同提供）：作用域与有效期
+    #[account(
+        seeds = [SESSION_KEY.as_bytes(), user.key().as_ref(), session_key.session_signer.as_ref()],
+        bump = session_key.bump,
+    )]
+    pub session_key: Option<Box<Account<'info, SessionKey>>>,
+
+    /// ✅ v3.26.0: 会话签名者（临时热钥，代替 user 签名）
+    pub session_signer: Option<Signer<'info>>,
+
+    /// ✅ v3.26.1: 用户委托权限（IntentNonce PDA，会话模式必须提供）：
+    /// 会话与意图共用的唯一 delegate，资金由该 PDA 从 user ATA 转出
+    #[account(
+        seeds = [INTENT_NONCE.as_bytes(), user.key().as_ref()],
+        bump = delegate_authority.bump,
+    )]
+    pub delegate_authority: Option<Box<Account<'info, IntentNonce>>>,
 
     /// 系统/代币/ATA程序
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -176,6 +208,30 @@ impl<'info> RedeemCompleteSet<'info> {
             msg!("✅ v3.0.10: Confirmed global_vault as mint authority");
         }
 
+        // ✅ v3.26.0: 交易授权（user 签名，或会话密钥签名）
+        let now = Clock::get()?.unix_timestamp;
+        let user_key = self.user.key();
+        let is_session = authorize_session(
+            &self.user,
+            self.session_key.as_deref().map(|s| &**s),
+            self.session_signer.as_deref(),
+            &self.market.key(),
+            SESSION_ACTION_REDEEM,
+            now,
+        )?;
+        // ✅ v3.26.1: 会话模式由用户委托权限 PDA（与 execute_intent 共用）签名转出
+        let delegate_bump = [self.delegate_authority.as_ref().map_or(0, |d| d.bump)];
+        let delegate_seeds: &[&[&[u8]]] = &[&[INTENT_NONCE.as_bytes(), user_key.as_ref(), &delegate_bump]];
+        let (authority, authority_seeds): (AccountInfo<'info>, &[&[&[u8]]]) = if is_session {
+            let delegate = self
+                .delegate_authority
+                .as_ref()
+                .ok_or(PredictionMarketError::InvalidSessionKey)?;
+            (delegate.to_account_info(), delegate_seeds)
+        } else {
+            (self.user.to_account_info(), &[])
+        };
+
         // 0. 🔒 初始化 user_info（如果是新创建的）
         if !self.user_info.is_initialized {
             self.user_info.user = self.user.key();
@@ -206,17 +262,21 @@ impl<'info> RedeemCompleteSet<'info> {
         // 紧急提取机制：
         //   - claim_rewards 在市场完成后允许执行（即使合约暂停）
         //   - 这确保用户资金不会被永久锁定
+        //
+        // ✅ v3.6.0: 例外 - 作废的条件市场按 50/50 结算，1:1 赎回与 claim 等价，继续允许
+        //   （与 claim_rewards 一致，作废后即使合约暂停也允许赎回）
         require!(
-            !self.market.is_completed,
+            !self.market.is_completed || self.market.is_voided,
             PredictionMarketError::CurveAlreadyCompleted
         );
 
         // ✅ FIX MEDIUM-4: 只在市场未完成时检查暂停状态
-        // 注意：上面已经确认市场未完成，所以这里必定执行
-        require!(
-            !self.global_config.is_paused,
-            PredictionMarketError::ContractPaused
-        );
+        if !self.market.is_voided {
+            require!(
+                !self.global_config.is_paused,
+                PredictionMarketError::ContractPaused
+            );
+        }
 
         // 验证用户有足够的代币
         require!(
@@ -243,28 +303,30 @@ impl<'info> RedeemCompleteSet<'info> {
         );
 
         // 1. 销毁用户的 YES 代币
-        token::burn(
-            CpiContext::new(
-                self.token_program.to_account_info(),
-                token::Burn {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                self.outcome_token_program.to_account_info(),
+                token_interface::Burn {
                     mint: self.yes_token.to_account_info(),
                     from: self.user_yes_ata.to_account_info(),
-                    authority: self.user.to_account_info(),
+                    authority: authority.clone(),
                 },
+                authority_seeds,
             ),
             amount,
         )?;
         msg!("✅ Burned {} YES tokens", amount);
 
         // 2. 销毁用户的 NO 代币
-        token::burn(
-            CpiContext::new(
-                self.token_program.to_account_info(),
-                token::Burn {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                self.outcome_token_program.to_account_info(),
+                token_interface::Burn {
                     mint: self.no_token.to_account_info(),
                     from: self.user_no_ata.to_account_info(),
-                    authority: self.user.to_account_info(),
+                    authority: authority.clone(),
                 },
+                authority_seeds,
             ),
             amount,
         )?;
@@ -279,17 +341,19 @@ impl<'info> RedeemCompleteSet<'info> {
             &[market_usdc_vault_bump],
         ]];
 
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::Transfer {
+                self.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
                     from: self.market_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
                     to: self.user_usdc_ata.to_account_info(),
                     authority: self.market_usdc_vault.to_account_info(),
                 },
                 signer_seeds,
             ),
             amount,
+            self.usdc_mint.decimals,
         )?;
         msg!("✅ Returned {} USDC to user from market vault", amount);
 
@@ -348,6 +412,19 @@ impl<'info> RedeemCompleteSet<'info> {
             timestamp: clock.unix_timestamp,
         });
 
+        // ✅ v3.26.0: 赎回不产生 USDC 支出，仅记录会话使用
+        if let Some(session) = self.session_key.as_ref() {
+            emit!(crate::events::SessionKeyUsedEvent {
+                owner: user_key,
+                session_signer: session.session_signer,
+                market: market_key,
+                action: SESSION_ACTION_REDEEM,
+                spend: 0,
+                total_spent: session.spent,
+                timestamp: now,
+            });
+        }
+
         // 🔒 v1.2.7: 发射金库余额快照事件（监控账本-金库一致性）
         // ⚠️ PRODUCT DECISION: reload() 必须保留
         // 用途：获取转账后的实时余额，用于计算准确的 balance_discrepancy
@@ -0,0 +1,194 @@
+//! ✅ v3.7.1: 乐观预言机：退还未最终确认提案的保证金（无需许可）
+//!
+//! 提案存续期间市场可能经其他路径结算（价格源、结算人、作废等），
+//! 此时 finalize_resolution / settle_resolution_dispute 会因 MarketIsCompleted 失败，
+//! 保证金将永久锁定在托管中。本指令在市场已结算后原路退还：
+//! - 提案保证金退还提案人，挑战保证金退还挑战人（无胜负裁决，不罚没）
+//! - 关闭保证金托管 ATA 与提案账户，租金退还提案人
+
+use crate::{
+    constants::{CONFIG, MARKET, RESOLUTION_PROPOSAL},
+    errors::PredictionMarketError,
+    events::ResolutionBondsRefundedEvent,
+    instructions::market::finalize_resolution::close_bond_vault,
+    state::{config::*, market::*, oracle::*},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：退还提案保证金
+#[derive(Accounts)]
+pub struct RefundResolutionBonds<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场（须已结算）
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 结算提案（退还后关闭，租金退还提案人）
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [RESOLUTION_PROPOSAL.as_bytes(), market.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// 提案人（接收提案账户与托管 ATA 的租金）
+    /// CHECK: address checked against proposal.proposer
+    #[account(
+        mut,
+        address = proposal.proposer @ PredictionMarketError::InvalidBondRecipient
+    )]
+    pub proposer: AccountInfo<'info>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 保证金托管 ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 提案人 USDC 账户
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        constraint = proposer_usdc_ata.owner == proposal.proposer @ PredictionMarketError::InvalidBondRecipient,
+    )]
+    pub proposer_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 挑战人 USDC 账户（仅已被挑战的提案需要提供）
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+    )]
+    pub disputer_usdc_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// 调用者（任何人）
+    pub caller: Signer<'info>,
+
+    /// 抵押品（USDC）代币程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RefundResolutionBonds<'info> {
+    /// 退还双方保证金并关闭提案
+    pub fn handler(&mut self) -> Result<()> {
+        require!(self.market.is_completed, PredictionMarketError::MarketNotCompleted);
+        // 最终确认的提案已在 finalize_resolution 中关闭，此处仅防御
+        require!(
+            self.proposal.status != ProposalStatus::Finalized,
+            PredictionMarketError::InvalidProposalStatus
+        );
+
+        let market_key = self.market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            RESOLUTION_PROPOSAL.as_bytes(),
+            market_key.as_ref(),
+            &[self.proposal.bump],
+        ]];
+        let proposal_info = self.proposal.to_account_info();
+
+        // 挑战保证金退还挑战人
+        let dispute_bond = self.proposal.dispute_bond;
+        if dispute_bond > 0 {
+            let disputer_usdc_ata = self
+                .disputer_usdc_ata
+                .as_ref()
+                .ok_or(PredictionMarketError::InvalidBondRecipient)?;
+            require!(
+                disputer_usdc_ata.owner == self.proposal.disputer,
+                PredictionMarketError::InvalidBondRecipient
+            );
+            self.transfer_from_vault(
+                disputer_usdc_ata.to_account_info(),
+                dispute_bond,
+                &proposal_info,
+                signer_seeds,
+            )?;
+        }
+
+        // 提案保证金（及外部转入的粉尘）退还提案人，保证托管账户可关闭
+        let proposer_refund = self
+            .bond_vault
+            .amount
+            .checked_sub(dispute_bond)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        if proposer_refund > 0 {
+            self.transfer_from_vault(
+                self.proposer_usdc_ata.to_account_info(),
+                proposer_refund,
+                &proposal_info,
+                signer_seeds,
+            )?;
+        }
+
+        close_bond_vault(
+            &self.token_program.to_account_info(),
+            &self.usdc_mint.to_account_info(),
+            &self.bond_vault.to_account_info(),
+            &proposal_info,
+            &self.proposer,
+            signer_seeds,
+        )?;
+
+        msg!(
+            "✅ v3.7.1 Resolution bonds refunded: market={}, proposer_refund={}, dispute_bond={}",
+            market_key,
+            proposer_refund,
+            dispute_bond
+        );
+
+        emit!(ResolutionBondsRefundedEvent {
+            caller: self.caller.key(),
+            market: market_key,
+            proposer: self.proposal.proposer,
+            proposer_refund,
+            disputer: self.proposal.disputer,
+            dispute_bond,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// 以提案 PDA 签名从托管转出
+    fn transfer_from_vault(
+        &self,
+        to: AccountInfo<'info>,
+        amount: u64,
+        proposal_info: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.bond_vault.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to,
+                    authority: proposal_info.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.usdc_mint.decimals,
+        )
+    }
+}
@@ -89,11 +89,7 @@ pub fn handler(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
         );
 
         // 计算实际比例用于日志（近似值）
-        let ratio_display = if smaller > 0 {
-            (larger * 100) / smaller // 以 0.01x 为单位
-        } else {
-            0
-        };
+        let ratio_display = (larger * 100).checked_div(smaller).unwrap_or(0); // 以 0.01x 为单位
 
         msg!(
             "✅ Pool ratio recovered: {:.2}:1 (threshold: < 3.5:1)",
@@ -1,107 +1,45 @@
-//! 市场指令：结算市场（由管理员触发）
+//! 市场结算核心逻辑（`SettleContext`）
+//!
+//! ✅ v3.12.1: 移除一步生效的管理员 resolution / resolve_scalar 指令。管理员结算只能经由
+//! propose_admin_resolution（标量市场为 propose_admin_scalar_resolution）→ 挑战窗口 →
+//! finalize_resolution 完成；其余结算路径（乐观预言机、价格源、结算人/委员会、事件组、作废）
+//! 均通过本模块的 `SettleContext::settle` 结算。
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET},
     errors::PredictionMarketError,
-    events::ResolutionEvent,
-    state::{config::*, market::*},
+    events::{MarketInvalidatedEvent, ResolutionEvent},
+    state::{event_group::*, market::*, market_metadata::*},
 };
-use anchor_lang::{prelude::*, system_program};
-use anchor_spl::{
-    associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token},
-};
-
-/// 账户集合：市场结算所需账户
-#[derive(Accounts)]
-pub struct Resolution<'info> {
-    /// 全局配置
-    #[account(
-        mut,
-        seeds = [CONFIG.as_bytes()],
-        bump,
-    )]
-    global_config: Box<Account<'info, Config>>,
-
-    /// 市场账户
-    #[account(
-        mut,
-        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()], 
-        bump
-    )]
-    market: Account<'info, Market>,
-
-    /// 全局金库（PDA，存放 USDC）
-    /// CHECK: global vault pda which stores USDC
-    #[account(
-        mut,
-        seeds = [GLOBAL.as_bytes()],
-        bump,
-    )]
-    pub global_vault: AccountInfo<'info>,
-
-    /// YES/NO 代币mint
-    pub yes_token: Box<Account<'info, Mint>>,
-    pub no_token: Box<Account<'info, Mint>>,
-
-    /// ✅ FIX CRITICAL: 添加 PDA 的代币账户用于清算
-    /// global_yes_ata - PDA 持有的 YES 代币（swap 卖出时积累）
-    #[account(
-        mut,
-        associated_token::mint = yes_token,
-        associated_token::authority = global_vault,
-    )]
-    pub global_yes_ata: Box<Account<'info, token::TokenAccount>>,
-
-    /// global_no_ata - PDA 持有的 NO 代币（swap 卖出时积累）
-    #[account(
-        mut,
-        associated_token::mint = no_token,
-        associated_token::authority = global_vault,
-    )]
-    pub global_no_ata: Box<Account<'info, token::TokenAccount>>,
-
-    // ✅ v1.0.23: 移除未使用的 user/user_info 账户（感谢审计发现!）
-    //
-    // 🔴 原问题：Resolution 强制要求 user 和 user_info 账户
-    //    但在 handler 函数中完全未使用这些账户
-    //    导致无人交易的市场无法结算（因为不存在 UserInfo PDA）
-    //
-    // ✅ 修复：移除这些账户约束
-    //    Resolution 是管理员操作，不需要用户账户
-    //    PDA 代币清算由 global_vault 签名执行
-    //
-    // 已删除：
-    // - user_info: Box<Account<'info, UserInfo>>
-    // - user: AccountInfo<'info>
-
-    /// 管理员（必须为全局authority）
-    /// 🔒 v1.1.0: CRITICAL - 强制验证 authority 必须是 global_config.authority
-    #[account(
-        mut,
-        constraint = authority.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
-    )]
-    pub authority: Signer<'info>,
-
-    /// 系统/代币/ATA程序
-    #[account(address = system_program::ID)]
-    pub system_program: Program<'info, System>,
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
-    #[account(address = associated_token::ID)]
-    pub associated_token_program: Program<'info, AssociatedToken>,
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// ✅ v3.7.0: 结算所需账户的借用视图
+///
+/// 所有结算路径（乐观预言机/管理员提案最终确认、价格源、结算人、事件组成员、作废）共用同一套结算逻辑，
+/// 确保 PDA 代币清算、抵押品释放与事件组/条件市场校验在所有结算路径上保持一致
+pub struct SettleContext<'a, 'info> {
+    pub market: &'a mut Account<'info, Market>,
+    pub global_vault: &'a AccountInfo<'info>,
+    pub yes_token: &'a InterfaceAccount<'info, Mint>,
+    pub no_token: &'a InterfaceAccount<'info, Mint>,
+    pub global_yes_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub global_no_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    /// YES/NO 代币程序（✅ v3.27.1: 可与抵押品代币程序不同）
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub event_group: Option<&'a Account<'info, EventGroup>>,
+    pub parent_market: Option<&'a Account<'info, Market>>,
+    /// 结算发起者（写入 ResolutionEvent）
+    pub authority: Pubkey,
+    /// ✅ v3.13.0: 结算证据（写入 ResolutionEvent）
+    /// ✅ v3.13.1: 所有路径必须提供有效证据（人工提交或由链上数据派生）
+    pub evidence: ResolutionEvidence,
+    /// ✅ v3.13.1: 市场元数据（market.has_metadata 为 true 时必须提供，结算时写入证据）
+    pub market_metadata: Option<&'a mut Account<'info, MarketMetadata>>,
 }
 
-impl<'info> Resolution<'info>{
-    /// 市场结算：由管理员调用，设置获胜方
-    ///
-    /// # 参数
-    /// * `yes_amount` - YES代币的赎回比例（0-10000，10000=100%）
-    /// * `no_amount` - NO代币的赎回比例（0-10000，10000=100%）
-    /// * `token_type` - 获胜方（0=NO, 1=YES, 2=平局）
-    /// * `is_completed` - 是否完成结算
-    /// * `global_vault_bump` - 全局金库bump
-    pub fn handler(&mut self, yes_amount: u64, no_amount: u64, token_type: u8, is_completed: bool, _global_vault_bump: u8) -> Result<()> {
+impl SettleContext<'_, '_> {
+    /// 结算核心逻辑：校验比例、销毁 PDA 持有的代币并释放抵押品
+    pub fn settle(&mut self, yes_amount: u64, no_amount: u64, token_type: u8, is_completed: bool, _global_vault_bump: u8) -> Result<()> {
         // ✅ v1.6.0: 验证枚举参数有效性（替代魔法数字）
         use crate::types::MarketOutcome;
 
@@ -110,18 +48,19 @@ impl<'info> Resolution<'info>{
 
         msg!("✅ v1.6.0 Resolution start: outcome={:?}, yes_amount={}, no_amount={}", outcome, yes_amount, no_amount);
 
-        // 1. 验证权限：仅限管理员
-        require!(
-            self.authority.key() == self.global_config.authority.key(),
-            PredictionMarketError::InvalidMigrationAuthority
-        );
-
         // 2. 验证市场未结算
         require!(
             !self.market.is_completed,
             PredictionMarketError::MarketIsCompleted
         );
 
+        // ✅ v3.13.1: 每条结算路径都必须携带证据；已承诺规则的市场必须写入元数据
+        self.evidence.validate()?;
+        require!(
+            !self.market.has_metadata || self.market_metadata.is_some(),
+            PredictionMarketError::MarketMetadataMismatch
+        );
+
         // ✅ v3.0.9: Gas 优化 - 统一获取 Clock，避免重复 syscall (~1,000 CU)
         let clock = Clock::get()?;
         let current_slot = clock.slot;
@@ -129,66 +68,97 @@ impl<'info> Resolution<'info>{
 
         // ✅ FIX HIGH-2: 验证市场已到达结束时间
         // 防止管理员在市场结束前提前结算
-        if let Some(ending_slot) = self.market.ending_slot {
-            require!(
-                current_slot >= ending_slot,
-                PredictionMarketError::MarketNotEnded
-            );
-            msg!(
-                "✅ Time check passed: current_slot={}, ending_slot={}",
-                current_slot,
-                ending_slot
-            );
-        }
-
-        // 3. ✅ v1.6.0: 枚举验证已在前面完成，此处保留向后兼容的数值验证
+        // ✅ v3.5.1: 事件组已作废时成员可提前按 Invalid 退出
+        let group_voided = outcome == MarketOutcome::Invalid
+            && self
+                .event_group
+                .is_some_and(|group| group.key() == self.market.event_group && group.is_voided);
+        // ✅ v3.6.1: 父市场结果不满足要求的条件市场同样可提前按 Invalid 作废
+        let parent_condition_failed = outcome == MarketOutcome::Invalid
+            && self.market.is_conditional()
+            && self.parent_market.is_some_and(|parent| {
+                parent.key() == self.market.parent_market
+                    && parent.is_completed
+                    && !self.market.parent_condition_met(parent)
+            });
+        // ✅ v3.14.0: 同时校验 slot 排期与 Unix 时间戳排期（end_time 以 Clock::unix_timestamp 为准）
         require!(
-            token_type <= 2,
-            PredictionMarketError::RESOLUTIONTOKEYTYPEERROR
+            group_voided
+                || parent_condition_failed
+                || self.market.has_ended(current_slot, current_timestamp),
+            PredictionMarketError::MarketNotEnded
+        );
+        msg!(
+            "✅ Time check passed: current_slot={}, ending_slot={:?}, now={}, end_time={}",
+            current_slot,
+            self.market.ending_slot,
+            current_timestamp,
+            self.market.end_time
         );
 
-        // 4. 验证赎回比例（使用基点：10000 = 100%）
-        // ✅ v1.2.4: 使用常量替代魔法值
-        use crate::constants::BASIS_POINTS_DIVISOR as MAX_BASIS_POINTS;
-
-        match token_type {
-            0 => {
-                // NO获胜：NO=100%, YES=0%
-                require!(
-                    no_amount == MAX_BASIS_POINTS,
-                    PredictionMarketError::RESOLUTIONNOAMOUNTERROR
-                );
-                require!(
-                    yes_amount == 0,
-                    PredictionMarketError::RESOLUTIONYESAMOUNTERROR
-                );
-                msg!("Resolution: NO wins (NO holders get 100%)");
-            },
-            1 => {
-                // YES获胜：YES=100%, NO=0%
-                require!(
-                    yes_amount == MAX_BASIS_POINTS,
-                    PredictionMarketError::RESOLUTIONYESAMOUNTERROR
-                );
-                require!(
-                    no_amount == 0,
-                    PredictionMarketError::RESOLUTIONNOAMOUNTERROR
-                );
-                msg!("Resolution: YES wins (YES holders get 100%)");
-            },
-            2 => {
-                // 平局：YES=50%, NO=50%（或自定义比例）
+        // 3-4. 验证获胜方与赎回比例
+        validate_resolution_ratios(yes_amount, no_amount, token_type)?;
+
+        // ✅ v3.5.0: 事件组成员必须与事件组结果一致（胜者 YES，其余 NO）
+        // ✅ v3.5.1: 作废的事件组成员只能按 Invalid 结算，退款比例取自事件组的归一化快照价格
+        let mut group_void_ratios = None;
+        if self.market.is_grouped() {
+            let group = self
+                .event_group
+                .as_ref()
+                .ok_or(PredictionMarketError::EventGroupMismatch)?;
+            require!(
+                group.key() == self.market.event_group,
+                PredictionMarketError::EventGroupMismatch
+            );
+            let index = group
+                .member_index(&self.market.key())
+                .ok_or(PredictionMarketError::EventGroupMismatch)?;
+
+            if outcome == MarketOutcome::Invalid {
+                require!(group.is_voided, PredictionMarketError::EventGroupNotVoided);
+                group_void_ratios = Some(group.void_refund_ratios(index));
+            } else {
+                require!(group.is_resolved, PredictionMarketError::EventGroupNotResolved);
+                let is_winner = index == group.winning_index as usize;
                 require!(
-                    yes_amount + no_amount == MAX_BASIS_POINTS,
-                    PredictionMarketError::RESOLUTIONYESAMOUNTERROR
+                    token_type == if is_winner { 1 } else { 0 },
+                    PredictionMarketError::EventGroupOutcomeMismatch
                 );
-                msg!("Resolution: Draw (YES={}, NO={})", yes_amount, no_amount);
-            },
-            _ => {
-                return Err(PredictionMarketError::RESOLUTIONTOKEYTYPEERROR.into());
             }
         }
 
+        // ✅ v3.10.0: 无效结果按最后成交价格快照退款（YES=p, NO=1-p）
+        let (yes_amount, no_amount) = if outcome == MarketOutcome::Invalid {
+            let ratios = group_void_ratios.unwrap_or_else(|| self.market.invalid_refund_ratios());
+            msg!(
+                "✅ v3.10.0 Invalid outcome: refund ratios from price snapshot YES={}, NO={}",
+                ratios.0,
+                ratios.1
+            );
+            ratios
+        } else {
+            (yes_amount, no_amount)
+        };
+
+        // ✅ v3.6.0: 条件市场只有在父市场按要求结算后才能正常结算，否则只能作废
+        // ✅ v3.10.0: Invalid 结果（作废退款）不依赖父市场状态
+        if self.market.is_conditional() && outcome != MarketOutcome::Invalid {
+            let parent = self
+                .parent_market
+                .as_ref()
+                .ok_or(PredictionMarketError::ParentMarketMismatch)?;
+            require!(
+                parent.key() == self.market.parent_market,
+                PredictionMarketError::ParentMarketMismatch
+            );
+            require!(parent.is_completed, PredictionMarketError::ParentMarketNotResolved);
+            require!(
+                self.market.parent_condition_met(parent),
+                PredictionMarketError::ParentConditionNotMet
+            );
+        }
+
         // 5. 设置结算参数（存储在market中）
         self.market.resolution_yes_ratio = yes_amount;
         self.market.resolution_no_ratio = no_amount;
@@ -294,10 +264,10 @@ impl<'info> Resolution<'info>{
                 &[_global_vault_bump],
             ]];
 
-            token::burn(
+            token_interface::burn(
                 CpiContext::new_with_signer(
                     self.token_program.to_account_info(),
-                    token::Burn {
+                    token_interface::Burn {
                         mint: self.yes_token.to_account_info(),
                         from: self.global_yes_ata.to_account_info(),
                         authority: self.global_vault.to_account_info(),
@@ -316,10 +286,10 @@ impl<'info> Resolution<'info>{
                 &[_global_vault_bump],
             ]];
 
-            token::burn(
+            token_interface::burn(
                 CpiContext::new_with_signer(
                     self.token_program.to_account_info(),
-                    token::Burn {
+                    token_interface::Burn {
                         mint: self.no_token.to_account_info(),
                         from: self.global_no_ata.to_account_info(),
                         authority: self.global_vault.to_account_info(),
@@ -376,6 +346,14 @@ impl<'info> Resolution<'info>{
             .checked_sub(total_pda_payout)
             .ok_or(PredictionMarketError::InsufficientLiquidity)?;
 
+        // ✅ v3.10.0: 无效结果下池内代币同样按快照价格退还给 LP（计入 Pool Ledger，供 LP 按本金退出）
+        if outcome == MarketOutcome::Invalid {
+            self.market.pool_collateral_reserve = self.market.pool_collateral_reserve
+                .checked_add(total_pda_payout)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            msg!("✅ v3.10.0: Credited {} USDC of pool token refunds to pool_collateral_reserve", total_pda_payout);
+        }
+
         // 11. 更新代币统计（只减去实际销毁的数量）
         self.market.total_yes_minted = self.market.total_yes_minted
             .checked_sub(yes_burnable)
@@ -455,19 +433,106 @@ impl<'info> Resolution<'info>{
             msg!("✅ v1.0.19: Cleared sentinel_no_minted flag (sentinel was destroyed)");
         }
 
+        // ✅ v3.13.1: 证据写入市场元数据
+        if let Some(metadata) = self.market_metadata.as_mut() {
+            metadata.record_evidence(&self.evidence, self.authority, current_timestamp);
+        }
+
         // ═══════════════════════════════════════════════════════════════
         // ✅ 发射市场解决事件
         // ═══════════════════════════════════════════════════════════════
         // ✅ v3.0.9: 使用预先缓存的 current_timestamp (line 128)
         emit!(ResolutionEvent {
-            authority: self.authority.key(),
+            authority: self.authority,
             market: self.market.key(),
             winner_token_type: token_type,
             yes_ratio: yes_amount,
             no_ratio: no_amount,
             timestamp: current_timestamp,  // ✅ v3.0.9: 复用缓存
+            evidence_hash: self.evidence.hash,
+            evidence_uri: self.evidence.uri.clone(),
         });
 
+        // ✅ v3.10.0: 无效结果事件（退款比例与价格快照）
+        if outcome == MarketOutcome::Invalid {
+            emit!(MarketInvalidatedEvent {
+                authority: self.authority,
+                market: self.market.key(),
+                snapshot_yes_price_bps: self.market.last_trade_yes_price_bps,
+                yes_refund_ratio: yes_amount,
+                no_refund_ratio: no_amount,
+                pool_refund_credited: total_pda_payout,
+                timestamp: current_timestamp,
+            });
+        }
+
         Ok(())
     }
 }
+
+/// 校验结算参数：获胜方（0=NO, 1=YES, 2=平局, 3=无效）与赎回比例（基点）是否一致
+///
+/// ✅ v3.7.0: 从结算核心逻辑中拆出，供乐观预言机在提案阶段提前校验
+pub fn validate_resolution_ratios(yes_amount: u64, no_amount: u64, token_type: u8) -> Result<()> {
+    // 3. ✅ v1.6.0: 枚举验证已在前面完成，此处保留向后兼容的数值验证
+    require!(
+        token_type <= 3,
+        PredictionMarketError::RESOLUTIONTOKEYTYPEERROR
+    );
+
+    // 4. 验证赎回比例（使用基点：10000 = 100%）
+    // ✅ v1.2.4: 使用常量替代魔法值
+    use crate::constants::BASIS_POINTS_DIVISOR as MAX_BASIS_POINTS;
+
+    match token_type {
+        0 => {
+            // NO获胜：NO=100%, YES=0%
+            require!(
+                no_amount == MAX_BASIS_POINTS,
+                PredictionMarketError::RESOLUTIONNOAMOUNTERROR
+            );
+            require!(
+                yes_amount == 0,
+                PredictionMarketError::RESOLUTIONYESAMOUNTERROR
+            );
+            msg!("Resolution: NO wins (NO holders get 100%)");
+        },
+        1 => {
+            // YES获胜：YES=100%, NO=0%
+            require!(
+                yes_amount == MAX_BASIS_POINTS,
+                PredictionMarketError::RESOLUTIONYESAMOUNTERROR
+            );
+            require!(
+                no_amount == 0,
+                PredictionMarketError::RESOLUTIONNOAMOUNTERROR
+            );
+            msg!("Resolution: YES wins (YES holders get 100%)");
+        },
+        2 => {
+            // 平局：YES=50%, NO=50%（或自定义比例）
+            require!(
+                yes_amount + no_amount == MAX_BASIS_POINTS,
+                PredictionMarketError::RESOLUTIONYESAMOUNTERROR
+            );
+            msg!("Resolution: Draw (YES={}, NO={})", yes_amount, no_amount);
+        },
+        3 => {
+            // ✅ v3.10.0: 无效：退款比例由链上价格快照决定，调用方必须传 0/0
+            require!(
+                yes_amount == 0,
+                PredictionMarketError::RESOLUTIONYESAMOUNTERROR
+            );
+            require!(
+                no_amount == 0,
+                PredictionMarketError::RESOLUTIONNOAMOUNTERROR
+            );
+            msg!("Resolution: Invalid (refund at last traded price snapshot)");
+        },
+        _ => {
+            return Err(PredictionMarketError::RESOLUTIONTOKEYTYPEERROR.into());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! ✅ v3.9.0: 指定结算人结算
+//!
+//! 市场创建时指定了单一结算人密钥时，该密钥可直接结算市场（参数与管理员结算提案一致）。
+//! 全局管理员仍可通过 propose_admin_resolution 两阶段结算。
+//!
+//! 结算通过 `SettleContext::settle` 完成，与其他结算路径的账本处理一致。
+//!
+//! ✅ v3.13.1: 结算人须附带结算证据（哈希 + URI），写入 MarketMetadata 并随 ResolutionEvent 发出。
+
+use crate::{
+    constants::{GLOBAL, MARKET, MARKET_METADATA},
+    errors::PredictionMarketError,
+    instructions::market::resolution::SettleContext,
+    state::{event_group::*, market::*, market_metadata::*},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：指定结算人结算
+#[derive(Accounts)]
+pub struct ResolveByResolver<'info> {
+    /// 市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// 全局金库（PDA，清算池内代币的签名者）
+    /// CHECK: global vault pda
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    #[account(mut)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库持有的 YES/NO（结算时清算）
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 所属事件组（仅事件组成员市场需要提供）
+    pub event_group: Option<Box<Account<'info, EventGroup>>>,
+
+    /// 父市场（仅条件市场需要提供）
+    pub parent_market: Option<Box<Account<'info, Market>>>,
+
+    /// ✅ v3.13.1: 市场元数据（记录结算证据；market.has_metadata 为 true 时必须提供）
+    #[account(
+        mut,
+        seeds = [MARKET_METADATA.as_bytes(), market.key().as_ref()],
+        bump = market_metadata.bump,
+    )]
+    pub market_metadata: Option<Box<Account<'info, MarketMetadata>>>,
+
+    /// 结算人（必须为 market.resolver，且不是委员会）
+    #[account(
+        constraint = !market.resolver_is_committee
+            && market.resolver == resolver.key() @ PredictionMarketError::InvalidResolver
+    )]
+    pub resolver: Signer<'info>,
+
+    /// YES/NO 代币程序（✅ v3.27.1: 可与抵押品代币程序不同）
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl ResolveByResolver<'_> {
+    /// 结算市场
+    ///
+    /// # 参数
+    /// * `yes_amount` - YES代币的赎回比例（0-10000）
+    /// * `no_amount` - NO代币的赎回比例（0-10000）
+    /// * `token_type` - 获胜方（0=NO, 1=YES, 2=平局, 3=无效：比例传 0/0，按价格快照退款）
+    /// * `evidence` - ✅ v3.13.1: 结算证据（哈希非全 0，URI 非空）
+    pub fn handler(
+        &mut self,
+        yes_amount: u64,
+        no_amount: u64,
+        token_type: u8,
+        evidence: ResolutionEvidence,
+        global_vault_bump: u8,
+    ) -> Result<()> {
+        // 标量市场的赔付比例由结算值决定，仅管理员可通过标量结算提案结算
+        require!(
+            !self.market.is_scalar,
+            PredictionMarketError::ScalarMarketRequiresValue
+        );
+
+        SettleContext {
+            market: &mut self.market,
+            global_vault: &self.global_vault,
+            yes_token: &self.yes_token,
+            no_token: &self.no_token,
+            global_yes_ata: &self.global_yes_ata,
+            global_no_ata: &self.global_no_ata,
+            token_program: &self.token_program,
+            event_group: self.event_group.as_deref(),
+            parent_market: self.parent_market.as_deref(),
+            authority: self.resolver.key(),
+            evidence,
+            market_metadata: self.market_metadata.as_deref_mut(),
+        }
+        .settle(yes_amount, no_amount, token_type, true, global_vault_bump)
+    }
+}
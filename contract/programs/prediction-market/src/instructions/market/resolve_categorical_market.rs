@@ -0,0 +1,223 @@
+//! ✅ v3.3.0: 分类市场结算（由管理员触发）
+//!
+//! 管理员提交赔付向量 payout_bps（长度 = outcome_count，总和 = 10000）：
+//! - 单一胜者：[0, 10000, 0, ...]
+//! - 按权重拆分：例如 [5000, 2500, 2500]
+//!
+//! 结算时根据各结果代币的实际流通量计算总负债，超出负债的部分（补贴剩余 + LP 费）
+//! 退还给创建者，剩余资金全部用于 claim_categorical_rewards 兑付。
+
+use crate::{
+    constants::{CATEGORICAL_MARKET, CONFIG, MARKET_USDC_VAULT},
+    errors::PredictionMarketError,
+    events::CategoricalResolutionEvent,
+    state::{categorical_market::*, config::*},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：分类市场结算所需账户
+#[derive(Accounts)]
+pub struct ResolveCategoricalMarket<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 分类市场账户
+    #[account(
+        mut,
+        seeds = [CATEGORICAL_MARKET.as_bytes(), market.creator.as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, CategoricalMarket>>,
+
+    /// 市场创建者（接收结算盈余）
+    /// CHECK: Verified against market.creator
+    #[account(
+        constraint = creator.key() == market.creator @ PredictionMarketError::IncorrectAuthority
+    )]
+    pub creator: AccountInfo<'info>,
+
+    /// 创建者 USDC ATA（接收补贴剩余与 LP 费）
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场USDC金库PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 管理员（必须为全局authority）
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// 分类市场结算
+///
+/// # remaining_accounts
+/// `[outcome_mint_0, ..., outcome_mint_{N-1}]`（只读，用于读取流通量）
+///
+/// # 负债计算
+/// `liability = Σ ceil(supply_i × payout_bps_i / 10000)`，向上取整保证逐个领取时不会资金不足
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveCategoricalMarket<'info>>,
+    payout_bps: Vec<u16>,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    require!(!accounts.market.is_completed, PredictionMarketError::MarketIsCompleted);
+
+    let clock = Clock::get()?;
+    if let Some(ending_slot) = accounts.market.ending_slot {
+        require!(clock.slot >= ending_slot, PredictionMarketError::MarketNotEnded);
+    }
+
+    let outcome_count = accounts.market.outcomes();
+    let winning_outcome = CategoricalMarket::validate_payout_vector(outcome_count, &payout_bps)?;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 计算总负债
+    // ═══════════════════════════════════════════════════════════════
+    require!(
+        ctx.remaining_accounts.len() == outcome_count,
+        PredictionMarketError::OutcomeAccountMismatch
+    );
+
+    let mut total_liability: u128 = 0;
+    for (i, mint_info) in ctx.remaining_accounts.iter().enumerate() {
+        require!(
+            mint_info.key() == accounts.market.outcome_mints[i],
+            PredictionMarketError::OutcomeAccountMismatch
+        );
+        let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+
+        let owed = (mint.supply as u128)
+            .checked_mul(payout_bps[i] as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .div_ceil(crate::constants::BASIS_POINTS_DIVISOR as u128);
+        total_liability = total_liability
+            .checked_add(owed)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    let total_liability: u64 = total_liability
+        .try_into()
+        .map_err(|_| PredictionMarketError::MathOverflow)?;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 偿付能力检查与盈余退还
+    // ═══════════════════════════════════════════════════════════════
+    let market = &accounts.market;
+    let total_available = market
+        .total_collateral_locked
+        .checked_add(market.pool_collateral_reserve)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    require!(
+        total_available >= total_liability,
+        PredictionMarketError::InsufficientLiquidity
+    );
+
+    let creator_refund = total_available
+        .checked_sub(total_liability)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .min(accounts.market_usdc_ata.amount);
+
+    let market_key = market.key();
+    if creator_refund > 0 {
+        let vault_bump = ctx.bumps.market_usdc_vault;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MARKET_USDC_VAULT.as_bytes(),
+            market_key.as_ref(),
+            &[vault_bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: accounts.market_usdc_ata.to_account_info(),
+                    mint: accounts.usdc_mint.to_account_info(),
+                    to: accounts.creator_usdc_ata.to_account_info(),
+                    authority: accounts.market_usdc_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            creator_refund,
+            accounts.usdc_mint.decimals,
+        )?;
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // 更新账本：剩余资金 = 总负债（优先计入 total_collateral_locked）
+    // ═══════════════════════════════════════════════════════════════
+    let remaining = total_available
+        .checked_sub(creator_refund)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let market = &mut accounts.market;
+    market.total_collateral_locked = market.total_collateral_locked.min(remaining);
+    market.pool_collateral_reserve = remaining
+        .checked_sub(market.total_collateral_locked)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    market.payout_bps = [0; crate::constants::MAX_CATEGORICAL_OUTCOMES];
+    market.payout_bps[..outcome_count].copy_from_slice(&payout_bps);
+    market.winning_outcome = winning_outcome;
+    market.is_completed = true;
+
+    msg!(
+        "✅ Categorical market resolved: winner={}, liability={}, creator_refund={}",
+        winning_outcome,
+        total_liability,
+        creator_refund
+    );
+
+    emit!(CategoricalResolutionEvent {
+        authority: accounts.authority.key(),
+        market: market_key,
+        winning_outcome,
+        payout_bps,
+        total_liability,
+        creator_refund,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
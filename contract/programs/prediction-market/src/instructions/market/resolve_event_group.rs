@@ -0,0 +1,180 @@
+//! ✅ v3.5.0: 事件组结算（由管理员触发）
+//!
+//! 指定唯一胜出成员，并将事件组金库中的全部担保抵押品注入胜者市场，
+//! 使胜者市场中由转换铸造的 YES 获得完整抵押。
+//! 成员市场随后各自通过结算提案结算，且必须与事件组结果一致（胜者 YES，其余 NO）。
+
+use crate::{
+    constants::{CONFIG, EVENT_GROUP, MARKET, MARKET_USDC_VAULT, MIN_EVENT_GROUP_MARKETS},
+    errors::PredictionMarketError,
+    events::EventGroupResolvedEvent,
+    state::{config::*, event_group::*, market::*},
+    token_compat,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：事件组结算所需账户
+#[derive(Accounts)]
+pub struct ResolveEventGroup<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 事件组
+    #[account(
+        mut,
+        seeds = [EVENT_GROUP.as_bytes(), &event_group.group_id.to_le_bytes()],
+        bump = event_group.bump,
+    )]
+    pub event_group: Box<Account<'info, EventGroup>>,
+
+    /// 胜出成员市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &winning_market.yes_token_mint.to_bytes(), &winning_market.no_token_mint.to_bytes()],
+        bump,
+        constraint = winning_market.event_group == event_group.key() @ PredictionMarketError::EventGroupMismatch
+    )]
+    pub winning_market: Box<Account<'info, Market>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 事件组 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = event_group,
+        associated_token::token_program = token_program,
+    )]
+    pub group_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 胜者市场 USDC 金库 PDA
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), winning_market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 胜者市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 管理员（必须为全局authority）
+    #[account(
+        constraint = authority.key() == global_config.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    /// 抵押品（USDC）代币程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl ResolveEventGroup<'_> {
+    /// 结算事件组
+    ///
+    /// # 参数
+    /// * `winning_index` - 胜出成员索引（须与 winning_market 对应）
+    pub fn handler(&mut self, winning_index: u8) -> Result<()> {
+        let group = &self.event_group;
+        require!(!group.is_resolved, PredictionMarketError::EventGroupAlreadyResolved);
+        require!(!group.is_voided, PredictionMarketError::EventGroupVoided);
+        require!(
+            group.market_count as usize >= MIN_EVENT_GROUP_MARKETS,
+            PredictionMarketError::EventGroupMismatch
+        );
+        require!(
+            group.member_index(&self.winning_market.key()) == Some(winning_index as usize),
+            PredictionMarketError::EventGroupOutcomeMismatch
+        );
+        require!(
+            !self.winning_market.is_completed,
+            PredictionMarketError::MarketIsCompleted
+        );
+
+        let clock = Clock::get()?;
+        if let Some(ending_slot) = self.winning_market.ending_slot {
+            require!(clock.slot >= ending_slot, PredictionMarketError::MarketNotEnded);
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // 将事件组金库全部余额注入胜者市场
+        // ═══════════════════════════════════════════════════════════════
+        let collateral = self.group_usdc_ata.amount;
+        require!(
+            collateral >= group.total_converted,
+            PredictionMarketError::InsufficientLiquidity
+        );
+
+        let group_key = group.key();
+        if collateral > 0 {
+            let group_id_bytes = group.group_id.to_le_bytes();
+            let group_seeds: &[&[&[u8]]] = &[&[
+                EVENT_GROUP.as_bytes(),
+                &group_id_bytes,
+                &[group.bump],
+            ]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.group_usdc_ata.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: self.market_usdc_ata.to_account_info(),
+                        authority: self.event_group.to_account_info(),
+                    },
+                    group_seeds,
+                ),
+                collateral,
+                self.usdc_mint.decimals,
+            )?;
+        }
+
+        // ✅ v3.27.0: 市场账本按金库实收金额记账（抵押品带转账手续费时扣除手续费）
+        let received = collateral
+            .checked_sub(token_compat::transfer_fee(&self.usdc_mint.to_account_info(), collateral)?)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let winning_market = &mut self.winning_market;
+        winning_market.total_collateral_locked = winning_market
+            .total_collateral_locked
+            .checked_add(received)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let group = &mut self.event_group;
+        group.is_resolved = true;
+        group.winning_index = winning_index;
+
+        msg!(
+            "✅ Event group resolved: winner={} ({}), collateral_transferred={}, total_converted={}",
+            winning_index,
+            winning_market.key(),
+            collateral,
+            group.total_converted
+        );
+
+        emit!(EventGroupResolvedEvent {
+            authority: self.authority.key(),
+            event_group: group_key,
+            winning_index,
+            winning_market: self.winning_market.key(),
+            collateral_transferred: collateral,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
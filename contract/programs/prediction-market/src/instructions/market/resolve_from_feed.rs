@@ -0,0 +1,189 @@
+//! ✅ v3.8.0: 价格源自动结算（无需许可）
+//!
+//! 市场创建时记录价格源账户与阈值；到达 resolve_after 后任何人可调用，
+//! 读取 Pyth 风格价格（price / conf / publish_time），校验 feed_id、结算窗口与置信区间后：
+//! - 价格严格高于阈值 → YES 胜出
+//! - 否则 → NO 胜出
+//!
+//! ✅ v3.8.1: 价格发布时间须落在 [resolve_after, resolve_after + max_staleness_secs] 窗口内，
+//! 调用者无法等待并挑选窗口之后的价格；窗口内无人结算时由管理员结算或到期作废。
+//!
+//! 结算通过 `SettleContext::settle` 完成，与其他结算路径的账本处理一致。
+//!
+//! ✅ v3.8.2: 不再固定价格源账户地址：Pyth pull oracle 的价格账户会被后续更新覆盖，
+//! 窗口内的历史价格只能由调用者自行提交（post_update）到新的 PriceUpdateV2 账户。
+//! 任何 Pyth Receiver 拥有、完全验证且 feed_id 与市场一致的账户均可用于结算，
+//! 发布时间仍须落在结算窗口内；`market.price_feed` 仅作为创建时登记的参考账户。
+//!
+//! ✅ v3.13.1: 结算证据由价格数据派生：哈希覆盖价格源账户、feed_id、price、conf、expo 与 publish_time，
+//! URI 记录价格源账户与发布时间，写入 MarketMetadata 后任何人可对照历史价格重新计算核验。
+
+use crate::{
+    constants::{GLOBAL, MARKET, MARKET_METADATA},
+    errors::PredictionMarketError,
+    events::PriceFeedResolutionEvent,
+    instructions::market::resolution::SettleContext,
+    price_feed::PriceFeedData,
+    state::{event_group::*, market::*, market_metadata::*},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：价格源自动结算
+#[derive(Accounts)]
+pub struct ResolveFromFeed<'info> {
+    /// 市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// 价格源账户（Pyth PriceUpdateV2，✅ v3.8.2: 可为调用者提交的任意历史价格账户）
+    /// CHECK: owner, layout and feed_id checked by PriceFeedData in the handler
+    pub price_feed: AccountInfo<'info>,
+
+    /// 全局金库（PDA，清算池内代币的签名者）
+    /// CHECK: global vault pda
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    #[account(mut)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库持有的 YES/NO（结算时清算）
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 所属事件组（仅事件组成员市场需要提供）
+    pub event_group: Option<Box<Account<'info, EventGroup>>>,
+
+    /// 父市场（仅条件市场需要提供）
+    pub parent_market: Option<Box<Account<'info, Market>>>,
+
+    /// ✅ v3.13.1: 市场元数据（记录结算证据；market.has_metadata 为 true 时必须提供）
+    #[account(
+        mut,
+        seeds = [MARKET_METADATA.as_bytes(), market.key().as_ref()],
+        bump = market_metadata.bump,
+    )]
+    pub market_metadata: Option<Box<Account<'info, MarketMetadata>>>,
+
+    /// 调用者（任何人）
+    pub caller: Signer<'info>,
+
+    /// YES/NO 代币程序（✅ v3.27.1: 可与抵押品代币程序不同）
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl ResolveFromFeed<'_> {
+    /// 读取价格源并结算市场
+    pub fn handler(&mut self, global_vault_bump: u8) -> Result<()> {
+        let config = self
+            .market
+            .price_feed_config()
+            .ok_or(PredictionMarketError::NotPriceFeedMarket)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= config.resolve_after, PredictionMarketError::MarketNotEnded);
+
+        // ═══════════════════════════════════════════════════════════════
+        // 读取并校验价格
+        // ═══════════════════════════════════════════════════════════════
+        let feed = PriceFeedData::try_from_account(&self.price_feed)?;
+        // ✅ v3.8.1: 账户内 feed_id 必须与市场创建时记录的一致
+        // ✅ v3.8.2: 账户地址不做限制，feed_id + Receiver owner + 完全验证即唯一的信任依据，
+        // 因此未登记 feed_id 的市场（旧账户迁移后为全 0）不可按价格源结算
+        require!(config.feed_id != [0u8; 32], PredictionMarketError::InvalidPriceFeed);
+        require!(feed.feed_id == config.feed_id, PredictionMarketError::InvalidPriceFeed);
+        feed.check_window(config.resolve_after, config.max_staleness_secs, now)?;
+        feed.check_confidence(config.max_conf_bps)?;
+
+        let above = feed.is_above(config.threshold, config.threshold_expo)?;
+        let (yes_ratio, no_ratio, token_type) = if above {
+            (crate::constants::BASIS_POINTS_DIVISOR, 0, 1)
+        } else {
+            (0, crate::constants::BASIS_POINTS_DIVISOR, 0)
+        };
+
+        msg!(
+            "✅ v3.8.0 Feed resolution: price={}e{} (conf={}, publish_time={}), threshold={}e{}, winner={}",
+            feed.price,
+            feed.expo,
+            feed.conf,
+            feed.publish_time,
+            config.threshold,
+            config.threshold_expo,
+            token_type
+        );
+
+        // ✅ v3.13.1: 由价格数据派生结算证据
+        let feed_key = self.price_feed.key();
+        let evidence = ResolutionEvidence::derived(
+            format!("pyth:{}?publish_time={}", feed_key, feed.publish_time),
+            &[
+                feed_key.as_ref(),
+                &feed.feed_id,
+                &feed.price.to_le_bytes(),
+                &feed.conf.to_le_bytes(),
+                &feed.expo.to_le_bytes(),
+                &feed.publish_time.to_le_bytes(),
+            ],
+        );
+
+        // ═══════════════════════════════════════════════════════════════
+        // 结算（所有结算路径共用核心逻辑）
+        // ═══════════════════════════════════════════════════════════════
+        SettleContext {
+            market: &mut self.market,
+            global_vault: &self.global_vault,
+            yes_token: &self.yes_token,
+            no_token: &self.no_token,
+            global_yes_ata: &self.global_yes_ata,
+            global_no_ata: &self.global_no_ata,
+            token_program: &self.token_program,
+            event_group: self.event_group.as_deref(),
+            parent_market: self.parent_market.as_deref(),
+            authority: self.caller.key(),
+            evidence,
+            market_metadata: self.market_metadata.as_deref_mut(),
+        }
+        .settle(yes_ratio, no_ratio, token_type, true, global_vault_bump)?;
+
+        emit!(PriceFeedResolutionEvent {
+            caller: self.caller.key(),
+            market: self.market.key(),
+            price_feed: config.feed,
+            price: feed.price,
+            conf: feed.conf,
+            expo: feed.expo,
+            publish_time: feed.publish_time,
+            threshold: config.threshold,
+            threshold_expo: config.threshold_expo,
+            winner_token_type: token_type,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! ✅ v3.26.0: 用户指令：撤销会话密钥（关闭账户，租金退回 owner）
+//!
+//! 账户关闭后会话签名者无法再通过 authorize_session 校验。
+//! ✅ v3.26.1: ATA 的 delegate 为会话与意图共用的委托权限 PDA，撤销单个会话无需（也不应）
+//! revoke ATA 授权；如需停止所有委托交易，客户端再 revoke ATA 的 delegate 授权。
+
+use crate::{constants::SESSION_KEY, events::SessionKeyRevokedEvent, state::session_key::SessionKey};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    /// 会话密钥
+    #[account(
+        mut,
+        close = owner,
+        seeds = [SESSION_KEY.as_bytes(), owner.key().as_ref(), session_key.session_signer.as_ref()],
+        bump = session_key.bump,
+    )]
+    pub session_key: Box<Account<'info, SessionKey>>,
+
+    /// 授权人
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+impl RevokeSessionKey<'_> {
+    pub fn handler(&mut self) -> Result<()> {
+        let session = &self.session_key;
+
+        emit!(SessionKeyRevokedEvent {
+            owner: session.owner,
+            session_signer: session.session_signer,
+            spent: session.spent,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("✅ v3.26.0: Session key {} revoked by {}", session.session_signer, session.owner);
+        Ok(())
+    }
+}
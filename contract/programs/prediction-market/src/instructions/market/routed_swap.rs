@@ -0,0 +1,664 @@
+//! ✅ v3.19.0: 智能路由交易：在直接成交与完整集合路径之间择优执行
+//!
+//! 同一笔交易存在三条等价路径：
+//! - 直接：与 LMSR 池子成交（与 swap 相同）
+//! - 铸造后卖出（仅买入）：买 k 个 NO = 铸造 k 个完整集合 + 将 k 个 YES 卖回池子
+//! - 买入后赎回（仅卖出）：卖 A 个 YES = 从池子买入 A 个 NO + 赎回 A 个完整集合
+//!
+//! 无手续费时 LMSR 满足 C(q + k·1) = C(q) + k，三条路径成本相同；
+//! 买卖费率不同、动态 b 值或取整时完整集合路径可能更优。
+//!
+//! 比较口径：
+//! - 买入：直接路径用 `amount` USDC 买到 k 个代币；若铸造后卖出获得同样 k 个代币的净成本更低，则改走该路径
+//! - 卖出：比较直接卖出 `amount` 个代币与买入后赎回的税后 USDC 净收入
+//!
+//! 完整集合路径需要用户 USDC 余额足以垫付中间步骤（铸造抵押品 / 买入成本），
+//! 不满足或超出池子限额时回退到直接路径。
+
+use crate::{
+    constants::{BASIS_POINTS_DIVISOR, CONFIG, GLOBAL, MARKET, MARKET_USDC_VAULT, MAX_POSITION_IMBALANCE_MULTIPLIER, MAX_SINGLE_TRADE_BPS},
+    errors::PredictionMarketError,
+    events::SwapExecuted,
+    state::{config::*, market::*},
+    token_compat,
+    types::SwapRoute,
+};
+use anchor_lang::{prelude::*, solana_program::program_option::COption, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：智能路由交易所需账户
+#[derive(Accounts)]
+pub struct RoutedSwap<'info> {
+    /// 全局配置
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场账户
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 全局金库（池子代币 authority，mint authority 未转移时的铸造签名者）
+    /// CHECK: global vault pda used as authority
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES 代币 mint（支持 global_vault 或 market PDA 作为 mint authority）
+    #[account(
+        mut,
+        constraint = {
+            let auth = yes_token.mint_authority;
+            auth == COption::Some(global_vault.key()) || auth == COption::Some(market.key())
+        } @ PredictionMarketError::InvalidAuthority
+    )]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// NO 代币 mint
+    #[account(
+        mut,
+        constraint = {
+            let auth = no_token.mint_authority;
+            auth == COption::Some(global_vault.key()) || auth == COption::Some(market.key())
+        } @ PredictionMarketError::InvalidAuthority
+    )]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库的 YES/NO ATA（池子储备）
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户的 YES/NO ATA（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = yes_token,
+        associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub user_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = no_token,
+        associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub user_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 市场专用 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 市场 USDC 金库 PDA（签名权限）
+    /// CHECK: market-specific usdc vault pda
+    #[account(
+        seeds = [MARKET_USDC_VAULT.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub market_usdc_vault: AccountInfo<'info>,
+
+    /// 用户 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 团队钱包 USDC ATA（接收平台手续费）
+    #[account(
+        mut,
+        constraint = team_usdc_ata.mint == usdc_mint.key() @ PredictionMarketError::InvalidMint,
+        constraint = team_usdc_ata.owner == global_config.team_wallet @ PredictionMarketError::IncorrectAuthority,
+    )]
+    pub team_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户签名者
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// 路由报价
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouteQuote {
+    /// 选中的路由
+    pub route: SwapRoute,
+    /// 完整集合数量（买入为 k，卖出为 A；直接路由为 0）
+    pub set_amount: u64,
+    /// 完整集合路由中池子腿的输入（铸造后卖出：卖出代币数；买入后赎回：含费 USDC）
+    pub pool_leg_amount: u64,
+    /// 直接路由的预期输出
+    pub direct_out: u64,
+    /// 选中路由的预期净输出（买入为代币，卖出为 USDC）
+    pub expected_out: u64,
+    /// 选中路由的预期净输入（买入为 USDC，卖出为代币）
+    pub expected_in: u64,
+}
+
+/// 按市场费率覆盖取 (platform_bps, lp_bps)
+/// ✅ v3.24.0: 与 Market::swap 一致，包含动态 LP 费率
+fn fee_bps(market: &Market, cfg: &Config, direction: u8, token_type: u8, now: i64) -> (u64, u64) {
+    let (platform_bps, lp_bps, _) = market.swap_fee_bps(cfg, direction, token_type, None, now);
+    (platform_bps, lp_bps)
+}
+
+/// 扣除手续费后的净额（平台费与 LP 费分别向下取整，与 Market::swap 一致）
+fn net_of_fee(amount: u64, (platform_bps, lp_bps): (u64, u64)) -> Result<u64> {
+    let fee = |bps: u64| -> Result<u64> {
+        Ok(((amount as u128)
+            .checked_mul(bps as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / BASIS_POINTS_DIVISOR as u128) as u64)
+    };
+    let total_fee = fee(platform_bps)?
+        .checked_add(fee(lp_bps)?)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    amount
+        .checked_sub(total_fee)
+        .ok_or_else(|| PredictionMarketError::MathOverflow.into())
+}
+
+/// 交易后持仓是否在 MAX_POSITION_IMBALANCE_MULTIPLIER × b 上限内（与 apply_buy / apply_sell 一致）
+fn within_position_cap(effective_b: u64, new_q_yes: i64, new_q_no: i64) -> bool {
+    new_q_yes.abs_diff(new_q_no) <= effective_b.saturating_mul(MAX_POSITION_IMBALANCE_MULTIPLIER)
+}
+
+/// 计算各路由报价并选择最优路由
+///
+/// 使用动态 b 值（与 Market::swap 一致）；`user_usdc_balance` 用于判断完整集合路径能否垫付
+pub fn quote_route(
+    market: &Market,
+    cfg: &Config,
+    amount: u64,
+    direction: u8,
+    token_type: u8,
+    user_usdc_balance: u64,
+    now: i64,
+) -> Result<RouteQuote> {
+    use crate::math::lmsr::{gross_up_for_fee, lmsr_buy_cost, lmsr_sell_payout, lmsr_tokens_for_usdc};
+
+    let effective_b = market.calculate_effective_lmsr_b()?;
+    let (q_yes, q_no) = (market.lmsr_q_yes, market.lmsr_q_no);
+    let is_yes = token_type == 1;
+    let opposite = 1 - token_type;
+    let max_trade_size = ((market.pool_collateral_reserve as u128)
+        .checked_mul(MAX_SINGLE_TRADE_BPS as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        / BASIS_POINTS_DIVISOR as u128) as u64;
+
+    if direction == 0 {
+        // 直接：amount USDC（含费）买入 k 个代币
+        let net = net_of_fee(amount, fee_bps(market, cfg, 0, token_type, now))?;
+        let k = lmsr_tokens_for_usdc(effective_b, q_yes, q_no, net, is_yes)?;
+        let direct = RouteQuote {
+            route: SwapRoute::Direct,
+            set_amount: 0,
+            pool_leg_amount: amount,
+            direct_out: k,
+            expected_out: k,
+            expected_in: amount,
+        };
+
+        // 铸造后卖出：铸造 k 个完整集合，将 k 个另一侧代币卖回池子
+        if k == 0 || k > max_trade_size || user_usdc_balance < k {
+            return Ok(direct);
+        }
+        let payout = lmsr_sell_payout(effective_b, q_yes, q_no, k, !is_yes)?;
+        let (new_q_yes, new_q_no) = if is_yes {
+            (q_yes, q_no.saturating_sub(k as i64))
+        } else {
+            (q_yes.saturating_sub(k as i64), q_no)
+        };
+        if payout > market.pool_collateral_reserve
+            || !within_position_cap(effective_b, new_q_yes, new_q_no)
+        {
+            return Ok(direct);
+        }
+        let proceeds = net_of_fee(payout, fee_bps(market, cfg, 1, opposite, now))?;
+        let cost = k.saturating_sub(proceeds);
+        if cost < amount {
+            return Ok(RouteQuote {
+                route: SwapRoute::MintAndSell,
+                set_amount: k,
+                pool_leg_amount: k,
+                direct_out: k,
+                expected_out: k,
+                expected_in: cost,
+            });
+        }
+        Ok(direct)
+    } else {
+        // 直接：卖出 amount 个代币
+        let payout = lmsr_sell_payout(effective_b, q_yes, q_no, amount, is_yes)?;
+        let direct_out = net_of_fee(payout, fee_bps(market, cfg, 1, token_type, now))?;
+        let direct = RouteQuote {
+            route: SwapRoute::Direct,
+            set_amount: 0,
+            pool_leg_amount: amount,
+            direct_out,
+            expected_out: direct_out,
+            expected_in: amount,
+        };
+
+        // 买入后赎回：从池子买入 amount 个另一侧代币，再赎回 amount 个完整集合
+        let (platform_bps, lp_bps) = fee_bps(market, cfg, 0, opposite, now);
+        let cost = lmsr_buy_cost(effective_b, q_yes, q_no, amount, !is_yes)?;
+        let gross = gross_up_for_fee(
+            cost,
+            platform_bps.checked_add(lp_bps).ok_or(PredictionMarketError::MathOverflow)?,
+        )?;
+        let opposite_reserve = if is_yes { market.pool_no_reserve } else { market.pool_yes_reserve };
+        let (new_q_yes, new_q_no) = if is_yes {
+            (q_yes, q_no.saturating_add(amount as i64))
+        } else {
+            (q_yes.saturating_add(amount as i64), q_no)
+        };
+        if gross > max_trade_size
+            || user_usdc_balance < gross
+            || opposite_reserve < amount
+            || market.total_collateral_locked < amount
+            || !within_position_cap(effective_b, new_q_yes, new_q_no)
+        {
+            return Ok(direct);
+        }
+        let redeem_out = amount.saturating_sub(gross);
+        if redeem_out > direct_out {
+            return Ok(RouteQuote {
+                route: SwapRoute::BuyAndRedeem,
+                set_amount: amount,
+                pool_leg_amount: gross,
+                direct_out,
+                expected_out: redeem_out,
+                expected_in: amount,
+            });
+        }
+        Ok(direct)
+    }
+}
+
+impl<'info> RoutedSwap<'info> {
+    /// 处理智能路由交易
+    ///
+    /// # 参数
+    /// * `amount` - 输入数量（买入为 USDC，卖出为代币）
+    /// * `direction` - 0=买, 1=卖
+    /// * `token_type` - 0=NO, 1=YES
+    /// * `minimum_receive_amount` - 最少输出（买入为代币，卖出为 USDC）
+    /// * `deadline` - 交易过期时间戳，0 表示不检查
+    #[allow(clippy::too_many_arguments)]
+    pub fn handler(
+        &mut self,
+        amount: u64,
+        direction: u8,
+        token_type: u8,
+        minimum_receive_amount: u64,
+        deadline: i64,
+        global_vault_bump: u8,
+        market_bump: u8,
+        market_usdc_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            self.usdc_mint.decimals == crate::constants::USDC_DECIMALS,
+            PredictionMarketError::InvalidParameter
+        );
+        require!(
+            self.global_vault.owner == &crate::ID,
+            PredictionMarketError::InvalidAuthority
+        );
+        require!(!self.global_config.is_paused, PredictionMarketError::ContractPaused);
+        require!(!self.market.market_paused, PredictionMarketError::MarketPaused);
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+        require!(direction <= 1, PredictionMarketError::InvalidTradeDirection);
+        require!(token_type <= 1, PredictionMarketError::InvalidTokenType);
+
+        let now = Clock::get()?.unix_timestamp;
+        if deadline > 0 {
+            require!(now <= deadline, PredictionMarketError::TransactionExpired);
+        }
+        require!(
+            self.market.pool_collateral_reserve >= self.global_config.min_trading_liquidity,
+            PredictionMarketError::MarketBelowMinLiquidity
+        );
+
+        let quote = quote_route(
+            &self.market,
+            &self.global_config,
+            amount,
+            direction,
+            token_type,
+            self.user_usdc_ata.amount,
+            now,
+        )?;
+
+        msg!(
+            "✅ v3.19.0: Route {:?} selected (direct_out={}, expected_out={}, expected_in={})",
+            quote.route,
+            quote.direct_out,
+            quote.expected_out,
+            quote.expected_in
+        );
+
+        let opposite = 1 - token_type;
+        let (amount_in, amount_out, fee, lp_fee_bps) = match quote.route {
+            SwapRoute::Direct => {
+                let result = self.pool_swap(
+                    amount,
+                    direction,
+                    token_type,
+                    minimum_receive_amount,
+                    None,
+                    global_vault_bump,
+                    market_usdc_vault_bump,
+                )?;
+                if direction == 0 {
+                    (
+                        result
+                            .usdc_amount
+                            .checked_add(result.fee_usdc)
+                            .ok_or(PredictionMarketError::MathOverflow)?,
+                        result.token_amount,
+                        result.fee_usdc,
+                        result.lp_fee_bps,
+                    )
+                } else {
+                    (result.token_amount, result.usdc_amount, result.fee_usdc, result.lp_fee_bps)
+                }
+            }
+            SwapRoute::MintAndSell => {
+                // 1. 铸造 k 个完整集合；2. 将 k 个另一侧代币卖回池子
+                let k = quote.set_amount;
+                require!(k >= minimum_receive_amount, PredictionMarketError::SlippageExceeded);
+
+                self.mint_sets(k, global_vault_bump, market_bump)?;
+                let result = self.pool_swap(k, 1, opposite, 0, None, global_vault_bump, market_usdc_vault_bump)?;
+
+                let cost = k
+                    .checked_sub(result.usdc_amount)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+                require!(cost <= amount, PredictionMarketError::SlippageExceeded);
+                (cost, k, result.fee_usdc, result.lp_fee_bps)
+            }
+            SwapRoute::BuyAndRedeem => {
+                // 1. 从池子精确买入 A 个另一侧代币；2. 赎回 A 个完整集合
+                let set_amount = quote.set_amount;
+                let result = self.pool_swap(
+                    quote.pool_leg_amount,
+                    0,
+                    opposite,
+                    set_amount,
+                    Some(set_amount),
+                    global_vault_bump,
+                    market_usdc_vault_bump,
+                )?;
+                let paid = result
+                    .usdc_amount
+                    .checked_add(result.fee_usdc)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+
+                self.redeem_sets(set_amount, market_usdc_vault_bump)?;
+
+                let usdc_out = set_amount
+                    .checked_sub(paid)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+                require!(
+                    usdc_out >= minimum_receive_amount,
+                    PredictionMarketError::SlippageExceeded
+                );
+                (set_amount, usdc_out, result.fee_usdc, result.lp_fee_bps)
+            }
+        };
+
+        // ✅ v3.10.0: 记录最后成交价格快照（Invalid 结果按此退款）
+        self.market.last_trade_yes_price_bps = self.market.calculate_yes_price_bps()?;
+
+        emit!(SwapExecuted {
+            market: self.market.key(),
+            user: self.user.key(),
+            is_yes: token_type == 1,
+            amount_in,
+            amount_out,
+            fee,
+            timestamp: now,
+            route: quote.route.to_u8(),
+            lp_fee_bps,
+        });
+
+        Ok(())
+    }
+
+    /// 与池子成交（委托给 `Market::swap`）
+    #[allow(clippy::too_many_arguments)]
+    fn pool_swap(
+        &mut self,
+        amount: u64,
+        direction: u8,
+        token_type: u8,
+        minimum_receive_amount: u64,
+        exact_output: Option<u64>,
+        global_vault_bump: u8,
+        market_usdc_vault_bump: u8,
+    ) -> Result<SwapResult> {
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+        let source = &mut self.global_vault.to_account_info();
+        let global_yes_ata_info = &mut self.global_yes_ata.to_account_info();
+        let user_yes_ata_info = &mut self.user_yes_ata.to_account_info();
+        let global_no_ata_info = &mut self.global_no_ata.to_account_info();
+        let user_no_ata_info = &mut self.user_no_ata.to_account_info();
+
+        self.market.swap(
+            &mut self.global_config,
+            global_yes_ata_info,
+            user_yes_ata_info,
+            global_no_ata_info,
+            user_no_ata_info,
+            source,
+            amount,
+            direction,
+            token_type,
+            minimum_receive_amount,
+            exact_output,
+            &self.user,
+            &[],
+            signer_seeds,
+            &self.collateral_token_program,
+            &self.outcome_token_program,
+            &self.usdc_mint,
+            &self.yes_token.to_account_info(),
+            &self.no_token.to_account_info(),
+            &self.market_usdc_ata,
+            &self.market_usdc_vault,
+            market_usdc_vault_bump,
+            &self.user_usdc_ata,
+            &self.team_usdc_ata,
+            None,
+            None,
+        )
+    }
+
+    /// 铸造完整集合（与 mint_complete_set 的资金流与账本更新一致）
+    fn mint_sets(&mut self, amount: u64, global_vault_bump: u8, market_bump: u8) -> Result<()> {
+        require!(!self.market.is_completed, PredictionMarketError::CurveAlreadyCompleted);
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.user_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.market_usdc_ata.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            token_compat::gross_up_transfer_fee(&self.usdc_mint.to_account_info(), amount)?,
+            self.usdc_mint.decimals,
+        )?;
+
+        let yes_bytes = self.yes_token.key().to_bytes();
+        let no_bytes = self.no_token.key().to_bytes();
+        let market_seeds: &[&[&[u8]]] = &[&[MARKET.as_bytes(), &yes_bytes, &no_bytes, &[market_bump]]];
+        let global_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+        let (authority, signer_seeds) =
+            if self.yes_token.mint_authority == COption::Some(self.market.key()) {
+                (self.market.to_account_info(), market_seeds)
+            } else {
+                (self.global_vault.to_account_info(), global_seeds)
+            };
+
+        for (mint, to) in [
+            (self.yes_token.to_account_info(), self.user_yes_ata.to_account_info()),
+            (self.no_token.to_account_info(), self.user_no_ata.to_account_info()),
+        ] {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    self.outcome_token_program.to_account_info(),
+                    token_interface::MintTo { mint, to, authority: authority.clone() },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+
+        let market = &mut self.market;
+        market.total_collateral_locked = market
+            .total_collateral_locked
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.total_yes_minted = market
+            .total_yes_minted
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.total_no_minted = market
+            .total_no_minted
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.token_yes_total_supply = market
+            .token_yes_total_supply
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.token_no_total_supply = market
+            .token_no_total_supply
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// 赎回完整集合（与 redeem_complete_set 的资金流与账本更新一致）
+    fn redeem_sets(&mut self, amount: u64, market_usdc_vault_bump: u8) -> Result<()> {
+        require!(
+            self.market.total_collateral_locked >= amount,
+            PredictionMarketError::InsufficientLiquidity
+        );
+        // 池子腿已改变金库余额，重新加载后再校验
+        self.market_usdc_ata.reload()?;
+        require!(
+            self.market_usdc_ata.amount >= amount,
+            PredictionMarketError::InsufficientLiquidity
+        );
+
+        for (mint, from) in [
+            (self.yes_token.to_account_info(), self.user_yes_ata.to_account_info()),
+            (self.no_token.to_account_info(), self.user_no_ata.to_account_info()),
+        ] {
+            token_interface::burn(
+                CpiContext::new(
+                    self.outcome_token_program.to_account_info(),
+                    token_interface::Burn { mint, from, authority: self.user.to_account_info() },
+                ),
+                amount,
+            )?;
+        }
+
+        let market_key = self.market.key();
+        let vault_seeds: &[&[&[u8]]] = &[&[
+            MARKET_USDC_VAULT.as_bytes(),
+            market_key.as_ref(),
+            &[market_usdc_vault_bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.market_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.user_usdc_ata.to_account_info(),
+                    authority: self.market_usdc_vault.to_account_info(),
+                },
+                vault_seeds,
+            ),
+            amount,
+            self.usdc_mint.decimals,
+        )?;
+
+        let market = &mut self.market;
+        market.total_collateral_locked = market
+            .total_collateral_locked
+            .checked_sub(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.total_yes_minted = market
+            .total_yes_minted
+            .checked_sub(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.total_no_minted = market
+            .total_no_minted
+            .checked_sub(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.token_yes_total_supply = market
+            .token_yes_total_supply
+            .checked_sub(amount)
+            .ok_or(PredictionMarketError::InsufficientBalance)?;
+        market.token_no_total_supply = market
+            .token_no_total_supply
+            .checked_sub(amount)
+            .ok_or(PredictionMarketError::InsufficientBalance)?;
+        Ok(())
+    }
+}
@@ -16,11 +16,12 @@ use crate::{
     constants::{CONFIG, GLOBAL, LPPOSITION, MARKET, MARKET_USDC_VAULT},  // 🔒 v1.2.7: 添加 MARKET_USDC_VAULT
     errors::PredictionMarketError,
     state::{config::*, market::*},
+    token_compat,
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：Pool 种子流动性注入
@@ -65,7 +66,7 @@ pub struct SeedPool<'info> {
             auth == COption::Some(global_vault.key()) || auth == COption::Some(market.key())
         } @ PredictionMarketError::InvalidAuthority
     )]
-    pub yes_token: Box<Account<'info, Mint>>,
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// NO 代币 mint
     /// ✅ v3.0.10: 支持两种 authority（global_vault 或 market PDA）
@@ -77,28 +78,30 @@ pub struct SeedPool<'info> {
             auth == COption::Some(global_vault.key()) || auth == COption::Some(market.key())
         } @ PredictionMarketError::InvalidAuthority
     )]
-    pub no_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// 全局 YES/NO 代币账户（Pool 库存）
     #[account(
         mut,
         associated_token::mint = yes_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub global_yes_ata: Box<Account<'info, TokenAccount>>,
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = no_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub global_no_ata: Box<Account<'info, TokenAccount>>,
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v1.1.0: USDC Mint
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 🔒 v1.2.7: 市场USDC金库PDA（签名权限）
     /// CHECK: market-specific usdc vault pda
@@ -113,16 +116,18 @@ pub struct SeedPool<'info> {
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub market_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v1.1.0: 种子提供者 USDC ATA
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = seeder,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub seeder_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub seeder_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 种子提供者的 LP Position（可选，如果想给初始 LP 份额）
     /// ✅ v3.0.2: 修复种子顺序为 [LPPOSITION, market, user]
@@ -145,8 +150,9 @@ pub struct SeedPool<'info> {
     /// 系统/代币/ATA程序
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -251,16 +257,18 @@ impl<'info> SeedPool<'info> {
 
         // 🔒 v1.2.7: 2. 转移 USDC 到 market_usdc_ata（修复金库错误）
         msg!("Transferring {} USDC to market vault", usdc_amount);
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new(
-                self.token_program.to_account_info(),
-                token::Transfer {
+                self.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
                     from: self.seeder_usdc_ata.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
                     to: self.market_usdc_ata.to_account_info(),
                     authority: self.seeder.to_account_info(),
                 },
             ),
-            usdc_amount,
+            token_compat::gross_up_transfer_fee(&self.usdc_mint.to_account_info(), usdc_amount)?,
+            self.usdc_mint.decimals,
         )?;
 
         // ═══════════════════════════════════════════════════════════
@@ -291,10 +299,10 @@ impl<'info> SeedPool<'info> {
 
         // ✅ v1.1.0: 铸造 YES 代币（数量 = USDC 数量，保持 1:1）
         msg!("Minting {} YES tokens to pool", usdc_amount);
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::MintTo {
+                self.outcome_token_program.to_account_info(),
+                token_interface::MintTo {
                     mint: self.yes_token.to_account_info(),
                     to: self.global_yes_ata.to_account_info(),
                     authority: signer_pda_info.clone(),
@@ -306,10 +314,10 @@ impl<'info> SeedPool<'info> {
 
         // ✅ v1.1.0: 铸造 NO 代币（数量 = USDC 数量，保持 1:1）
         msg!("Minting {} NO tokens to pool", usdc_amount);
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::MintTo {
+                self.outcome_token_program.to_account_info(),
+                token_interface::MintTo {
                     mint: self.no_token.to_account_info(),
                     to: self.global_no_ata.to_account_info(),
                     authority: signer_pda_info.clone(),
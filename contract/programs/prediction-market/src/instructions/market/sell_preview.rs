@@ -8,7 +8,7 @@ use crate::{
     state::{config::Config, market::Market},
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 #[derive(Accounts)]
 pub struct SellPreview<'info> {
@@ -27,14 +27,14 @@ pub struct SellPreview<'info> {
     pub market: Account<'info, Market>,
 
     /// YES/NO mint（用于市场 PDA 推导）
-    pub yes_token: Account<'info, Mint>,
-    pub no_token: Account<'info, Mint>,
+    pub yes_token: InterfaceAccount<'info, Mint>,
+    pub no_token: InterfaceAccount<'info, Mint>,
 
     /// USDC mint（只读校验）
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ crate::errors::PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     /// 市场专用 USDC 金库 PDA（只读）
     /// CHECK: derived PDA, read-only
@@ -49,7 +49,7 @@ pub struct SellPreview<'info> {
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
     )]
-    pub market_usdc_ata: Account<'info, TokenAccount>,
+    pub market_usdc_ata: InterfaceAccount<'info, TokenAccount>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -91,8 +91,9 @@ pub fn handler(
     )?;
 
     // 读取生效费率（支持市场级覆盖）
-    let platform_sell_bps = if market.has_fee_override { market.platform_sell_fee_override } else { cfg.platform_sell_fee };
-    let lp_sell_bps = if market.has_fee_override { market.lp_sell_fee_override } else { cfg.lp_sell_fee };
+    // ✅ v3.24.0: 包含动态 LP 费率（与 Market::swap 一致）
+    let (platform_sell_bps, lp_sell_bps, _) =
+        market.swap_fee_bps(cfg, 1, token_type, None, Clock::get()?.unix_timestamp);
 
     let platform_fee = (usdc_out as u128)
         .checked_mul(platform_sell_bps as u128)
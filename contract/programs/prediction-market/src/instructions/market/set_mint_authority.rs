@@ -16,7 +16,7 @@ use crate::{
     state::{config::Config, market::Market},
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, spl_token::instruction::AuthorityType, Mint, SetAuthority, Token};
+use anchor_spl::token_interface::{self, Mint, SetAuthority, TokenInterface, spl_token_2022::instruction::AuthorityType};
 
 /// 账户集合：转移 Mint 权限
 #[derive(Accounts)]
@@ -43,7 +43,7 @@ pub struct SetMintAuthority<'info> {
         constraint = yes_token.mint_authority == anchor_lang::solana_program::program_option::COption::Some(global_vault.key())
             @ PredictionMarketError::InvalidMintAuthority
     )]
-    pub yes_token: Account<'info, Mint>,
+    pub yes_token: InterfaceAccount<'info, Mint>,
 
     /// NO 代币 Mint
     #[account(
@@ -51,7 +51,7 @@ pub struct SetMintAuthority<'info> {
         constraint = no_token.mint_authority == anchor_lang::solana_program::program_option::COption::Some(global_vault.key())
             @ PredictionMarketError::InvalidMintAuthority
     )]
-    pub no_token: Account<'info, Mint>,
+    pub no_token: InterfaceAccount<'info, Mint>,
 
     /// 市场 PDA（新的 mint authority）
     #[account(
@@ -68,7 +68,7 @@ pub struct SetMintAuthority<'info> {
     pub authority: Signer<'info>,
 
     /// SPL Token 程序
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<SetMintAuthority>) -> Result<()> {
@@ -96,7 +96,7 @@ pub fn handler(ctx: Context<SetMintAuthority>) -> Result<()> {
     );
 
     // 转移 YES Token mint authority
-    token::set_authority(
+    token_interface::set_authority(
         CpiContext::new_with_signer(
             token_program.to_account_info(),
             SetAuthority {
@@ -112,7 +112,7 @@ pub fn handler(ctx: Context<SetMintAuthority>) -> Result<()> {
     msg!("✅ YES token mint authority transferred to market PDA");
 
     // 转移 NO Token mint authority
-    token::set_authority(
+    token_interface::set_authority(
         CpiContext::new_with_signer(
             token_program.to_account_info(),
             SetAuthority {
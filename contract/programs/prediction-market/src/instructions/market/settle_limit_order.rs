@@ -0,0 +1,192 @@
+//! ✅ v3.17.0: 限价订单簿：领取成交所得 / 撤单
+//!
+//! 不受暂停与市场结算状态限制，挂单人随时可以取回托管资金。
+
+use crate::{
+    constants::{CONFIG, MARKET, ORDER_BOOK},
+    errors::PredictionMarketError,
+    events::LimitOrderSettledEvent,
+    state::{config::*, market::*, order_book::*},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：领取 / 撤单
+#[derive(Accounts)]
+pub struct SettleLimitOrder<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 市场
+    #[account(
+        seeds = [MARKET.as_bytes(), &market.yes_token_mint.to_bytes(), &market.no_token_mint.to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 订单簿
+    #[account(
+        mut,
+        seeds = [ORDER_BOOK.as_bytes(), market.key().as_ref()],
+        bump = order_book.bump,
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    /// YES/NO 代币 mint
+    #[account(address = market.yes_token_mint @ PredictionMarketError::InvalidMint)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = market.no_token_mint @ PredictionMarketError::InvalidMint)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 订单簿托管 ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = order_book,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub book_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = order_book,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub book_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = order_book,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub book_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 挂单人 USDC ATA
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub owner_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 挂单人 YES/NO ATA（不存在则创建）
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = yes_token,
+        associated_token::authority = owner,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub owner_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = no_token,
+        associated_token::authority = owner,
+        associated_token::token_program = outcome_token_program,
+    )]
+    pub owner_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 挂单人
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl SettleLimitOrder<'_> {
+    /// 领取挂单成交所得；`cancel = true` 时同时撤单并退还未成交部分
+    pub fn handler(&mut self, order_id: u64, cancel: bool) -> Result<()> {
+        let index = self.order_book.index_of(order_id)?;
+        let order = self.order_book.orders[index];
+        require!(order.owner == self.owner.key(), PredictionMarketError::IncorrectAuthority);
+
+        let (usdc_amount, token_amount) = self.order_book.settle(index, cancel)?;
+
+        let market_key = self.market.key();
+        let book_seeds: &[&[&[u8]]] = &[&[
+            ORDER_BOOK.as_bytes(),
+            market_key.as_ref(),
+            &[self.order_book.bump],
+        ]];
+
+        if usdc_amount > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.book_usdc_ata.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: self.owner_usdc_ata.to_account_info(),
+                        authority: self.order_book.to_account_info(),
+                    },
+                    book_seeds,
+                ),
+                usdc_amount,
+                self.usdc_mint.decimals,
+            )?;
+        }
+
+        if token_amount > 0 {
+            let (from, to, mint) = if order.token_type == 1 {
+                (&self.book_yes_ata, &self.owner_yes_ata, &self.yes_token)
+            } else {
+                (&self.book_no_ata, &self.owner_no_ata, &self.no_token)
+            };
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.outcome_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: from.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: self.order_book.to_account_info(),
+                    },
+                    book_seeds,
+                ),
+                token_amount,
+                mint.decimals,
+            )?;
+        }
+
+        emit!(LimitOrderSettledEvent {
+            market: market_key,
+            order_id,
+            owner: order.owner,
+            usdc_amount,
+            token_amount,
+            cancelled: cancel,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "✅ v3.17.0: Limit order #{} settled (cancel={}): {} USDC, {} tokens",
+            order_id,
+            cancel,
+            usdc_amount,
+            token_amount
+        );
+        Ok(())
+    }
+}
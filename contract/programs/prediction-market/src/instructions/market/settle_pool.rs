@@ -10,7 +10,7 @@ use crate::{
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：Pool 结算所需账户
@@ -43,23 +43,25 @@ pub struct SettlePool<'info> {
     pub global_vault: AccountInfo<'info>,
 
     /// YES/NO 代币mint
-    pub yes_token: Box<Account<'info, Mint>>,
-    pub no_token: Box<Account<'info, Mint>>,
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// 全局 YES/NO 代币账户（Pool 库存）
     #[account(
         mut,
         associated_token::mint = yes_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
     )]
-    pub global_yes_ata: Box<Account<'info, TokenAccount>>,
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = no_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
     )]
-    pub global_no_ata: Box<Account<'info, TokenAccount>>,
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 权限签名者（管理员）
     #[account(
@@ -71,8 +73,7 @@ pub struct SettlePool<'info> {
     /// 系统/代币/ATA程序
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -105,9 +106,9 @@ impl<'info> SettlePool<'info> {
             PredictionMarketError::MarketNotCompleted
         );
 
-        // 验证有获胜方设置（0=NO, 1=YES, 2=平局）
+        // 验证有获胜方设置（0=NO, 1=YES, 2=平局, 3=无效）
         require!(
-            self.market.winner_token_type <= 2,
+            self.market.winner_token_type <= 3,
             PredictionMarketError::RESOLUTIONTOKEYTYPEERROR
         );
 
@@ -123,9 +124,10 @@ impl<'info> SettlePool<'info> {
         // 保存输家代币数量用于事件发射
         let loser_tokens_transferred: u64;
 
-        if self.market.winner_token_type == 2 {
+        if self.market.winner_token_type >= 2 {
             // ═══════════════════════════════════════════════════════════
-            // 平局场景：两种代币都有价值，按比例保留
+            // 平局/无效场景：两种代币都有价值，按比例保留
+            // ✅ v3.10.0: 无效结果与平局相同处理（比例来自价格快照）
             // ═══════════════════════════════════════════════════════════
 
             loser_tokens_transferred = 0; // 平局没有输家
@@ -208,10 +210,10 @@ impl<'info> SettlePool<'info> {
                     loser_reserve
                 );
 
-                token::burn(
+                token_interface::burn(
                     CpiContext::new_with_signer(
                         self.token_program.to_account_info(),
-                        token::Burn {
+                        token_interface::Burn {
                             mint: if self.market.winner_token_type == 0 {
                                 self.yes_token.to_account_info()
                             } else {
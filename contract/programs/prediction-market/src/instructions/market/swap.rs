@@ -1,14 +1,29 @@
 //! 市场指令：代币交换（买/卖 YES 或 NO）
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET, MARKET_USDC_VAULT, USERINFO},
+    constants::{
+        CONFIG, FEE_TIER, FEE_TIER_TABLE, GLOBAL, MARKET, MARKET_USDC_VAULT, MAX_BOOK_FILLS_PER_SWAP,
+        INTENT_NONCE, ORDER_BOOK, REFERRER, SESSION_ACTION_SWAP, SESSION_KEY, USERINFO,
+    },
     errors::PredictionMarketError,
-    state::{config::*, market::*},
+    events::{
+        FeeTierAppliedEvent, LimitOrderFilledEvent, ReferralFeeAccruedEvent, SessionKeyUsedEvent, TradeEvent,
+    },
+    state::{
+        config::*,
+        fee_tier::{FeeRates, FeeTierTable, UserFeeTier},
+        intent_nonce::IntentNonce,
+        market::*,
+        order_book::*,
+        referrer::ReferrerStats,
+        session_key::{authorize_session, SessionKey},
+    },
+    token_compat,
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：交易所需账户
@@ -47,24 +62,26 @@ pub struct Swap<'info> {
     pub global_vault: AccountInfo<'info>,
 
     /// YES/NO 代币mint
-    pub yes_token: Box<Account<'info, Mint>>,
-    pub no_token: Box<Account<'info, Mint>>,
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// ✅ v3.1.4: 全局金库的YES/NO ATA（使用 Box<Account<TokenAccount>> 统一类型）
     #[account(
         mut,
         associated_token::mint = yes_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub global_yes_ata: Box<Account<'info, TokenAccount>>,
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v3.1.4: 全局金库的 NO Token ATA
     #[account(
         mut,
         associated_token::mint = no_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub global_no_ata: Box<Account<'info, TokenAccount>>,
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v3.1.4: 用户的 YES ATA（不存在则创建）
     /// 代币先发送到这里，如果指定了recipient则再转账
@@ -73,8 +90,9 @@ pub struct Swap<'info> {
         payer = user,
         associated_token::mint = yes_token,
         associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub user_yes_ata: Box<Account<'info, TokenAccount>>,
+    pub user_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v3.1.4: 用户的 NO ATA（不存在则创建）
     #[account(
@@ -82,8 +100,9 @@ pub struct Swap<'info> {
         payer = user,
         associated_token::mint = no_token,
         associated_token::authority = user,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub user_no_ata: Box<Account<'info, TokenAccount>>,
+    pub user_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 用户信息（按需初始化）
     #[account(
@@ -103,15 +122,16 @@ pub struct Swap<'info> {
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 🔒 v1.2.7: 市场专用 USDC 金库（隔离流动性）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub market_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// 🔒 v1.2.7: 市场USDC金库PDA（签名权限）
     /// CHECK: market-specific usdc vault pda
@@ -126,20 +146,24 @@ pub struct Swap<'info> {
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub user_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// ✅ v1.1.0: 团队钱包 USDC ATA（用于接收平台手续费）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = team_wallet,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub team_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub team_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// 用户签名者（支付USDC的人）
+    /// 用户（支付USDC的人）
+    /// ✅ v3.26.0: 须签名，或提供有效的 session_key + session_signer
+    /// CHECK: signer or valid session key, verified by authorize_session in handler
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub user: AccountInfo<'info>,
 
     /// ✅ v1.2.0: 代币接收者（可选，接收YES/NO代币的人）
     /// 支持代买功能：可以与 user 不同
@@ -155,11 +179,74 @@ pub struct Swap<'info> {
     /// CHECK: Recipient's NO token account
     pub recipient_no_ata: Option<AccountInfo<'info>>,
 
+    /// ✅ v3.17.0: 订单簿（可选）：提供时先吃优于 LMSR 边际价格的挂单，剩余部分进入池子
+    #[account(
+        mut,
+        seeds = [ORDER_BOOK.as_bytes(), market.key().as_ref()],
+        bump = order_book.bump,
+    )]
+    pub order_book: Option<Box<Account<'info, OrderBook>>>,
+
+    /// ✅ v3.17.0: 订单簿托管 ATA（与 order_book 一同提供，在 fill_from_order_book 中校验）
+    #[account(mut)]
+    pub book_usdc_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub book_yes_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub book_no_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// ✅ v3.22.0: 引荐人统计账户（可选）：提供时平台费按引荐人分成比例分出
+    #[account(
+        mut,
+        seeds = [REFERRER.as_bytes(), referrer_stats.referrer.as_ref()],
+        bump = referrer_stats.bump,
+    )]
+    pub referrer_stats: Option<Box<Account<'info, ReferrerStats>>>,
+
+    /// ✅ v3.22.0: 引荐分成托管 ATA（与 referrer_stats 一同提供，在 referral_accounts 中校验）
+    #[account(mut)]
+    pub referrer_usdc_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// ✅ v3.23.0: 费率分级表（可选，与 user_fee_tier 一同提供）
+    #[account(
+        seeds = [FEE_TIER_TABLE.as_bytes()],
+        bump = fee_tier_table.bump,
+    )]
+    pub fee_tier_table: Option<Box<Account<'info, FeeTierTable>>>,
+
+    /// ✅ v3.23.0: 用户费率分级账户（可选）：按 30 天滚动成交量选择档位，并累计本次成交量
+    #[account(
+        mut,
+        seeds = [FEE_TIER.as_bytes(), user.key().as_ref()],
+        bump = user_fee_tier.bump,
+    )]
+    pub user_fee_tier: Option<Box<Account<'info, UserFeeTier>>>,
+
+    /// ✅ v3.26.0: 会话密钥（可选，与 session_signer 一同提供）：作用域、有效期与支出上限
+    #[account(
+        mut,
+        seeds = [SESSION_KEY.as_bytes(), user.key().as_ref(), session_key.session_signer.as_ref()],
+        bump = session_key.bump,
+    )]
+    pub session_key: Option<Box<Account<'info, SessionKey>>>,
+
+    /// ✅ v3.26.0: 会话签名者（临时热钥，代替 user 签名）
+    pub session_signer: Option<Signer<'info>>,
+
+    /// ✅ v3.26.1: 用户委托权限（IntentNonce PDA，会话模式必须提供）：
+    /// 会话与意图共用的唯一 delegate，资金由该 PDA 从 user ATA 转出
+    #[account(
+        seeds = [INTENT_NONCE.as_bytes(), user.key().as_ref()],
+        bump = delegate_authority.bump,
+    )]
+    pub delegate_authority: Option<Box<Account<'info, IntentNonce>>>,
+
     /// 系统/代币/ATA程序
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// ✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -169,7 +256,9 @@ impl<'info> Swap<'info> {
     ///
     /// # 参数
     /// * `deadline` - 交易过期时间戳（Unix timestamp in seconds），如果为 0 则不检查
-    pub fn handler(&mut self, amount: u64, direction: u8, token_type: u8 ,minimum_receive_amount: u64, deadline: i64, global_vault_bump:u8, market_usdc_vault_bump: u8) -> Result<()> {
+    /// * `exact_output` - ✅ v3.16.0: 精确输出模式（Some = 期望输出，此时 `amount` 为输入上限）
+    #[allow(clippy::too_many_arguments)]
+    pub fn handler(&mut self, amount: u64, direction: u8, token_type: u8 ,minimum_receive_amount: u64, deadline: i64, exact_output: Option<u64>, global_vault_bump:u8, market_usdc_vault_bump: u8) -> Result<()> {
         // ✅ v3.0.5: 重入锁已移至 Market::swap 内部（market.rs:456）
         // 避免双重上锁导致所有交易失败（P0 修复）
 
@@ -220,6 +309,58 @@ impl<'info> Swap<'info> {
         let _yes_token_key = self.yes_token.key();
         let user_key = self.user.key();
 
+        // ✅ v3.26.0: 交易授权（user 签名，或会话密钥签名）
+        let is_session = authorize_session(
+            &self.user,
+            self.session_key.as_deref().map(|s| &**s),
+            self.session_signer.as_deref(),
+            &market_key,
+            SESSION_ACTION_SWAP,
+            current_timestamp,
+        )?;
+        // 会话模式不允许代买：输出只能进入 user 自己的 ATA
+        require!(
+            !is_session || self.recipient.is_none(),
+            PredictionMarketError::SessionKeyNotAllowed
+        );
+        // ✅ v3.26.1: 会话模式由用户委托权限 PDA（与 execute_intent 共用）签名转出
+        let delegate_bump = [self.delegate_authority.as_ref().map_or(0, |d| d.bump)];
+        let delegate_seeds: &[&[&[u8]]] = &[&[INTENT_NONCE.as_bytes(), user_key.as_ref(), &delegate_bump]];
+        let (authority, authority_seeds): (AccountInfo<'info>, &[&[&[u8]]]) = if is_session {
+            let delegate = self
+                .delegate_authority
+                .as_ref()
+                .ok_or(PredictionMarketError::InvalidSessionKey)?;
+            (delegate.to_account_info(), delegate_seeds)
+        } else {
+            (self.user.to_account_info(), &[])
+        };
+
+        // ✅ v3.17.0: 混合路由：先吃订单簿中优于 LMSR 边际价格的挂单，剩余部分进入池子
+        // 精确输出模式只走池子（保持 lmsr_buy_cost 精确计价）
+        let (book_amount_in, book_amount_out) = if exact_output.is_none() {
+            self.fill_from_order_book(
+                amount,
+                direction,
+                token_type,
+                user_key,
+                current_timestamp,
+                &authority,
+                authority_seeds,
+            )?
+        } else {
+            (0, 0)
+        };
+        let pool_amount = amount
+            .checked_sub(book_amount_in)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        // ✅ v3.22.0: 引荐分成（可选，需在借用 market 之前校验）
+        let referral = self.referral_accounts(user_key)?;
+
+        // ✅ v3.23.0: 分级费率按交易前的滚动成交量选择
+        let (fee_rates, rolling_volume) = self.fee_tier_rates(current_timestamp)?;
+
         let market = &mut self.market;
 
         // 校验结束时间
@@ -231,9 +372,23 @@ impl<'info> Swap<'info> {
             )
         }
 
+        // ✅ v3.14.0: Unix 时间戳排期（以 Clock::unix_timestamp 为准）
+        if market.start_time > 0 {
+            require!(
+                current_timestamp >= market.start_time,
+                PredictionMarketError::MarketNotStarted
+            );
+        }
+        if market.end_time > 0 {
+            require!(
+                current_timestamp < market.end_time,
+                PredictionMarketError::MarketEnded
+            );
+        }
+
         // 不能在完成后再交易
         require!(
-            market.is_completed == false,
+            !market.is_completed,
             PredictionMarketError::CurveAlreadyCompleted
         );
 
@@ -256,7 +411,7 @@ impl<'info> Swap<'info> {
         let user_info_pda = &mut self.user_info;
 
         // 初始化用户信息（如未初始化）
-        if user_info_pda.is_initialized == false {
+        if !user_info_pda.is_initialized {
             msg!("User info does not exist, initializing...");
             user_info_pda.user = self.user.key();
             // ✅ FIX CRITICAL-2: 不再初始化已删除的余额字段
@@ -290,13 +445,10 @@ impl<'info> Swap<'info> {
 
         // ✅ v3.1.4: 获取可变 AccountInfo 以兼容市场 swap 方法签名（YES/NO ATA 为 AccountInfo）
         let source = &mut self.global_vault.to_account_info();
-        let team_wallet = &mut self.team_wallet;
 
-        let yes_token = &mut self.yes_token;
         let global_yes_ata_info = &mut self.global_yes_ata.to_account_info();
         let user_yes_ata_info = &mut self.user_yes_ata.to_account_info();
 
-        let no_token = &mut self.no_token;
         let global_no_ata_info = &mut self.global_no_ata.to_account_info();
         let user_no_ata_info = &mut self.user_no_ata.to_account_info();
 
@@ -307,33 +459,108 @@ impl<'info> Swap<'info> {
         ]];
 
         // ✅ v3.1.4: 交由市场逻辑处理交换
-        let swap_result = market.swap(
-            &mut self.global_config,
-            yes_token.as_ref(),
-            global_yes_ata_info,
-            user_yes_ata_info,
-            no_token.as_ref(),
-            global_no_ata_info,
-            user_no_ata_info,
-            source,
-            team_wallet,
-            amount,
-            direction,
-            token_type,
-            minimum_receive_amount,
-            &self.user,
-            signer_seeds,
-            user_info_pda,
-            &self.token_program,
-            &self.system_program,
-            // 🔒 v1.2.7: USDC 相关账户 (使用市场专用金库)
-            &self.usdc_mint,
-            &self.market_usdc_ata,
-            &self.market_usdc_vault,
-            market_usdc_vault_bump,
-            &self.user_usdc_ata,
-            &self.team_usdc_ata,
-        )?;
+        // ✅ v3.17.0: 订单簿已完全成交时跳过池子，滑点保护按订单簿 + 池子的总输出计算
+        let swap_result = if pool_amount == 0 {
+            require!(
+                book_amount_out >= minimum_receive_amount,
+                PredictionMarketError::SlippageExceeded
+            );
+            SwapResult { usdc_amount: 0, token_amount: 0, fee_usdc: 0, referral_fee: 0, maker_rebate: 0, lp_fee_bps: 0 }
+        } else {
+            market.swap(
+                &mut self.global_config,
+                global_yes_ata_info,
+                user_yes_ata_info,
+                global_no_ata_info,
+                user_no_ata_info,
+                source,
+                pool_amount,
+                direction,
+                token_type,
+                minimum_receive_amount.saturating_sub(book_amount_out),
+                exact_output,
+                &authority,
+                authority_seeds,
+                signer_seeds,
+                &self.collateral_token_program,
+                &self.outcome_token_program,
+                &self.usdc_mint,
+                &self.yes_token.to_account_info(),
+                &self.no_token.to_account_info(),
+                // 🔒 v1.2.7: USDC 相关账户 (使用市场专用金库)
+                &self.market_usdc_ata,
+                &self.market_usdc_vault,
+                market_usdc_vault_bump,
+                &self.user_usdc_ata,
+                &self.team_usdc_ata,
+                referral.as_ref().map(|(vault, share_bps)| (vault, *share_bps)),
+                fee_rates,
+            )?
+        };
+
+        // ✅ v3.23.0: 池子成交量（USDC，含手续费，不含做市返佣）
+        let volume = swap_result
+            .usdc_amount
+            .checked_add(swap_result.fee_usdc)
+            .and_then(|v| v.checked_sub(swap_result.maker_rebate))
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        // ✅ v3.22.0: 记录引荐成交量与分成（仅池子成交部分，订单簿成交不收手续费）
+        if pool_amount > 0 {
+            if let Some(referrer_stats) = self.referrer_stats.as_mut() {
+                referrer_stats.record(volume, swap_result.referral_fee)?;
+
+                emit!(ReferralFeeAccruedEvent {
+                    referrer: referrer_stats.referrer,
+                    user: user_key,
+                    market: market_key,
+                    volume,
+                    referral_fee: swap_result.referral_fee,
+                    timestamp: current_timestamp,
+                });
+            }
+        }
+
+        // ✅ v3.23.0: 累计用户滚动成交量（仅池子成交部分）
+        if pool_amount > 0 {
+            if let Some(user_fee_tier) = self.user_fee_tier.as_mut() {
+                user_fee_tier.record(volume, current_timestamp)?;
+
+                if let Some(rates) = fee_rates {
+                    emit!(FeeTierAppliedEvent {
+                        user: user_key,
+                        market: market_key,
+                        rolling_volume_30d: rolling_volume,
+                        platform_fee_bps: rates.platform_fee_bps,
+                        lp_fee_bps: rates.lp_fee_bps,
+                        maker_rebate: swap_result.maker_rebate,
+                        timestamp: current_timestamp,
+                    });
+                }
+            }
+        }
+
+        // ✅ v3.26.0: 会话支出（买入时 user 付出的 USDC：订单簿成交 + 池子成交含手续费）计入上限
+        if let Some(session) = self.session_key.as_mut() {
+            let spend = if direction == 0 {
+                book_amount_in
+                    .checked_add(if pool_amount > 0 { volume } else { 0 })
+                    .ok_or(PredictionMarketError::MathOverflow)?
+            } else {
+                0
+            };
+            session.record_spend(spend)?;
+
+            emit!(SessionKeyUsedEvent {
+                owner: user_key,
+                session_signer: session.session_signer,
+                market: market_key,
+                action: SESSION_ACTION_SWAP,
+                spend,
+                total_spent: session.spent,
+                timestamp: current_timestamp,
+            });
+        }
 
         // ✅ v1.2.0: 如果指定了recipient，将代币转账给recipient
         if let Some(recipient) = &self.recipient {
@@ -350,7 +577,13 @@ impl<'info> Swap<'info> {
                 self.yes_token.key()
             };
 
-            let recipient_token_account = anchor_spl::token::TokenAccount::try_deserialize(
+            // ✅ v3.27.0: recipient ATA 须属于 YES/NO 代币程序
+            require_keys_eq!(
+                *recipient_ata.owner,
+                self.outcome_token_program.key(),
+                PredictionMarketError::TokenProgramMismatch
+            );
+            let recipient_token_account = anchor_spl::token_interface::TokenAccount::try_deserialize(
                 &mut &recipient_ata.try_borrow_data()?[..]
             )?;
 
@@ -364,29 +597,34 @@ impl<'info> Swap<'info> {
                 PredictionMarketError::IncorrectAuthority
             );
 
-            let user_ata = if token_type == 0 {
-                &self.user_no_ata
+            let (user_ata, token_mint) = if token_type == 0 {
+                (&self.user_no_ata, &self.no_token)
             } else {
-                &self.user_yes_ata
+                (&self.user_yes_ata, &self.yes_token)
             };
 
             // 只在买入时转账（卖出时代币从user扣除，USDC发送给user）
             if direction == 0 {
-                anchor_spl::token::transfer(
+                token_interface::transfer_checked(
                     CpiContext::new(
-                        self.token_program.to_account_info(),
-                        anchor_spl::token::Transfer {
+                        self.outcome_token_program.to_account_info(),
+                        token_interface::TransferChecked {
                             from: user_ata.to_account_info(),
+                            mint: token_mint.to_account_info(),
                             to: recipient_ata.to_account_info(),
                             authority: self.user.to_account_info(),
                         },
                     ),
-                    swap_result.token_amount,
+                    swap_result.token_amount + book_amount_out,
+                    token_mint.decimals,
                 )?;
-                msg!("✅ Transferred {} tokens to recipient", swap_result.token_amount);
+                msg!("✅ Transferred {} tokens to recipient", swap_result.token_amount + book_amount_out);
             }
         }
 
+        // ✅ v3.10.0: 记录最后成交价格快照（Invalid 结果按此退款）
+        self.market.last_trade_yes_price_bps = self.market.calculate_yes_price_bps()?;
+
         // ═══════════════════════════════════════════════════════════════
         // ✅ v1.0.12: 发射准确的交易事件
         // ✅ v1.2.0: 添加 recipient 字段（支持代买，可选）
@@ -398,34 +636,41 @@ impl<'info> Swap<'info> {
         // ✅ v3.0.6: Gas 优化 - 条件编译事件
         // 生产模式: 精简事件 (~100 字节)
         // 调试模式: 完整事件 (~200 字节)
+        // ✅ v3.17.0: 池子未参与时不发出池子成交事件（订单簿成交已逐笔发出 TradeEvent）
         #[cfg(not(feature = "event-debug"))]
-        emit!(crate::events::SwapExecuted {
-            market: market_key,  // ✅ v3.0.7: 复用缓存的 key
-            user: user_key,      // ✅ v3.0.7: 复用缓存的 key
-            is_yes: token_type == 1,
-            amount_in: if direction == 0 { swap_result.usdc_amount } else { swap_result.token_amount },
-            amount_out: if direction == 0 { swap_result.token_amount } else { swap_result.usdc_amount },
-            fee: swap_result.fee_usdc,
-            timestamp: current_timestamp,  // ✅ v3.0.7: 复用 clock
-        });
+        if pool_amount > 0 {
+            emit!(crate::events::SwapExecuted {
+                market: market_key,  // ✅ v3.0.7: 复用缓存的 key
+                user: user_key,      // ✅ v3.0.7: 复用缓存的 key
+                is_yes: token_type == 1,
+                amount_in: if direction == 0 { swap_result.usdc_amount } else { swap_result.token_amount },
+                amount_out: if direction == 0 { swap_result.token_amount } else { swap_result.usdc_amount },
+                fee: swap_result.fee_usdc,
+                timestamp: current_timestamp,  // ✅ v3.0.7: 复用 clock
+                route: crate::types::SwapRoute::Direct.to_u8(),
+                lp_fee_bps: swap_result.lp_fee_bps,
+            });
+        }
 
         #[cfg(feature = "event-debug")]
-        emit!(TradeEvent {
-            user: user_key,                             // ✅ v3.0.7: 复用缓存的 key
-            recipient: recipient_key,                   // ✅ v1.2.0: 代币接收者（如果未指定则为user）
-            token_yes: yes_token_key,                   // ✅ v3.0.7: 复用缓存的 key
-            token_no: no_token_key,                     // ✅ v3.0.7: 复用缓存的 key
-            market_info: market_key,                    // ✅ v3.0.7: 复用缓存的 key
-            usdc_amount: swap_result.usdc_amount,       // ✅ v1.1.0: 实际 USDC 数量（买=支付，卖=收到）
-            token_amount: swap_result.token_amount,     // ✅ 实际代币数量（买=收到，卖=支付）
-            fee_usdc: swap_result.fee_usdc,             // ✅ v1.1.0: 实际手续费（USDC）
-            is_buy: direction == 0,
-            is_yes_no: token_type == 1,
-            real_usdc_reserves: self.market.pool_collateral_reserve,  // ✅ v1.1.0: USDC 储备
-            real_token_yes_reserves: self.market.pool_yes_reserve,
-            real_token_no_reserves: self.market.pool_no_reserve,
-            timestamp: current_timestamp,  // ✅ v3.0.7: 复用 clock
-        });
+        if pool_amount > 0 {
+            emit!(TradeEvent {
+                user: user_key,                             // ✅ v3.0.7: 复用缓存的 key
+                recipient: recipient_key,                   // ✅ v1.2.0: 代币接收者（如果未指定则为user）
+                token_yes: yes_token_key,                   // ✅ v3.0.7: 复用缓存的 key
+                token_no: no_token_key,                     // ✅ v3.0.7: 复用缓存的 key
+                market_info: market_key,                    // ✅ v3.0.7: 复用缓存的 key
+                usdc_amount: swap_result.usdc_amount,       // ✅ v1.1.0: 实际 USDC 数量（买=支付，卖=收到）
+                token_amount: swap_result.token_amount,     // ✅ 实际代币数量（买=收到，卖=支付）
+                fee_usdc: swap_result.fee_usdc,             // ✅ v1.1.0: 实际手续费（USDC）
+                is_buy: direction == 0,
+                is_yes_no: token_type == 1,
+                real_usdc_reserves: self.market.pool_collateral_reserve,  // ✅ v1.1.0: USDC 储备
+                real_token_yes_reserves: self.market.pool_yes_reserve,
+                real_token_no_reserves: self.market.pool_no_reserve,
+                timestamp: current_timestamp,  // ✅ v3.0.7: 复用 clock
+            });
+        }
 
         // ═══════════════════════════════════════════════════════════════
         // ✅ v1.5.2: 发射市场风险指标事件（用于前端风险仪表盘）
@@ -458,21 +703,10 @@ impl<'info> Swap<'info> {
         } as u16;
 
         // 计算距离结算的时间（小时）
-        let time_to_settlement_hours = if let Some(ending_slot) = self.market.ending_slot {
-            let current_slot = clock.slot;
-            if current_slot < ending_slot {
-                let slots_remaining = ending_slot.saturating_sub(current_slot);
-                // 每个槽位约0.4秒 = 0.4/3600小时
-                ((slots_remaining as u128)
-                    .checked_mul(4)
-                    .unwrap_or(0)
-                    .checked_div(36000) // 0.4s * 10 / 3600s = 4 / 36000
-                    .unwrap_or(0) as u64)
-            } else {
-                0
-            }
-        } else {
-            u64::MAX // 无结束时间
+        // ✅ v3.14.0: 设置了 end_time 时使用实际时间，否则按每个槽位约 0.4 秒估算
+        let time_to_settlement_hours = match self.market.seconds_to_end(clock.slot, clock.unix_timestamp) {
+            Some(secs) => (secs.max(0) / 3600) as u64,
+            None => u64::MAX, // 无结束时间
         };
 
         // 获取当前生效的b值（已经在交易时计算过）
@@ -500,4 +734,270 @@ impl<'info> Swap<'info> {
 
         Ok(())
     }
+
+    /// ✅ v3.22.0: 校验引荐账户并返回 (托管账户, 生效分成比例)
+    ///
+    /// referrer_stats 与 referrer_usdc_vault 须同时提供或同时省略；不允许引荐自己
+    fn referral_accounts(&self, user_key: Pubkey) -> Result<Option<(AccountInfo<'info>, u16)>> {
+        match (self.referrer_stats.as_ref(), self.referrer_usdc_vault.as_ref()) {
+            (None, None) => Ok(None),
+            (Some(stats), Some(vault)) => {
+                require!(stats.referrer != user_key, PredictionMarketError::SelfReferral);
+                require!(
+                    vault.key()
+                        == associated_token::get_associated_token_address_with_program_id(
+                            &stats.key(),
+                            &self.usdc_mint.key(),
+                            &self.collateral_token_program.key(),
+                        ),
+                    PredictionMarketError::InvalidReferrer
+                );
+                let share_bps = stats.effective_share_bps(self.global_config.max_referral_share_bps);
+                Ok(Some((vault.to_account_info(), share_bps)))
+            }
+            _ => err!(PredictionMarketError::InvalidReferrer),
+        }
+    }
+
+    /// ✅ v3.23.0: 校验费率分级账户并返回 (分级费率, 交易前 30 天滚动成交量)
+    ///
+    /// fee_tier_table 与 user_fee_tier 须同时提供或同时省略；成交量未达最低档位时费率为 None
+    fn fee_tier_rates(&self, now: i64) -> Result<(Option<FeeRates>, u64)> {
+        match (self.fee_tier_table.as_ref(), self.user_fee_tier.as_ref()) {
+            (None, None) => Ok((None, 0)),
+            (Some(table), Some(account)) => {
+                let rolling_volume = account.rolling_volume(now);
+                Ok((table.rates_for(account.is_maker, rolling_volume), rolling_volume))
+            }
+            _ => err!(PredictionMarketError::InvalidFeeTierAccount),
+        }
+    }
+
+    /// ✅ v3.17.0: 吃订单簿中优于 LMSR 边际价格的挂单（订单簿成交不收取手续费）
+    ///
+    /// - 买入：按价格从低到高吃低于 LMSR 边际价格的卖单，支付 USDC
+    /// - 卖出：按价格从高到低吃高于 LMSR 边际价格的买单，获得 USDC
+    ///
+    /// # 返回
+    /// `(消耗的输入, 获得的输出)`；未提供订单簿时返回 `(0, 0)`
+    ///
+    /// ✅ v3.17.2: 买入的输入为含 USDC 转账手续费的实际支付（不超过 `amount`），
+    /// 卖出的输出为扣除转账手续费后的实际到账 USDC
+    #[allow(clippy::too_many_arguments)]
+    fn fill_from_order_book(
+        &mut self,
+        amount: u64,
+        direction: u8,
+        token_type: u8,
+        user_key: Pubkey,
+        now: i64,
+        authority: &AccountInfo<'info>,
+        authority_seeds: &[&[&[u8]]],
+    ) -> Result<(u64, u64)> {
+        let Some(book) = self.order_book.as_mut() else {
+            return Ok((0, 0));
+        };
+        let (Some(book_usdc_ata), Some(book_yes_ata), Some(book_no_ata)) =
+            (self.book_usdc_ata.as_ref(), self.book_yes_ata.as_ref(), self.book_no_ata.as_ref())
+        else {
+            return err!(PredictionMarketError::InvalidOrderBookEscrow);
+        };
+
+        // ✅ v3.17.2: 托管账户须为订单簿的规范 ATA（与 post/settle_limit_order 一致），
+        // 防止成交资金流入挂单人无法领取的其他订单簿名下代币账户
+        let book_key = book.key();
+        let book_ata = |mint: Pubkey, token_program: Pubkey| {
+            associated_token::get_associated_token_address_with_program_id(&book_key, &mint, &token_program)
+        };
+        require!(
+            book_usdc_ata.key() == book_ata(self.usdc_mint.key(), self.collateral_token_program.key())
+                && book_yes_ata.key() == book_ata(self.yes_token.key(), self.outcome_token_program.key())
+                && book_no_ata.key() == book_ata(self.no_token.key(), self.outcome_token_program.key()),
+            PredictionMarketError::InvalidOrderBookEscrow
+        );
+
+        // 参考价格：交易前 LMSR 边际价格（订单簿成交不改变池子状态）
+        let yes_price_bps = self.market.calculate_yes_price_bps()?;
+        let pool_price_bps = if token_type == 1 {
+            yes_price_bps
+        } else {
+            (crate::constants::BASIS_POINTS_DIVISOR as u16) - yes_price_bps
+        };
+
+        // ✅ v3.17.2: 买入时用户实际支付按转账手续费上浮，订单簿可用的净额预算须扣除手续费，
+        // 使上浮后的总支付不超过 amount
+        let mut input_left = if direction == 0 {
+            amount
+                .checked_sub(token_compat::transfer_fee(&self.usdc_mint.to_account_info(), amount)?)
+                .ok_or(PredictionMarketError::MathOverflow)?
+        } else {
+            amount
+        };
+        let budget = input_left;
+        let mut output: u64 = 0;
+        let mut fills: Vec<(LimitOrder, u64, u64)> = Vec::new();
+
+        while fills.len() < MAX_BOOK_FILLS_PER_SWAP && input_left > 0 {
+            let best = if direction == 0 {
+                book.best_ask(token_type, pool_price_bps)
+            } else {
+                book.best_bid(token_type, pool_price_bps)
+            };
+            let Some(index) = best else { break };
+
+            let order = book.orders[index];
+            let (tokens, usdc) = if direction == 0 {
+                let affordable = (input_left as u128)
+                    .checked_mul(crate::constants::BASIS_POINTS_DIVISOR as u128)
+                    .ok_or(PredictionMarketError::MathOverflow)?
+                    / order.price_bps as u128;
+                let tokens = (order.remaining as u128).min(affordable) as u64;
+                if tokens == 0 {
+                    break;
+                }
+                (tokens, book.fill_ask(index, tokens)?)
+            } else {
+                let tokens = order.remaining.min(input_left);
+                (tokens, book.fill_bid(index, tokens)?)
+            };
+
+            let (spent, received) = if direction == 0 { (usdc, tokens) } else { (tokens, usdc) };
+            input_left = input_left
+                .checked_sub(spent)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            output = output
+                .checked_add(received)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            fills.push((book.orders[index], tokens, usdc));
+        }
+
+        if fills.is_empty() {
+            return Ok((0, 0));
+        }
+
+        let amount_in = budget - input_left;
+        let (user_token_ata, book_token_ata, token_mint) = if token_type == 1 {
+            (&self.user_yes_ata, book_yes_ata, &self.yes_token)
+        } else {
+            (&self.user_no_ata, book_no_ata, &self.no_token)
+        };
+
+        // 输入：用户 → 订单簿托管（用户签名，✅ v3.26.0: 会话模式由用户委托权限 PDA 签名）
+        // ✅ v3.27.0: USDC 输入按转账手续费上浮，托管账户恰好收到挂单成交金额
+        // ✅ v3.17.2: 返回上浮后的实际支付，池子预算与会话支出上限均按实际支付计算
+        let (in_from, in_to, in_mint, in_program, in_amount) = if direction == 0 {
+            (
+                self.user_usdc_ata.to_account_info(),
+                book_usdc_ata.to_account_info(),
+                &self.usdc_mint,
+                &self.collateral_token_program,
+                token_compat::gross_up_transfer_fee(&self.usdc_mint.to_account_info(), amount_in)?,
+            )
+        } else {
+            (
+                user_token_ata.to_account_info(),
+                book_token_ata.to_account_info(),
+                token_mint,
+                &self.outcome_token_program,
+                amount_in,
+            )
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                in_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: in_from,
+                    mint: in_mint.to_account_info(),
+                    to: in_to,
+                    authority: authority.clone(),
+                },
+                authority_seeds,
+            ),
+            in_amount,
+            in_mint.decimals,
+        )?;
+        require!(in_amount <= amount, PredictionMarketError::MathOverflow);
+
+        // 输出：订单簿托管 → 用户（订单簿 PDA 签名）
+        let market_key = self.market.key();
+        let book_seeds: &[&[&[u8]]] = &[&[ORDER_BOOK.as_bytes(), market_key.as_ref(), &[book.bump]]];
+        let (out_from, out_to, out_mint, out_program) = if direction == 0 {
+            (
+                book_token_ata.to_account_info(),
+                user_token_ata.to_account_info(),
+                token_mint,
+                &self.outcome_token_program,
+            )
+        } else {
+            (
+                book_usdc_ata.to_account_info(),
+                self.user_usdc_ata.to_account_info(),
+                &self.usdc_mint,
+                &self.collateral_token_program,
+            )
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                out_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: out_from,
+                    mint: out_mint.to_account_info(),
+                    to: out_to,
+                    authority: book.to_account_info(),
+                },
+                book_seeds,
+            ),
+            output,
+            out_mint.decimals,
+        )?;
+
+        // 逐笔发出成交事件（TradeEvent 与池子成交格式一致，兼容现有索引器）
+        for (order, tokens, usdc) in fills {
+            emit!(TradeEvent {
+                user: user_key,
+                recipient: user_key,
+                token_yes: self.yes_token.key(),
+                token_no: self.no_token.key(),
+                market_info: market_key,
+                usdc_amount: usdc,
+                token_amount: tokens,
+                fee_usdc: 0,
+                is_buy: direction == 0,
+                is_yes_no: token_type == 1,
+                real_usdc_reserves: self.market.pool_collateral_reserve,
+                real_token_yes_reserves: self.market.pool_yes_reserve,
+                real_token_no_reserves: self.market.pool_no_reserve,
+                timestamp: now,
+            });
+            emit!(LimitOrderFilledEvent {
+                market: market_key,
+                order_id: order.order_id,
+                maker: order.owner,
+                taker: user_key,
+                token_type,
+                price_bps: order.price_bps,
+                token_amount: tokens,
+                usdc_amount: usdc,
+                remaining: order.remaining,
+                timestamp: now,
+            });
+        }
+
+        // ✅ v3.17.2: 卖出所得 USDC 扣除转账手续费后才是用户实际到账金额（用于滑点保护）
+        let received = if direction == 0 {
+            output
+        } else {
+            output
+                .checked_sub(token_compat::transfer_fee(&self.usdc_mint.to_account_info(), output)?)
+                .ok_or(PredictionMarketError::MathOverflow)?
+        };
+
+        msg!(
+            "✅ v3.17.0: Order book filled input={}, output={} (LMSR price={}bps)",
+            in_amount,
+            received,
+            pool_price_bps
+        );
+        Ok((in_amount, received))
+    }
 }
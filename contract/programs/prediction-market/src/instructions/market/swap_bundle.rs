@@ -0,0 +1,379 @@
+//! ✅ v3.18.0: 多市场原子交易组合（聚合滑点保护）
+//!
+//! 对冲策略常需同时交易多个相关市场（例如卖出 A 市场的 YES 并买入 B 市场的 YES）。
+//! 本指令在一笔交易内依次执行所有交易腿，按用户 USDC 的净变化量执行一个统一约束：
+//!
+//! `net_usdc_out = Σ 卖出所得 - Σ 买入支出（含手续费）≥ min_net_usdc_out`
+//!
+//! - `min_net_usdc_out > 0`：整体至少净收到该数量 USDC
+//! - `min_net_usdc_out < 0`：整体净支出不超过 `|min_net_usdc_out|`
+//!
+//! 卖出腿先于买入腿执行，卖出所得可直接为买入腿提供资金；
+//! 各腿自身不做滑点检查（min_out = 0），任一腿失败或聚合约束不满足时整笔交易回滚。
+
+use crate::{
+    constants::{CONFIG, GLOBAL, MARKET_USDC_VAULT, MAX_BUNDLE_LEGS},
+    errors::PredictionMarketError,
+    events::{SwapBundleExecutedEvent, SwapExecuted},
+    state::{config::*, market::*},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/// 交易腿参数（市场及其账户通过 remaining_accounts 按腿顺序传入）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BundleLeg {
+    /// 交易方向（0=买, 1=卖）
+    pub direction: u8,
+    /// 代币类型（0=NO, 1=YES）
+    pub token_type: u8,
+    /// 输入数量（买入为 USDC，卖出为代币）
+    pub amount: u64,
+}
+
+/// 每条交易腿在 remaining_accounts 中占用的账户数量
+/// ✅ v3.27.0: 追加 YES/NO mint（transfer_checked 需要 mint 账户）
+pub const BUNDLE_LEG_ACCOUNTS: usize = 9;
+
+/// 账户集合：组合交易所需的公共账户
+#[derive(Accounts)]
+pub struct SwapBundle<'info> {
+    /// 全局配置
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 全局金库（YES/NO 池子代币的 authority）
+    /// CHECK: global vault pda used as authority
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 用户 USDC ATA（所有腿共用，净额结算）
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 团队钱包 USDC ATA（接收平台手续费）
+    #[account(
+        mut,
+        constraint = team_usdc_ata.mint == usdc_mint.key() @ PredictionMarketError::InvalidMint,
+        constraint = team_usdc_ata.owner == global_config.team_wallet @ PredictionMarketError::IncorrectAuthority,
+    )]
+    pub team_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 用户签名者
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 代币程序（✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序）
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
+}
+
+/// 单条交易腿的已校验账户
+struct LegAccounts<'info> {
+    market: Account<'info, Market>,
+    market_usdc_vault: AccountInfo<'info>,
+    market_usdc_vault_bump: u8,
+    market_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+    global_yes_ata: AccountInfo<'info>,
+    global_no_ata: AccountInfo<'info>,
+    user_yes_ata: AccountInfo<'info>,
+    user_no_ata: AccountInfo<'info>,
+    yes_mint: AccountInfo<'info>,
+    no_mint: AccountInfo<'info>,
+}
+
+/// 校验一条交易腿的 remaining_accounts：
+/// `[market, market_usdc_vault, market_usdc_ata, global_yes_ata, global_no_ata, user_yes_ata, user_no_ata, yes_mint, no_mint]`
+fn load_leg_accounts<'info>(
+    infos: &'info [AccountInfo<'info>],
+    global_vault: &Pubkey,
+    usdc_mint: &Pubkey,
+    user: &Pubkey,
+    collateral_token_program: &Pubkey,
+    outcome_token_program: &Pubkey,
+) -> Result<LegAccounts<'info>> {
+    let [market_info, vault_info, vault_ata_info, global_yes_info, global_no_info, user_yes_info, user_no_info, yes_mint_info, no_mint_info] =
+        infos
+    else {
+        return err!(PredictionMarketError::BundleAccountMismatch);
+    };
+
+    require!(market_info.is_writable, PredictionMarketError::BundleAccountMismatch);
+    let market = Account::<Market>::try_from(market_info)?;
+
+    let (expected_vault, vault_bump) = Pubkey::find_program_address(
+        &[MARKET_USDC_VAULT.as_bytes(), market_info.key.as_ref()],
+        &crate::ID,
+    );
+    require!(vault_info.key() == expected_vault, PredictionMarketError::InvalidAuthority);
+    require!(
+        vault_ata_info.key()
+            == get_associated_token_address_with_program_id(&expected_vault, usdc_mint, collateral_token_program),
+        PredictionMarketError::InvalidMint
+    );
+    let market_usdc_ata = InterfaceAccount::<TokenAccount>::try_from(vault_ata_info)?;
+
+    // YES/NO mint：须属于 YES/NO 代币程序
+    require!(
+        yes_mint_info.key() == market.yes_token_mint
+            && no_mint_info.key() == market.no_token_mint
+            && yes_mint_info.owner == outcome_token_program
+            && no_mint_info.owner == outcome_token_program,
+        PredictionMarketError::InvalidMint
+    );
+
+    // 池子代币账户：global_vault 的 YES/NO ATA
+    require!(
+        global_yes_info.key()
+            == get_associated_token_address_with_program_id(
+                global_vault,
+                &market.yes_token_mint,
+                outcome_token_program,
+            )
+            && global_no_info.key()
+                == get_associated_token_address_with_program_id(
+                    global_vault,
+                    &market.no_token_mint,
+                    outcome_token_program,
+                ),
+        PredictionMarketError::BundleAccountMismatch
+    );
+
+    // 用户代币账户：需预先创建
+    for (info, mint) in [
+        (user_yes_info, market.yes_token_mint),
+        (user_no_info, market.no_token_mint),
+    ] {
+        let ata = InterfaceAccount::<TokenAccount>::try_from(info)?;
+        require!(ata.mint == mint, PredictionMarketError::InvalidMint);
+        require!(ata.owner == *user, PredictionMarketError::IncorrectAuthority);
+    }
+
+    Ok(LegAccounts {
+        market,
+        market_usdc_vault: vault_info.clone(),
+        market_usdc_vault_bump: vault_bump,
+        market_usdc_ata,
+        global_yes_ata: global_yes_info.clone(),
+        global_no_ata: global_no_info.clone(),
+        user_yes_ata: user_yes_info.clone(),
+        user_no_ata: user_no_info.clone(),
+        yes_mint: yes_mint_info.clone(),
+        no_mint: no_mint_info.clone(),
+    })
+}
+
+/// 多市场组合交易
+///
+/// # 参数
+/// * `legs` - 交易腿（1 - MAX_BUNDLE_LEGS 条）
+/// * `min_net_usdc_out` - 用户 USDC 净变化下限（负数表示最大净支出）
+/// * `deadline` - 交易过期时间戳，0 表示不检查
+///
+/// # remaining_accounts（每条腿 9 个，按 `legs` 顺序）
+/// `[market, market_usdc_vault, market_usdc_ata, global_yes_ata, global_no_ata, user_yes_ata, user_no_ata, yes_mint, no_mint]`
+///
+/// 多于 3 条腿时需配合地址查找表（ALT）以容纳账户数量
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapBundle<'info>>,
+    legs: Vec<BundleLeg>,
+    min_net_usdc_out: i64,
+    deadline: i64,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    require!(
+        accounts.usdc_mint.decimals == crate::constants::USDC_DECIMALS,
+        PredictionMarketError::InvalidParameter
+    );
+    require!(
+        accounts.global_vault.owner == &crate::ID,
+        PredictionMarketError::InvalidAuthority
+    );
+    require!(
+        !accounts.global_config.is_paused,
+        PredictionMarketError::ContractPaused
+    );
+    require!(
+        !legs.is_empty() && legs.len() <= MAX_BUNDLE_LEGS,
+        PredictionMarketError::InvalidBundleLegCount
+    );
+    require!(
+        ctx.remaining_accounts.len() == legs.len() * BUNDLE_LEG_ACCOUNTS,
+        PredictionMarketError::BundleAccountMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    if deadline > 0 {
+        require!(now <= deadline, PredictionMarketError::TransactionExpired);
+    }
+
+    let user_key = accounts.user.key();
+    let usdc_mint_key = accounts.usdc_mint.key();
+    let global_vault_key = accounts.global_vault.key();
+    let collateral_token_program_key = accounts.collateral_token_program.key();
+    let outcome_token_program_key = accounts.outcome_token_program.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[ctx.bumps.global_vault]]];
+
+    // 卖出腿先执行，所得 USDC 为买入腿提供资金（同类腿保持原顺序）
+    let mut order: Vec<usize> = (0..legs.len()).collect();
+    order.sort_by_key(|&i| legs[i].direction == 0);
+
+    let mut total_usdc_in: u64 = 0;
+    let mut total_usdc_out: u64 = 0;
+    let mut total_fee: u64 = 0;
+
+    for i in order {
+        let leg = legs[i];
+        require!(leg.amount > 0, PredictionMarketError::InvalidAmount);
+
+        let infos = &ctx.remaining_accounts[i * BUNDLE_LEG_ACCOUNTS..(i + 1) * BUNDLE_LEG_ACCOUNTS];
+        let LegAccounts {
+            mut market,
+            market_usdc_vault,
+            market_usdc_vault_bump,
+            market_usdc_ata,
+            mut global_yes_ata,
+            mut global_no_ata,
+            mut user_yes_ata,
+            mut user_no_ata,
+            yes_mint,
+            no_mint,
+        } = load_leg_accounts(
+            infos,
+            &global_vault_key,
+            &usdc_mint_key,
+            &user_key,
+            &collateral_token_program_key,
+            &outcome_token_program_key,
+        )?;
+
+        // 与 swap 一致的市场级前置检查（时间窗口与完成状态由 Market::swap 校验）
+        require!(!market.market_paused, PredictionMarketError::MarketPaused);
+        require!(
+            market.pool_collateral_reserve >= accounts.global_config.min_trading_liquidity,
+            PredictionMarketError::MarketBelowMinLiquidity
+        );
+
+        let mut source = accounts.global_vault.to_account_info();
+        let result = market.swap(
+            &mut accounts.global_config,
+            &mut global_yes_ata,
+            &mut user_yes_ata,
+            &mut global_no_ata,
+            &mut user_no_ata,
+            &mut source,
+            leg.amount,
+            leg.direction,
+            leg.token_type,
+            0, // 单腿不做滑点检查，由聚合约束统一保护
+            None,
+            &accounts.user,
+            &[],
+            signer_seeds,
+            &accounts.collateral_token_program,
+            &accounts.outcome_token_program,
+            &accounts.usdc_mint,
+            &yes_mint,
+            &no_mint,
+            &market_usdc_ata,
+            &market_usdc_vault,
+            market_usdc_vault_bump,
+            &accounts.user_usdc_ata,
+            &accounts.team_usdc_ata,
+            None,
+            None,
+        )?;
+
+        if leg.direction == 0 {
+            // 买入：用户支付 = 净额 + 手续费
+            let paid = result
+                .usdc_amount
+                .checked_add(result.fee_usdc)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            total_usdc_in = total_usdc_in
+                .checked_add(paid)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        } else {
+            total_usdc_out = total_usdc_out
+                .checked_add(result.usdc_amount)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        }
+        total_fee = total_fee
+            .checked_add(result.fee_usdc)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        // ✅ v3.10.0: 记录最后成交价格快照（Invalid 结果按此退款）
+        market.last_trade_yes_price_bps = market.calculate_yes_price_bps()?;
+
+        emit!(SwapExecuted {
+            market: market.key(),
+            user: user_key,
+            is_yes: leg.token_type == 1,
+            amount_in: if leg.direction == 0 { result.usdc_amount } else { result.token_amount },
+            amount_out: if leg.direction == 0 { result.token_amount } else { result.usdc_amount },
+            fee: result.fee_usdc,
+            timestamp: now,
+            route: crate::types::SwapRoute::Direct.to_u8(),
+            lp_fee_bps: result.lp_fee_bps,
+        });
+
+        // 持久化市场账本（remaining_accounts 不会被 Anchor 自动写回）
+        // 同一市场出现在多条腿时，下一条腿重新加载即可看到本腿的状态
+        market.exit(&crate::ID)?;
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // 聚合滑点保护：用户 USDC 净变化不低于 min_net_usdc_out
+    // ═══════════════════════════════════════════════════════════════
+    let net_usdc_out = (total_usdc_out as i128) - (total_usdc_in as i128);
+    require!(
+        net_usdc_out >= min_net_usdc_out as i128,
+        PredictionMarketError::SlippageExceeded
+    );
+    let net_usdc_out =
+        i64::try_from(net_usdc_out).map_err(|_| PredictionMarketError::MathOverflow)?;
+
+    msg!(
+        "✅ v3.18.0: Bundle executed legs={}, usdc_in={}, usdc_out={}, net={} (min {})",
+        legs.len(),
+        total_usdc_in,
+        total_usdc_out,
+        net_usdc_out,
+        min_net_usdc_out
+    );
+
+    emit!(SwapBundleExecutedEvent {
+        user: user_key,
+        leg_count: legs.len() as u8,
+        total_usdc_in,
+        total_usdc_out,
+        net_usdc_out,
+        total_fee,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -8,7 +8,7 @@ use crate::{
     state::market::*,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 
 /// 账户集合：更新市场名称所需账户
 #[derive(Accounts)]
@@ -23,8 +23,8 @@ pub struct UpdateMarketName<'info> {
     pub market: Account<'info, Market>,
 
     /// YES/NO 代币mint（用于验证市场PDA）
-    pub yes_token: Account<'info, Mint>,
-    pub no_token: Account<'info, Mint>,
+    pub yes_token: InterfaceAccount<'info, Mint>,
+    pub no_token: InterfaceAccount<'info, Mint>,
 
     /// 创建者（必须是市场的创建者）
     pub creator: Signer<'info>,
@@ -0,0 +1,153 @@
+//! ✅ v3.6.0: 作废条件市场（无需许可）
+//!
+//! 父市场结算结果与条件市场要求不一致（反向、平局或父市场自身作废）时，
+//! 任何人都可以作废条件市场。
+//!
+//! ✅ v3.6.1: 作废改为 Invalid 结算（`SettleContext::settle`，与 void_unresolved_market 一致）：
+//! - 池内 YES/NO 随结算销毁，对应退款计入 pool_collateral_reserve
+//! - 退款比例来自最后成交价格快照（YES=p, NO=1-p），以 10c 买入的 YES 按 10c 退还
+//! - claim_rewards 领取退款；redeem_complete_set 继续允许 1:1 赎回完整集合
+//! - 标记 pool_settled，LP 通过 withdraw_liquidity 按本金退出
+//!
+//! ✅ v3.13.1: 结算证据由父市场结果派生（父市场地址、获胜方、是否作废），写入 MarketMetadata。
+//!
+//! ✅ v3.6.2: 条件市场不可加入事件组（add_market_to_event_group 拒绝），因此结算时无需事件组账户。
+
+use crate::{
+    constants::{GLOBAL, MARKET, MARKET_METADATA},
+    errors::PredictionMarketError,
+    events::ConditionalMarketVoidedEvent,
+    instructions::market::resolution::SettleContext,
+    state::{market::*, market_metadata::*},
+    types::MarketOutcome,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：作废条件市场所需账户
+#[derive(Accounts)]
+pub struct VoidConditionalMarket<'info> {
+    /// 条件市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// 父市场
+    #[account(
+        constraint = parent_market.key() == market.parent_market @ PredictionMarketError::ParentMarketMismatch
+    )]
+    pub parent_market: Box<Account<'info, Market>>,
+
+    /// ✅ v3.6.1: 全局金库（PDA，清算池内代币的签名者）
+    /// CHECK: global vault pda
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    #[account(mut)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库持有的 YES/NO（结算时清算）
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 触发者（任何人）
+    pub caller: Signer<'info>,
+
+    /// YES/NO 代币程序（✅ v3.27.1: 可与抵押品代币程序不同）
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// ✅ v3.13.1: 市场元数据（记录结算证据；market.has_metadata 为 true 时必须提供）
+    #[account(
+        mut,
+        seeds = [MARKET_METADATA.as_bytes(), market.key().as_ref()],
+        bump = market_metadata.bump,
+    )]
+    pub market_metadata: Option<Box<Account<'info, MarketMetadata>>>,
+}
+
+impl VoidConditionalMarket<'_> {
+    /// 作废条件市场
+    pub fn handler(&mut self, global_vault_bump: u8) -> Result<()> {
+        self.market.check_conditional_void(&self.parent_market)?;
+
+        // ✅ v3.13.1: 由父市场结果派生结算证据
+        let parent_key = self.parent_market.key();
+        let evidence = ResolutionEvidence::derived(
+            format!("void:parent-market:{}", parent_key),
+            &[
+                self.market.key().as_ref(),
+                parent_key.as_ref(),
+                &[self.parent_market.winner_token_type, self.parent_market.is_voided as u8],
+            ],
+        );
+
+        // ═══════════════════════════════════════════════════════════════
+        // ✅ v3.6.1: 以 Invalid 结果结算（按价格快照退款，清算池内代币）
+        // ═══════════════════════════════════════════════════════════════
+        SettleContext {
+            market: &mut self.market,
+            global_vault: &self.global_vault,
+            yes_token: &self.yes_token,
+            no_token: &self.no_token,
+            global_yes_ata: &self.global_yes_ata,
+            global_no_ata: &self.global_no_ata,
+            token_program: &self.token_program,
+            event_group: None,
+            parent_market: Some(&*self.parent_market),
+            authority: self.caller.key(),
+            evidence,
+            market_metadata: self.market_metadata.as_deref_mut(),
+        }
+        .settle(0, 0, MarketOutcome::Invalid.to_u8(), true, global_vault_bump)?;
+
+        // 池内代币已在结算时清算，直接开放 LP 退出
+        let market = &mut self.market;
+        market.is_voided = true;
+        market.pool_settled = true;
+
+        msg!(
+            "✅ v3.6.1 Conditional market voided: parent={}, required={}, parent_winner={}, parent_voided={}, refund YES={} NO={}",
+            market.parent_market,
+            market.parent_required_outcome,
+            self.parent_market.winner_token_type,
+            self.parent_market.is_voided,
+            market.resolution_yes_ratio,
+            market.resolution_no_ratio
+        );
+
+        emit!(ConditionalMarketVoidedEvent {
+            caller: self.caller.key(),
+            market: market.key(),
+            parent_market: market.parent_market,
+            required_outcome: market.parent_required_outcome,
+            parent_winner_token_type: self.parent_market.winner_token_type,
+            parent_voided: self.parent_market.is_voided,
+            collateral_locked: market.total_collateral_locked,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
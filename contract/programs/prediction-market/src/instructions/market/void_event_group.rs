@@ -0,0 +1,200 @@
+//! ✅ v3.5.1: 作废事件组（由管理员触发）
+//!
+//! ✅ v3.11.1: 所有成员市场均超过结算截止（resolution_deadline_slot）后，任何人都可作废，
+//! 保证管理员失联时事件组资金不会永久锁定；任一成员无截止时仍只能由管理员作废。
+//!
+//! 事件组无法按唯一胜者结算时（事件取消、成员设置错误等）作废事件组：
+//! - 各成员的快照 YES 价格归一化为合计 100%，记录在事件组中
+//! - 事件组金库按 `total_converted × p_i` 向各成员市场注入担保抵押品
+//!   （成员 Invalid 赔付缺口，见 `state::event_group` 模块文档）
+//! - 成员市场随后通过 void_unresolved_market 按 Invalid 结算，退款比例取自事件组
+
+use crate::{
+    constants::{CONFIG, EVENT_GROUP, MARKET_USDC_VAULT, MIN_EVENT_GROUP_MARKETS},
+    errors::PredictionMarketError,
+    events::EventGroupVoidedEvent,
+    state::{config::*, event_group::*, market::*},
+    token_compat,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+/// 账户集合：作废事件组所需账户
+#[derive(Accounts)]
+pub struct VoidEventGroup<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, Config>>,
+
+    /// 事件组
+    #[account(
+        mut,
+        seeds = [EVENT_GROUP.as_bytes(), &event_group.group_id.to_le_bytes()],
+        bump = event_group.bump,
+    )]
+    pub event_group: Box<Account<'info, EventGroup>>,
+
+    /// USDC Mint
+    #[account(
+        constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 事件组 USDC 金库
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = event_group,
+        associated_token::token_program = token_program,
+    )]
+    pub group_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 调用者（✅ v3.11.1: 全局 authority 随时可调用；其他人须等所有成员超过结算截止）
+    pub caller: Signer<'info>,
+
+    /// 抵押品（USDC）代币程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 作废事件组
+///
+/// # remaining_accounts（按成员索引顺序）
+/// `[market, market_usdc_ata]`
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, VoidEventGroup<'info>>) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    let group = &accounts.event_group;
+    require!(!group.is_resolved, PredictionMarketError::EventGroupAlreadyResolved);
+    require!(!group.is_voided, PredictionMarketError::EventGroupVoided);
+    require!(
+        group.market_count as usize >= MIN_EVENT_GROUP_MARKETS,
+        PredictionMarketError::EventGroupMismatch
+    );
+
+    let group_key = group.key();
+    let members = group.members().to_vec();
+    let current_slot = Clock::get()?.slot;
+    let by_authority = accounts.caller.key() == accounts.global_config.authority;
+    require!(
+        ctx.remaining_accounts.len() == members.len() * 2,
+        PredictionMarketError::EventGroupMismatch
+    );
+
+    // ═══════════════════════════════════════════════════════════════
+    // 1. 校验成员并读取快照价格
+    // ═══════════════════════════════════════════════════════════════
+    let mut member_markets = Vec::with_capacity(members.len());
+    let mut snapshot_prices = Vec::with_capacity(members.len());
+    for (member, infos) in members.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let [market_info, vault_ata_info] = infos else {
+            return err!(PredictionMarketError::EventGroupMismatch);
+        };
+        require!(market_info.key() == *member, PredictionMarketError::EventGroupMismatch);
+
+        let market = Account::<Market>::try_from(market_info)?;
+        require!(market.event_group == group_key, PredictionMarketError::EventGroupMismatch);
+        require!(!market.is_completed, PredictionMarketError::MarketIsCompleted);
+        // ✅ v3.11.1: 非管理员调用时，每个成员都必须已超过结算截止
+        if !by_authority {
+            market.check_resolution_deadline(current_slot)?;
+        }
+
+        let (expected_vault, _) = Pubkey::find_program_address(
+            &[MARKET_USDC_VAULT.as_bytes(), market_info.key.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            vault_ata_info.key()
+                == get_associated_token_address_with_program_id(
+                    &expected_vault,
+                    &accounts.usdc_mint.key(),
+                    &accounts.token_program.key(),
+                ),
+            PredictionMarketError::InvalidMint
+        );
+
+        snapshot_prices.push(market.last_trade_yes_price_bps);
+        member_markets.push((market, vault_ata_info));
+    }
+
+    let group = &mut accounts.event_group;
+    group.void_yes_price_bps = EventGroup::normalize_void_prices(&snapshot_prices);
+    group.is_voided = true;
+
+    // ═══════════════════════════════════════════════════════════════
+    // 2. 按归一化价格向各成员注入担保抵押品
+    // ═══════════════════════════════════════════════════════════════
+    require!(
+        accounts.group_usdc_ata.amount >= group.total_converted,
+        PredictionMarketError::InsufficientLiquidity
+    );
+
+    let group_id_bytes = group.group_id.to_le_bytes();
+    let group_seeds: &[&[&[u8]]] = &[&[EVENT_GROUP.as_bytes(), &group_id_bytes, &[group.bump]]];
+
+    let mut collateral_distributed: u64 = 0;
+    for (i, (mut market, vault_ata_info)) in member_markets.into_iter().enumerate() {
+        let share = group
+            .void_collateral_share(i)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        if share == 0 {
+            continue;
+        }
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: accounts.group_usdc_ata.to_account_info(),
+                    mint: accounts.usdc_mint.to_account_info(),
+                    to: vault_ata_info.clone(),
+                    authority: group.to_account_info(),
+                },
+                group_seeds,
+            ),
+            share,
+            accounts.usdc_mint.decimals,
+        )?;
+
+        // 与 resolve_event_group 一致：市场账本按金库实收金额记账
+        let received = share
+            .checked_sub(token_compat::transfer_fee(&accounts.usdc_mint.to_account_info(), share)?)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.total_collateral_locked = market
+            .total_collateral_locked
+            .checked_add(received)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        // 持久化成员市场账本（remaining_accounts 不会被 Anchor 自动写回）
+        market.exit(&crate::ID)?;
+
+        collateral_distributed = collateral_distributed
+            .checked_add(share)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+
+    msg!(
+        "✅ v3.5.1 Event group voided: members={}, by_authority={}, total_converted={}, collateral_distributed={}",
+        members.len(),
+        by_authority,
+        group.total_converted,
+        collateral_distributed
+    );
+
+    emit!(EventGroupVoidedEvent {
+        caller: accounts.caller.key(),
+        event_group: group_key,
+        total_converted: group.total_converted,
+        void_yes_price_bps: group.void_yes_price_bps[..members.len()].to_vec(),
+        collateral_distributed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
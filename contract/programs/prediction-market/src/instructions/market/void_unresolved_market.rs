@@ -0,0 +1,165 @@
+//! ✅ v3.11.0: 作废超过结算截止仍未结算的市场（无需许可）
+//!
+//! 活性保证：管理员密钥丢失或长期不结算时，`market_usdc_ata` 中的资金不应永久锁定。
+//! 市场创建时记录 `resolution_deadline_slot = ending_slot + 宽限期`，超过截止后任何人可调用：
+//! - 以 Invalid 结果结算（`SettleContext::settle`，比例来自最后成交价格快照）
+//! - 标记 is_voided（redeem_complete_set 继续允许 1:1 赎回完整集合）
+//! - 标记 pool_settled（无需管理员 settle_pool），LP 可通过 withdraw_liquidity 按本金退出
+//!
+//! 交易者通过 claim_rewards 领取退款。
+//!
+//! ✅ v3.5.1: 事件组成员市场不适用截止作废；事件组经 void_event_group 作废后，
+//! 成员市场通过本指令立即按 Invalid 结算（退款比例取自事件组，无需等待截止）。
+//!
+//! ✅ v3.11.1: 事件组的活性保证在组级别：所有成员均超过结算截止后，
+//! 任何人都可调用 void_event_group 作废事件组，再逐个作废成员市场。
+//!
+//! ✅ v3.13.1: 结算证据由作废依据派生（截止 slot 与当前 slot，或已作废的事件组），写入 MarketMetadata。
+
+use crate::{
+    constants::{GLOBAL, MARKET, MARKET_METADATA},
+    errors::PredictionMarketError,
+    events::MarketDeadlineVoidedEvent,
+    instructions::market::resolution::SettleContext,
+    state::{event_group::*, market::*, market_metadata::*},
+    types::MarketOutcome,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// 账户集合：作废未结算市场
+#[derive(Accounts)]
+pub struct VoidUnresolvedMarket<'info> {
+    /// 市场
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// 全局金库（PDA，清算池内代币的签名者）
+    /// CHECK: global vault pda
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    #[account(mut)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 全局金库持有的 YES/NO（结算时清算）
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 调用者（任何人）
+    pub caller: Signer<'info>,
+
+    /// YES/NO 代币程序（✅ v3.27.1: 可与抵押品代币程序不同）
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// ✅ v3.5.1: 所属事件组（仅事件组成员市场需要提供，须已作废）
+    pub event_group: Option<Box<Account<'info, EventGroup>>>,
+
+    /// ✅ v3.13.1: 市场元数据（记录结算证据；market.has_metadata 为 true 时必须提供）
+    #[account(
+        mut,
+        seeds = [MARKET_METADATA.as_bytes(), market.key().as_ref()],
+        bump = market_metadata.bump,
+    )]
+    pub market_metadata: Option<Box<Account<'info, MarketMetadata>>>,
+}
+
+impl VoidUnresolvedMarket<'_> {
+    /// 作废市场并开放退款
+    pub fn handler(&mut self, global_vault_bump: u8) -> Result<()> {
+        let deadline = self.market.resolution_deadline_slot;
+        let clock = Clock::get()?;
+        let market_key = self.market.key();
+
+        // ✅ v3.13.1: 由作废依据派生结算证据
+        let evidence = if self.market.is_grouped() {
+            // ✅ v3.5.1: 事件组成员随事件组作废
+            let group = self
+                .event_group
+                .as_ref()
+                .ok_or(PredictionMarketError::EventGroupMismatch)?;
+            require!(
+                group.key() == self.market.event_group,
+                PredictionMarketError::EventGroupMismatch
+            );
+            require!(group.is_voided, PredictionMarketError::EventGroupNotVoided);
+            let group_key = group.key();
+            ResolutionEvidence::derived(
+                format!("void:event-group:{}", group_key),
+                &[market_key.as_ref(), group_key.as_ref()],
+            )
+        } else {
+            self.market.check_resolution_deadline(clock.slot)?;
+            ResolutionEvidence::derived(
+                format!("void:resolution-deadline:{}@{}", deadline, clock.slot),
+                &[market_key.as_ref(), &deadline.to_le_bytes(), &clock.slot.to_le_bytes()],
+            )
+        };
+
+        // ═══════════════════════════════════════════════════════════════
+        // 以 Invalid 结果结算（所有结算路径共用核心逻辑）
+        // ═══════════════════════════════════════════════════════════════
+        SettleContext {
+            market: &mut self.market,
+            global_vault: &self.global_vault,
+            yes_token: &self.yes_token,
+            no_token: &self.no_token,
+            global_yes_ata: &self.global_yes_ata,
+            global_no_ata: &self.global_no_ata,
+            token_program: &self.token_program,
+            event_group: self.event_group.as_deref(),
+            parent_market: None,
+            authority: self.caller.key(),
+            evidence,
+            market_metadata: self.market_metadata.as_deref_mut(),
+        }
+        .settle(0, 0, MarketOutcome::Invalid.to_u8(), true, global_vault_bump)?;
+
+        // 管理员失联时无人调用 settle_pool：池内代币已在结算时清算，直接开放 LP 退出
+        let market = &mut self.market;
+        market.is_voided = true;
+        market.pool_settled = true;
+
+        msg!(
+            "✅ v3.11.0 Market voided after resolution deadline: deadline_slot={}, current_slot={}, refund YES={} NO={}",
+            deadline,
+            clock.slot,
+            market.resolution_yes_ratio,
+            market.resolution_no_ratio
+        );
+
+        emit!(MarketDeadlineVoidedEvent {
+            caller: self.caller.key(),
+            market: market.key(),
+            resolution_deadline_slot: deadline,
+            yes_refund_ratio: market.resolution_yes_ratio,
+            no_refund_ratio: market.resolution_no_ratio,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
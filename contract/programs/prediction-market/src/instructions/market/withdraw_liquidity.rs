@@ -27,13 +27,13 @@ use crate::{
         MODERATE_IMBALANCE_MAX_WITHDRAW_BPS,
     },
     errors::PredictionMarketError,
-    events::WithdrawLiquidityEvent,
+    events::{LpInvalidUnwindEvent, WithdrawLiquidityEvent},
     state::{config::Config, market::{LPPosition, Market}},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Burn, Mint, Token, TokenAccount},
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface},
 };
 
 /// 账户集合：撤出LP所需账户（v3.0 单币LP）
@@ -61,7 +61,7 @@ pub struct WithdrawLiquidity<'info> {
         constraint = yes_token.mint_authority == anchor_lang::solana_program::program_option::COption::Some(market.key())
             @ PredictionMarketError::MintAuthorityNotTransferred
     )]
-    pub yes_token: Account<'info, Mint>,
+    pub yes_token: InterfaceAccount<'info, Mint>,
 
     /// NO Token Mint（✅ v3.0: authority 必须是 market PDA）
     #[account(
@@ -69,7 +69,7 @@ pub struct WithdrawLiquidity<'info> {
         constraint = no_token.mint_authority == anchor_lang::solana_program::program_option::COption::Some(market.key())
             @ PredictionMarketError::MintAuthorityNotTransferred
     )]
-    pub no_token: Account<'info, Mint>,
+    pub no_token: InterfaceAccount<'info, Mint>,
 
     /// ✅ v3.0.2: Global Vault PDA（用于签名代币操作）
     /// CHECK: global vault pda used as authority
@@ -84,30 +84,33 @@ pub struct WithdrawLiquidity<'info> {
         mut,
         associated_token::mint = yes_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub global_yes_ata: Account<'info, TokenAccount>,
+    pub global_yes_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// ✅ v3.0.2: Global 的 NO Token ATA（统一代币托管，销毁源）
     #[account(
         mut,
         associated_token::mint = no_token,
         associated_token::authority = global_vault,
+        associated_token::token_program = outcome_token_program,
     )]
-    pub global_no_ata: Account<'info, TokenAccount>,
+    pub global_no_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// USDC Mint
     #[account(
         constraint = usdc_mint.key() == global_config.usdc_mint @ PredictionMarketError::InvalidMint
     )]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Market USDC ATA（市场专用金库）
     #[account(
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = market_usdc_vault,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub market_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub market_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Market USDC Vault PDA（签名权限）
     /// CHECK: market-specific usdc vault pda
@@ -122,8 +125,9 @@ pub struct WithdrawLiquidity<'info> {
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
     )]
-    pub user_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// LP Position
     #[account(
@@ -138,8 +142,9 @@ pub struct WithdrawLiquidity<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
-    /// SPL Token 程序
-    pub token_program: Program<'info, Token>,
+    /// 代币程序（✅ v3.27.1: 抵押品（USDC）与 YES/NO 可分属不同代币程序）
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub outcome_token_program: Interface<'info, TokenInterface>,
 
     /// Associated Token 程序
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -172,16 +177,106 @@ pub fn handler(
     // ═══════════════════════════════════════════════════════════════
 
     // ✅ v3.1.2: 市场级暂停保护
+    // ✅ v3.11.0: 已作废（Invalid）市场不受暂停限制，保证 LP 在管理员失联时仍可退出
+    let invalid_exit = market.is_invalid();
     require!(
-        !market.market_paused,
+        invalid_exit || !market.market_paused,
         PredictionMarketError::MarketPaused
     );
 
-    require!(!ctx.accounts.global_config.is_paused, PredictionMarketError::ContractPaused);
+    require!(invalid_exit || !ctx.accounts.global_config.is_paused, PredictionMarketError::ContractPaused);
     require!(lp_shares > 0, PredictionMarketError::InvalidAmount);
     require!(lp_position.lp_shares >= lp_shares, PredictionMarketError::InsufficientBalance);
     require!(market.total_lp_shares > 0, PredictionMarketError::InsufficientLiquidity);
 
+    // ✅ v3.20.0: 流动性变动可能改变 b 值与现价，先按变动前价格推进 TWAP（已结算市场价格冻结）
+    if !market.is_completed {
+        market.update_twap(current_timestamp)?;
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.10.0: 无效市场：LP 按投入本金退出
+    // ═══════════════════════════════════════════════════════════════
+    //
+    // 结算时池内代币已按价格快照折算为 USDC 计入 pool_collateral_reserve，
+    // 此处按份额比例退还 min(投入本金, 池内 USDC 份额)，不收早退惩罚、不受撤出限额与熔断器约束。
+    // 高于本金的部分（作废市场中的做市盈利）保留在池中，最后一个 LP 退出后随尾款回收。
+    if invalid_exit {
+        require!(market.pool_settled, PredictionMarketError::MarketNotCompleted);
+
+        let (usdc_out, invested_usdc_share, pool_share_usdc) =
+            market.invalid_lp_unwind(lp_shares, lp_position.lp_shares, lp_position.invested_usdc)?;
+
+        require!(usdc_out >= min_usdc_out, PredictionMarketError::SlippageExceeded);
+
+        // CEI: 先更新状态
+        market.pool_collateral_reserve = market
+            .pool_collateral_reserve
+            .checked_sub(usdc_out)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.total_lp_shares = market
+            .total_lp_shares
+            .checked_sub(lp_shares)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        if market.total_lp_shares == 0 {
+            // 所有 LP 已退出：剩余盈利释放为尾款（reclaim_dust）
+            market.pool_collateral_reserve = 0;
+        }
+
+        lp_position.lp_shares = lp_position
+            .lp_shares
+            .checked_sub(lp_shares)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        lp_position.invested_usdc = lp_position
+            .invested_usdc
+            .checked_sub(invested_usdc_share)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        if usdc_out > 0 {
+            let market_key = market.key();
+            let vault_signer_seeds: &[&[&[u8]]] = &[&[
+                MARKET_USDC_VAULT.as_bytes(),
+                market_key.as_ref(),
+                &[ctx.bumps.market_usdc_vault],
+            ]];
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.market_usdc_ata.to_account_info(),
+                        mint: ctx.accounts.usdc_mint.to_account_info(),
+                        to: ctx.accounts.user_usdc_ata.to_account_info(),
+                        authority: ctx.accounts.market_usdc_vault.to_account_info(),
+                    },
+                    vault_signer_seeds,
+                ),
+                usdc_out,
+                ctx.accounts.usdc_mint.decimals,
+            )?;
+        }
+
+        msg!(
+            "✅ v3.10.0 Invalid market LP unwind: {} shares → {} USDC (invested={}, pool_share={})",
+            lp_shares,
+            usdc_out,
+            invested_usdc_share,
+            pool_share_usdc
+        );
+
+        emit!(LpInvalidUnwindEvent {
+            user: user.key(),
+            market: market.key(),
+            lp_shares_burned: lp_shares,
+            invested_usdc: invested_usdc_share,
+            pool_share_usdc,
+            usdc_out,
+            timestamp: current_timestamp,
+        });
+
+        return Ok(());
+    }
+
     // 市场已完成但未结算：限制提现
     if market.is_completed && !market.pool_settled {
         let usdc_to_withdraw = (lp_shares as u128)
@@ -324,9 +419,9 @@ pub fn handler(
         // ✅ v3.0.7: 使用预先计算的 PDA (line 162-164)
 
         // Burn YES
-        token::burn(
+        token_interface::burn(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.outcome_token_program.to_account_info(),
                 Burn {
                     mint: ctx.accounts.yes_token.to_account_info(),
                     from: ctx.accounts.global_yes_ata.to_account_info(),
@@ -338,9 +433,9 @@ pub fn handler(
         )?;
 
         // Burn NO
-        token::burn(
+        token_interface::burn(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.outcome_token_program.to_account_info(),
                 Burn {
                     mint: ctx.accounts.no_token.to_account_info(),
                     from: ctx.accounts.global_no_ata.to_account_info(),
@@ -377,9 +472,9 @@ pub fn handler(
         // ✅ v3.0.2: 从global ATA销毁，需要global_vault签名
         // ✅ v3.0.7: 使用预先计算的 PDA (line 162-164)
 
-        token::burn(
+        token_interface::burn(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.outcome_token_program.to_account_info(),
                 Burn {
                     mint: ctx.accounts.yes_token.to_account_info(),
                     from: ctx.accounts.global_yes_ata.to_account_info(),
@@ -430,9 +525,9 @@ pub fn handler(
         // ✅ v3.0.2: 从global ATA销毁，需要global_vault签名
         // ✅ v3.0.7: 使用预先计算的 PDA (line 162-164)
 
-        token::burn(
+        token_interface::burn(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.outcome_token_program.to_account_info(),
                 Burn {
                     mint: ctx.accounts.no_token.to_account_info(),
                     from: ctx.accounts.global_no_ata.to_account_info(),
@@ -650,9 +745,9 @@ pub fn handler(
     };
 
     // 预检查：计算提现后的池子状态
-    let pool_yes_after = market.pool_yes_reserve.checked_sub(yes_share).unwrap_or(0);
-    let pool_no_after = market.pool_no_reserve.checked_sub(no_share).unwrap_or(0);
-    let pool_usdc_after = market.pool_collateral_reserve.checked_sub(usdc_share).unwrap_or(0);
+    let pool_yes_after = market.pool_yes_reserve.saturating_sub(yes_share);
+    let pool_no_after = market.pool_no_reserve.saturating_sub(no_share);
+    let pool_usdc_after = market.pool_collateral_reserve.saturating_sub(usdc_share);
 
     let total_pool_value_after = pool_usdc_after
         .checked_add(pool_yes_after)
@@ -743,11 +838,10 @@ pub fn handler(
             (market.pool_no_reserve, market.pool_yes_reserve)
         };
 
-        let ratio_check = if smaller > 0 {
-            larger / smaller >= CIRCUIT_BREAKER_RATIO
-        } else {
-            true // 单边池子，触发熔断
-        };
+        // 单边池子（smaller == 0）同样触发熔断
+        let ratio_check = larger
+            .checked_div(smaller)
+            .is_none_or(|ratio| ratio >= CIRCUIT_BREAKER_RATIO);
 
         // 条件 2: 单边储备 < 初始储备的 10%
         let min_reserve_threshold_yes = (market.initial_yes_reserve as u128)
@@ -809,17 +903,19 @@ pub fn handler(
             &[ctx.bumps.market_usdc_vault],
         ]];
 
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
+                ctx.accounts.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
                     from: ctx.accounts.market_usdc_ata.to_account_info(),
+                    mint: ctx.accounts.usdc_mint.to_account_info(),
                     to: ctx.accounts.user_usdc_ata.to_account_info(),
                     authority: ctx.accounts.market_usdc_vault.to_account_info(),
                 },
                 vault_signer_seeds,
             ),
             final_usdc_out,
+            ctx.accounts.usdc_mint.decimals,
         )?;
 
         msg!(
@@ -258,7 +258,7 @@ mod tests {
 
     #[test]
     fn test_calculate_compensation_amount() {
-        let mut config = Config {
+        let config = Config {
             authority: Pubkey::default(),
             pending_authority: Pubkey::default(),
             team_wallet: Pubkey::default(),
@@ -282,6 +282,7 @@ mod tests {
             insurance_loss_threshold_bps: 1000, // 10%
             insurance_max_compensation_bps: 5000, // 50%
             insurance_pool_enabled: true,
+            max_referral_share_bps: 0,
         };
 
         // 损失率 5% < 阈值 10%，不补偿
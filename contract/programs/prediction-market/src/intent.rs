@@ -0,0 +1,216 @@
+//! ✅ v3.25.0: 签名交易意图（免 SOL 交易）
+//!
+//! 用户在链下对 `SwapIntent` 签名（ed25519），由 relayer 提交 execute_intent 交易并支付 SOL，
+//! 用户以 USDC 向 relayer 支付签名中约定的 relayer_fee。
+//!
+//! **签名校验**：交易中紧邻 execute_intent 之前必须是一条 Ed25519 预编译指令，
+//! 其中恰好包含一个签名，公钥、签名与消息均内嵌在该指令数据中（instruction_index = u16::MAX）。
+//! 预编译程序在交易执行前验证签名，本模块只需确认公钥与消息与意图一致。
+//!
+//! **签名消息**：`INTENT_DOMAIN || program_id || borsh(SwapIntent)`
+//!
+//! **资金授权**：用户一次性将 USDC（卖出时还有 YES/NO）ATA 的 delegate 设为其 `IntentNonce` PDA
+//! （approve 交易可由 relayer 代付手续费），授权额度即意图可动用的上限。
+//! ✅ v3.26.1: 该 PDA 同时是会话密钥的委托权限，授权额度由意图与会话共享。
+//!
+//! **防重放**：`IntentNonce.next_nonce` 严格递增，每个 nonce 只能执行一次。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use crate::errors::PredictionMarketError;
+
+/// 签名消息域前缀（防止与其他协议的签名消息混用）
+pub const INTENT_DOMAIN: &[u8] = b"prediction-market:swap-intent:v1";
+
+/// Ed25519 预编译程序（Anchor 0.32 的 solana_program 未重导出该 ID）
+const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+/// Ed25519 预编译指令头部：num_signatures(1) + padding(1)
+const ED25519_HEADER_LEN: usize = 2;
+
+/// 单个签名的偏移量描述：7 × u16
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// 公钥与签名长度
+const ED25519_PUBKEY_LEN: usize = 32;
+const ED25519_SIGNATURE_LEN: usize = 64;
+
+/// 交易意图（用户链下签名）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapIntent {
+    /// 市场账户
+    pub market: Pubkey,
+    /// 0 = 买入, 1 = 卖出
+    pub direction: u8,
+    /// 0 = NO, 1 = YES
+    pub token_type: u8,
+    /// 输入数量（买入为 USDC 含手续费，卖出为代币）
+    pub amount: u64,
+    /// 最小输出（滑点保护）
+    pub min_out: u64,
+    /// 过期时间（Unix 时间戳）
+    pub deadline: i64,
+    /// 用户意图序号（须等于 IntentNonce.next_nonce）
+    pub nonce: u64,
+    /// 支付给 relayer 的 USDC
+    pub relayer_fee: u64,
+}
+
+impl SwapIntent {
+    /// 待签名消息
+    pub fn message(&self, program_id: &Pubkey) -> Result<Vec<u8>> {
+        let mut message = Vec::with_capacity(INTENT_DOMAIN.len() + 32 + 75);
+        message.extend_from_slice(INTENT_DOMAIN);
+        message.extend_from_slice(program_id.as_ref());
+        self.serialize(&mut message)
+            .map_err(|_| PredictionMarketError::InvalidIntent)?;
+        Ok(message)
+    }
+}
+
+/// 解析 Ed25519 预编译指令数据，返回 (公钥, 消息)
+///
+/// 仅接受单签名、且公钥/签名/消息均位于本指令数据内的格式
+pub fn parse_ed25519_instruction(data: &[u8]) -> Result<(Pubkey, Vec<u8>)> {
+    require!(
+        data.len() >= ED25519_HEADER_LEN + ED25519_OFFSETS_LEN && data[0] == 1,
+        PredictionMarketError::InvalidIntentSignature
+    );
+
+    let read_u16 = |index: usize| -> u16 {
+        let start = ED25519_HEADER_LEN + index * 2;
+        u16::from_le_bytes([data[start], data[start + 1]])
+    };
+    let signature_offset = read_u16(0) as usize;
+    let signature_ix = read_u16(1);
+    let pubkey_offset = read_u16(2) as usize;
+    let pubkey_ix = read_u16(3);
+    let message_offset = read_u16(4) as usize;
+    let message_len = read_u16(5) as usize;
+    let message_ix = read_u16(6);
+
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        PredictionMarketError::InvalidIntentSignature
+    );
+    require!(
+        data.get(signature_offset..signature_offset + ED25519_SIGNATURE_LEN).is_some(),
+        PredictionMarketError::InvalidIntentSignature
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN)
+        .ok_or(PredictionMarketError::InvalidIntentSignature)?;
+    let message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(PredictionMarketError::InvalidIntentSignature)?;
+
+    Ok((Pubkey::try_from(pubkey).unwrap(), message.to_vec()))
+}
+
+/// 校验当前指令之前的 Ed25519 预编译指令由 `signer` 对 `message` 签名
+pub fn verify_ed25519_signed(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(*instructions.key, ix_sysvar::ID, PredictionMarketError::InvalidIntentSignature);
+
+    let current = ix_sysvar::load_current_index_checked(instructions)?;
+    require!(current > 0, PredictionMarketError::InvalidIntentSignature);
+
+    let ed25519_ix = ix_sysvar::load_instruction_at_checked(current as usize - 1, instructions)?;
+    require_keys_eq!(
+        ed25519_ix.program_id,
+        ED25519_PROGRAM_ID,
+        PredictionMarketError::InvalidIntentSignature
+    );
+
+    let (pubkey, signed_message) = parse_ed25519_instruction(&ed25519_ix.data)?;
+    require!(
+        pubkey == *signer && signed_message == message,
+        PredictionMarketError::InvalidIntentSignature
+    );
+    Ok(())
+}
+
+/// 构造单签名 Ed25519 预编译指令数据，供测试与客户端参考（链上指令不调用）
+pub fn encode_ed25519_instruction_data(pubkey: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+    let pubkey_offset = ED25519_HEADER_LEN + ED25519_OFFSETS_LEN;
+    let signature_offset = pubkey_offset + ED25519_PUBKEY_LEN;
+    let message_offset = signature_offset + ED25519_SIGNATURE_LEN;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for value in [
+        signature_offset as u16,
+        u16::MAX,
+        pubkey_offset as u16,
+        u16::MAX,
+        message_offset as u16,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent() -> SwapIntent {
+        SwapIntent {
+            market: Pubkey::new_unique(),
+            direction: 0,
+            token_type: 1,
+            amount: 10_000_000,
+            min_out: 9_000_000,
+            deadline: 1_700_000_000,
+            nonce: 3,
+            relayer_fee: 20_000,
+        }
+    }
+
+    #[test]
+    fn test_message_binds_program_and_fields() {
+        let intent = intent();
+        let program_id = Pubkey::new_unique();
+        let message = intent.message(&program_id).unwrap();
+
+        assert!(message.starts_with(INTENT_DOMAIN));
+        assert_eq!(&message[INTENT_DOMAIN.len()..INTENT_DOMAIN.len() + 32], program_id.as_ref());
+        assert_ne!(message, intent.message(&Pubkey::new_unique()).unwrap());
+
+        let mut other = intent;
+        other.nonce += 1;
+        assert_ne!(message, other.message(&program_id).unwrap());
+    }
+
+    #[test]
+    fn test_parse_ed25519_instruction() {
+        let signer = Pubkey::new_unique();
+        let message = intent().message(&Pubkey::new_unique()).unwrap();
+        let data = encode_ed25519_instruction_data(&signer, &[7u8; 64], &message);
+
+        let (pubkey, parsed) = parse_ed25519_instruction(&data).unwrap();
+        assert_eq!(pubkey, signer);
+        assert_eq!(parsed, message);
+
+        // 多签名
+        let mut multi = data.clone();
+        multi[0] = 2;
+        assert!(parse_ed25519_instruction(&multi).is_err());
+
+        // 引用其他指令中的数据
+        let mut external = data.clone();
+        external[4] = 0;
+        external[5] = 0;
+        assert!(parse_ed25519_instruction(&external).is_err());
+
+        // 截断
+        assert!(parse_ed25519_instruction(&data[..data.len() - 1]).is_err());
+    }
+}
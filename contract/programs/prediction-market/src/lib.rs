@@ -33,20 +33,48 @@ pub mod state;      // 状态结构定义
 pub mod types;      // 类型定义（枚举等）✅ v1.6.0: 替代魔法数字
 pub mod utils;      // 工具函数
 pub mod insurance;  // ✅ v3.2.0: 保险池资金隔离验证模块
+pub mod price_feed; // ✅ v3.8.0: Pyth 风格价格源解析
+pub mod intent;     // ✅ v3.25.0: 签名交易意图（Ed25519 预编译校验）
+pub mod token_compat; // ✅ v3.27.0: SPL Token / Token-2022 兼容（扩展白名单、转账手续费）
 
 // 导入指令模块
-#[allow(ambiguous_glob_imports)]
 use instructions::{
     accept_authority::*, add_liquidity::*, add_to_whitelist::*, claim_fees_preview::*, claim_lp_fees::*, claim_rewards::*, claim_rewards_preview::*,
     configure::*, create_market::*, emergency_pause::*, emergency_unpause::*, ensure_team_usdc_ata::*, configure_market_fees::*, mint_complete_set::*, mint_no_token::*, nominate_authority::*,
     pause::*, reclaim_dust::*, redeem_complete_set::*, remove_from_whitelist::*, reset_circuit_breaker::*,
-    resolution::*, seed_pool::*, sell_preview::*, set_mint_authority::*, settle_pool::*, swap::*, update_market_name::*,
+    seed_pool::*, sell_preview::*, set_mint_authority::*, settle_pool::*, swap::*, update_market_name::*,
     withdraw_liquidity::*, withdraw_preview::*, pause_market::*,
+    create_categorical_market::*, mint_categorical_set::*, redeem_categorical_set::*, categorical_swap::*,
+    resolve_categorical_market::*, claim_categorical_rewards::*,
+    create_event_group::*, add_market_to_event_group::*, convert_neg_risk_positions::*, resolve_event_group::*, void_event_group::*,
+    void_conditional_market::*,
+    configure_oracle::*, propose_resolution::*, dispute_resolution::*, finalize_resolution::*,
+    resolve_from_feed::*,
+    create_resolver_committee::*, resolve_by_resolver::*, cast_resolution_vote::*,
+    invalid_refund_preview::*, void_unresolved_market::*,
+    propose_admin_resolution::*, amend_resolution::*,
+    refund_resolution_bonds::*,
+    buy_preview::*,
+    initialize_order_book::*, post_limit_order::*, match_limit_orders::*, settle_limit_order::*,
+    swap_bundle::*, routed_swap::*,
+    get_twap::*, migrate_market_account::*,
+    create_conditional_order::*, execute_conditional_order::*, cancel_conditional_order::*,
+    configure_referrer::*, claim_referral_fees::*,
+    configure_fee_tiers::*, set_maker_rebate::*, open_fee_tier_account::*,
+    configure_dynamic_fee::*,
+    execute_intent::*,
+    create_session_key::*,
+    revoke_session_key::*,
+    create_outcome_mint::*,
 };
 
 // 导入状态模块
 use state::config::*;
 use state::market::*;
+use state::categorical_market::*;
+use state::market_metadata::ResolutionEvidence;
+use state::fee_tier::FeeTier;
+use intent::SwapIntent;
 
 // 声明程序ID
 // Localhost: G9h26GViC3ma7Zg58HAbLaqEXgYEWLCCiNjfWkooevq2
@@ -136,7 +164,7 @@ pub mod prediction_market {
     /// # 返回
     /// * `Result<()>` - 操作结果
     pub fn create_market(ctx: Context<CreateMarket>, params: CreateMarketParams) -> Result<()> {
-        ctx.accounts.handler(params, ctx.bumps.global_vault)
+        ctx.accounts.handler(params, ctx.bumps.global_vault, ctx.bumps.market_metadata)
     }
 
     /// 交易代币
@@ -168,41 +196,12 @@ pub mod prediction_market {
             token_type,
             minimum_receive_amount,
             deadline,
+            None,
             ctx.bumps.global_vault,
             ctx.bumps.market_usdc_vault,
         )
     }
 
-    /// 市场结算
-    /// 
-    /// 由管理员调用，用于结算预测市场的结果
-    /// 根据实际结果分配奖励给持有正确代币的用户
-    /// 
-    /// # 参数
-    /// * `ctx` - 指令上下文
-    /// * `yes_amount` - YES代币的奖励数量
-    /// * `no_amount` - NO代币的奖励数量
-    /// * `token_type` - 获胜的代币类型
-    /// * `is_completed` - 市场是否完成
-    /// 
-    /// # 返回
-    /// * `Result<()>` - 操作结果
-    pub fn resolution(
-        ctx: Context<Resolution>,
-        yes_amount: u64,
-        no_amount: u64,
-        token_type: u8,
-        is_completed: bool,
-    ) -> Result<()> {
-        ctx.accounts.handler(
-            yes_amount,
-            no_amount,
-            token_type,
-            is_completed,
-            ctx.bumps.global_vault,
-        )
-    }
-
     /// 添加流动性（✅ v3.0: 单币LP - 用户只提供USDC）
     ///
     /// ✅ v3.0: 单币LP系统
@@ -643,12 +642,741 @@ pub mod prediction_market {
     }
 
     /// 市场级暂停（管理员）
-    pub fn pause_market(mut ctx: Context<PauseMarket>) -> Result<()> {
-        instructions::pause_market::PauseMarket::pause(&mut ctx.accounts)
+    pub fn pause_market(ctx: Context<PauseMarket>) -> Result<()> {
+        instructions::pause_market::PauseMarket::pause(ctx.accounts)
     }
 
     /// 市场级恢复（管理员）
-    pub fn unpause_market(mut ctx: Context<PauseMarket>) -> Result<()> {
-        instructions::pause_market::PauseMarket::unpause(&mut ctx.accounts)
+    pub fn unpause_market(ctx: Context<PauseMarket>) -> Result<()> {
+        instructions::pause_market::PauseMarket::unpause(ctx.accounts)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.3.0: 分类市场（N 结果，3-16 个互斥结果）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 创建分类市场（✅ v3.3.0）
+    ///
+    /// 结果代币 mint 需预先创建（mint authority = 分类市场 PDA），
+    /// 通过 remaining_accounts 按结果索引顺序传入。
+    /// 创建者注入 b × ln(N) 的补贴作为 LMSR 做市商的最大亏损准备金。
+    pub fn create_categorical_market<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateCategoricalMarket<'info>>,
+        params: CreateCategoricalMarketParams,
+    ) -> Result<()> {
+        instructions::create_categorical_market::handler(ctx, params)
+    }
+
+    /// 分类市场铸造完整集合（✅ v3.3.0）
+    ///
+    /// 存入 amount USDC，获得每个结果各 amount 个代币。
+    /// remaining_accounts: `[mint_0, user_ata_0, mint_1, user_ata_1, ...]`
+    pub fn mint_categorical_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintCategoricalSet<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::mint_categorical_set::handler(ctx, amount)
+    }
+
+    /// 分类市场赎回完整集合（✅ v3.3.0）
+    ///
+    /// 销毁每个结果各 amount 个代币，取回 amount USDC（仅结算前）。
+    /// remaining_accounts: `[mint_0, user_ata_0, mint_1, user_ata_1, ...]`
+    pub fn redeem_categorical_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedeemCategoricalSet<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::redeem_categorical_set::handler(ctx, amount)
+    }
+
+    /// 分类市场交易（✅ v3.3.0）
+    ///
+    /// # 参数
+    /// * `outcome_index` - 结果索引
+    /// * `direction` - 交易方向（0=买入，1=卖出）
+    /// * `amount` - 买入时为 USDC 数量，卖出时为结果代币数量
+    /// * `minimum_receive_amount` - 最小接收数量（滑点保护）
+    /// * `deadline` - 交易截止时间戳（Unix timestamp），设为 0 则不检查
+    pub fn categorical_swap(
+        ctx: Context<CategoricalSwap>,
+        outcome_index: u8,
+        direction: u8,
+        amount: u64,
+        minimum_receive_amount: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            outcome_index,
+            direction,
+            amount,
+            minimum_receive_amount,
+            deadline,
+            ctx.bumps.market_usdc_vault,
+        )
+    }
+
+    /// 分类市场结算（✅ v3.3.0，管理员）
+    ///
+    /// # 参数
+    /// * `payout_bps` - 各结果赔付比例（基点，总和 10000）
+    ///
+    /// remaining_accounts: `[outcome_mint_0, ..., outcome_mint_{N-1}]`
+    pub fn resolve_categorical_market<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveCategoricalMarket<'info>>,
+        payout_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::resolve_categorical_market::handler(ctx, payout_bps)
+    }
+
+    /// 分类市场领取奖励（✅ v3.3.0）
+    ///
+    /// 销毁指定结果的全部代币，按赔付比例领取 USDC
+    pub fn claim_categorical_rewards(
+        ctx: Context<ClaimCategoricalRewards>,
+        outcome_index: u8,
+    ) -> Result<()> {
+        ctx.accounts.handler(outcome_index, ctx.bumps.market_usdc_vault)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.5.0: 负风险（neg-risk）事件组
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 创建事件组（✅ v3.5.0，管理员）
+    ///
+    /// # 参数
+    /// * `group_id` - 事件组编号（PDA 种子）
+    /// * `display_name` - 事件显示名称（最大64字符）
+    pub fn create_event_group(
+        ctx: Context<CreateEventGroup>,
+        group_id: u64,
+        display_name: String,
+    ) -> Result<()> {
+        ctx.accounts.handler(group_id, display_name, ctx.bumps.event_group)
+    }
+
+    /// 将二元市场加入事件组（✅ v3.5.0，管理员）
+    ///
+    /// 首次转换后成员列表冻结
+    pub fn add_market_to_event_group(ctx: Context<AddMarketToEventGroup>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// 负风险转换（✅ v3.5.0）
+    ///
+    /// 销毁 no_mask 选中的 k 个成员各 amount 个 NO，
+    /// 获得 (k - 1) × amount USDC + 其余成员各 amount 个 YES。
+    /// remaining_accounts 格式见 `convert_neg_risk_positions::handler`
+    pub fn convert_neg_risk_positions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConvertNegRiskPositions<'info>>,
+        no_mask: u16,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::convert_neg_risk_positions::handler(ctx, no_mask, amount)
+    }
+
+    /// 结算事件组（✅ v3.5.0，管理员）
+    ///
+    /// 指定唯一胜出成员并将担保抵押品注入胜者市场；
+    /// 成员市场随后通过结算提案结算（胜者 YES，其余 NO）
+    pub fn resolve_event_group(ctx: Context<ResolveEventGroup>, winning_index: u8) -> Result<()> {
+        ctx.accounts.handler(winning_index)
+    }
+
+    /// 作废事件组（✅ v3.5.1，管理员；✅ v3.11.1: 所有成员超过结算截止后任何人可调用）
+    ///
+    /// 按归一化快照价格向各成员注入担保抵押品；
+    /// 成员市场随后通过 void_unresolved_market 按 Invalid 结算
+    ///
+    /// remaining_accounts 按成员顺序传入 `[market, market_usdc_ata]`
+    pub fn void_event_group<'info>(ctx: Context<'_, '_, 'info, 'info, VoidEventGroup<'info>>) -> Result<()> {
+        instructions::void_event_group::handler(ctx)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.6.0: 条件市场
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 作废条件市场（✅ v3.6.0，任何人可调用）
+    ///
+    /// 父市场未按要求结算时，条件市场按 Invalid 结算（✅ v3.6.1: 价格快照退款并清算池内代币），
+    /// 持有人通过 claim_rewards / redeem_complete_set 取回抵押品，LP 通过 withdraw_liquidity 退出
+    pub fn void_conditional_market(ctx: Context<VoidConditionalMarket>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.global_vault)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.7.0: 乐观预言机（提案 → 挑战窗口 → 最终确认/争议裁决）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 配置乐观预言机（✅ v3.7.0，管理员）
+    ///
+    /// # 参数
+    /// * `liveness_secs` - 挑战窗口（秒）
+    /// * `min_bond` - 最低提案保证金
+    /// * `enabled` - 是否接受新提案
+    /// * `admin_challenge_secs` - ✅ v3.12.0: 管理员两阶段结算挑战窗口（秒）
+    pub fn configure_oracle(
+        ctx: Context<ConfigureOracle>,
+        liveness_secs: i64,
+        min_bond: u64,
+        enabled: bool,
+        admin_challenge_secs: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(liveness_secs, min_bond, enabled, admin_challenge_secs, ctx.bumps.oracle_config)
+    }
+
+    /// 提交结算提案（✅ v3.7.0，任何人）
+    ///
+    /// 市场结束后质押 USDC 保证金提交结果，进入挑战窗口
+    /// （✅ v3.13.1: 须附带结算证据，最终确认时写入 MarketMetadata）
+    pub fn propose_resolution(
+        ctx: Context<ProposeResolution>,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        bond: u64,
+        evidence_hash: [u8; 32],
+        evidence_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            yes_ratio,
+            no_ratio,
+            token_type,
+            bond,
+            ResolutionEvidence { hash: evidence_hash, uri: evidence_uri },
+            ctx.bumps.proposal,
+        )
+    }
+
+    /// 挑战结算提案（✅ v3.7.0，任何人）
+    ///
+    /// 挑战窗口内质押等额保证金，提案升级至管理员裁决
+    pub fn dispute_resolution(ctx: Context<DisputeResolution>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// 最终确认无争议提案（✅ v3.7.0，任何人）
+    ///
+    /// 挑战窗口结束后按提案结果结算市场，保证金退还提案人
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+        ctx.accounts.finalize(ctx.bumps.global_vault)
+    }
+
+    /// 裁决争议提案（✅ v3.7.0，管理员）
+    ///
+    /// 按管理员给出的结果结算市场；败方保证金罚没给胜方
+    /// （✅ v3.13.1: 须附带裁决证据）
+    pub fn settle_resolution_dispute(
+        ctx: Context<FinalizeResolution>,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        evidence_hash: [u8; 32],
+        evidence_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.settle_dispute(
+            yes_ratio,
+            no_ratio,
+            token_type,
+            ResolutionEvidence { hash: evidence_hash, uri: evidence_uri },
+            ctx.bumps.global_vault,
+        )
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.8.0: 价格源自动结算
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 价格源自动结算（✅ v3.8.0，任何人可调用）
+    ///
+    /// 读取调用者提供的 Pyth PriceUpdateV2（✅ v3.8.2: 任意 feed_id 一致的历史价格账户），
+    /// 校验 feed_id、结算窗口（✅ v3.8.1）与置信区间后，价格严格高于阈值则 YES 胜出，否则 NO 胜出
+    pub fn resolve_from_feed(ctx: Context<ResolveFromFeed>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.global_vault)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.9.0: 市场级结算人 / M-of-N 结算委员会
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 创建结算委员会（✅ v3.9.0，任何人）
+    ///
+    /// N 个成员、阈值 M；创建后可在 create_market 时指定为市场结算人
+    pub fn create_resolver_committee(
+        ctx: Context<CreateResolverCommittee>,
+        committee_id: u64,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(committee_id, members, threshold, ctx.bumps.committee)
+    }
+
+    /// 指定结算人结算（✅ v3.9.0，market.resolver 单一密钥）
+    ///
+    /// 参数与 propose_admin_resolution 一致；全局管理员仍可通过结算提案结算
+    /// （✅ v3.13.1: 须附带结算证据）
+    pub fn resolve_by_resolver(
+        ctx: Context<ResolveByResolver>,
+        yes_amount: u64,
+        no_amount: u64,
+        token_type: u8,
+        evidence_hash: [u8; 32],
+        evidence_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            yes_amount,
+            no_amount,
+            token_type,
+            ResolutionEvidence { hash: evidence_hash, uri: evidence_uri },
+            ctx.bumps.global_vault,
+        )
+    }
+
+    /// 结算委员会投票（✅ v3.9.0，委员会成员）
+    ///
+    /// 任一结果累计 M 票后自动结算；✅ v3.9.1: 仅当没有结果还能达到 M 票时才升级至全局管理员
+    ///
+    /// ✅ v3.13.1: 每张投票须附带结算证据，达到阈值的投票证据写入 MarketMetadata
+    pub fn cast_resolution_vote(
+        ctx: Context<CastResolutionVote>,
+        yes_amount: u64,
+        no_amount: u64,
+        token_type: u8,
+        evidence_hash: [u8; 32],
+        evidence_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            yes_amount,
+            no_amount,
+            token_type,
+            ResolutionEvidence { hash: evidence_hash, uri: evidence_uri },
+            ctx.bumps.votes,
+            ctx.bumps.global_vault,
+        )
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.10.0: 无效结果退款
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 无效结果退款预览（✅ v3.10.0: 只读）
+    ///
+    /// Invalid 结果（token_type = 3，比例参数传 0/0）按最后成交价格快照退款：
+    /// 交易者 YES 按 p、NO 按 1-p 通过 claim_rewards 领取；LP 通过 withdraw_liquidity 按本金退出
+    pub fn invalid_refund_preview(
+        ctx: Context<InvalidRefundPreview>,
+        yes_amount: u64,
+        no_amount: u64,
+    ) -> Result<InvalidRefundPreviewResult> {
+        instructions::invalid_refund_preview::handler(ctx, yes_amount, no_amount)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.11.0: 结算截止（活性保证）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 作废超过结算截止仍未结算的市场（✅ v3.11.0，任何人可调用）
+    ///
+    /// 以 Invalid 结果结算并开放退款：交易者通过 claim_rewards、LP 通过 withdraw_liquidity 退出
+    pub fn void_unresolved_market(ctx: Context<VoidUnresolvedMarket>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.global_vault)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.12.0: 管理员两阶段结算（提案 → 挑战窗口 → 最终确认）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 管理员提交结算提案（✅ v3.12.0）
+    ///
+    /// 仅记录结果并开启挑战窗口；窗口结束后任何人通过 finalize_resolution 最终确认，
+    /// 此时才清算池内代币并开放 claim_rewards / settle_pool
+    ///
+    /// ✅ v3.12.1: 取代一次性 resolution 指令；✅ v3.13.1: 必须附带结算证据（最终确认时写入 MarketMetadata）
+    pub fn propose_admin_resolution(
+        ctx: Context<ProposeAdminResolution>,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        evidence_hash: [u8; 32],
+        evidence_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            yes_ratio,
+            no_ratio,
+            token_type,
+            ResolutionEvidence { hash: evidence_hash, uri: evidence_uri },
+            ctx.bumps.proposal,
+        )
+    }
+
+    /// 管理员提交标量市场结算提案（✅ v3.12.1，取代一次性 resolve_scalar 指令）
+    ///
+    /// 赔付比例按结算值在 [lower_bound, upper_bound] 内线性插值，最终确认时记录结算值
+    pub fn propose_admin_scalar_resolution(
+        ctx: Context<ProposeAdminResolution>,
+        resolved_value: i64,
+        evidence_hash: [u8; 32],
+        evidence_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.handler_scalar(
+            resolved_value,
+            ResolutionEvidence { hash: evidence_hash, uri: evidence_uri },
+            ctx.bumps.proposal,
+        )
+    }
+
+    /// 修改管理员结算提案（✅ v3.12.0，挑战窗口内，窗口重新计时；✅ v3.13.1: 同时更新结算证据）
+    pub fn amend_resolution(
+        ctx: Context<AmendResolution>,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        evidence_hash: [u8; 32],
+        evidence_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            yes_ratio,
+            no_ratio,
+            token_type,
+            ResolutionEvidence { hash: evidence_hash, uri: evidence_uri },
+        )
+    }
+
+    /// 撤回管理员结算提案（✅ v3.12.0，挑战窗口内）
+    pub fn retract_resolution(ctx: Context<RetractResolution>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// 退还未最终确认提案的保证金（✅ v3.7.1，任何人）
+    ///
+    /// 市场经其他路径结算后，原路退还提案人与挑战人的保证金并关闭提案
+    pub fn refund_resolution_bonds(ctx: Context<RefundResolutionBonds>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.15.0: 买入预览
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 买入预览（只读）
+    ///
+    /// 返回支付给定 USDC 可获得的代币数量、费用拆分、成交均价/边际价格与价格冲击，
+    /// 并标明单笔上限、最小交易流动性、持仓上限与动态 b 值是否会改变或拒绝本次交易
+    pub fn buy_preview(
+        ctx: Context<BuyPreview>,
+        usdc_amount: u64,
+        token_type: u8,
+    ) -> Result<BuyPreviewResult> {
+        instructions::buy_preview::handler(ctx, usdc_amount, token_type)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.16.0: 精确输出交易
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 精确输出交易（复用 swap 账户）
+    ///
+    /// - 买入（direction=0）：恰好买入 `amount_out` 个代币，按 `lmsr_buy_cost` 含费计价，
+    ///   支付 USDC 不超过 `max_amount_in`（max_usdc_in）
+    /// - 卖出（direction=1）：恰好收到 `amount_out` USDC（税后），
+    ///   卖出代币不超过 `max_amount_in`（max_tokens_in）
+    ///
+    /// 取整产生的余量（≤ 1-2 个最小单位）留在池中
+    pub fn swap_exact_output(
+        ctx: Context<Swap>,
+        amount_out: u64,
+        max_amount_in: u64,
+        direction: u8,
+        token_type: u8,
+        deadline: i64,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            max_amount_in,
+            direction,
+            token_type,
+            amount_out,
+            deadline,
+            Some(amount_out),
+            ctx.bumps.global_vault,
+            ctx.bumps.market_usdc_vault,
+        )
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.17.0: 限价订单簿
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 创建市场订单簿及托管账户（任何人，每个市场一次）
+    pub fn initialize_order_book(ctx: Context<InitializeOrderBook>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.order_book)
+    }
+
+    /// 挂限价单（买单托管 USDC，卖单托管代币）
+    ///
+    /// # 参数
+    /// * `side` - 方向（0=买单, 1=卖单）
+    /// * `token_type` - 代币类型（0=NO, 1=YES）
+    /// * `price_bps` - 限价（基点，1-9999）
+    /// * `amount` - 代币数量（✅ v3.17.1: 数量 × 限价须 ≥ MIN_LIMIT_ORDER_NOTIONAL；每人最多 MAX_ORDERS_PER_OWNER 个槽位）
+    pub fn post_limit_order(
+        ctx: Context<PostLimitOrder>,
+        side: u8,
+        token_type: u8,
+        price_bps: u16,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.handler(side, token_type, price_bps, amount)
+    }
+
+    /// 撮合价格交叉的买卖单（任何人，按先挂单的价格成交）
+    pub fn match_limit_orders(
+        ctx: Context<MatchLimitOrders>,
+        bid_order_id: u64,
+        ask_order_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.handler(bid_order_id, ask_order_id)
+    }
+
+    /// 领取挂单成交所得（完全成交的挂单同时关闭）
+    pub fn settle_limit_order(ctx: Context<SettleLimitOrder>, order_id: u64) -> Result<()> {
+        ctx.accounts.handler(order_id, false)
+    }
+
+    /// 撤单：领取成交所得并退还未成交部分
+    pub fn cancel_limit_order(ctx: Context<SettleLimitOrder>, order_id: u64) -> Result<()> {
+        ctx.accounts.handler(order_id, true)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.18.0: 多市场组合交易
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 多市场组合交易（原子执行，聚合滑点保护）
+    ///
+    /// 卖出腿先于买入腿执行；用户 USDC 净变化（收到 - 支付）不得低于 `min_net_usdc_out`，
+    /// 负数表示允许的最大净支出
+    ///
+    /// remaining_accounts 格式见 `swap_bundle::handler`
+    pub fn swap_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapBundle<'info>>,
+        legs: Vec<BundleLeg>,
+        min_net_usdc_out: i64,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::swap_bundle::handler(ctx, legs, min_net_usdc_out, deadline)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.19.0: 智能路由交易
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 智能路由交易（参数语义与 swap 相同）
+    ///
+    /// 比较直接成交与完整集合路径（买入：铸造后卖出另一侧；卖出：买入另一侧后赎回），
+    /// 原子执行更优者，所选路由记录在 SwapExecuted 事件的 `route` 字段
+    pub fn routed_swap(
+        ctx: Context<RoutedSwap>,
+        amount: u64,
+        direction: u8,
+        token_type: u8,
+        minimum_receive_amount: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            amount,
+            direction,
+            token_type,
+            minimum_receive_amount,
+            deadline,
+            ctx.bumps.global_vault,
+            ctx.bumps.market,
+            ctx.bumps.market_usdc_vault,
+        )
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.20.0: TWAP 价格查询
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 查询最近 `window_secs` 秒的 YES/NO 时间加权平均价格（只读）
+    ///
+    /// 可查询窗口受观测缓冲区限制（约 TWAP_OBSERVATION_COUNT × 观测间隔），
+    /// 历史不足时返回 InsufficientTwapHistory
+    pub fn get_twap(ctx: Context<GetTwap>, window_secs: u32) -> Result<TwapResult> {
+        instructions::get_twap::handler(ctx, window_secs)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.21.0: 条件订单（止损 / 止盈）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 创建条件订单：托管代币，YES 价格穿越触发价后由 keeper 卖出
+    ///
+    /// # 参数
+    /// * `order_id` - 订单编号（下单人自选，同一市场内不可重复）
+    /// * `token_type` - 代币类型（0=NO, 1=YES）
+    /// * `trigger_direction` - 触发方向（0=YES 价格 ≤ 触发价, 1=YES 价格 ≥ 触发价）
+    /// * `trigger_price_bps` - 触发价（YES 价格，基点）
+    /// * `amount` - 托管代币数量
+    /// * `min_usdc_out` - 扣除 keeper 费后的最少所得（USDC）
+    /// * `keeper_fee_bps` - Keeper 执行费（基点，≤ MAX_KEEPER_FEE_BPS）
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_conditional_order(
+        ctx: Context<CreateConditionalOrder>,
+        order_id: u64,
+        token_type: u8,
+        trigger_direction: u8,
+        trigger_price_bps: u16,
+        amount: u64,
+        min_usdc_out: u64,
+        keeper_fee_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            order_id,
+            token_type,
+            trigger_direction,
+            trigger_price_bps,
+            amount,
+            min_usdc_out,
+            keeper_fee_bps,
+            ctx.bumps.conditional_order,
+        )
+    }
+
+    /// 执行条件订单（任何 keeper，触发条件满足时）
+    pub fn execute_conditional_order(ctx: Context<ExecuteConditionalOrder>) -> Result<()> {
+        ctx.accounts
+            .handler(ctx.bumps.global_vault, ctx.bumps.market_usdc_vault)
+    }
+
+    /// 撤销条件订单并取回托管代币（下单人，随时可用）
+    pub fn cancel_conditional_order(ctx: Context<CancelConditionalOrder>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.22.0: 引荐分成
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 创建/更新引荐人账户及平台费分成比例（管理员）
+    ///
+    /// 实际分成受 Config.max_referral_share_bps 限制，且不超过平台费中的团队份额
+    pub fn configure_referrer(ctx: Context<ConfigureReferrer>, share_bps: u16) -> Result<()> {
+        ctx.accounts.handler(share_bps, ctx.bumps.referrer_stats)
+    }
+
+    /// 引荐人领取累计分成
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.23.0: 成交量分级费率与做市返佣
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 配置成交量分级费率表与做市返佣档（管理员，整表替换）
+    ///
+    /// 分级费率只降不升：实际费率 = min(基础费率, 档位费率)
+    pub fn configure_fee_tiers(
+        ctx: Context<ConfigureFeeTiers>,
+        tiers: Vec<FeeTier>,
+        maker_platform_fee_bps: u16,
+        maker_lp_fee_bps: i16,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            tiers,
+            maker_platform_fee_bps,
+            maker_lp_fee_bps,
+            ctx.bumps.fee_tier_table,
+        )
+    }
+
+    /// 加入/移出做市商白名单（管理员）
+    pub fn set_maker_rebate(ctx: Context<SetMakerRebate>, is_maker: bool) -> Result<()> {
+        ctx.accounts.handler(is_maker, ctx.bumps.user_fee_tier)
+    }
+
+    /// 创建费率分级账户（用户）
+    pub fn open_fee_tier_account(ctx: Context<OpenFeeTierAccount>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.user_fee_tier)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.24.0: 动态 LP 费率
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 配置市场级动态 LP 费率（管理员）
+    ///
+    /// 启用后 LP 费率随池子不平衡度与近期价格波动上调，并限制在 [min_bps, max_bps] 内；
+    /// 实际费率记录在 SwapExecuted 事件的 `lp_fee_bps` 字段
+    pub fn configure_dynamic_fee(ctx: Context<ConfigureDynamicFee>, params: DynamicFeeParams) -> Result<()> {
+        ctx.accounts.handler(params)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.25.0: 签名交易意图（免 SOL 交易）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 执行用户签名的交易意图（relayer 提交并支付 SOL，以 USDC 获得 relayer_fee）
+    ///
+    /// 前一条指令须为 user 对 `SwapIntent` 消息的 Ed25519 预编译签名；
+    /// 用户须事先将 ATA 的 delegate 授权给其 IntentNonce PDA
+    pub fn execute_intent(ctx: Context<ExecuteIntent>, intent: SwapIntent) -> Result<()> {
+        ctx.accounts.handler(
+            intent,
+            ctx.bumps.intent_nonce,
+            ctx.bumps.global_vault,
+            ctx.bumps.market_usdc_vault,
+        )
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.26.0: 会话密钥（委托临时热钥交易）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 创建会话密钥：限定有效期、USDC 支出上限、市场与操作
+    ///
+    /// 会话签名者可代替 owner 签名 swap / mint_complete_set / redeem_complete_set，
+    /// 资金由用户委托权限 PDA（✅ v3.26.1: 与 execute_intent 共用的 IntentNonce PDA）以 delegate 身份从 owner 的 ATA 转出
+    pub fn create_session_key(ctx: Context<CreateSessionKey>, params: SessionKeyParams) -> Result<()> {
+        ctx.accounts
+            .handler(params, ctx.bumps.session_key, ctx.bumps.delegate_authority)
+    }
+
+    /// 撤销会话密钥（关闭账户，租金退回 owner）
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.27.0: Token-2022 结果代币（metadata-pointer 元数据）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 创建 Token-2022 结果代币 mint，元数据写入 mint 自身（替代 mint_no_token 的 Metaplex 流程）
+    ///
+    /// # 参数
+    /// * `is_yes` - true 为 YES mint（名称 YES_NAME），false 为 NO mint（名称 NO_NAME）
+    /// * `symbol` / `uri` - 元数据符号与 URI
+    pub fn create_outcome_mint(
+        ctx: Context<CreateOutcomeMint>,
+        is_yes: bool,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.handler(is_yes, symbol, uri, ctx.bumps.global_vault)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.20.1: 旧市场账户扩容
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 将升级前创建的市场账户扩容至当前大小（任何人，调用者支付租金差额）
+    ///
+    /// 新追加字段（TWAP 缓冲区、动态费率、feed_id 等）以 0 填充；旧市场在扩容前无法执行任何指令
+    pub fn migrate_market_account(ctx: Context<MigrateMarketAccount>) -> Result<()> {
+        ctx.accounts.handler()
     }
 }
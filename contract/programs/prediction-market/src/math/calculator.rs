@@ -419,6 +419,51 @@ mod tests {
             market_paused: false,
             // ✅ v3.1.3: 哨兵代币追踪
             sentinel_no_minted: true,
+            // ✅ v3.4.0: 标量市场
+            is_scalar: false,
+            scalar_lower_bound: 0,
+            scalar_upper_bound: 0,
+            scalar_resolved_value: 0,
+            // ✅ v3.5.0: 负风险事件组
+            event_group: Pubkey::default(),
+            // ✅ v3.6.0: 条件市场
+            parent_market: Pubkey::default(),
+            parent_required_outcome: 0,
+            is_voided: false,
+            // ✅ v3.8.0: 价格源自动结算
+            price_feed: Pubkey::default(),
+            price_threshold: 0,
+            price_threshold_expo: 0,
+            price_resolve_after: 0,
+            price_max_staleness_secs: 0,
+            price_max_conf_bps: 0,
+            // ✅ v3.9.0: 市场级结算人
+            resolver: Pubkey::default(),
+            resolver_is_committee: false,
+            // ✅ v3.10.0: 无效结果退款
+            last_trade_yes_price_bps: 5000,
+            // ✅ v3.11.0: 结算截止
+            resolution_deadline_slot: 0,
+            // ✅ v3.13.0: 规则元数据
+            has_metadata: false,
+            // ✅ v3.14.0: Unix 时间戳排期
+            start_time: 0,
+            end_time: 0,
+            // ✅ v3.20.0: TWAP 价格累加器
+            twap_cumulative_yes_price: 0,
+            twap_last_update_ts: 0,
+            twap_observation_index: 0,
+            twap_observation_count: 0,
+            twap_observations: [crate::state::market::TwapObservation::default(); crate::constants::TWAP_OBSERVATION_COUNT],
+            // ✅ v3.24.0: 动态 LP 费率
+            dynamic_fee_enabled: false,
+            dynamic_fee_min_bps: 0,
+            dynamic_fee_max_bps: 0,
+            dynamic_fee_imbalance_bps: 0,
+            dynamic_fee_volatility_multiplier_bps: 0,
+            volatility_ewma_bps: 0,
+            volatility_last_update_ts: 0,
+            price_feed_id: [0u8; 32],
         }
     }
 
@@ -667,8 +712,8 @@ mod tests {
         let yes_bps = calc.yes_price_bps().unwrap();
         let no_bps = calc.no_price_bps().unwrap();
 
-        assert!(yes_bps >= 0 && yes_bps <= 10000, "YES price should be in [0, 10000]");
-        assert!(no_bps >= 0 && no_bps <= 10000, "NO price should be in [0, 10000]");
+        assert!((0..=10000).contains(&yes_bps), "YES price should be in [0, 10000]");
+        assert!((0..=10000).contains(&no_bps), "NO price should be in [0, 10000]");
         assert_eq!(yes_bps + no_bps, 10000, "Prices should sum to 100%");
     }
 
@@ -751,4 +796,137 @@ mod tests {
         // 即使价格接近 100%，成本也不应超过 2 倍金额
         assert!(cost <= amount * 2, "Cost should not exceed 2x amount");
     }
+
+    // ═══════════════════════════════════════════════════════════════
+    // 10. ✅ v3.20.0: TWAP 累加器
+    // ═══════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_twap_accumulates_pre_trade_price() {
+        let mut market = create_test_market(1_000_000, 0, 0);
+
+        // 未初始化时无法查询
+        assert!(market.twap_yes_price_bps(60, 1_000).is_err());
+
+        // 首次更新仅记录起点
+        market.update_twap(1_000).unwrap();
+        assert_eq!(market.twap_cumulative_yes_price, 0);
+        assert_eq!(market.twap_observation_count, 1);
+
+        // 300 秒 @ 50%
+        market.update_twap(1_300).unwrap();
+        assert_eq!(market.twap_cumulative_yes_price, 5_000 * 300);
+        assert_eq!(market.twap_observation_count, 2);
+
+        // 模拟成交使价格偏斜，下一段时间按新价格累加
+        market.lmsr_q_yes = 500_000;
+        let skewed = market.calculate_yes_price_bps().unwrap() as u128;
+        assert!(skewed > 5_000);
+        market.update_twap(1_600).unwrap();
+
+        // 600 秒窗口 = 两段价格的时间加权平均
+        let (twap, observed) = market.twap_yes_price_bps(600, 1_600).unwrap();
+        assert_eq!(observed, 600);
+        assert_eq!(twap as u128, (5_000 * 300 + skewed * 300) / 600);
+
+        // 未更新时按现价外推
+        let (twap, observed) = market.twap_yes_price_bps(300, 1_900).unwrap();
+        assert_eq!(observed, 300);
+        assert_eq!(twap as u128, skewed);
+
+        // 窗口超出历史或为 0 时拒绝
+        assert!(market.twap_yes_price_bps(10_000, 1_600).is_err());
+        assert!(market.twap_yes_price_bps(0, 1_600).is_err());
+    }
+
+    #[test]
+    fn test_twap_observation_ring_buffer_wraps() {
+        use crate::constants::{TWAP_MIN_OBSERVATION_INTERVAL_SECS, TWAP_OBSERVATION_COUNT};
+
+        let mut market = create_test_market(1_000_000, 0, 0);
+        let mut now = 1_000;
+        market.update_twap(now).unwrap();
+
+        // 间隔不足时不写入新观测
+        market.update_twap(now + 1).unwrap();
+        assert_eq!(market.twap_observation_count, 1);
+
+        for _ in 0..TWAP_OBSERVATION_COUNT * 2 {
+            now += TWAP_MIN_OBSERVATION_INTERVAL_SECS;
+            market.update_twap(now).unwrap();
+        }
+        assert_eq!(market.twap_observation_count as usize, TWAP_OBSERVATION_COUNT);
+
+        // 最旧观测已被覆盖，仅能查询缓冲区覆盖的窗口
+        let max_window = (TWAP_OBSERVATION_COUNT as i64 - 1) * TWAP_MIN_OBSERVATION_INTERVAL_SECS;
+        let (twap, _) = market.twap_yes_price_bps(max_window as u32, now).unwrap();
+        assert_eq!(twap, 5_000);
+        assert!(market.twap_yes_price_bps(max_window as u32 + 1, now).is_err());
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // 11. ✅ v3.24.0: 动态 LP 费率
+    // ═══════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_dynamic_lp_fee_scales_with_imbalance() {
+        let mut market = create_test_market(1_000_000, 0, 0);
+        market.pool_yes_reserve = 1_000_000;
+        market.pool_no_reserve = 1_000_000;
+
+        // 未启用：基础费率
+        assert_eq!(market.dynamic_lp_fee_bps(200, 0, 1, 1_000), 200);
+
+        market.dynamic_fee_enabled = true;
+        market.dynamic_fee_min_bps = 100;
+        market.dynamic_fee_max_bps = 600;
+        market.dynamic_fee_imbalance_bps = 300;
+
+        // 平衡池：无溢价
+        assert_eq!(market.dynamic_lp_fee_bps(200, 0, 1, 1_000), 200);
+
+        // YES 储备 : NO 储备 = 1 : 2（ratio = 200，溢价 300 × 100 / 300 = 100）
+        market.pool_yes_reserve = 500_000;
+        // 买 YES / 卖 NO 加剧不平衡
+        assert_eq!(market.dynamic_lp_fee_bps(200, 0, 1, 1_000), 300);
+        assert_eq!(market.dynamic_lp_fee_bps(200, 1, 0, 1_000), 300);
+        // 买 NO / 卖 YES 使池子回归平衡：基础费率
+        assert_eq!(market.dynamic_lp_fee_bps(200, 0, 0, 1_000), 200);
+        assert_eq!(market.dynamic_lp_fee_bps(200, 1, 1, 1_000), 200);
+
+        // 单边池：溢价满额并受上限约束
+        market.pool_yes_reserve = 0;
+        assert_eq!(market.dynamic_lp_fee_bps(200, 0, 1, 1_000), 500);
+        assert_eq!(market.dynamic_lp_fee_bps(400, 0, 1, 1_000), 600);
+
+        // 下限
+        assert_eq!(market.dynamic_lp_fee_bps(0, 0, 0, 1_000), 100);
+    }
+
+    #[test]
+    fn test_volatility_ewma_and_decay() {
+        use crate::constants::DYNAMIC_FEE_VOLATILITY_HALF_LIFE_SECS;
+
+        let mut market = create_test_market(1_000_000, 0, 0);
+        market.dynamic_fee_enabled = true;
+        market.dynamic_fee_max_bps = 1_000;
+        market.dynamic_fee_volatility_multiplier_bps = 5_000;
+
+        // 价格变动 500 bps → EWMA = 500 × 20% = 100
+        market.update_volatility_ewma(5_000, 5_500, 1_000);
+        assert_eq!(market.volatility_ewma_bps, 100);
+
+        // 再次变动 500 bps → 100 × 80% + 500 × 20% = 180
+        market.update_volatility_ewma(5_500, 5_000, 1_000);
+        assert_eq!(market.volatility_ewma_bps, 180);
+
+        // 波动率溢价 = 180 × 50% = 90
+        assert_eq!(market.dynamic_lp_fee_bps(200, 0, 1, 1_000), 290);
+
+        // 一个半衰期后减半，长时间无成交后归零
+        let later = 1_000 + DYNAMIC_FEE_VOLATILITY_HALF_LIFE_SECS;
+        assert_eq!(market.decayed_volatility_bps(later), 90);
+        assert_eq!(market.dynamic_lp_fee_bps(200, 0, 1, later), 245);
+        assert_eq!(market.decayed_volatility_bps(1_000 + 40 * DYNAMIC_FEE_VOLATILITY_HALF_LIFE_SECS), 0);
+    }
 }
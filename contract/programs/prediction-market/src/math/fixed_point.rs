@@ -562,7 +562,7 @@ pub fn fp_ln(x: FixedPoint) -> Result<FixedPoint> {
 
     // 向下移动到 < 2.0
     while normalized >= TWO {
-        normalized = normalized >> 1;
+        normalized >>= 1;
         exponent += 1;
     }
 
@@ -727,7 +727,7 @@ pub fn fp_exp(x: FixedPoint) -> Result<FixedPoint> {
     if n >= 0 {
         result = result.checked_shl(n as u32).ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
     } else {
-        result = result >> ((-n) as u32);
+        result >>= (-n) as u32;
     }
 
     Ok(result)
@@ -910,7 +910,7 @@ mod tests {
         assert!(result.is_ok(), "Max safe integer multiplication should not overflow");
 
         // 验证结果的正确性
-        let expected = ((1u64 << 32) - 1) as u64 * ((1u64 << 32) - 1) as u64;
+        let expected = ((1u64 << 32) - 1) * ((1u64 << 32) - 1);
         assert_eq!(to_u64(result.unwrap()), expected);
     }
 
@@ -934,11 +934,7 @@ mod tests {
 
         // 3.75 的定点表示
         let expected = from_u64(3) + (from_u64(3) >> 2); // 3 + 0.75
-        let diff = if result > expected {
-            result - expected
-        } else {
-            expected - result
-        };
+        let diff = result.abs_diff(expected);
 
         // 允许最多 1 LSB 的舍入误差
         assert!(diff <= 1, "Precision error too large: diff={}", diff);
@@ -954,11 +950,7 @@ mod tests {
         let result1 = fp_mul(fp_mul(a, b).unwrap(), c).unwrap(); // (7×3)×2
         let result2 = fp_mul(a, fp_mul(b, c).unwrap()).unwrap(); // 7×(3×2)
 
-        let diff = if result1 > result2 {
-            result1 - result2
-        } else {
-            result2 - result1
-        };
+        let diff = result1.abs_diff(result2);
 
         // 由于舍入，结果可能略有不同，但应在可接受范围内
         // 允许相对误差 < 2^-60 (约 10^-18)
@@ -1038,11 +1030,7 @@ mod tests {
 
         // 重新乘以 3，应该接近 1
         let recomputed = fp_mul(result, three).unwrap();
-        let diff = if recomputed > one {
-            recomputed - one
-        } else {
-            one - recomputed
-        };
+        let diff = recomputed.abs_diff(one);
 
         // 允许相对误差 < 2^-60
         assert!(
@@ -1062,11 +1050,7 @@ mod tests {
         let result1 = fp_div(fp_div(a, b).unwrap(), c).unwrap(); // (100/7)/3
         let expected = fp_div(a, fp_mul(b, c).unwrap()).unwrap(); // 100/(7*3)
 
-        let diff = if result1 > expected {
-            result1 - expected
-        } else {
-            expected - result1
-        };
+        let diff = result1.abs_diff(expected);
 
         // 允许一定的累积误差（< 2^-50）
         let max_acceptable_diff = ONE >> 50;
@@ -1095,8 +1079,8 @@ mod tests {
         let result = fp_div(very_large, very_small);
         // 注意：根据实现，这可能成功或失败
         // 如果 very_large 本身不超过 2^64，则 result 应该等于 very_large
-        if result.is_ok() {
-            assert_eq!(to_u64(result.unwrap()), u64::MAX);
+        if let Ok(result) = result {
+            assert_eq!(to_u64(result), u64::MAX);
         }
     }
 
@@ -1110,11 +1094,7 @@ mod tests {
             let quotient = fp_div(a, b).unwrap();
             let recovered = fp_mul(quotient, b).unwrap();
 
-            let diff = if recovered > a {
-                recovered - a
-            } else {
-                a - recovered
-            };
+            let diff = recovered.abs_diff(a);
 
             // 允许最多 1% 相对误差（因为截断）
             let max_error = a / 100;
@@ -1144,7 +1124,7 @@ mod tests {
 
         // exp(1) ≈ e
         let result = fp_exp(ONE).unwrap();
-        let diff = if result > E { result - E } else { E - result };
+        let diff = result.abs_diff(E);
         assert!(diff < (ONE / 100)); // 误差 < 1%
     }
 
@@ -1219,11 +1199,7 @@ mod tests {
             let exp_x = fp_exp(x).unwrap();
             let recovered = fp_ln(exp_x).unwrap();
 
-            let diff = if recovered > x {
-                recovered - x
-            } else {
-                x - recovered
-            };
+            let diff = recovered.abs_diff(x);
 
             // 绝对误差应 < 0.01
             assert!(
@@ -1244,11 +1220,7 @@ mod tests {
         let cost = fp_log_sum_exp(q_yes, q_no).unwrap();
 
         // cost 应约等于 q_yes (因为 q_no 贡献可忽略)
-        let diff = if cost > q_yes {
-            cost - q_yes
-        } else {
-            q_yes - cost
-        };
+        let diff = cost.abs_diff(q_yes);
 
         assert!(
             diff < from_u64(10),
@@ -1287,11 +1259,7 @@ mod proptests {
 
             if let (Ok(ab), Ok(bc)) = (fp_mul(a_fp, b_fp), fp_mul(b_fp, c_fp)) {
                 if let (Ok(result1), Ok(result2)) = (fp_mul(ab, c_fp), fp_mul(a_fp, bc)) {
-                    let diff = if result1 > result2 {
-                        result1 - result2
-                    } else {
-                        result2 - result1
-                    };
+                    let diff = result1.abs_diff(result2);
 
                     // 允许一定的累积误差
                     let max_acceptable = ONE >> 50;
@@ -1308,11 +1276,7 @@ mod proptests {
 
             if let Ok(quotient) = fp_div(a_fp, b_fp) {
                 if let Ok(recovered) = fp_mul(quotient, b_fp) {
-                    let diff = if recovered > a_fp {
-                        recovered - a_fp
-                    } else {
-                        a_fp - recovered
-                    };
+                    let diff = recovered.abs_diff(a_fp);
 
                     // 允许最多 1% 相对误差
                     let max_error = a_fp / 100;
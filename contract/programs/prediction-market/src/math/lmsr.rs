@@ -275,22 +275,12 @@ pub fn lmsr_cost(b: u64, q_yes: i64, q_no: i64) -> Result<u64> {
 
     // 转换为定点数
     let b_fp = from_u64(b);
-    let q_yes_fp = from_u64(q_yes.abs() as u64);
-    let q_no_fp = from_u64(q_no.abs() as u64);
+    let q_yes_fp = from_u64(q_yes.unsigned_abs());
+    let q_no_fp = from_u64(q_no.unsigned_abs());
 
-    // 计算 q_yes / b 和 q_no / b
-    let q_yes_over_b = if q_yes >= 0 {
-        fp_div(q_yes_fp, b_fp)?
-    } else {
-        // 负数：返回负值的绝对值，后续处理
-        fp_div(q_yes_fp, b_fp)?
-    };
-
-    let q_no_over_b = if q_no >= 0 {
-        fp_div(q_no_fp, b_fp)?
-    } else {
-        fp_div(q_no_fp, b_fp)?
-    };
+    // 计算 |q_yes| / b 和 |q_no| / b（负数取绝对值，符号在后续 exp 时处理）
+    let q_yes_over_b = fp_div(q_yes_fp, b_fp)?;
+    let q_no_over_b = fp_div(q_no_fp, b_fp)?;
 
     // 使用 log-sum-exp：ln(exp(a) + exp(b)) = max(a,b) + ln(1 + exp(-|a-b|))
     let log_sum = if q_yes >= 0 && q_no >= 0 {
@@ -473,23 +463,12 @@ pub fn lmsr_marginal_price(b: u64, q_yes: i64, q_no: i64) -> Result<FixedPoint>
 
     // 转换为定点数
     let b_fp = from_u64(b);
-    let q_yes_fp = from_u64(q_yes.abs() as u64);
-    let q_no_fp = from_u64(q_no.abs() as u64);
+    let q_yes_fp = from_u64(q_yes.unsigned_abs());
+    let q_no_fp = from_u64(q_no.unsigned_abs());
 
-    // 计算 q_yes / b 和 q_no / b
-    let q_yes_over_b = if q_yes >= 0 {
-        fp_div(q_yes_fp, b_fp)?
-    } else {
-        // 负数需要特殊处理
-        let val = fp_div(q_yes_fp, b_fp)?;
-        val // 注意：这里应该是负数，但我们先用绝对值，后续 exp 时处理
-    };
-
-    let q_no_over_b = if q_no >= 0 {
-        fp_div(q_no_fp, b_fp)?
-    } else {
-        fp_div(q_no_fp, b_fp)?
-    };
+    // 计算 |q_yes| / b 和 |q_no| / b（负数先用绝对值，符号在后续 exp 时处理）
+    let q_yes_over_b = fp_div(q_yes_fp, b_fp)?;
+    let q_no_over_b = fp_div(q_no_fp, b_fp)?;
 
     // 计算 exp(q_yes/b) 和 exp(q_no/b)
     // 注意符号：exp(-x) = 1/exp(x)
@@ -575,8 +554,8 @@ pub fn lmsr_buy_cost(
             Ok(marginal_price) => {
                 // 成本 = amount × 价格（定点数乘法）
                 let estimated_cost = fp_mul(from_u64(amount), marginal_price)
-                    .and_then(|fp| Ok(to_u64(fp)))
-                    .unwrap_or_else(|_| amount / 2); // 失败时默认 50% 价格
+                    .map(to_u64)
+                    .unwrap_or(amount / 2); // 失败时默认 50% 价格
 
                 estimated_cost.max(1) // 确保至少 1 个最小单位
             }
@@ -652,8 +631,8 @@ pub fn lmsr_sell_payout(
             Ok(marginal_price) => {
                 // 收益 = amount × 价格（定点数乘法）
                 let estimated_payout = fp_mul(from_u64(amount), marginal_price)
-                    .and_then(|fp| Ok(to_u64(fp)))
-                    .unwrap_or_else(|_| amount / 2); // 失败时默认 50% 价格
+                    .map(to_u64)
+                    .unwrap_or(amount / 2); // 失败时默认 50% 价格
 
                 estimated_payout.max(1) // 确保至少 1 个最小单位
             }
@@ -722,7 +701,7 @@ pub fn lmsr_tokens_for_usdc(
         // ✅ v1.2.5: 强制上界不超过 MAX_POSITION（防止后续溢出）
         // 确保 q_yes/q_no + upper <= MAX_POSITION
         let max_safe_increment = (MAX_POSITION as u64)
-            .saturating_sub((if is_yes { q_yes } else { q_no }).abs() as u64);
+            .saturating_sub((if is_yes { q_yes } else { q_no }).unsigned_abs());
         upper.min(max_safe_increment)
     } else {
         // price < 0.01，使用保守上界
@@ -731,7 +710,7 @@ pub fn lmsr_tokens_for_usdc(
 
         // ✅ v1.2.5: 同样限制最大增量
         let max_safe_increment = (MAX_POSITION as u64)
-            .saturating_sub((if is_yes { q_yes } else { q_no }).abs() as u64);
+            .saturating_sub((if is_yes { q_yes } else { q_no }).unsigned_abs());
         upper.min(max_safe_increment)
     };
 
@@ -796,7 +775,7 @@ pub fn lmsr_tokens_to_sell(
 
         // ✅ v1.2.5: 强制上界不超过 MAX_POSITION（防止后续溢出）
         // 卖出时：确保 |q_yes/q_no - upper| <= MAX_POSITION
-        let current_position = (if is_yes { q_yes } else { q_no }).abs() as u64;
+        let current_position = (if is_yes { q_yes } else { q_no }).unsigned_abs();
         let max_safe_decrement = current_position.saturating_add(MAX_POSITION as u64);
         upper.min(max_safe_decrement)
     } else {
@@ -804,7 +783,7 @@ pub fn lmsr_tokens_to_sell(
             .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
         // ✅ v1.2.5: 同样限制最大减量
-        let current_position = (if is_yes { q_yes } else { q_no }).abs() as u64;
+        let current_position = (if is_yes { q_yes } else { q_no }).unsigned_abs();
         let max_safe_decrement = current_position.saturating_add(MAX_POSITION as u64);
         upper.min(max_safe_decrement)
     };
@@ -835,6 +814,265 @@ pub fn lmsr_tokens_to_sell(
     Ok(low)
 }
 
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.16.0: 精确输出（exact-output）反推
+// ═══════════════════════════════════════════════════════════════
+//
+// 与 lmsr_tokens_for_usdc / lmsr_tokens_to_sell 不同，这里不使用收敛阈值，
+// 结果精确到最小单位，供 swap_exact_output 使用。
+
+/// 反推含费总额：返回 gross 使得 gross - ⌊gross × fee_bps / 10000⌋ ≥ net
+///
+/// gross = ⌈net × 10000 / (10000 - fee_bps)⌉，平台费与 LP 费分别向下取整，
+/// 因此实际净额只会 ≥ net（差额 ≤ 1-2 个最小单位）。
+pub fn gross_up_for_fee(net: u64, fee_bps: u64) -> Result<u64> {
+    let divisor = crate::constants::BASIS_POINTS_DIVISOR;
+    require!(fee_bps < divisor, crate::errors::PredictionMarketError::InvalidParameter);
+
+    let keep_bps = (divisor - fee_bps) as u128;
+    let gross = (net as u128)
+        .checked_mul(divisor as u128)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+        .checked_add(keep_bps - 1)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+        / keep_bps;
+
+    u64::try_from(gross).map_err(|_| crate::errors::PredictionMarketError::MathOverflow.into())
+}
+
+/// 精确二分：卖出收益 ≥ `payout` 所需的最少代币数量
+///
+/// # 参数
+/// * `max_tokens` - 卖出数量上限（用户的 max_tokens_in）
+///
+/// # 错误
+/// * `SlippageExceeded` - 卖出 `max_tokens` 仍不足 `payout`
+///
+/// # Gas 限制
+/// - 迭代次数 ≤ log2(max_tokens)（u64 最多 64 次）
+pub fn lmsr_min_tokens_for_payout(
+    b: u64,
+    q_yes: i64,
+    q_no: i64,
+    payout: u64,
+    is_yes: bool,
+    max_tokens: u64,
+) -> Result<u64> {
+    require!(payout > 0 && max_tokens > 0, crate::errors::PredictionMarketError::InvalidAmount);
+    require!(
+        lmsr_sell_payout(b, q_yes, q_no, max_tokens, is_yes)? >= payout,
+        crate::errors::PredictionMarketError::SlippageExceeded
+    );
+
+    let mut low: u64 = 1;
+    let mut high: u64 = max_tokens;
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if lmsr_sell_payout(b, q_yes, q_no, mid, is_yes)? >= payout {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    Ok(low)
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ✅ v3.3.0: 多结果 LMSR（分类市场，N = 2..=16）
+// ═══════════════════════════════════════════════════════════════
+//
+// 成本函数推广到 N 维：
+//
+// ```text
+// C(q) = b × ln(Σ_i exp(q_i/b))
+// p_i  = exp(q_i/b) / Σ_j exp(q_j/b)
+// ```
+//
+// 与二元版本不同，这里统一使用「平移」形式的 log-sum-exp：
+//
+// ```text
+// m    = max_i q_i
+// C(q) = m + b × ln(Σ_i exp(-(m - q_i)/b))
+// ```
+//
+// 每一项的指数都 ≤ 0，Σ ∈ [1, N]，因此 ln(Σ) ≥ 0，
+// 负持仓只会体现在 m 的符号上，成本直接以 i64 返回，无需截断为 0。
+
+/// 多结果 LMSR 的指数截断阈值（exp(-20) ≈ 2e-9，可忽略）
+const MULTI_EXP_CUTOFF: u64 = 20;
+
+/// 校验多结果 LMSR 的输入，返回 (max_q, Σ exp(-(max_q - q_i)/b), 各项)
+fn lmsr_shifted_terms(b: u64, q: &[i64]) -> Result<(i64, FixedPoint, Vec<FixedPoint>)> {
+    require!(b > 0 && b <= MAX_B_PARAM, crate::errors::PredictionMarketError::InvalidParameter);
+    require!(
+        q.len() >= 2 && q.len() <= crate::constants::MAX_CATEGORICAL_OUTCOMES,
+        crate::errors::PredictionMarketError::InvalidOutcomeCount
+    );
+    for q_i in q {
+        require!(q_i.abs() <= MAX_POSITION, crate::errors::PredictionMarketError::ValueTooLarge);
+    }
+
+    let b_fp = from_u64(b);
+    let max_q = *q.iter().max().ok_or(crate::errors::PredictionMarketError::InvalidOutcomeCount)?;
+
+    let mut terms = Vec::with_capacity(q.len());
+    let mut sum: FixedPoint = 0;
+    for q_i in q {
+        // d_i = m - q_i ≥ 0（|q_i| ≤ MAX_POSITION，不会溢出 i64）
+        let d = (max_q - q_i) as u64;
+        let x = fp_div(from_u64(d), b_fp)?;
+        let term = if x < from_u64(MULTI_EXP_CUTOFF) {
+            fp_div(constants::ONE, fp_exp(x)?)?
+        } else {
+            0
+        };
+        sum = sum.checked_add(term).ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+        terms.push(term);
+    }
+
+    Ok((max_q, sum, terms))
+}
+
+/// 多结果 LMSR 成本函数：C(q) = b × ln(Σ exp(q_i/b))
+///
+/// # 参数
+/// * `b` - 流动性参数（USDC 最小单位）
+/// * `q` - 各结果的净持仓（长度 2..=MAX_CATEGORICAL_OUTCOMES）
+///
+/// # 返回
+/// * `Result<i64>` - 成本（可为负，调用方只关心差值）
+///
+/// # 示例
+/// ```text
+/// lmsr_cost_multi(100_000_000, &[0, 0, 0]) = 100 USDC × ln(3) ≈ 109.86 USDC
+/// ```
+pub fn lmsr_cost_multi(b: u64, q: &[i64]) -> Result<i64> {
+    let (max_q, sum, _) = lmsr_shifted_terms(b, q)?;
+
+    // Σ ≥ 1（最大项恒为 exp(0) = 1），ln(Σ) ≥ 0
+    let ln_sum = fp_ln(sum)?;
+    let scaled = to_u64(fp_mul(from_u64(b), ln_sum)?);
+
+    max_q
+        .checked_add(scaled as i64)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow.into())
+}
+
+/// 多结果 LMSR 边际价格：p_i = exp(q_i/b) / Σ exp(q_j/b)
+///
+/// # 返回
+/// * `Result<Vec<FixedPoint>>` - 各结果价格（定点数，Σ ≈ 1）
+pub fn lmsr_prices_multi(b: u64, q: &[i64]) -> Result<Vec<FixedPoint>> {
+    let (_, sum, terms) = lmsr_shifted_terms(b, q)?;
+
+    terms.into_iter().map(|term| fp_div(term, sum)).collect()
+}
+
+/// 多结果 LMSR 买入成本：C(q + δ·e_i) - C(q)
+///
+/// # 参数
+/// * `outcome` - 买入的结果索引
+/// * `amount` - 买入代币数量（最小单位）
+pub fn lmsr_buy_cost_multi(b: u64, q: &[i64], outcome: usize, amount: u64) -> Result<u64> {
+    require!(outcome < q.len(), crate::errors::PredictionMarketError::InvalidOutcomeIndex);
+
+    let cost_before = lmsr_cost_multi(b, q)?;
+
+    let mut q_after = q.to_vec();
+    q_after[outcome] = q_after[outcome]
+        .checked_add(amount as i64)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+    let cost_after = lmsr_cost_multi(b, &q_after)?;
+
+    let diff = cost_after
+        .checked_sub(cost_before)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+
+    // 成本函数单调递增，diff < 0 只可能来自舍入，按最小成本 1 处理
+    Ok((diff.max(0) as u64).max(1))
+}
+
+/// 多结果 LMSR 卖出收益：C(q) - C(q - δ·e_i)
+///
+/// # 参数
+/// * `outcome` - 卖出的结果索引
+/// * `amount` - 卖出代币数量（最小单位）
+pub fn lmsr_sell_payout_multi(b: u64, q: &[i64], outcome: usize, amount: u64) -> Result<u64> {
+    require!(outcome < q.len(), crate::errors::PredictionMarketError::InvalidOutcomeIndex);
+
+    let cost_before = lmsr_cost_multi(b, q)?;
+
+    let mut q_after = q.to_vec();
+    q_after[outcome] = q_after[outcome]
+        .checked_sub(amount as i64)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+    let cost_after = lmsr_cost_multi(b, &q_after)?;
+
+    let diff = cost_before
+        .checked_sub(cost_after)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+
+    // 与买入相反：卖出收益向下取整，舍入误差由池子一方保留
+    Ok(diff.max(0) as u64)
+}
+
+/// 使用二分法计算给定 USDC 能买入的结果代币数量（多结果版本）
+///
+/// 返回满足 `lmsr_buy_cost_multi(tokens) <= usdc_amount` 的最大代币数量
+/// （在 CONVERGENCE_THRESHOLD 精度内），确保池子不会少收 USDC。
+///
+/// # Gas 限制
+/// - 最大迭代次数：50
+/// - 收敛阈值：100_000 最小单位
+pub fn lmsr_tokens_for_usdc_multi(b: u64, q: &[i64], outcome: usize, usdc_amount: u64) -> Result<u64> {
+    require!(outcome < q.len(), crate::errors::PredictionMarketError::InvalidOutcomeIndex);
+
+    let prices = lmsr_prices_multi(b, q)?;
+    let price_fp = prices[outcome];
+    let min_reasonable_price = fp_div(from_u64(1), from_u64(crate::constants::MIN_COST_DIVISOR))?; // 0.01
+
+    // 与二元版本一致：upper ≈ usdc / price × 1.5，并限制在 MAX_POSITION 内
+    let upper = if price_fp >= min_reasonable_price {
+        let tokens_estimate = fp_div(from_u64(usdc_amount), price_fp)?;
+        to_u64(fp_mul(tokens_estimate, from_u64(15))? / 10)
+    } else {
+        usdc_amount
+            .checked_mul(150)
+            .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+    };
+    let max_safe_increment = (MAX_POSITION as u64).saturating_sub(q[outcome].unsigned_abs());
+
+    let mut low: u64 = 0;
+    let mut high: u64 = upper.min(max_safe_increment);
+    let mut best: u64 = 0;
+    let mut iterations = 0u8;
+
+    while low <= high && iterations < MAX_ITERATIONS {
+        iterations = iterations.checked_add(1).ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+
+        let mid = low + (high - low) / 2;
+        if mid == 0 {
+            low = 1;
+            continue;
+        }
+
+        let cost = lmsr_buy_cost_multi(b, q, outcome, mid)?;
+        if cost > usdc_amount {
+            high = mid - 1;
+        } else {
+            best = mid;
+            if usdc_amount - cost < CONVERGENCE_THRESHOLD {
+                break;
+            }
+            low = mid.checked_add(1).ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+        }
+    }
+
+    Ok(best)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -847,7 +1085,7 @@ mod tests {
 
         // ln(2) ≈ 0.693，所以 cost ≈ 0.693 USDC
         let expected = (b as f64 * 0.693) as u64;
-        let diff = if cost > expected { cost - expected } else { expected - cost };
+        let diff = cost.abs_diff(expected);
         assert!(diff < b / 10); // 误差 < 10%
     }
 
@@ -858,7 +1096,7 @@ mod tests {
         let price = lmsr_marginal_price(b, 0, 0).unwrap();
 
         let price_pct = to_u64(fp_mul(price, from_u64(crate::constants::MIN_COST_DIVISOR)).unwrap());
-        assert!(price_pct >= 49 && price_pct <= 51); // 49% - 51%
+        assert!((49..=51).contains(&price_pct)); // 49% - 51%
     }
 
     #[test]
@@ -889,11 +1127,8 @@ mod tests {
 
         let cost = lmsr_cost(b, q_yes, q_no);
         assert!(cost.is_ok(), "lmsr_cost 应该不崩溃");
-
-        let cost_val = cost.unwrap();
-        // 由于返回 u64，负成本会被截断为 0
+        // 由于返回 u64，负成本会被截断为 0（结果天然非负）
         // 这是当前实现的限制，未来可改进为 i64
-        assert!(cost_val >= 0, "成本应为非负（当前实现限制）");
     }
 
     #[test]
@@ -1015,10 +1250,7 @@ mod tests {
 
         let cost = lmsr_cost(b, q_yes, q_no);
         assert!(cost.is_ok(), "极端双负仓位 lmsr_cost 应不崩溃");
-
-        let cost_val = cost.unwrap();
-        // 由于 u64 限制，负成本会被截断为 0
-        assert!(cost_val >= 0, "成本应为非负（当前实现限制）");
+        // 由于 u64 限制，负成本会被截断为 0（结果天然非负）
     }
 
     #[test]
@@ -1046,11 +1278,7 @@ mod tests {
         let sell_payout_val = sell_payout.unwrap();
 
         // 买入成本和卖出收益应接近（允许一定滑点）
-        let diff = if buy_cost_val > sell_payout_val {
-            buy_cost_val - sell_payout_val
-        } else {
-            sell_payout_val - buy_cost_val
-        };
+        let diff = buy_cost_val.abs_diff(sell_payout_val);
 
         // 允许最多 10% 的差异（考虑极端市场状态）
         assert!(
@@ -1121,7 +1349,7 @@ mod tests {
 
         // 最终仓位应接近 +50 USDC
         assert!(
-            q_yes >= 45_000_000 && q_yes <= 55_000_000,
+            (45_000_000..=55_000_000).contains(&q_yes),
             "最终仓位应接近 +50 USDC: actual={}",
             q_yes
         );
@@ -1155,4 +1383,112 @@ mod tests {
             expected_initial_cost
         );
     }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.3.0: 多结果 LMSR 测试（分类市场）
+    // ═══════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_lmsr_cost_multi_neutral() {
+        // 🎯 中立 3 结果市场：C(0,0,0) = b × ln(3)
+        let b = 100_000_000; // 100 USDC
+        let cost = lmsr_cost_multi(b, &[0, 0, 0]).unwrap();
+
+        // fp_ln 精度约 1e-5（相对误差），容差取 b / 10000
+        let expected = (b as f64 * 3f64.ln()) as i64;
+        assert!((cost - expected).abs() < (b / 10_000) as i64, "cost={}, expected≈{}", cost, expected);
+    }
+
+    #[test]
+    fn test_lmsr_cost_multi_matches_binary() {
+        // 🎯 N=2 时应与二元 lmsr_cost 一致（正持仓区间）
+        let b = 100_000_000;
+        let cases = [(0, 0), (10_000_000, 0), (25_000_000, 5_000_000)];
+
+        for (q_yes, q_no) in cases {
+            let binary = lmsr_cost(b, q_yes, q_no).unwrap() as i64;
+            let multi = lmsr_cost_multi(b, &[q_yes, q_no]).unwrap();
+            assert!(
+                (binary - multi).abs() < 1_000,
+                "q=({}, {}): binary={}, multi={}",
+                q_yes,
+                q_no,
+                binary,
+                multi
+            );
+        }
+    }
+
+    #[test]
+    fn test_lmsr_prices_multi_sum_to_one() {
+        // 🎯 价格之和应为 1，且持仓最大的结果价格最高
+        let b = 100_000_000;
+        let q = [30_000_000, -10_000_000, 5_000_000, 0];
+        let prices = lmsr_prices_multi(b, &q).unwrap();
+
+        let sum: u128 = prices.iter().sum();
+        let diff = sum.abs_diff(constants::ONE);
+        assert!(diff < constants::ONE / 1_000_000, "Σp 应≈1, diff={}", diff);
+        assert!(prices[0] > prices[2] && prices[2] > prices[3] && prices[3] > prices[1]);
+    }
+
+    #[test]
+    fn test_lmsr_multi_negative_positions() {
+        // 🎯 全负持仓不再被截断为 0：成本差值仍然正确
+        let b = 100_000_000;
+        let q = [-400_000_000, -200_000_000, -300_000_000];
+
+        let cost = lmsr_cost_multi(b, &q).unwrap();
+        assert!(cost < 0, "全负持仓成本应为负: {}", cost);
+
+        let buy = lmsr_buy_cost_multi(b, &q, 0, 1_000_000).unwrap();
+        let sell = lmsr_sell_payout_multi(b, &[-399_000_000, -200_000_000, -300_000_000], 0, 1_000_000).unwrap();
+        assert!(buy > 0 && buy < 1_000_000);
+        assert!(buy.abs_diff(sell) <= 1, "买卖往返应一致: buy={}, sell={}", buy, sell);
+    }
+
+    #[test]
+    fn test_lmsr_tokens_for_usdc_multi_never_overcharges_pool() {
+        // 🎯 二分结果的实际成本不超过支付的 USDC
+        let b = 100_000_000;
+        let q = [0, 10_000_000, -5_000_000, 0, 0];
+        let usdc = 7_000_000;
+
+        for outcome in 0..q.len() {
+            let tokens = lmsr_tokens_for_usdc_multi(b, &q, outcome, usdc).unwrap();
+            assert!(tokens > usdc, "价格 < 1 时代币数量应大于支付金额");
+            let cost = lmsr_buy_cost_multi(b, &q, outcome, tokens).unwrap();
+            assert!(cost <= usdc, "outcome={}: cost={} > usdc={}", outcome, cost, usdc);
+        }
+    }
+
+    #[test]
+    fn test_exact_output_inversion() {
+        // 🎯 含费反推：扣费后净额不少于目标
+        let gross = gross_up_for_fee(1_000_000, 300).unwrap();
+        assert_eq!(gross, 1_030_928);
+        let fees = gross * 200 / 10_000 + gross * 100 / 10_000;
+        assert!(gross - fees >= 1_000_000);
+        assert!(gross_up_for_fee(1, 10_000).is_err());
+
+        // 🎯 卖出反推：恰好是满足收益的最少代币
+        let b = 100_000_000;
+        let (q_yes, q_no) = (50_000_000, 0);
+        let payout = 5_000_000;
+        let tokens = lmsr_min_tokens_for_payout(b, q_yes, q_no, payout, true, 100_000_000).unwrap();
+        assert!(lmsr_sell_payout(b, q_yes, q_no, tokens, true).unwrap() >= payout);
+        assert!(lmsr_sell_payout(b, q_yes, q_no, tokens - 1, true).unwrap() < payout);
+
+        // 上限不足时拒绝
+        assert!(lmsr_min_tokens_for_payout(b, q_yes, q_no, payout, true, tokens - 1).is_err());
+    }
+
+    #[test]
+    fn test_lmsr_multi_rejects_invalid_inputs() {
+        let b = 100_000_000;
+        assert!(lmsr_cost_multi(b, &[0]).is_err(), "单结果应拒绝");
+        assert!(lmsr_cost_multi(b, &[0; 17]).is_err(), "超过 16 个结果应拒绝");
+        assert!(lmsr_buy_cost_multi(b, &[0, 0, 0], 3, 1).is_err(), "索引越界应拒绝");
+        assert!(lmsr_cost_multi(0, &[0, 0, 0]).is_err(), "b=0 应拒绝");
+    }
 }
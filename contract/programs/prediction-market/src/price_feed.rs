@@ -0,0 +1,232 @@
+//! ✅ v3.8.0: Pyth 风格价格源解析
+//!
+//! 读取 Pyth pull oracle 的 `PriceUpdateV2` 账户（price / conf / exponent / publish_time），
+//! 不引入 pyth-solana-receiver-sdk 依赖，仅按固定布局解析所需字段。
+//!
+//! **账户布局**（Borsh）：
+//! ```text
+//! [0..8)    discriminator = sha256("account:PriceUpdateV2")[..8]
+//! [8..40)   write_authority: Pubkey
+//! [40..)    verification_level: enum { Partial { num_signatures: u8 } = 0, Full = 1 }
+//!           price_message: { feed_id: [u8; 32], price: i64, conf: u64, exponent: i32,
+//!                            publish_time: i64, prev_publish_time: i64, ema_price: i64, ema_conf: u64 }
+//!           posted_slot: u64
+//! ```
+//!
+//! ✅ v3.8.1: 解析 feed_id 以便与市场记录的 feed_id 比对；结算价格须落在
+//! [resolve_after, resolve_after + max_staleness_secs] 窗口内（`check_window`），
+//! 防止调用者等待并挑选窗口之后更有利的价格。
+//!
+//! 本地测试（单元测试 / solana-program-test）可通过 `encode_price_update_v2` 构造 mock 账户数据，
+//! 并以 `PYTH_RECEIVER_PROGRAM_ID` 作为 owner 注入。
+
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+
+/// Pyth Solana Receiver 程序 ID（PriceUpdateV2 账户的 owner）
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// PriceUpdateV2 账户 discriminator
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// verification_level = Full 的枚举标签
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+
+/// 价格数据（来自 PriceUpdateV2.price_message）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceFeedData {
+    /// ✅ v3.8.1: 价格源 feed_id
+    pub feed_id: [u8; 32],
+    /// 价格（实际价格 = price × 10^expo）
+    pub price: i64,
+    /// 置信区间（与 price 同精度）
+    pub conf: u64,
+    /// 精度指数
+    pub expo: i32,
+    /// 发布时间（Unix 时间戳）
+    pub publish_time: i64,
+}
+
+impl PriceFeedData {
+    /// 从价格源账户读取（校验 owner）
+    pub fn try_from_account(info: &AccountInfo) -> Result<Self> {
+        require!(
+            info.owner == &PYTH_RECEIVER_PROGRAM_ID,
+            PredictionMarketError::InvalidPriceFeed
+        );
+        let data = info.try_borrow_data()?;
+        Self::parse(&data)
+    }
+
+    /// 解析 PriceUpdateV2 账户数据（仅接受完全验证的价格）
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() > 40 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+            PredictionMarketError::InvalidPriceFeed
+        );
+
+        // Partial 验证的价格不可用于结算
+        require!(
+            data[40] == VERIFICATION_LEVEL_FULL,
+            PredictionMarketError::InvalidPriceFeed
+        );
+
+        // price_message 起始：tag(1) 之后为 feed_id(32)
+        let read = |start: usize, len: usize| -> Result<&[u8]> {
+            data.get(start..start + len)
+                .ok_or_else(|| PredictionMarketError::InvalidPriceFeed.into())
+        };
+        let feed_id: [u8; 32] = read(41, 32)?.try_into().unwrap();
+        let offset = 41 + 32;
+
+        let price = i64::from_le_bytes(read(offset, 8)?.try_into().unwrap());
+        let conf = u64::from_le_bytes(read(offset + 8, 8)?.try_into().unwrap());
+        let expo = i32::from_le_bytes(read(offset + 16, 4)?.try_into().unwrap());
+        let publish_time = i64::from_le_bytes(read(offset + 20, 8)?.try_into().unwrap());
+
+        Ok(Self { feed_id, price, conf, expo, publish_time })
+    }
+
+    /// ✅ v3.8.1: 结算窗口检查：价格必须在 [resolve_after, min(resolve_after + window_secs, now)] 之间发布
+    pub fn check_window(&self, resolve_after: i64, window_secs: u32, now: i64) -> Result<()> {
+        require!(
+            self.publish_time >= resolve_after,
+            PredictionMarketError::PriceFeedTooEarly
+        );
+        let window_end = resolve_after
+            .checked_add(window_secs as i64)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(
+            self.publish_time <= window_end && self.publish_time <= now,
+            PredictionMarketError::PriceFeedOutsideWindow
+        );
+        Ok(())
+    }
+
+    /// 时效检查：价格必须在 [now - max_staleness_secs, now] 之间发布
+    pub fn check_staleness(&self, now: i64, max_staleness_secs: u32) -> Result<()> {
+        let age = now
+            .checked_sub(self.publish_time)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(
+            age >= 0 && age <= max_staleness_secs as i64,
+            PredictionMarketError::PriceFeedStale
+        );
+        Ok(())
+    }
+
+    /// 置信区间检查：conf / price ≤ max_conf_bps / 10000
+    pub fn check_confidence(&self, max_conf_bps: u16) -> Result<()> {
+        require!(self.price > 0, PredictionMarketError::InvalidPriceFeed);
+
+        let lhs = (self.conf as u128)
+            .checked_mul(crate::constants::BASIS_POINTS_DIVISOR as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        let rhs = (self.price as u128)
+            .checked_mul(max_conf_bps as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(lhs <= rhs, PredictionMarketError::PriceConfidenceTooWide);
+        Ok(())
+    }
+
+    /// 价格是否严格高于阈值（threshold × 10^threshold_expo），统一到较小的精度指数后比较
+    pub fn is_above(&self, threshold: i64, threshold_expo: i32) -> Result<bool> {
+        let common = self.expo.min(threshold_expo);
+        let scale = |value: i64, expo: i32| -> Result<i128> {
+            10i128
+                .checked_pow((expo - common) as u32)
+                .and_then(|factor| (value as i128).checked_mul(factor))
+                .ok_or_else(|| PredictionMarketError::MathOverflow.into())
+        };
+
+        Ok(scale(self.price, self.expo)? > scale(threshold, threshold_expo)?)
+    }
+}
+
+/// 构造 PriceUpdateV2 账户数据，供单元测试与 solana-program-test 注入 mock 价格源（链上指令不调用）
+pub fn encode_price_update_v2(feed_id: [u8; 32], price: i64, conf: u64, expo: i32, publish_time: i64) -> Vec<u8> {
+    let mut data = Vec::with_capacity(134);
+    data.extend_from_slice(&PRICE_UPDATE_V2_DISCRIMINATOR);
+    data.extend_from_slice(&[0u8; 32]); // write_authority
+    data.push(VERIFICATION_LEVEL_FULL);
+    data.extend_from_slice(&feed_id);
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&conf.to_le_bytes());
+    data.extend_from_slice(&expo.to_le_bytes());
+    data.extend_from_slice(&publish_time.to_le_bytes());
+    data.extend_from_slice(&publish_time.to_le_bytes()); // prev_publish_time
+    data.extend_from_slice(&price.to_le_bytes()); // ema_price
+    data.extend_from_slice(&conf.to_le_bytes()); // ema_conf
+    data.extend_from_slice(&0u64.to_le_bytes()); // posted_slot
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mock_price_update() {
+        // SOL = $142.50（expo = -8）
+        let data = encode_price_update_v2([7u8; 32], 14_250_000_000, 5_000_000, -8, 1_700_000_000);
+        let feed = PriceFeedData::parse(&data).unwrap();
+        assert_eq!(feed.feed_id, [7u8; 32]);
+        assert_eq!(feed.price, 14_250_000_000);
+        assert_eq!(feed.conf, 5_000_000);
+        assert_eq!(feed.expo, -8);
+        assert_eq!(feed.publish_time, 1_700_000_000);
+
+        // 错误 discriminator
+        let mut bad = data.clone();
+        bad[0] ^= 1;
+        assert!(PriceFeedData::parse(&bad).is_err());
+
+        // Partial 验证
+        let mut partial = data.clone();
+        partial[40] = 0;
+        assert!(PriceFeedData::parse(&partial).is_err());
+
+        // 截断
+        assert!(PriceFeedData::parse(&data[..80]).is_err());
+    }
+
+    #[test]
+    fn test_staleness_and_confidence() {
+        let feed = PriceFeedData { feed_id: [0u8; 32], price: 10_000, conf: 50, expo: -2, publish_time: 1_000 };
+
+        assert!(feed.check_staleness(1_060, 60).is_ok());
+        assert!(feed.check_staleness(1_061, 60).is_err());
+        // 未来时间戳
+        assert!(feed.check_staleness(999, 60).is_err());
+
+        // conf / price = 0.5%
+        assert!(feed.check_confidence(50).is_ok());
+        assert!(feed.check_confidence(49).is_err());
+    }
+
+    #[test]
+    fn test_is_above_threshold_across_exponents() {
+        // $142.50 (expo -8)
+        let feed = PriceFeedData { feed_id: [0u8; 32], price: 14_250_000_000, conf: 0, expo: -8, publish_time: 0 };
+
+        assert!(feed.is_above(140, 0).unwrap());
+        assert!(!feed.is_above(150, 0).unwrap());
+        // 恰好等于阈值不算"高于"
+        assert!(!feed.is_above(14_250, -2).unwrap());
+        assert!(feed.is_above(14_249, -2).unwrap());
+    }
+
+    #[test]
+    fn test_publish_time_window() {
+        let at = |publish_time| PriceFeedData { feed_id: [0u8; 32], price: 1, conf: 0, expo: 0, publish_time };
+
+        // 窗口 [1_000, 1_060]
+        assert!(at(1_000).check_window(1_000, 60, 5_000).is_ok());
+        assert!(at(1_060).check_window(1_000, 60, 5_000).is_ok());
+        assert!(at(999).check_window(1_000, 60, 5_000).is_err());
+        // 窗口结束后发布的价格不可用于结算（防止挑选更晚的价格）
+        assert!(at(1_061).check_window(1_000, 60, 5_000).is_err());
+        // 未来时间戳
+        assert!(at(1_030).check_window(1_000, 60, 1_020).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! ✅ v3.3.0: 分类市场（N 结果）状态
+//!
+//! 二元 `Market` 固定为 YES/NO 两个结果。选举、赛事等问题通常有 3-16 个互斥结果，
+//! 此前只能拆成多个互不相通的二元市场，流动性无法共享。
+//!
+//! 分类市场采用 **LMSR 做市商直接铸造/销毁** 的模型（而非二元市场的双账本 + LP 池）：
+//! - 买入：用户支付 USDC，做市商按 C(q + δ·e_i) - C(q) 铸造结果代币
+//! - 卖出：用户销毁结果代币，做市商按 C(q) - C(q - δ·e_i) 支付 USDC
+//! - 完整集合：1 USDC ↔ 每个结果各 1 个代币（与二元 mint_complete_set 对齐）
+//! - 创建者注入 b × ln(N) 的补贴，覆盖 LMSR 的最大理论亏损
+//!
+//! **偿付能力不变量**：
+//! `pool_collateral_reserve ≥ subsidy + C(q) - C(0) ≥ max_i q_i`，
+//! 因此任意赔付向量下 `Σ p_i × supply_i ≤ total_collateral_locked + pool_collateral_reserve`。
+
+use anchor_lang::prelude::*;
+use crate::constants::{CATEGORICAL_MARKET, CATEGORICAL_SPLIT_OUTCOME, MAX_CATEGORICAL_OUTCOMES, MIN_CATEGORICAL_OUTCOMES};
+use crate::errors::PredictionMarketError;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct CategoricalMarket {
+    pub creator: Pubkey,
+
+    /// 创建者自选的市场编号（PDA 种子之一，允许同一创建者创建多个市场）
+    pub market_id: u64,
+
+    /// 结果数量（3-16）
+    pub outcome_count: u8,
+
+    /// 各结果代币 mint（仅前 outcome_count 项有效，其余为默认值）
+    /// mint authority 必须是本市场 PDA
+    pub outcome_mints: [Pubkey; MAX_CATEGORICAL_OUTCOMES],
+
+    // ═══════════════════════════════════════════════════════════════
+    // Settlement Ledger（结算账本）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 完整集合的 1:1 抵押品锁定量
+    pub total_collateral_locked: u64,
+
+    /// 当前流通的完整集合数量
+    pub total_sets_minted: u64,
+
+    // ═══════════════════════════════════════════════════════════════
+    // LMSR 做市商账本
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 做市商持有的 USDC（补贴 + 净交易收入 + LP 费用）
+    pub pool_collateral_reserve: u64,
+
+    /// 创建者注入的补贴（b × ln(N)，向上取整）
+    pub subsidy: u64,
+
+    /// 流动性参数
+    pub lmsr_b: u64,
+
+    /// 各结果的做市商净卖出量（仅前 outcome_count 项有效）
+    pub lmsr_q: [i64; MAX_CATEGORICAL_OUTCOMES],
+
+    // ═══════════════════════════════════════════════════════════════
+    // 市场状态
+    // ═══════════════════════════════════════════════════════════════
+
+    pub start_slot: Option<u64>,
+    pub ending_slot: Option<u64>,
+
+    pub is_completed: bool,
+
+    /// 各结果赔付比例（基点，总和 10000，结算后有效）
+    pub payout_bps: [u16; MAX_CATEGORICAL_OUTCOMES],
+
+    /// 获胜结果索引（CATEGORICAL_SPLIT_OUTCOME = 按权重拆分）
+    pub winning_outcome: u8,
+
+    /// 重入保护标志
+    pub swap_in_progress: bool,
+    pub claim_in_progress: bool,
+
+    /// 市场级暂停
+    pub market_paused: bool,
+
+    /// 市场显示名称（最大64字符）
+    #[max_len(64)]
+    pub display_name: String,
+
+    /// 市场创建时间戳（Unix时间戳，秒）
+    pub created_at: i64,
+
+    /// 市场 PDA bump（用于 mint/burn 签名）
+    pub bump: u8,
+}
+
+impl CategoricalMarket {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = CATEGORICAL_MARKET;
+
+    /// display_name 的最大长度(字符数)
+    pub const MAX_DISPLAY_NAME_LEN: usize = 64;
+
+    /// 有效结果数量
+    pub fn outcomes(&self) -> usize {
+        self.outcome_count as usize
+    }
+
+    /// 有效的 LMSR 持仓切片
+    pub fn active_q(&self) -> &[i64] {
+        &self.lmsr_q[..self.outcomes()]
+    }
+
+    /// 有效的结果 mint 切片
+    pub fn active_mints(&self) -> &[Pubkey] {
+        &self.outcome_mints[..self.outcomes()]
+    }
+
+    /// 校验结果索引并返回 usize
+    pub fn outcome_index(&self, outcome_index: u8) -> Result<usize> {
+        let index = outcome_index as usize;
+        require!(index < self.outcomes(), PredictionMarketError::InvalidOutcomeIndex);
+        Ok(index)
+    }
+
+    /// 当前各结果价格（基点，0-10000）
+    pub fn prices_bps(&self) -> Result<Vec<u16>> {
+        let prices = crate::math::lmsr::lmsr_prices_multi(self.lmsr_b, self.active_q())?;
+        let one = crate::math::fixed_point::constants::ONE;
+
+        prices
+            .into_iter()
+            .map(|p| {
+                let bps = p
+                    .checked_mul(crate::constants::BASIS_POINTS_DIVISOR as u128)
+                    .ok_or(PredictionMarketError::MathOverflow)?
+                    / one;
+                Ok(bps.min(crate::constants::BASIS_POINTS_DIVISOR as u128) as u16)
+            })
+            .collect()
+    }
+
+    /// 校验交易时间窗口（与二元 Market::swap 一致）
+    pub fn check_trading_window(&self, current_slot: u64) -> Result<()> {
+        if let Some(start_slot) = self.start_slot {
+            require!(current_slot >= start_slot, PredictionMarketError::MarketNotStarted);
+        }
+        if let Some(ending_slot) = self.ending_slot {
+            require!(current_slot < ending_slot, PredictionMarketError::MarketEnded);
+        }
+        Ok(())
+    }
+
+    /// 校验赔付向量并返回 winning_outcome
+    ///
+    /// - 长度必须等于结果数量
+    /// - 总和必须为 10000 bps
+    /// - 单一结果 10000 bps → 返回该索引；否则返回 CATEGORICAL_SPLIT_OUTCOME
+    pub fn validate_payout_vector(outcome_count: usize, payout_bps: &[u16]) -> Result<u8> {
+        require!(payout_bps.len() == outcome_count, PredictionMarketError::InvalidPayoutVector);
+
+        let total: u64 = payout_bps.iter().map(|p| *p as u64).sum();
+        require!(
+            total == crate::constants::BASIS_POINTS_DIVISOR,
+            PredictionMarketError::InvalidPayoutVector
+        );
+
+        let winner = payout_bps
+            .iter()
+            .position(|p| *p as u64 == crate::constants::BASIS_POINTS_DIVISOR)
+            .map(|i| i as u8)
+            .unwrap_or(CATEGORICAL_SPLIT_OUTCOME);
+
+        Ok(winner)
+    }
+
+    /// 校验结果数量是否在 [MIN, MAX] 范围内
+    pub fn validate_outcome_count(outcome_count: u8) -> Result<()> {
+        let n = outcome_count as usize;
+        require!(
+            (MIN_CATEGORICAL_OUTCOMES..=MAX_CATEGORICAL_OUTCOMES).contains(&n),
+            PredictionMarketError::InvalidOutcomeCount
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateCategoricalMarketParams {
+    /// 创建者自选的市场编号（PDA 种子）
+    pub market_id: u64,
+
+    /// 结果数量（3-16），结果 mint 通过 remaining_accounts 按索引顺序传入
+    pub outcome_count: u8,
+
+    /// 市场显示名称（最大64字符）
+    pub display_name: String,
+
+    pub start_slot: Option<u64>,
+    pub ending_slot: Option<u64>,
+
+    /// LMSR 流动性参数，0 表示使用全局配置 initial_real_token_reserves_config
+    pub lmsr_b: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_payout_vector_single_winner() {
+        let winner = CategoricalMarket::validate_payout_vector(4, &[0, 0, 10000, 0]).unwrap();
+        assert_eq!(winner, 2);
+    }
+
+    #[test]
+    fn test_validate_payout_vector_weighted_split() {
+        let winner = CategoricalMarket::validate_payout_vector(3, &[5000, 2500, 2500]).unwrap();
+        assert_eq!(winner, CATEGORICAL_SPLIT_OUTCOME);
+    }
+
+    #[test]
+    fn test_validate_payout_vector_rejects_bad_vectors() {
+        // 长度不匹配
+        assert!(CategoricalMarket::validate_payout_vector(3, &[10000, 0]).is_err());
+        // 总和不为 10000
+        assert!(CategoricalMarket::validate_payout_vector(3, &[5000, 2500, 2000]).is_err());
+        assert!(CategoricalMarket::validate_payout_vector(3, &[10000, 10000, 0]).is_err());
+    }
+
+    #[test]
+    fn test_validate_outcome_count_bounds() {
+        assert!(CategoricalMarket::validate_outcome_count(2).is_err());
+        assert!(CategoricalMarket::validate_outcome_count(3).is_ok());
+        assert!(CategoricalMarket::validate_outcome_count(16).is_ok());
+        assert!(CategoricalMarket::validate_outcome_count(17).is_err());
+    }
+}
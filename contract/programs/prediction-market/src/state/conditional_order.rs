@@ -0,0 +1,144 @@
+//! ✅ v3.21.0: 条件订单（止损 / 止盈）
+//!
+//! 用户将 YES/NO 代币托管在订单 PDA 名下，并设置 YES 价格的触发条件：
+//! - 触发方向 BELOW：`calculate_yes_price_bps() ≤ trigger_price_bps`（如持有 YES 的止损）
+//! - 触发方向 ABOVE：`calculate_yes_price_bps() ≥ trigger_price_bps`（如持有 YES 的止盈）
+//!
+//! 条件满足后任何 keeper 都可以调用 execute_conditional_order，通过 `Market::swap` 卖出路径成交，
+//! 所得扣除 keeper 执行费后须不低于 `min_usdc_out`。下单人可随时撤单取回托管代币。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::{BASIS_POINTS_DIVISOR, CONDITIONAL_ORDER, MAX_KEEPER_FEE_BPS};
+use crate::errors::PredictionMarketError;
+
+/// 触发方向：YES 价格跌至触发价及以下
+pub const TRIGGER_BELOW: u8 = 0;
+
+/// 触发方向：YES 价格涨至触发价及以上
+pub const TRIGGER_ABOVE: u8 = 1;
+
+/// 条件订单（托管代币账户为该 PDA 的 ATA）
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ConditionalOrder {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 下单人
+    pub owner: Pubkey,
+
+    /// 订单编号（下单人自选，用于派生 PDA）
+    pub order_id: u64,
+
+    /// 代币类型（0=NO, 1=YES）
+    pub token_type: u8,
+
+    /// 触发方向（0=BELOW, 1=ABOVE）
+    pub trigger_direction: u8,
+
+    /// 触发价（YES 价格，基点，1-9999）
+    pub trigger_price_bps: u16,
+
+    /// 托管代币数量
+    pub amount: u64,
+
+    /// 扣除 keeper 费后的最少所得（USDC）
+    pub min_usdc_out: u64,
+
+    /// Keeper 执行费（基点，≤ MAX_KEEPER_FEE_BPS）
+    pub keeper_fee_bps: u16,
+
+    /// 创建时间
+    pub created_at: i64,
+
+    /// PDA bump（托管账户签名）
+    pub bump: u8,
+}
+
+impl ConditionalOrder {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = CONDITIONAL_ORDER;
+
+    /// 校验订单参数
+    pub fn validate(&self) -> Result<()> {
+        require!(self.token_type <= 1, PredictionMarketError::InvalidTokenType);
+        require!(self.amount > 0, PredictionMarketError::InvalidAmount);
+        require!(
+            self.trigger_direction <= TRIGGER_ABOVE
+                && (1..BASIS_POINTS_DIVISOR as u16).contains(&self.trigger_price_bps)
+                && self.keeper_fee_bps <= MAX_KEEPER_FEE_BPS,
+            PredictionMarketError::InvalidConditionalOrder
+        );
+        Ok(())
+    }
+
+    /// 当前 YES 价格是否满足触发条件
+    pub fn is_triggered(&self, yes_price_bps: u16) -> bool {
+        match self.trigger_direction {
+            TRIGGER_BELOW => yes_price_bps <= self.trigger_price_bps,
+            _ => yes_price_bps >= self.trigger_price_bps,
+        }
+    }
+
+    /// 按卖出所得计算 keeper 执行费与下单人所得
+    ///
+    /// # 返回
+    /// `(keeper_fee, owner_amount)`
+    pub fn split_proceeds(&self, proceeds: u64) -> Result<(u64, u64)> {
+        let keeper_fee = (proceeds as u128)
+            .checked_mul(self.keeper_fee_bps as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / BASIS_POINTS_DIVISOR as u128;
+        let keeper_fee = keeper_fee as u64;
+        Ok((keeper_fee, proceeds - keeper_fee))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(trigger_direction: u8, trigger_price_bps: u16, keeper_fee_bps: u16) -> ConditionalOrder {
+        ConditionalOrder {
+            market: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            order_id: 1,
+            token_type: 1,
+            trigger_direction,
+            trigger_price_bps,
+            amount: 1_000_000,
+            min_usdc_out: 0,
+            keeper_fee_bps,
+            created_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_trigger_directions() {
+        let stop_loss = order(TRIGGER_BELOW, 4_000, 10);
+        assert!(!stop_loss.is_triggered(4_001));
+        assert!(stop_loss.is_triggered(4_000));
+        assert!(stop_loss.is_triggered(1_000));
+
+        let take_profit = order(TRIGGER_ABOVE, 7_000, 10);
+        assert!(!take_profit.is_triggered(6_999));
+        assert!(take_profit.is_triggered(7_000));
+        assert!(take_profit.is_triggered(9_500));
+    }
+
+    #[test]
+    fn test_validate_and_split() {
+        assert!(order(TRIGGER_BELOW, 5_000, MAX_KEEPER_FEE_BPS).validate().is_ok());
+        assert!(order(2, 5_000, 0).validate().is_err());
+        assert!(order(TRIGGER_ABOVE, 0, 0).validate().is_err());
+        assert!(order(TRIGGER_ABOVE, 10_000, 0).validate().is_err());
+        assert!(order(TRIGGER_ABOVE, 5_000, MAX_KEEPER_FEE_BPS + 1).validate().is_err());
+
+        // 50 bps：1 USDC 所得中 keeper 获得 0.005 USDC
+        let (keeper_fee, owner_amount) = order(TRIGGER_BELOW, 5_000, 50).split_proceeds(1_000_000).unwrap();
+        assert_eq!((keeper_fee, owner_amount), (5_000, 995_000));
+    }
+}
@@ -234,6 +234,16 @@ pub struct Config {
     ///
     /// 默认值: false (禁用)
     pub insurance_pool_enabled: bool,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.22.0: 引荐分成
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 引荐人平台费分成上限（基点，0 = 关闭引荐分成）
+    ///
+    /// 实际分成 = min(引荐人账户的 share_bps, 本上限)，且不超过平台费中的团队份额，
+    /// 保险池份额与 LP 费不受影响
+    pub max_referral_share_bps: u16,
 }
 
 /// 数量配置枚举
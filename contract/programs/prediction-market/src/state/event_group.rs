@@ -0,0 +1,213 @@
+//! ✅ v3.5.0: 负风险（neg-risk）事件组
+//!
+//! 将多个互斥的二元 `Market` 关联为一个事件（例如"谁会赢得选举？"的 N 个候选人市场），
+//! 恰好一个成员最终结算为 YES。
+//!
+//! **转换（convert）**：用户销毁事件组中 k 个市场各 a 个 NO，获得
+//! `(k - 1) × a` USDC + 其余 N - k 个市场各 a 个 YES。
+//!
+//! **资金流**：
+//! - 被销毁 NO 对应的 k × a USDC 抵押品从各市场金库转入事件组金库
+//! - 事件组金库向用户支付 (k - 1) × a USDC，净留存 a
+//! - 掩码外的 N - k 个成员新铸 a 个 YES（无市场内抵押品）
+//! - 掩码内的 k 个成员不获得任何 YES：其 NO 被销毁、抵押品转出，
+//!   市场内原有 YES 中有 a 个失去市场内抵押品
+//! - 因此无论胜者是谁，胜者市场恰好缺 a 的抵押品由事件组担保（total_converted 累计）；
+//!   resolve_event_group 时将事件组金库全部注入胜者市场的 total_collateral_locked
+//!
+//! **作废（✅ v3.5.1）**：事件组无法正常结算时由 void_event_group 作废，成员市场改按 Invalid 结算。
+//! 成员 i 以快照价格 p_i 退款时缺口为 p_i × total_converted，各成员快照价格归一化为合计 100%，
+//! 事件组金库按归一化价格向各成员注入担保，总额不超过 total_converted
+
+use anchor_lang::prelude::*;
+use crate::constants::{BASIS_POINTS_DIVISOR, EVENT_GROUP, MAX_EVENT_GROUP_MARKETS};
+use crate::errors::PredictionMarketError;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct EventGroup {
+    /// 创建者（全局管理员）
+    pub authority: Pubkey,
+
+    /// 事件组编号（PDA 种子）
+    pub group_id: u64,
+
+    /// 成员市场数量
+    pub market_count: u8,
+
+    /// 成员市场（仅前 market_count 项有效）
+    pub markets: [Pubkey; MAX_EVENT_GROUP_MARKETS],
+
+    /// 累计转换量（= 胜者市场需由事件组担保的抵押品缺口）
+    ///
+    /// 掩码外成员以新铸 YES、掩码内成员以转出抵押品的形式产生相同的缺口；
+    /// 首次转换后成员列表冻结，确保每个成员的担保缺口一致
+    pub total_converted: u64,
+
+    /// 是否已结算
+    pub is_resolved: bool,
+
+    /// 胜出成员索引（is_resolved 后有效）
+    pub winning_index: u8,
+
+    /// 事件显示名称（最大64字符）
+    #[max_len(64)]
+    pub display_name: String,
+
+    /// 创建时间戳
+    pub created_at: i64,
+
+    /// PDA bump（事件组金库签名）
+    pub bump: u8,
+
+    /// ✅ v3.5.1: 是否已作废（成员市场改按 Invalid 结算）
+    pub is_voided: bool,
+
+    /// ✅ v3.5.1: 作废时各成员的归一化 YES 退款价格（基点，合计不超过 10000）
+    pub void_yes_price_bps: [u16; MAX_EVENT_GROUP_MARKETS],
+}
+
+impl EventGroup {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = EVENT_GROUP;
+
+    /// display_name 的最大长度(字符数)
+    pub const MAX_DISPLAY_NAME_LEN: usize = 64;
+
+    /// 有效成员切片
+    pub fn members(&self) -> &[Pubkey] {
+        &self.markets[..self.market_count as usize]
+    }
+
+    /// 查找成员索引
+    pub fn member_index(&self, market: &Pubkey) -> Option<usize> {
+        self.members().iter().position(|m| m == market)
+    }
+
+    /// 校验转换掩码并返回被转换 NO 的市场数量 k
+    ///
+    /// - 掩码第 i 位 = 1 表示销毁成员 i 的 NO
+    /// - 至少选择一个成员，且不得引用不存在的成员
+    pub fn validate_convert_mask(&self, no_mask: u16) -> Result<u32> {
+        let k = no_mask.count_ones();
+        require!(k > 0, PredictionMarketError::InvalidConvertMask);
+
+        let valid_bits: u32 = (1u32 << self.market_count) - 1;
+        require!(
+            (no_mask as u32) & !valid_bits == 0,
+            PredictionMarketError::InvalidConvertMask
+        );
+
+        Ok(k)
+    }
+
+    /// ✅ v3.5.1: 将各成员的快照 YES 价格归一化为合计 100%（向下取整，合计不超过 10000）
+    ///
+    /// 互斥事件的 YES 价格之和应为 100%；快照价格合计为 0 时按成员数均分
+    pub fn normalize_void_prices(prices: &[u16]) -> [u16; MAX_EVENT_GROUP_MARKETS] {
+        let mut normalized = [0u16; MAX_EVENT_GROUP_MARKETS];
+        let total: u64 = prices.iter().map(|p| *p as u64).sum();
+        for (slot, price) in normalized.iter_mut().zip(prices) {
+            *slot = (*price as u64 * BASIS_POINTS_DIVISOR)
+                .checked_div(total)
+                .unwrap_or_else(|| BASIS_POINTS_DIVISOR / prices.len() as u64) as u16;
+        }
+        normalized
+    }
+
+    /// ✅ v3.5.1: 作废后成员的 Invalid 退款比例（YES, NO）
+    pub fn void_refund_ratios(&self, index: usize) -> (u64, u64) {
+        let yes_ratio = (self.void_yes_price_bps[index] as u64).min(BASIS_POINTS_DIVISOR);
+        (yes_ratio, BASIS_POINTS_DIVISOR - yes_ratio)
+    }
+
+    /// ✅ v3.5.1: 作废时注入成员市场的担保抵押品 = total_converted × p_i（向下取整）
+    ///
+    /// 成员的 Invalid 赔付缺口恰为 p_i × total_converted，各成员注入额合计不超过 total_converted
+    pub fn void_collateral_share(&self, index: usize) -> Option<u64> {
+        let share = (self.total_converted as u128)
+            .checked_mul(self.void_yes_price_bps[index] as u128)?
+            .checked_div(BASIS_POINTS_DIVISOR as u128)?;
+        u64::try_from(share).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group_with(count: u8) -> EventGroup {
+        let mut markets = [Pubkey::default(); MAX_EVENT_GROUP_MARKETS];
+        for m in markets.iter_mut().take(count as usize) {
+            *m = Pubkey::new_unique();
+        }
+        EventGroup {
+            authority: Pubkey::new_unique(),
+            group_id: 1,
+            market_count: count,
+            markets,
+            total_converted: 0,
+            is_resolved: false,
+            winning_index: 0,
+            display_name: String::new(),
+            created_at: 0,
+            bump: 255,
+            is_voided: false,
+            void_yes_price_bps: [0; MAX_EVENT_GROUP_MARKETS],
+        }
+    }
+
+    #[test]
+    fn test_validate_convert_mask() {
+        let group = group_with(3);
+
+        assert_eq!(group.validate_convert_mask(0b001).unwrap(), 1);
+        assert_eq!(group.validate_convert_mask(0b111).unwrap(), 3);
+
+        // 空掩码
+        assert!(group.validate_convert_mask(0).is_err());
+        // 引用不存在的成员
+        assert!(group.validate_convert_mask(0b1000).is_err());
+    }
+
+    #[test]
+    fn test_member_index() {
+        let group = group_with(4);
+        assert_eq!(group.member_index(&group.markets[2]), Some(2));
+        assert_eq!(group.member_index(&Pubkey::new_unique()), None);
+        // 未使用的槽位不算成员
+        assert_eq!(group.member_index(&Pubkey::default()), None);
+    }
+
+    #[test]
+    fn test_normalize_void_prices() {
+        // 快照价格合计 120% → 归一化为 50% / 25% / 25%
+        let normalized = EventGroup::normalize_void_prices(&[6_000, 3_000, 3_000]);
+        assert_eq!(&normalized[..3], &[5_000, 2_500, 2_500]);
+
+        // 合计不足 100% 时放大，向下取整后合计不超过 10000
+        let normalized = EventGroup::normalize_void_prices(&[1_000, 1_000, 1_000]);
+        assert_eq!(&normalized[..3], &[3_333, 3_333, 3_333]);
+
+        // 无成交快照时均分
+        let normalized = EventGroup::normalize_void_prices(&[0, 0]);
+        assert_eq!(&normalized[..2], &[5_000, 5_000]);
+    }
+
+    #[test]
+    fn test_void_collateral_shares_cover_invalid_deficit() {
+        let mut group = group_with(3);
+        group.total_converted = 1_000_001;
+        group.void_yes_price_bps = EventGroup::normalize_void_prices(&[7_000, 2_000, 3_000]);
+
+        let shares: Vec<u64> = (0..3).map(|i| group.void_collateral_share(i).unwrap()).collect();
+        assert!(shares.iter().sum::<u64>() <= group.total_converted);
+
+        // 成员缺口 = p_i × total_converted（向下取整），注入额恰好覆盖
+        for (i, share) in shares.iter().enumerate() {
+            let (yes_ratio, no_ratio) = group.void_refund_ratios(i);
+            assert_eq!(yes_ratio + no_ratio, BASIS_POINTS_DIVISOR);
+            assert_eq!(*share, group.total_converted * yes_ratio / BASIS_POINTS_DIVISOR);
+        }
+    }
+}
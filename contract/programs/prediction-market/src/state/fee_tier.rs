@@ -0,0 +1,258 @@
+//! ✅ v3.23.0: 成交量分级费率与做市返佣
+//!
+//! - `FeeTierTable`：全局分级表（管理员通过 configure_fee_tiers 维护），
+//!   按 30 天滚动成交量选择档位，另有一个做市返佣档供白名单做市商使用
+//! - `UserFeeTier`：每个用户一个，按日分桶记录 30 天滚动成交量，
+//!   `is_maker` 由管理员通过 set_maker_rebate 设置
+//!
+//! swap 携带两个账户时按档位费率收费。分级费率只降不升：
+//! 实际费率 = min(基础费率, 档位费率)，基础费率仍支持市场级覆盖。
+//! 做市档的 LP 费率可为负数（返佣），返佣由该笔交易的团队份额出资，
+//! LP 的累计收益（fee_per_share_cumulative）保持单调不减。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::{
+    BASIS_POINTS_DIVISOR, FEE_TIER, FEE_TIER_TABLE, FEE_TIER_WINDOW_DAYS, MAX_FEE_TIERS,
+    MAX_MAKER_REBATE_BPS, SECONDS_PER_DAY,
+};
+use crate::errors::PredictionMarketError;
+
+/// 费率档位
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct FeeTier {
+    /// 适用该档位的最低 30 天滚动成交量（USDC）
+    pub min_volume_30d: u64,
+    /// 平台费率（基点，买卖相同）
+    pub platform_fee_bps: u16,
+    /// LP 费率（基点，买卖相同）
+    pub lp_fee_bps: u16,
+}
+
+/// 用户适用的费率（传入 `Market::swap`）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeRates {
+    /// 平台费率（基点）
+    pub platform_fee_bps: u16,
+    /// LP 费率（基点，负数为做市返佣）
+    pub lp_fee_bps: i16,
+}
+
+/// 全局费率分级表
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct FeeTierTable {
+    /// 有效档位数量（0 = 关闭成交量分级）
+    pub tier_count: u8,
+
+    /// 档位（按 min_volume_30d 严格递增）
+    pub tiers: [FeeTier; MAX_FEE_TIERS],
+
+    /// 做市返佣档：平台费率（基点）
+    pub maker_platform_fee_bps: u16,
+
+    /// 做市返佣档：LP 费率（基点，负数为返佣，不低于 -MAX_MAKER_REBATE_BPS）
+    pub maker_lp_fee_bps: i16,
+
+    /// 最后更新时间
+    pub updated_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl FeeTierTable {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = FEE_TIER_TABLE;
+
+    /// 校验档位递增与费率范围
+    pub fn validate(&self) -> Result<()> {
+        let count = self.tier_count as usize;
+        require!(count <= MAX_FEE_TIERS, PredictionMarketError::InvalidFeeTierTable);
+
+        let tiers = &self.tiers[..count];
+        require!(
+            tiers.iter().all(|t| {
+                t.platform_fee_bps as u64 <= BASIS_POINTS_DIVISOR
+                    && t.lp_fee_bps as u64 <= BASIS_POINTS_DIVISOR
+            }) && tiers.windows(2).all(|w| w[0].min_volume_30d < w[1].min_volume_30d),
+            PredictionMarketError::InvalidFeeTierTable
+        );
+
+        require!(
+            self.maker_platform_fee_bps as u64 <= BASIS_POINTS_DIVISOR
+                && self.maker_lp_fee_bps >= -(MAX_MAKER_REBATE_BPS as i16)
+                && self.maker_lp_fee_bps as i64 <= BASIS_POINTS_DIVISOR as i64,
+            PredictionMarketError::InvalidFeeTierTable
+        );
+        Ok(())
+    }
+
+    /// 按滚动成交量选择费率；做市商使用返佣档
+    ///
+    /// 成交量低于最低档位时返回 None（按基础费率收费）
+    pub fn rates_for(&self, is_maker: bool, rolling_volume: u64) -> Option<FeeRates> {
+        if is_maker {
+            return Some(FeeRates {
+                platform_fee_bps: self.maker_platform_fee_bps,
+                lp_fee_bps: self.maker_lp_fee_bps,
+            });
+        }
+
+        self.tiers[..self.tier_count as usize]
+            .iter()
+            .rev()
+            .find(|t| rolling_volume >= t.min_volume_30d)
+            .map(|t| FeeRates {
+                platform_fee_bps: t.platform_fee_bps,
+                lp_fee_bps: t.lp_fee_bps as i16,
+            })
+    }
+}
+
+/// 用户费率分级账户（30 天滚动成交量）
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct UserFeeTier {
+    /// 用户
+    pub user: Pubkey,
+
+    /// 是否适用做市返佣档（管理员白名单）
+    pub is_maker: bool,
+
+    /// 按日分桶的成交量（USDC，下标 = 天数 % FEE_TIER_WINDOW_DAYS）
+    pub daily_volume: [u64; FEE_TIER_WINDOW_DAYS],
+
+    /// 最近一次记录成交的天数（Unix 时间戳 / 86400）
+    pub last_day: i64,
+
+    /// 累计成交量（USDC）
+    pub total_volume: u64,
+
+    /// 创建时间
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl UserFeeTier {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = FEE_TIER;
+
+    fn bucket(day: i64) -> usize {
+        day.rem_euclid(FEE_TIER_WINDOW_DAYS as i64) as usize
+    }
+
+    /// 截至 `now` 的 30 天滚动成交量（含当天）
+    pub fn rolling_volume(&self, now: i64) -> u64 {
+        let today = now.div_euclid(SECONDS_PER_DAY);
+        (0..FEE_TIER_WINDOW_DAYS as i64)
+            .map(|offset| self.last_day - offset)
+            .filter(|day| today - day < FEE_TIER_WINDOW_DAYS as i64)
+            .fold(0u64, |sum, day| sum.saturating_add(self.daily_volume[Self::bucket(day)]))
+    }
+
+    /// 记录一笔成交（跨天时清空已过期的日桶）
+    pub fn record(&mut self, volume: u64, now: i64) -> Result<()> {
+        let today = now.div_euclid(SECONDS_PER_DAY);
+        if today > self.last_day {
+            let stale_days = (today - self.last_day).min(FEE_TIER_WINDOW_DAYS as i64);
+            for day in (today - stale_days + 1)..=today {
+                self.daily_volume[Self::bucket(day)] = 0;
+            }
+            self.last_day = today;
+        }
+
+        // 时钟回拨时计入最近一天的桶
+        let bucket = Self::bucket(self.last_day);
+        self.daily_volume[bucket] = self.daily_volume[bucket]
+            .checked_add(volume)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.total_volume = self
+            .total_volume
+            .checked_add(volume)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(tiers: &[FeeTier], maker_lp_fee_bps: i16) -> FeeTierTable {
+        let mut slots = [FeeTier::default(); MAX_FEE_TIERS];
+        slots[..tiers.len()].copy_from_slice(tiers);
+        FeeTierTable {
+            tier_count: tiers.len() as u8,
+            tiers: slots,
+            maker_platform_fee_bps: 50,
+            maker_lp_fee_bps,
+            updated_at: 0,
+            bump: 255,
+        }
+    }
+
+    fn tier(min_volume_30d: u64, platform_fee_bps: u16, lp_fee_bps: u16) -> FeeTier {
+        FeeTier { min_volume_30d, platform_fee_bps, lp_fee_bps }
+    }
+
+    fn account() -> UserFeeTier {
+        UserFeeTier {
+            user: Pubkey::new_unique(),
+            is_maker: false,
+            daily_volume: [0; FEE_TIER_WINDOW_DAYS],
+            last_day: 0,
+            total_volume: 0,
+            created_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_validate_and_select_tier() {
+        let t = table(&[tier(10_000_000_000, 80, 150), tier(100_000_000_000, 50, 100)], -20);
+        assert!(t.validate().is_ok());
+
+        assert_eq!(t.rates_for(false, 9_999_999_999), None);
+        assert_eq!(
+            t.rates_for(false, 10_000_000_000),
+            Some(FeeRates { platform_fee_bps: 80, lp_fee_bps: 150 })
+        );
+        assert_eq!(
+            t.rates_for(false, 500_000_000_000),
+            Some(FeeRates { platform_fee_bps: 50, lp_fee_bps: 100 })
+        );
+        assert_eq!(
+            t.rates_for(true, 0),
+            Some(FeeRates { platform_fee_bps: 50, lp_fee_bps: -20 })
+        );
+
+        // 档位未递增 / 返佣超过上限
+        assert!(table(&[tier(10, 80, 150), tier(10, 50, 100)], 0).validate().is_err());
+        assert!(table(&[], -(MAX_MAKER_REBATE_BPS as i16) - 1).validate().is_err());
+        assert!(table(&[tier(0, 10_001, 0)], 0).validate().is_err());
+    }
+
+    #[test]
+    fn test_rolling_volume_window() {
+        let mut a = account();
+        let day = SECONDS_PER_DAY;
+
+        a.record(100, 100 * day).unwrap();
+        a.record(50, 100 * day + 3_600).unwrap();
+        a.record(200, 110 * day).unwrap();
+        assert_eq!(a.rolling_volume(110 * day), 350);
+
+        // 第 130 天：第 100 天的成交已滑出窗口
+        assert_eq!(a.rolling_volume(129 * day), 350);
+        assert_eq!(a.rolling_volume(130 * day), 200);
+
+        // 跨越超过一个窗口后旧桶被清空
+        a.record(7, 200 * day).unwrap();
+        assert_eq!(a.rolling_volume(200 * day), 7);
+        assert_eq!(a.total_volume, 357);
+    }
+}
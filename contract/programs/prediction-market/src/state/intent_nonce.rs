@@ -0,0 +1,74 @@
+//! ✅ v3.25.0: 用户意图序号账户
+//!
+//! 每个用户一个，记录下一个可执行的意图序号（防重放）。
+//! 该 PDA 也是用户 USDC / YES / NO ATA 的 delegate，execute_intent 以其签名动用授权额度。
+//!
+//! ✅ v3.26.1: 该 PDA 是用户唯一的委托权限：会话密钥（swap / mint_complete_set / redeem_complete_set）
+//! 同样以其签名转出，避免会话与意图各自设置 delegate 互相覆盖。create_session_key 会在不存在时创建它。
+//! 各会话的支出上限记录在 SessionKey 中，意图的额度由用户逐笔签名约定。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::INTENT_NONCE;
+use crate::errors::PredictionMarketError;
+
+/// 用户意图序号
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct IntentNonce {
+    /// 用户
+    pub user: Pubkey,
+
+    /// 下一个可执行的意图序号
+    pub next_nonce: u64,
+
+    /// 最后一次执行时间
+    pub last_executed_at: i64,
+
+    /// PDA bump（delegate 签名）
+    pub bump: u8,
+}
+
+impl IntentNonce {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = INTENT_NONCE;
+
+    /// 消费一个序号：必须与 next_nonce 相等，成功后递增
+    pub fn consume(&mut self, nonce: u64, now: i64) -> Result<()> {
+        require!(nonce == self.next_nonce, PredictionMarketError::InvalidIntentNonce);
+        self.next_nonce = self
+            .next_nonce
+            .checked_add(1)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.last_executed_at = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_is_single_use_and_sequential() {
+        let mut account = IntentNonce {
+            user: Pubkey::new_unique(),
+            next_nonce: 0,
+            last_executed_at: 0,
+            bump: 255,
+        };
+
+        // 跳号
+        assert!(account.consume(1, 100).is_err());
+
+        account.consume(0, 100).unwrap();
+        assert_eq!(account.next_nonce, 1);
+        assert_eq!(account.last_executed_at, 100);
+
+        // 重放
+        assert!(account.consume(0, 200).is_err());
+        account.consume(1, 200).unwrap();
+        assert_eq!(account.next_nonce, 2);
+    }
+}
@@ -1,7 +1,9 @@
+use crate::constants::TWAP_OBSERVATION_COUNT;
 use crate::state::config::*;
+use crate::state::fee_tier::FeeRates;
 
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 // use anchor_spl::token::{self};
 
@@ -70,6 +72,9 @@ pub struct SwapResult {
     pub usdc_amount: u64,       // ✅ v1.1.0: 实际的 USDC 数量（买单=输入，卖单=输出税后）
     pub token_amount: u64,      // 实际的代币数量（买单=输出，卖单=输入）
     pub fee_usdc: u64,          // ✅ v1.1.0: 总手续费（platform_fee + lp_fee，USDC）
+    pub referral_fee: u64,      // ✅ v3.22.0: 平台费中分给引荐人的部分（USDC）
+    pub maker_rebate: u64,      // ✅ v3.23.0: 做市返佣（USDC，由团队份额出资）
+    pub lp_fee_bps: u16,        // ✅ v3.24.0: 本次适用的 LP 费率（基点，含动态溢价）
 }
 
 /// ✅ v3.1.0: 内部交换结果
@@ -171,7 +176,7 @@ pub struct Market {
     /// Resolution 结算参数
     pub resolution_yes_ratio: u64,  // YES代币赎回比例（基点，10000=100%）
     pub resolution_no_ratio: u64,   // NO代币赎回比例（基点，10000=100%）
-    pub winner_token_type: u8,      // 获胜方（0=NO, 1=YES, 2=平局）
+    pub winner_token_type: u8,      // 获胜方（0=NO, 1=YES, 2=平局, 3=无效）
 
     /// 重入保护标志
     pub swap_in_progress: bool,
@@ -332,6 +337,178 @@ pub struct Market {
     /// - create_market：设为 true（总是铸造）
     /// - 迁移脚本：旧账户设为 true（假设都已铸造）
     pub sentinel_no_minted: bool,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.4.0: 标量（区间）市场
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 是否为标量市场（YES = LONG，NO = SHORT）
+    ///
+    /// 标量市场只能按数值结算（✅ v3.12.1: propose_admin_scalar_resolution）：
+    /// - LONG 赔付 = (clamp(value) - lower) / (upper - lower)
+    /// - SHORT 赔付 = 1 - LONG 赔付
+    pub is_scalar: bool,
+
+    /// 区间下界（结算值 ≤ 下界时 SHORT 获得 100%）
+    pub scalar_lower_bound: i64,
+
+    /// 区间上界（结算值 ≥ 上界时 LONG 获得 100%）
+    pub scalar_upper_bound: i64,
+
+    /// 结算时报告的原始数值（未截断，便于链下审计）
+    pub scalar_resolved_value: i64,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.5.0: 负风险事件组
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 所属事件组（Pubkey::default() = 独立市场）
+    ///
+    /// 事件组成员必须通过事件组结算：胜者 YES，其余 NO
+    pub event_group: Pubkey,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.6.0: 条件市场（父市场按指定方向结算后才生效）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 父市场（Pubkey::default() = 非条件市场）
+    pub parent_market: Pubkey,
+
+    /// 要求的父市场结果（0=NO, 1=YES）
+    pub parent_required_outcome: u8,
+
+    /// 是否已作废（父市场未按要求结算）
+    ///
+    /// 作废后按 50/50 平局结算：完整集合 1 YES + 1 NO 通过 claim_rewards 取回 1 USDC，
+    /// 也可直接通过 redeem_complete_set 1:1 赎回
+    pub is_voided: bool,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.8.0: 价格源自动结算（"SOL 在 T 时刻是否高于 $X"）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 价格源账户（Pubkey::default() = 未配置）
+    pub price_feed: Pubkey,
+
+    /// 价格阈值（实际阈值 = price_threshold × 10^price_threshold_expo）
+    pub price_threshold: i64,
+
+    /// 价格阈值精度指数
+    pub price_threshold_expo: i32,
+
+    /// 最早结算时间（Unix 时间戳，价格发布时间不得早于此时间）
+    pub price_resolve_after: i64,
+
+    /// 价格最大允许延迟（秒）
+    pub price_max_staleness_secs: u32,
+
+    /// 价格置信区间上限（基点）
+    pub price_max_conf_bps: u16,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.9.0: 市场级结算人
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 指定结算人（Pubkey::default() = 仅全局管理员）
+    ///
+    /// 全局管理员仅在委员会僵局升级或超过结算截止后才可提案结算（✅ v3.9.2）
+    pub resolver: Pubkey,
+
+    /// resolver 是否为 ResolverCommittee 账户（否则为单一密钥）
+    pub resolver_is_committee: bool,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.10.0: 无效结果退款
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 最后成交后的 YES 价格快照（基点，每次 swap 更新）
+    ///
+    /// 市场以 Invalid 结算时，YES 按该价格、NO 按 (10000 - 该价格) 退款
+    pub last_trade_yes_price_bps: u16,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.11.0: 结算截止（活性保证）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 结算截止 slot（0 = 无截止，仅无 ending_slot 的市场）
+    ///
+    /// 超过截止仍未结算时，任何人可调用 void_unresolved_market 作废市场并开放退款
+    pub resolution_deadline_slot: u64,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.13.0: 规则承诺与结算证据
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 是否已创建 MarketMetadata 账户（规则哈希、结算来源、分类标签）
+    ///
+    /// 为 true 时，所有结算路径都必须传入该账户以记录结算证据
+    pub has_metadata: bool,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.14.0: Unix 时间戳排期
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 交易开始时间（Unix 时间戳，0 = 未设置）
+    pub start_time: i64,
+
+    /// 交易结束时间（Unix 时间戳，0 = 未设置）
+    ///
+    /// 设置后交易/添加流动性/结算均以 Clock::unix_timestamp 校验，
+    /// 动态 b 值阶段也按实际剩余时间计算（不再按 400ms/slot 估算）
+    pub end_time: i64,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.20.0: TWAP 价格累加器
+    // ═══════════════════════════════════════════════════════════════
+
+    /// YES 价格时间累计值（Σ 价格基点 × 持续秒数）
+    ///
+    /// 在每次 swap / 添加 / 移除流动性前，按变动前的现价累加
+    pub twap_cumulative_yes_price: u128,
+
+    /// 累加器最后更新时间（Unix 时间戳，0 = 尚未初始化）
+    pub twap_last_update_ts: i64,
+
+    /// 最新观测在环形缓冲区中的下标
+    pub twap_observation_index: u8,
+
+    /// 已写入的观测数量（≤ TWAP_OBSERVATION_COUNT）
+    pub twap_observation_count: u8,
+
+    /// 累计值观测环形缓冲区（相邻观测间隔 ≥ TWAP_MIN_OBSERVATION_INTERVAL_SECS）
+    pub twap_observations: [TwapObservation; TWAP_OBSERVATION_COUNT],
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.24.0: 动态 LP 费率（随池子不平衡度与近期波动率上调）
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 是否启用动态 LP 费率（管理员通过 configure_dynamic_fee 设置）
+    pub dynamic_fee_enabled: bool,
+
+    /// 动态 LP 费率下限（基点）
+    pub dynamic_fee_min_bps: u16,
+
+    /// 动态 LP 费率上限（基点，≤ MAX_DYNAMIC_LP_FEE_BPS）
+    pub dynamic_fee_max_bps: u16,
+
+    /// 不平衡溢价满额（基点，不平衡度达到满刻度时加收）
+    pub dynamic_fee_imbalance_bps: u16,
+
+    /// 波动率溢价系数（基点，溢价 = 波动率 EWMA × 系数 / 10000）
+    pub dynamic_fee_volatility_multiplier_bps: u16,
+
+    /// 每笔成交 YES 价格变动的 EWMA（基点，按半衰期随时间衰减）
+    pub volatility_ewma_bps: u32,
+
+    /// 波动率 EWMA 最后更新时间（Unix 时间戳，0 = 尚无样本）
+    pub volatility_last_update_ts: i64,
+
+    // ═══════════════════════════════════════════════════════════════
+    // ✅ v3.8.1: 价格源 feed_id 绑定
+    // ═══════════════════════════════════════════════════════════════
+
+    /// 价格源 feed_id（PriceUpdateV2.price_message.feed_id，全 0 = 未配置）
+    pub price_feed_id: [u8; 32],
 }
 
 impl Market {
@@ -353,10 +530,49 @@ impl Market {
     /// - v3.1.2 新增市场级暂停: bool(1)
     /// - v3.1.3 新增哨兵追踪: bool(1)
     /// - v3.1.4 新增 add_liquidity 重入保护: bool(1)
-    pub const INIT_SPACE: usize = std::mem::size_of::<Market>() + 60 + 10 + 8 + 41 + 33 + 1 + 1 + 1;
+    /// - v3.4.0 新增标量市场字段: bool(1) + 3 * i64(24) = 25 字节
+    /// - v3.5.0 新增事件组归属: Pubkey(32)
+    /// - v3.6.0 新增条件市场字段: Pubkey(32) + u8(1) + bool(1) = 34 字节
+    /// - v3.8.0 新增价格源字段: Pubkey(32) + i64(8) + i32(4) + i64(8) + u32(4) + u16(2) = 58 字节
+    /// - v3.8.1 新增价格源 feed_id: [u8; 32] = 32 字节
+    /// - v3.9.0 新增结算人字段: Pubkey(32) + bool(1) = 33 字节
+    /// - v3.10.0 新增价格快照字段: u16(2) = 2 字节
+    /// - v3.11.0 新增结算截止字段: u64(8) = 8 字节
+    /// - v3.13.0 新增规则元数据标志: bool(1)
+    /// - v3.14.0 新增 Unix 时间戳排期: 2 * i64(16) = 16 字节
+    /// - v3.20.0 新增 TWAP 累加器: u128(16) + i64(8) + 2 * u8(2) + 24 * TwapObservation(24) = 602 字节
+    /// - v3.24.0 新增动态 LP 费率: bool(1) + 4 * u16(8) + u32(4) + i64(8) = 21 字节
+    ///
+    /// ✅ v3.20.1: 升级前创建的市场按旧版 INIT_SPACE 分配，剩余空间容纳不下追加字段，
+    /// 须先调用 migrate_market_account 扩容（追加字段以 0 填充，0 即"未设置"）
+    pub const INIT_SPACE: usize = std::mem::size_of::<Market>() + 60 + 10 + 8 + 41 + 33 + 1 + 1 + 1 + 25 + 32 + 34 + 58 + 32 + 33 + 2 + 8 + 1 + 16
+        + 16 + 8 + 2 + TWAP_OBSERVATION_COUNT * 24
+        + 21;
 
     /// display_name 的最大长度(字符数)
     pub const MAX_DISPLAY_NAME_LEN: usize = 64;
+
+    /// ✅ v3.20.2: v3.20.0 起追加在末尾的定长字段字节数（TWAP 602 + 动态费率 21 + feed_id 32）
+    pub const LEGACY_APPENDED_SPACE: usize = 602 + 21 + 32;
+
+    /// ✅ v3.20.2: 旧版布局账户中实际序列化内容的长度（含鉴别器）
+    ///
+    /// update_market_name 缩短 display_name 时序列化结果原地变短，尾部残留旧字节，
+    /// 扩容前须将该长度之后的字节清零，否则残留字节会被解码为追加字段。
+    /// 追加字段均为定长：按零填充解码后重新序列化，减去追加字段长度即得旧版长度
+    /// （残留字节不超过 display_name 最大长度，只落在 TWAP 整数字段内，不会导致解码失败）
+    pub fn legacy_serialized_len(data: &[u8]) -> Result<usize> {
+        let mut padded = data.to_vec();
+        padded.resize(data.len() + Self::LEGACY_APPENDED_SPACE, 0);
+        let market = Self::try_deserialize(&mut padded.as_slice())?;
+
+        let mut serialized = Vec::with_capacity(padded.len());
+        market.try_serialize(&mut serialized)?;
+        serialized
+            .len()
+            .checked_sub(Self::LEGACY_APPENDED_SPACE)
+            .ok_or_else(|| crate::errors::PredictionMarketError::InvalidMarketAccount.into())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -395,6 +611,182 @@ pub struct CreateMarketParams {
     /// 范围：2000-8000 (20%-80%)
     /// 如果设置为0，则使用默认值5000 (50%)
     pub initial_yes_prob: u16,
+
+    /// ✅ v3.4.0: 标量市场区间（None = 普通二元市场）
+    pub scalar_bounds: Option<ScalarBounds>,
+
+    /// ✅ v3.6.0: 条件市场要求的父市场结果（0=NO, 1=YES；需同时传入 parent_market 账户）
+    pub parent_required_outcome: Option<u8>,
+
+    /// ✅ v3.8.0: 价格源自动结算配置（None = 手动/预言机结算）
+    pub price_feed: Option<PriceFeedConfig>,
+
+    /// ✅ v3.9.0: 单一结算人密钥（结算委员会通过 resolver_committee 账户指定，二者互斥）
+    pub resolver: Option<Pubkey>,
+
+    /// ✅ v3.11.0: 结算宽限期（ending_slot 之后的 slot 数，None = 默认约 7 天）
+    pub resolution_grace_slots: Option<u64>,
+
+    /// ✅ v3.13.0: 规则承诺（规则文本哈希、结算来源、分类标签），写入 MarketMetadata 账户
+    pub rules: crate::state::market_metadata::MarketRules,
+
+    /// ✅ v3.14.0: 交易开始时间（Unix 时间戳，None = 不限制；可与 start_slot 同时使用）
+    pub start_time: Option<i64>,
+
+    /// ✅ v3.14.0: 交易结束时间（Unix 时间戳，None = 不限制；可与 ending_slot 同时使用）
+    pub end_time: Option<i64>,
+}
+
+/// ✅ v3.8.0: 价格源自动结算配置
+///
+/// 例如 "SOL 在 2025-12-31 00:00 UTC 是否高于 $150"：
+/// threshold = 150, threshold_expo = 0, resolve_after = 1767139200
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceFeedConfig {
+    /// 价格源账户（Pyth PriceUpdateV2；✅ v3.8.2: 仅作参考，结算时按 feed_id 匹配）
+    pub feed: Pubkey,
+    /// ✅ v3.8.1: 价格源 feed_id（结算时与账户内 price_message.feed_id 比对）
+    pub feed_id: [u8; 32],
+    /// 阈值（价格严格高于阈值时 YES 胜出）
+    pub threshold: i64,
+    /// 阈值精度指数
+    pub threshold_expo: i32,
+    /// 最早结算时间（Unix 时间戳）
+    pub resolve_after: i64,
+    /// 价格最大允许延迟（秒，1 - 3600）
+    ///
+    /// ✅ v3.8.1: 同时作为结算价格窗口，发布时间须在 [resolve_after, resolve_after + max_staleness_secs]
+    pub max_staleness_secs: u32,
+    /// 价格置信区间上限（基点，1 - 1000）
+    pub max_conf_bps: u16,
+}
+
+impl PriceFeedConfig {
+    /// 校验配置范围
+    pub fn validate(&self) -> Result<()> {
+        use crate::constants::{MAX_PRICE_FEED_CONF_BPS, MAX_PRICE_FEED_STALENESS_SECS};
+
+        require!(
+            self.feed != Pubkey::default()
+                && self.feed_id != [0u8; 32]
+                && self.threshold > 0
+                && self.resolve_after > 0
+                && (1..=MAX_PRICE_FEED_STALENESS_SECS).contains(&self.max_staleness_secs)
+                && (1..=MAX_PRICE_FEED_CONF_BPS).contains(&self.max_conf_bps),
+            crate::errors::PredictionMarketError::InvalidPriceFeedConfig
+        );
+        Ok(())
+    }
+}
+
+/// ✅ v3.22.0: 计算引荐人分成
+///
+/// 分成 = 平台费 × share_bps / 10000，且不超过团队份额（保险池份额优先保留）
+pub fn calculate_referral_fee(platform_fee: u64, team_fee: u64, share_bps: u16) -> Result<u64> {
+    let share = (platform_fee as u128)
+        .checked_mul(share_bps as u128)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+        / crate::constants::BASIS_POINTS_DIVISOR as u128;
+    Ok((share as u64).min(team_fee))
+}
+
+/// ✅ v3.23.0: 合并基础费率与分级费率（分级只降不升）
+///
+/// # 返回
+/// `(平台费率, LP 费率, 做市返佣费率)`；LP 费率为负时 LP 费为 0，绝对值作为返佣费率
+pub fn resolve_fee_bps(base_platform_bps: u64, base_lp_bps: u64, tier: Option<FeeRates>) -> (u64, u64, u64) {
+    let Some(rates) = tier else {
+        return (base_platform_bps, base_lp_bps, 0);
+    };
+    let platform_bps = base_platform_bps.min(rates.platform_fee_bps as u64);
+    let lp_bps = (base_lp_bps as i64).min(rates.lp_fee_bps as i64);
+    if lp_bps >= 0 {
+        (platform_bps, lp_bps as u64, 0)
+    } else {
+        (platform_bps, 0, lp_bps.unsigned_abs())
+    }
+}
+
+/// ✅ v3.23.0: 计算做市返佣
+///
+/// 返佣 = 成交额 × rebate_bps / 10000，且不超过团队份额（平台费扣除保险池份额）
+pub fn calculate_maker_rebate(amount: u64, rebate_bps: u64, platform_fee: u64, insurance_allocation_bps: u16) -> Result<u64> {
+    if rebate_bps == 0 {
+        return Ok(0);
+    }
+    let rebate = (amount as u128)
+        .checked_mul(rebate_bps as u128)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+        / crate::constants::BASIS_POINTS_DIVISOR as u128;
+    let insurance = (platform_fee as u128)
+        .checked_mul(insurance_allocation_bps as u128)
+        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+        / crate::constants::BASIS_POINTS_DIVISOR as u128;
+    let team_share = platform_fee as u128 - insurance.min(platform_fee as u128);
+    Ok(rebate.min(team_share) as u64)
+}
+
+/// ✅ v3.20.0: TWAP 累计值观测点
+///
+/// 两个观测之间的平均价格 = (cumulative_b - cumulative_a) / (timestamp_b - timestamp_a)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct TwapObservation {
+    /// 观测时间（Unix 时间戳）
+    pub timestamp: i64,
+    /// 观测时的 YES 价格累计值（基点 × 秒）
+    pub cumulative_yes_price: u128,
+}
+
+/// ✅ v3.4.0: 标量市场区间
+///
+/// 例如 "BTC 12 月 31 日价格在 50k-150k 之间"：lower = 50_000, upper = 150_000
+/// 数值单位由市场规则自行约定（如美元、美分或带精度的整数）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScalarBounds {
+    pub lower: i64,
+    pub upper: i64,
+}
+
+impl ScalarBounds {
+    /// 校验区间有效（lower < upper）
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.lower < self.upper,
+            crate::errors::PredictionMarketError::InvalidScalarBounds
+        );
+        Ok(())
+    }
+
+    /// 根据结算值计算 LONG(YES)/SHORT(NO) 赔付比例（基点）
+    ///
+    /// 结算值先截断到 [lower, upper]，再线性插值：
+    /// - value ≤ lower → (0, 10000)
+    /// - value ≥ upper → (10000, 0)
+    /// - 其余 → LONG = (value - lower) × 10000 / (upper - lower)，SHORT = 10000 - LONG
+    ///
+    /// # 返回值
+    /// (yes_ratio, no_ratio)，总和恒为 10000
+    pub fn payout_ratios(&self, resolved_value: i64) -> Result<(u64, u64)> {
+        self.validate()?;
+
+        let clamped = resolved_value.clamp(self.lower, self.upper);
+
+        // 使用 i128 防止 (upper - lower) 溢出
+        let range = (self.upper as i128) - (self.lower as i128);
+        let offset = (clamped as i128) - (self.lower as i128);
+
+        let yes_ratio = (offset as u128)
+            .checked_mul(crate::constants::BASIS_POINTS_DIVISOR as u128)
+            .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+            .checked_div(range as u128)
+            .ok_or(crate::errors::PredictionMarketError::MathOverflow)? as u64;
+
+        let no_ratio = crate::constants::BASIS_POINTS_DIVISOR
+            .checked_sub(yes_ratio)
+            .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+
+        Ok((yes_ratio, no_ratio))
+    }
 }
 pub trait MarketAccount<'info> {
     #[allow(clippy::too_many_arguments)]
@@ -402,41 +794,56 @@ pub trait MarketAccount<'info> {
         &mut self,
         global_config: &mut Account<'info, Config>,
 
-        yes_token_mint: &Account<'info, Mint>,
+        // ✅ v3.18.0: 移除未使用的 mint / team_wallet / user_info / system_program / usdc_mint 参数
+        // （批量交易按腿从 remaining_accounts 传入账户，减少每腿所需账户数量）
         _global_yes_ata: &mut AccountInfo<'info>,
         user_yes_ata: &mut AccountInfo<'info>,
 
-        no_token_mint: &Account<'info, Mint>,
         _global_no_ata: &mut AccountInfo<'info>,
         user_no_ata: &mut AccountInfo<'info>,
 
         source: &mut AccountInfo<'info>,
-        _team_wallet: &mut AccountInfo<'info>,  // ✅ v1.1.0: 不再使用（改用 team_usdc_ata）
 
         amount: u64,
         direction: u8,
         token_type: u8,
         minimum_receive_amount: u64,
+        // ✅ v3.16.0: 精确输出模式（Some = 期望输出，此时 amount 为输入上限）
+        exact_output: Option<u64>,
 
-        user: &Signer<'info>,
+        // ✅ v3.21.0: 用户可为 PDA（如条件订单托管账户），此时通过 user_signer 签名
+        user: &AccountInfo<'info>,
+        user_signer: &[&[&[u8]]],
         signer: &[&[&[u8]]],
 
-        user_info_pda: &mut Account<'info, UserInfo>,
+        // ✅ v3.27.1: 抵押品与 YES/NO 可分属不同代币程序（如 SPL Token USDC + Token-2022 YES/NO）
+        collateral_token_program: &Interface<'info, TokenInterface>,
+        outcome_token_program: &Interface<'info, TokenInterface>,
 
-        token_program: &Program<'info, Token>,
-        _system_program: &Program<'info, System>,
+        // ✅ v3.27.0: transfer_checked 所需 mint（YES/NO 精度 = token_decimals_config）
+        usdc_mint: &InterfaceAccount<'info, Mint>,
+        yes_mint: &AccountInfo<'info>,
+        no_mint: &AccountInfo<'info>,
 
         // 🔒 v1.2.7: USDC 相关账户 (使用市场专用金库)
-        _usdc_mint: &Account<'info, Mint>,
-        market_usdc_ata: &Account<'info, TokenAccount>,
+        market_usdc_ata: &InterfaceAccount<'info, TokenAccount>,
         market_usdc_vault: &AccountInfo<'info>,
         market_usdc_vault_bump: u8,
-        user_usdc_ata: &Account<'info, TokenAccount>,
-        team_usdc_ata: &Account<'info, TokenAccount>,
+        user_usdc_ata: &InterfaceAccount<'info, TokenAccount>,
+        team_usdc_ata: &InterfaceAccount<'info, TokenAccount>,
+
+        // ✅ v3.22.0: 引荐人分成（引荐人 USDC 托管账户, 平台费分成比例基点）
+        referral: Option<(&AccountInfo<'info>, u16)>,
+
+        // ✅ v3.23.0: 成交量分级 / 做市返佣费率（None = 基础费率）
+        fee_rates: Option<FeeRates>,
     ) -> Result<SwapResult>;
 
     fn apply_buy(&mut self, sol_amount: u64, token_type: u8) -> Option<BuyResult>;
 
+    /// ✅ v3.16.0: 精确输出买入（成本不超过 max_cost）
+    fn apply_buy_exact(&mut self, token_amount: u64, max_cost: u64, token_type: u8) -> Option<BuyResult>;
+
     fn apply_sell(&mut self, token_amount: u64, token_type: u8) -> Option<SellResult>;
 
     fn get_tokens_for_buy_sol(&self, sol_amount: u64, token_type: u8) -> Option<BuyResult>;
@@ -454,39 +861,50 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
         &mut self,
         global_config: &mut Account<'info, Config>,
 
-        _yes_token_mint: &Account<'info, Mint>,
+        // ✅ v3.18.0: 移除未使用的 mint / team_wallet / user_info / system_program / usdc_mint 参数
         _global_yes_ata: &mut AccountInfo<'info>,
         user_yes_ata: &mut AccountInfo<'info>,
 
-        _no_token_mint: &Account<'info, Mint>,
         _global_no_ata: &mut AccountInfo<'info>,
         user_no_ata: &mut AccountInfo<'info>,
 
         source: &mut AccountInfo<'info>,
-        _team_wallet: &mut AccountInfo<'info>,  // ✅ v1.1.0: 不再使用（改用 team_usdc_ata）
 
         amount: u64,
         direction: u8,
         token_type: u8,
         minimum_receive_amount: u64,
+        // ✅ v3.16.0: 精确输出模式（Some = 期望输出，此时 amount 为输入上限）
+        exact_output: Option<u64>,
 
-        user: &Signer<'info>,
+        // ✅ v3.21.0: 用户可为 PDA（如条件订单托管账户），此时通过 user_signer 签名
+        user: &AccountInfo<'info>,
+        user_signer: &[&[&[u8]]],
         signer: &[&[&[u8]]],
 
-        _user_info_pda: &mut Account<'info, UserInfo>,
+        // ✅ v3.27.1: 抵押品与 YES/NO 可分属不同代币程序（如 SPL Token USDC + Token-2022 YES/NO）
+        collateral_token_program: &Interface<'info, TokenInterface>,
+        outcome_token_program: &Interface<'info, TokenInterface>,
 
-        token_program: &Program<'info, Token>,
-        _system_program: &Program<'info, System>,
+        // ✅ v3.27.0: transfer_checked 所需 mint（YES/NO 精度 = token_decimals_config）
+        usdc_mint: &InterfaceAccount<'info, Mint>,
+        yes_mint: &AccountInfo<'info>,
+        no_mint: &AccountInfo<'info>,
 
         // 🔒 v1.2.7: USDC 相关账户 (使用市场专用金库)
-        _usdc_mint: &Account<'info, Mint>,
-        market_usdc_ata: &Account<'info, TokenAccount>,
+        market_usdc_ata: &InterfaceAccount<'info, TokenAccount>,
         market_usdc_vault: &AccountInfo<'info>,
         market_usdc_vault_bump: u8,
-        user_usdc_ata: &Account<'info, TokenAccount>,
-        team_usdc_ata: &Account<'info, TokenAccount>,
+        user_usdc_ata: &InterfaceAccount<'info, TokenAccount>,
+        team_usdc_ata: &InterfaceAccount<'info, TokenAccount>,
+
+        // ✅ v3.22.0: 引荐人分成（引荐人 USDC 托管账户, 平台费分成比例基点）
+        referral: Option<(&AccountInfo<'info>, u16)>,
+
+        // ✅ v3.23.0: 成交量分级 / 做市返佣费率（None = 基础费率）
+        fee_rates: Option<FeeRates>,
     ) -> Result<SwapResult> {
-        use anchor_spl::token;
+        use anchor_spl::token_interface;
 
         msg!("Swap: direction={}, token_type={}, amount={}", direction, token_type, amount);
 
@@ -509,23 +927,15 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
         let _reentrancy_guard = crate::utils::ReentrancyGuard::new(&mut self.swap_in_progress)?;
 
         // 🔒 P0 修复：校验市场交易时间窗口（Clock::get() 是外部调用）
-        let current_slot = Clock::get()?.slot;
+        // ✅ v3.14.0: 同时校验 slot 排期与 Unix 时间戳排期
+        let clock = Clock::get()?;
+        self.check_trading_window(clock.slot, clock.unix_timestamp)?;
 
-        // 校验市场已开始
-        if let Some(start_slot) = self.start_slot {
-            require!(
-                current_slot >= start_slot,
-                crate::errors::PredictionMarketError::MarketNotStarted
-            );
-        }
+        // ✅ v3.20.0: 按成交前价格推进 TWAP 累加器（须在替换 b 值之前）
+        self.update_twap(clock.unix_timestamp)?;
 
-        // 校验市场未结束
-        if let Some(ending_slot) = self.ending_slot {
-            require!(
-                current_slot < ending_slot,
-                crate::errors::PredictionMarketError::MarketEnded
-            );
-        }
+        // ✅ v3.24.0: 成交前价格（用于波动率 EWMA，须在替换 b 值之前）
+        let pre_trade_yes_price_bps = self.calculate_yes_price_bps()?;
 
         // ✅ v1.5.0: 计算动态调整后的b值（基于距离结算时间）
         let effective_b = self.calculate_effective_lmsr_b()?;
@@ -545,6 +955,24 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
         // _reentrancy_guard 会在函数退出时自动清理
         // lmsr_b 将在函数结束前手动恢复（闭包结束后）
         let swap_result = (|| -> Result<SwapResult> {
+            // ✅ v3.16.0: 精确输出模式：由 LMSR 反推实际输入（amount 为输入上限）
+            // 在 max_trade_size 检查之前完成，确保上限按实际输入校验
+            let (amount, minimum_receive_amount) = match exact_output {
+                Some(amount_out) => {
+                    // ✅ v3.24.0: 与成交分支使用同一套费率（分级 / 动态），做市返佣不计入（保守估计输入）
+                    let (platform_bps, lp_bps, _) =
+                        self.swap_fee_bps(global_config, direction, token_type, fee_rates, clock.unix_timestamp);
+                    let fee_bps = platform_bps
+                        .checked_add(lp_bps)
+                        .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+                    let amount_in = self.exact_output_amount_in(amount_out, direction, token_type, fee_bps, amount)?;
+
+                    msg!("✅ v3.16.0: exact output={}, resolved input={} (max {})", amount_out, amount_in, amount);
+                    (amount_in, amount_out)
+                }
+                None => (amount, minimum_receive_amount),
+            };
+
             // ✅ v2.5: 将 max_trade_size 检查移到闭包内（防止 b 值永久修改）
             //
             // ❌ 旧问题：该检查在闭包外，失败时会跳过 Line 996 的 b 值恢复
@@ -582,7 +1010,7 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
 
             // ✅ v3.0: 硬上限检查（2b上限，88%价格保护）
             // 禁止买入多数方当 |q_yes - q_no| >= 2b
-            let imbalance = (self.lmsr_q_yes - self.lmsr_q_no).abs() as u64;
+            let imbalance = (self.lmsr_q_yes - self.lmsr_q_no).unsigned_abs();
             let hard_cap = self.lmsr_b
                 .checked_mul(crate::constants::MAX_POSITION_IMBALANCE_MULTIPLIER)
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
@@ -607,8 +1035,10 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
             }
 
             // 计算手续费（支持市场级覆盖）
-            let platform_buy_bps = if self.has_fee_override { self.platform_buy_fee_override } else { global_config.platform_buy_fee };
-            let lp_buy_bps = if self.has_fee_override { self.lp_buy_fee_override } else { global_config.lp_buy_fee };
+            // ✅ v3.23.0: 分级费率只降不升，负 LP 费率转为做市返佣
+            // ✅ v3.24.0: 启用动态费率时 LP 费率随不平衡度与波动率上调
+            let (platform_buy_bps, lp_buy_bps, rebate_bps) =
+                self.swap_fee_bps(global_config, direction, token_type, fee_rates, clock.unix_timestamp);
 
             let platform_fee = amount
                 .checked_mul(platform_buy_bps)
@@ -627,14 +1057,23 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
             let total_fee = platform_fee.checked_add(lp_fee)
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
+            // ✅ v3.23.0: 做市返佣由团队份额出资，计入用户净投入（用户总支出仍为 amount）
+            let maker_rebate = calculate_maker_rebate(amount, rebate_bps, platform_fee, global_config.lp_insurance_allocation_bps)?;
+
             let amount_after_fee = amount.checked_sub(total_fee)
+                .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+                .checked_add(maker_rebate)
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
-            msg!("Fees - platform: {}, lp: {}, net amount: {}", platform_fee, lp_fee, amount_after_fee);
+            msg!("Fees - platform: {}, lp: {}, rebate: {}, net amount: {}", platform_fee, lp_fee, maker_rebate, amount_after_fee);
 
             // 计算可获得的代币数量（使用AMM公式）
-            let buy_result = self.apply_buy(amount_after_fee, token_type)
-                .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+            // ✅ v3.16.0: 精确输出模式直接按 lmsr_buy_cost 成交 N 个代币（取整余量留在池中）
+            let buy_result = match exact_output {
+                Some(token_amount) => self.apply_buy_exact(token_amount, amount_after_fee, token_type),
+                None => self.apply_buy(amount_after_fee, token_type),
+            }
+            .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
             // 检查滑点保护
             require!(
@@ -655,11 +1094,19 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                 0
             };
 
-            // 团队钱包获得剩余部分（默认80%）
+            // 团队钱包获得剩余部分（默认80%，✅ v3.23.0: 扣除做市返佣）
             let team_fee = platform_fee
                 .checked_sub(insurance_allocation)
+                .and_then(|v| v.checked_sub(maker_rebate))
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
+            // ✅ v3.22.0: 引荐人分成从团队份额中扣除（不影响 LP 费与保险池）
+            let referral_fee = match referral {
+                Some((_, share_bps)) => calculate_referral_fee(platform_fee, team_fee, share_bps)?,
+                None => 0,
+            };
+            let team_fee = team_fee - referral_fee;
+
             // 🔒 v1.2.7 + v1.4.0: 用户转 USDC 到市场专用金库
             // 包括：AMM交易金额 + LP费用 + 保险池份额
             let usdc_to_vault = amount_after_fee
@@ -668,33 +1115,60 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                 .checked_add(insurance_allocation)
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
-            token::transfer(
-                CpiContext::new(
-                    token_program.to_account_info(),
-                    token::Transfer {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
                         from: user_usdc_ata.to_account_info(),
+                        mint: usdc_mint.to_account_info(),
                         to: market_usdc_ata.to_account_info(),
                         authority: user.to_account_info(),
                     },
+                    user_signer,
                 ),
-                usdc_to_vault,
+                // ✅ v3.27.0: 转账手续费由用户承担，金库恰好收到 usdc_to_vault（与账本一致）
+                crate::token_compat::gross_up_transfer_fee(&usdc_mint.to_account_info(), usdc_to_vault)?,
+                usdc_mint.decimals,
             )?;
 
             // ✅ v1.4.0: 团队钱包获得平台费的80%
             if team_fee > 0 {
-                token::transfer(
-                    CpiContext::new(
-                        token_program.to_account_info(),
-                        token::Transfer {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        collateral_token_program.to_account_info(),
+                        token_interface::TransferChecked {
                             from: user_usdc_ata.to_account_info(),
+                            mint: usdc_mint.to_account_info(),
                             to: team_usdc_ata.to_account_info(),
                             authority: user.to_account_info(),
                         },
+                        user_signer,
                     ),
                     team_fee,
+                    usdc_mint.decimals,
                 )?;
             }
 
+            // ✅ v3.22.0: 引荐人分成转入引荐人托管账户（由 claim_referral_fees 领取）
+            if let Some((referrer_vault, _)) = referral {
+                if referral_fee > 0 {
+                    token_interface::transfer_checked(
+                        CpiContext::new_with_signer(
+                            collateral_token_program.to_account_info(),
+                            token_interface::TransferChecked {
+                                from: user_usdc_ata.to_account_info(),
+                                mint: usdc_mint.to_account_info(),
+                                to: referrer_vault.clone(),
+                                authority: user.to_account_info(),
+                            },
+                            user_signer,
+                        ),
+                        referral_fee,
+                        usdc_mint.decimals,
+                    )?;
+                }
+            }
+
             // ✅ v1.4.0: 更新全局保险池余额
             // ✅ v2.0: 同步更新市场级保险池贡献追踪
             // 架构说明：
@@ -760,17 +1234,19 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                     .checked_sub(buy_result.token_amount)
                     .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
-                token::transfer(
+                token_interface::transfer_checked(
                     CpiContext::new_with_signer(
-                        token_program.to_account_info(),
-                        token::Transfer {
+                        outcome_token_program.to_account_info(),
+                        token_interface::TransferChecked {
                             from: _global_no_ata.to_account_info(),
+                            mint: no_mint.clone(),
                             to: user_no_ata.to_account_info(),
                             authority: source.to_account_info(),
                         },
                         signer,
                     ),
                     buy_result.token_amount,
+                    global_config.token_decimals_config,
                 )?;
 
                 msg!(
@@ -791,17 +1267,19 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                     .checked_sub(buy_result.token_amount)
                     .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
-                token::transfer(
+                token_interface::transfer_checked(
                     CpiContext::new_with_signer(
-                        token_program.to_account_info(),
-                        token::Transfer {
+                        outcome_token_program.to_account_info(),
+                        token_interface::TransferChecked {
                             from: _global_yes_ata.to_account_info(),
+                            mint: yes_mint.clone(),
                             to: user_yes_ata.to_account_info(),
                             authority: source.to_account_info(),
                         },
                         signer,
                     ),
                     buy_result.token_amount,
+                    global_config.token_decimals_config,
                 )?;
 
                 msg!(
@@ -826,6 +1304,9 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                 usdc_amount: amount_after_fee,      // ✅ v1.1.0: 用户支付的 USDC（税后）
                 token_amount: buy_result.token_amount, // 用户获得的代币
                 fee_usdc: total_fee,                // ✅ v1.1.0: 总手续费（USDC）
+                referral_fee,                       // ✅ v3.22.0: 引荐人分成
+                maker_rebate,                       // ✅ v3.23.0: 做市返佣
+                lp_fee_bps: lp_buy_bps as u16,      // ✅ v3.24.0: 适用 LP 费率
             })
 
         } else {
@@ -840,8 +1321,10 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
             // 计算手续费（支持市场级覆盖）
-            let platform_sell_bps = if self.has_fee_override { self.platform_sell_fee_override } else { global_config.platform_sell_fee };
-            let lp_sell_bps = if self.has_fee_override { self.lp_sell_fee_override } else { global_config.lp_sell_fee };
+            // ✅ v3.23.0: 分级费率只降不升，负 LP 费率转为做市返佣
+            // ✅ v3.24.0: 启用动态费率时 LP 费率随不平衡度与波动率上调
+            let (platform_sell_bps, lp_sell_bps, rebate_bps) =
+                self.swap_fee_bps(global_config, direction, token_type, fee_rates, clock.unix_timestamp);
 
             let platform_fee = sell_result.change_amount
                 .checked_mul(platform_sell_bps)
@@ -860,7 +1343,17 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
             let total_fee = platform_fee.checked_add(lp_fee)
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
+            // ✅ v3.23.0: 做市返佣由团队份额出资，计入用户所得
+            let maker_rebate = calculate_maker_rebate(
+                sell_result.change_amount,
+                rebate_bps,
+                platform_fee,
+                global_config.lp_insurance_allocation_bps,
+            )?;
+
             let amount_after_fee = sell_result.change_amount.checked_sub(total_fee)
+                .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
+                .checked_add(maker_rebate)
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
             // 检查滑点保护
@@ -875,7 +1368,13 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                 crate::errors::PredictionMarketError::InsufficientLiquidity
             );
 
-            msg!("Selling {} tokens for {} USDC (after fee)", amount, amount_after_fee);
+            // ✅ v3.16.0: 精确输出模式用户恰好收到 X USDC，取整余量留在池中
+            let user_receive = exact_output.unwrap_or(amount_after_fee);
+            let pool_payout = sell_result.change_amount
+                .checked_sub(amount_after_fee - user_receive)
+                .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
+
+            msg!("Selling {} tokens for {} USDC (after fee)", amount, user_receive);
 
             // 🔒 预先计算团队手续费与保险分配，用于最小余额校验
             let insurance_allocation_check = if platform_fee > 0 {
@@ -888,13 +1387,14 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
 
             let team_fee_check = platform_fee
                 .checked_sub(insurance_allocation_check)
+                .and_then(|v| v.checked_sub(maker_rebate))
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
             // 🔒 市场金库最小余额保护：确保本次 SELL 完成后余额不低于最小金库余额
-            // 预计本次从 market_usdc_ata 流出：amount_after_fee（给用户） + team_fee_check（团队费）
+            // 预计本次从 market_usdc_ata 流出：user_receive（给用户） + team_fee_check（团队费）
             let vault_balance_before = market_usdc_ata.amount;
             let projected_remaining = (vault_balance_before as i128)
-                .checked_sub(amount_after_fee as i128)
+                .checked_sub(user_receive as i128)
                 .and_then(|v| v.checked_sub(team_fee_check as i128))
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)? as i64;
             require!(
@@ -913,16 +1413,19 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                     .checked_add(amount)
                     .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
-                token::transfer(
-                    CpiContext::new(
-                        token_program.to_account_info(),
-                        token::Transfer {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        outcome_token_program.to_account_info(),
+                        token_interface::TransferChecked {
                             from: user_no_ata.to_account_info(),
+                            mint: no_mint.clone(),
                             to: _global_no_ata.to_account_info(),
                             authority: user.to_account_info(),
                         },
+                        user_signer,
                     ),
                     amount,
+                    global_config.token_decimals_config,
                 )?;
 
                 msg!(
@@ -938,16 +1441,19 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                     .checked_add(amount)
                     .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
-                token::transfer(
-                    CpiContext::new(
-                        token_program.to_account_info(),
-                        token::Transfer {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        outcome_token_program.to_account_info(),
+                        token_interface::TransferChecked {
                             from: user_yes_ata.to_account_info(),
+                            mint: yes_mint.clone(),
                             to: _global_yes_ata.to_account_info(),
                             authority: user.to_account_info(),
                         },
+                        user_signer,
                     ),
                     amount,
+                    global_config.token_decimals_config,
                 )?;
 
                 msg!(
@@ -961,7 +1467,7 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
 
             // 2. Pool 支付 USDC（减少储备）
             self.pool_collateral_reserve = self.pool_collateral_reserve
-                .checked_sub(sell_result.change_amount)
+                .checked_sub(pool_payout)
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
             // 🔒 v1.2.7: 从市场专用金库转 USDC 给用户（扣除手续费后）
@@ -972,17 +1478,19 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                 &[market_usdc_vault_bump],
             ]];
 
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
-                    token_program.to_account_info(),
-                    token::Transfer {
+                    collateral_token_program.to_account_info(),
+                    token_interface::TransferChecked {
                         from: market_usdc_ata.to_account_info(),
+                        mint: usdc_mint.to_account_info(),
                         to: user_usdc_ata.to_account_info(),
                         authority: market_usdc_vault.to_account_info(),
                     },
                     market_signer_seeds,
                 ),
-                amount_after_fee,
+                user_receive,
+                usdc_mint.decimals,
             )?;
 
             // ✅ v1.4.0: 平台手续费分配：部分给团队，部分给保险池
@@ -996,26 +1504,57 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                 0
             };
 
+            // ✅ v3.23.0: 做市返佣已计入 user_receive，从团队份额中扣除
             let team_fee = platform_fee
                 .checked_sub(insurance_allocation)
+                .and_then(|v| v.checked_sub(maker_rebate))
                 .ok_or(crate::errors::PredictionMarketError::MathOverflow)?;
 
+            // ✅ v3.22.0: 引荐人分成从团队份额中扣除（金库总流出不变，最小余额校验仍然有效）
+            let referral_fee = match referral {
+                Some((_, share_bps)) => calculate_referral_fee(platform_fee, team_fee, share_bps)?,
+                None => 0,
+            };
+            let team_fee = team_fee - referral_fee;
+
             // 🔒 v1.2.7 + v1.4.0: 转团队手续费给团队钱包（80%）
             if team_fee > 0 {
-                token::transfer(
+                token_interface::transfer_checked(
                     CpiContext::new_with_signer(
-                        token_program.to_account_info(),
-                        token::Transfer {
+                        collateral_token_program.to_account_info(),
+                        token_interface::TransferChecked {
                             from: market_usdc_ata.to_account_info(),
+                            mint: usdc_mint.to_account_info(),
                             to: team_usdc_ata.to_account_info(),
                             authority: market_usdc_vault.to_account_info(),
                         },
                         market_signer_seeds,
                     ),
                     team_fee,
+                    usdc_mint.decimals,
                 )?;
             }
 
+            // ✅ v3.22.0: 引荐人分成转入引荐人托管账户
+            if let Some((referrer_vault, _)) = referral {
+                if referral_fee > 0 {
+                    token_interface::transfer_checked(
+                        CpiContext::new_with_signer(
+                            collateral_token_program.to_account_info(),
+                            token_interface::TransferChecked {
+                                from: market_usdc_ata.to_account_info(),
+                                mint: usdc_mint.to_account_info(),
+                                to: referrer_vault.clone(),
+                                authority: market_usdc_vault.to_account_info(),
+                            },
+                            market_signer_seeds,
+                        ),
+                        referral_fee,
+                        usdc_mint.decimals,
+                    )?;
+                }
+            }
+
             // ✅ v1.4.0: 保险池份额（20%）留在 market_usdc_ata，更新全局账本
             // ✅ v2.5: 同步更新市场级保险池贡献追踪（对齐买入分支）
             if insurance_allocation > 0 {
@@ -1064,9 +1603,12 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
             // ✅ v1.0.12: 返回准确的交易数据
             // ✅ v1.1.0: 更新为 USDC 字段名
             Ok(SwapResult {
-                usdc_amount: amount_after_fee,     // ✅ v1.1.0: 用户获得的 USDC（税后）
+                usdc_amount: user_receive,         // ✅ v1.1.0: 用户获得的 USDC（税后）
                 token_amount: amount,              // 用户卖出的代币数量
                 fee_usdc: total_fee,               // ✅ v1.1.0: 总手续费（USDC）
+                referral_fee,                      // ✅ v3.22.0: 引荐人分成
+                maker_rebate,                      // ✅ v3.23.0: 做市返佣
+                lp_fee_bps: lp_sell_bps as u16,    // ✅ v3.24.0: 适用 LP 费率
             })
             }
         })(); // 立即执行闭包
@@ -1075,6 +1617,12 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
         // 注意：必须在闭包之后、返回之前恢复
         self.lmsr_b = _b_restorer.original_b;
 
+        // ✅ v3.24.0: 按成交前后价格变动更新波动率 EWMA（均按原始 b 值计价）
+        if swap_result.is_ok() {
+            let post_trade_yes_price_bps = self.calculate_yes_price_bps()?;
+            self.update_volatility_ewma(pre_trade_yes_price_bps, post_trade_yes_price_bps, clock.unix_timestamp);
+        }
+
         // ✅ v2.3: 返回闭包的结果
         // _reentrancy_guard 会在这里 drop，自动解锁 swap_in_progress
         swap_result
@@ -1142,6 +1690,57 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
         Some(result)
     }
 
+    fn apply_buy_exact(&mut self, token_amount: u64, max_cost: u64, token_type: u8) -> Option<BuyResult> {
+        use crate::math::LmsrCalculator;
+
+        // ✅ v3.16.0: 直接使用 lmsr_buy_cost，不经过二分反推
+        let cost = crate::math::lmsr::lmsr_buy_cost(
+            self.lmsr_b,
+            self.lmsr_q_yes,
+            self.lmsr_q_no,
+            token_amount,
+            token_type == 1,
+        )
+        .ok()?;
+        if cost > max_cost {
+            msg!("⚠️ Exact-output cost {} exceeds budget {}", cost, max_cost);
+            return None;
+        }
+
+        let calc = LmsrCalculator::new(self);
+        let (new_q_yes, new_q_no) = calc
+            .new_positions_after_buy(token_amount, token_type == 1)
+            .ok()?;
+
+        // 更新持仓
+        self.lmsr_q_yes = new_q_yes;
+        self.lmsr_q_no = new_q_no;
+
+        // ✅ v1.3.1: 验证持仓不平衡度（与 apply_buy 一致）
+        let max_imbalance = (self.lmsr_b as i64)
+            .checked_mul(crate::constants::MAX_POSITION_IMBALANCE_MULTIPLIER as i64)?;
+        let current_imbalance = (new_q_yes - new_q_no).abs();
+
+        if current_imbalance > max_imbalance {
+            msg!(
+                "⚠️ Position imbalance limit exceeded: current={}, max={} ({}x lmsr_b)",
+                current_imbalance,
+                max_imbalance,
+                crate::constants::MAX_POSITION_IMBALANCE_MULTIPLIER
+            );
+            return None;
+        }
+
+        Some(BuyResult {
+            token_amount,
+            change_amount: cost,
+            current_yes_reserves: 0, // LMSR不使用reserves
+            current_no_reserves: 0,
+            new_yes_reserves: 0,
+            new_no_reserves: 0,
+        })
+    }
+
     fn apply_sell(&mut self, change_amount: u64, token_type: u8) -> Option<SellResult> {
         use crate::math::LmsrCalculator;
 
@@ -1228,6 +1827,45 @@ impl Market {
         crate::math::lmsr::lmsr_tokens_for_usdc(b, q_yes, q_no, sol_amount, is_yes)
     }
 
+    /// ✅ v3.16.0: 精确输出模式：计算恰好获得 `amount_out` 所需的输入
+    ///
+    /// - 买入（direction=0）：`amount_out` 为代币数量，返回含费 USDC（lmsr_buy_cost 按费率反推）
+    /// - 卖出（direction=1）：`amount_out` 为税后 USDC，返回需卖出的最少代币数量
+    ///
+    /// 使用当前 `lmsr_b`（swap 中已替换为动态 b 值）；所需输入超过 `max_amount_in` 时返回 SlippageExceeded
+    pub fn exact_output_amount_in(
+        &self,
+        amount_out: u64,
+        direction: u8,
+        token_type: u8,
+        fee_bps: u64,
+        max_amount_in: u64,
+    ) -> Result<u64> {
+        require!(amount_out > 0, crate::errors::PredictionMarketError::InvalidAmount);
+        let is_yes = token_type == 1;
+
+        let amount_in = if direction == 0 {
+            let cost = crate::math::lmsr::lmsr_buy_cost(self.lmsr_b, self.lmsr_q_yes, self.lmsr_q_no, amount_out, is_yes)?;
+            crate::math::lmsr::gross_up_for_fee(cost, fee_bps)?
+        } else {
+            let payout = crate::math::lmsr::gross_up_for_fee(amount_out, fee_bps)?;
+            crate::math::lmsr::lmsr_min_tokens_for_payout(
+                self.lmsr_b,
+                self.lmsr_q_yes,
+                self.lmsr_q_no,
+                payout,
+                is_yes,
+                max_amount_in,
+            )?
+        };
+
+        require!(
+            amount_in <= max_amount_in,
+            crate::errors::PredictionMarketError::SlippageExceeded
+        );
+        Ok(amount_in)
+    }
+
     /// ✅ v1.3.1: 计算当前YES代币的市场价格（基点）
     ///
     /// 🔒 CRITICAL FIX (2025-11-03): 使用精确的LMSR边际价格函数
@@ -1250,10 +1888,10 @@ impl Market {
         // 将 FixedPoint 转换为基点（0-10000）
         // FixedPoint 的精度是 10^18，价格范围是 [0, 1]
         // 需要转换为 [0, 10000] 的基点
-        let price_bps = (yes_price_fp as u128)
+        let price_bps = yes_price_fp
             .checked_mul(10000)
             .ok_or(crate::errors::PredictionMarketError::MathOverflow)?
-            .checked_div(crate::math::fixed_point::constants::ONE as u128)
+            .checked_div(crate::math::fixed_point::constants::ONE)
             .ok_or(crate::errors::PredictionMarketError::MathOverflow)? as u64;
 
         // 限制在0-10000范围内（防御性编程）
@@ -1280,24 +1918,24 @@ impl Market {
     /// - 原价格50%时，买10000 USDC YES原本推至60% → 现在仅推至55%
     pub fn calculate_effective_lmsr_b(&self) -> Result<u64> {
         // 如果市场没有结束时间，使用原始b值
-        let ending_slot = match self.ending_slot {
-            Some(slot) => slot,
-            None => return Ok(self.lmsr_b),
-        };
+        if self.ending_slot.is_none() && self.end_time == 0 {
+            return Ok(self.lmsr_b);
+        }
 
         // 获取当前时间
         let clock = Clock::get()?;
-        let current_slot = clock.slot;
-
-        // 如果已经结束，使用原始b值（不应该还在交易，但作为保护）
-        if current_slot >= ending_slot {
-            return Ok(self.lmsr_b);
-        }
+        self.effective_lmsr_b_at(clock.slot, clock.unix_timestamp)
+    }
 
+    /// ✅ v3.14.1: 按给定 slot / 时间戳计算有效 b 值（阶段选择逻辑，不读取 Clock）
+    pub fn effective_lmsr_b_at(&self, current_slot: u64, now: i64) -> Result<u64> {
         // 计算距离结算的时间（秒）
-        // Solana slot时间约400ms = 0.4秒
-        let slots_remaining = ending_slot.saturating_sub(current_slot);
-        let seconds_to_settlement = (slots_remaining as i64) * 4 / 10; // 0.4秒 = 4/10
+        // ✅ v3.14.0: 设置了 end_time 时使用实际时间，否则按 slot 估算
+        let seconds_to_settlement = match self.seconds_to_end(current_slot, now) {
+            Some(secs) if secs > 0 => secs,
+            // 如果已经结束，使用原始b值（不应该还在交易，但作为保护）
+            _ => return Ok(self.lmsr_b),
+        };
 
         // 根据时间选择b值乘数
         let b_multiplier = if seconds_to_settlement < crate::constants::FINAL_STAGE_SECONDS {
@@ -1357,6 +1995,376 @@ impl Market {
     // ✅ v3.0: 熔断机制和内部交换辅助方法
     // ═══════════════════════════════════════════════════════════════
 
+    /// ✅ v3.5.0: 是否属于负风险事件组
+    pub fn is_grouped(&self) -> bool {
+        self.event_group != Pubkey::default()
+    }
+
+    /// ✅ v3.8.0: 价格源自动结算配置（未配置返回 None）
+    pub fn price_feed_config(&self) -> Option<PriceFeedConfig> {
+        (self.price_feed != Pubkey::default()).then_some(PriceFeedConfig {
+            feed: self.price_feed,
+            feed_id: self.price_feed_id,
+            threshold: self.price_threshold,
+            threshold_expo: self.price_threshold_expo,
+            resolve_after: self.price_resolve_after,
+            max_staleness_secs: self.price_max_staleness_secs,
+            max_conf_bps: self.price_max_conf_bps,
+        })
+    }
+
+    /// ✅ v3.14.0: 校验交易时间窗口（slot 排期与 Unix 时间戳排期，均设置时同时生效）
+    pub fn check_trading_window(&self, current_slot: u64, now: i64) -> Result<()> {
+        use crate::errors::PredictionMarketError;
+
+        // 校验市场已开始
+        if let Some(start_slot) = self.start_slot {
+            require!(current_slot >= start_slot, PredictionMarketError::MarketNotStarted);
+        }
+        if self.start_time > 0 {
+            require!(now >= self.start_time, PredictionMarketError::MarketNotStarted);
+        }
+
+        // 校验市场未结束
+        if let Some(ending_slot) = self.ending_slot {
+            require!(current_slot < ending_slot, PredictionMarketError::MarketEnded);
+        }
+        if self.end_time > 0 {
+            require!(now < self.end_time, PredictionMarketError::MarketEnded);
+        }
+
+        Ok(())
+    }
+
+    /// ✅ v3.20.0: 按变动前的现价推进 TWAP 累加器，并按间隔写入观测点
+    ///
+    /// 必须在任何改变 LMSR 持仓或 b 值的操作之前调用，
+    /// 这样累加的是上一段时间内实际生效的价格
+    pub fn update_twap(&mut self, now: i64) -> Result<()> {
+        use crate::constants::TWAP_MIN_OBSERVATION_INTERVAL_SECS;
+        use crate::errors::PredictionMarketError;
+
+        // 首次调用：仅记录起点，不累加
+        if self.twap_last_update_ts == 0 {
+            self.twap_last_update_ts = now;
+            self.twap_observation_index = 0;
+            self.twap_observation_count = 1;
+            self.twap_observations[0] = TwapObservation {
+                timestamp: now,
+                cumulative_yes_price: self.twap_cumulative_yes_price,
+            };
+            return Ok(());
+        }
+
+        let elapsed = now.saturating_sub(self.twap_last_update_ts);
+        if elapsed <= 0 {
+            return Ok(());
+        }
+
+        let spot = self.calculate_yes_price_bps()? as u128;
+        self.twap_cumulative_yes_price = self
+            .twap_cumulative_yes_price
+            .checked_add(spot * elapsed as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.twap_last_update_ts = now;
+
+        // 距离最新观测足够久时写入新观测（环形覆盖最旧的观测）
+        let latest = self.twap_observations[self.twap_observation_index as usize];
+        if now.saturating_sub(latest.timestamp) >= TWAP_MIN_OBSERVATION_INTERVAL_SECS {
+            let next = (self.twap_observation_index as usize + 1) % TWAP_OBSERVATION_COUNT;
+            self.twap_observations[next] = TwapObservation {
+                timestamp: now,
+                cumulative_yes_price: self.twap_cumulative_yes_price,
+            };
+            self.twap_observation_index = next as u8;
+            self.twap_observation_count = self
+                .twap_observation_count
+                .saturating_add(1)
+                .min(TWAP_OBSERVATION_COUNT as u8);
+        }
+
+        Ok(())
+    }
+
+    /// ✅ v3.24.0: 按半衰期衰减到 `now` 的波动率 EWMA（基点）
+    pub fn decayed_volatility_bps(&self, now: i64) -> u32 {
+        use crate::constants::DYNAMIC_FEE_VOLATILITY_HALF_LIFE_SECS;
+
+        let elapsed = now.saturating_sub(self.volatility_last_update_ts);
+        if self.volatility_last_update_ts == 0 || elapsed <= 0 {
+            return self.volatility_ewma_bps;
+        }
+        let half_lives = elapsed / DYNAMIC_FEE_VOLATILITY_HALF_LIFE_SECS;
+        if half_lives >= 32 {
+            0
+        } else {
+            self.volatility_ewma_bps >> half_lives
+        }
+    }
+
+    /// ✅ v3.24.0: 以一笔成交前后的 YES 价格变动更新波动率 EWMA
+    pub fn update_volatility_ewma(&mut self, pre_trade_yes_price_bps: u16, post_trade_yes_price_bps: u16, now: i64) {
+        use crate::constants::{BASIS_POINTS_DIVISOR, DYNAMIC_FEE_EWMA_ALPHA_BPS};
+
+        let sample = pre_trade_yes_price_bps.abs_diff(post_trade_yes_price_bps) as u64;
+        let decayed = self.decayed_volatility_bps(now) as u64;
+        let ewma = (decayed * (BASIS_POINTS_DIVISOR - DYNAMIC_FEE_EWMA_ALPHA_BPS)
+            + sample * DYNAMIC_FEE_EWMA_ALPHA_BPS)
+            / BASIS_POINTS_DIVISOR;
+
+        self.volatility_ewma_bps = ewma as u32;
+        self.volatility_last_update_ts = now;
+    }
+
+    /// ✅ v3.24.0: 动态 LP 费率（基点）
+    ///
+    /// 费率 = clamp(基础 LP 费率 + 不平衡溢价 + 波动率溢价, min, max)：
+    /// - 不平衡溢价按 `get_imbalance_ratio()` 线性增长，仅对加剧不平衡的成交收取，
+    ///   池子回归平衡时自然回落
+    /// - 波动率溢价 = 衰减后的波动率 EWMA × 系数，无成交时按半衰期回落
+    ///
+    /// 未启用时返回基础费率
+    pub fn dynamic_lp_fee_bps(&self, base_lp_bps: u64, direction: u8, token_type: u8, now: i64) -> u64 {
+        use crate::constants::{BASIS_POINTS_DIVISOR, DYNAMIC_FEE_IMBALANCE_FULL_SCALE};
+
+        if !self.dynamic_fee_enabled {
+            return base_lp_bps;
+        }
+
+        // 买 YES / 卖 NO 都使池子 YES 储备相对 NO 储备减少
+        let shifts_toward_no = (direction == 0) == (token_type == 1);
+        let worsens_imbalance = match self.pool_yes_reserve.cmp(&self.pool_no_reserve) {
+            std::cmp::Ordering::Less => shifts_toward_no,
+            std::cmp::Ordering::Greater => !shifts_toward_no,
+            std::cmp::Ordering::Equal => true,
+        };
+
+        let imbalance_premium = if worsens_imbalance {
+            let excess = self
+                .get_imbalance_ratio()
+                .saturating_sub(100)
+                .min(DYNAMIC_FEE_IMBALANCE_FULL_SCALE);
+            (self.dynamic_fee_imbalance_bps as u128 * excess / DYNAMIC_FEE_IMBALANCE_FULL_SCALE) as u64
+        } else {
+            0
+        };
+
+        let volatility_premium = self.decayed_volatility_bps(now) as u64
+            * self.dynamic_fee_volatility_multiplier_bps as u64
+            / BASIS_POINTS_DIVISOR;
+
+        base_lp_bps
+            .saturating_add(imbalance_premium)
+            .saturating_add(volatility_premium)
+            .clamp(self.dynamic_fee_min_bps as u64, self.dynamic_fee_max_bps as u64)
+    }
+
+    /// ✅ v3.24.0: 本次成交适用的 (平台费率, LP 费率, 做市返佣费率)
+    ///
+    /// 依次应用：市场级覆盖 → 成交量分级（只降不升）→ 动态 LP 费率（做市返佣成交不适用）
+    pub fn swap_fee_bps(
+        &self,
+        global_config: &Config,
+        direction: u8,
+        token_type: u8,
+        fee_rates: Option<FeeRates>,
+        now: i64,
+    ) -> (u64, u64, u64) {
+        let (platform_bps, lp_bps) = match (direction, self.has_fee_override) {
+            (0, true) => (self.platform_buy_fee_override, self.lp_buy_fee_override),
+            (0, false) => (global_config.platform_buy_fee, global_config.lp_buy_fee),
+            (_, true) => (self.platform_sell_fee_override, self.lp_sell_fee_override),
+            (_, false) => (global_config.platform_sell_fee, global_config.lp_sell_fee),
+        };
+        let (platform_bps, lp_bps, rebate_bps) = resolve_fee_bps(platform_bps, lp_bps, fee_rates);
+        if rebate_bps > 0 {
+            return (platform_bps, lp_bps, rebate_bps);
+        }
+        (platform_bps, self.dynamic_lp_fee_bps(lp_bps, direction, token_type, now), 0)
+    }
+
+    /// ✅ v3.20.0: 查询最近 `window_secs` 秒的 YES 时间加权平均价格
+    ///
+    /// 当前累计值按现价外推到 `now`，起点取不晚于 `now - window_secs` 的最新观测，
+    /// 因此实际覆盖窗口可能略长于请求窗口（最多一个观测间隔）
+    ///
+    /// # 返回值
+    /// `(twap_bps, observed_window_secs)`
+    pub fn twap_yes_price_bps(&self, window_secs: u32, now: i64) -> Result<(u16, i64)> {
+        use crate::errors::PredictionMarketError;
+
+        require!(window_secs > 0, PredictionMarketError::InvalidTwapWindow);
+        require!(
+            self.twap_last_update_ts > 0 && self.twap_observation_count > 0,
+            PredictionMarketError::InsufficientTwapHistory
+        );
+
+        // 外推当前累计值
+        let spot = self.calculate_yes_price_bps()? as u128;
+        let since_update = now.saturating_sub(self.twap_last_update_ts).max(0) as u128;
+        let cumulative_now = self
+            .twap_cumulative_yes_price
+            .checked_add(spot * since_update)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        // 从最新观测向前查找第一个覆盖窗口起点的观测
+        let target = now.saturating_sub(window_secs as i64);
+        let start = (0..self.twap_observation_count as usize)
+            .map(|i| {
+                let idx = (self.twap_observation_index as usize + TWAP_OBSERVATION_COUNT - i)
+                    % TWAP_OBSERVATION_COUNT;
+                self.twap_observations[idx]
+            })
+            .find(|obs| obs.timestamp <= target)
+            .ok_or(PredictionMarketError::InsufficientTwapHistory)?;
+
+        let observed = now.saturating_sub(start.timestamp);
+        require!(observed > 0, PredictionMarketError::InsufficientTwapHistory);
+
+        let twap = cumulative_now
+            .checked_sub(start.cumulative_yes_price)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / observed as u128;
+
+        Ok((twap.min(10000) as u16, observed))
+    }
+
+    /// ✅ v3.14.0: 市场是否已到达结束时间（可结算）
+    ///
+    /// 未设置任何结束时间的市场始终视为可结算（与 v3.14.0 之前的行为一致）
+    pub fn has_ended(&self, current_slot: u64, now: i64) -> bool {
+        let slot_ended = match self.ending_slot {
+            Some(end) => current_slot >= end,
+            None => true,
+        };
+        slot_ended && (self.end_time == 0 || now >= self.end_time)
+    }
+
+    /// ✅ v3.14.0: 距离结束的秒数（None = 无结束时间）
+    ///
+    /// 设置了 end_time 时按实际时间计算；仅有 ending_slot 时按 400ms/slot 估算
+    pub fn seconds_to_end(&self, current_slot: u64, now: i64) -> Option<i64> {
+        if self.end_time > 0 {
+            Some(self.end_time.saturating_sub(now))
+        } else {
+            self.ending_slot
+                .map(|end| (end.saturating_sub(current_slot) as i64).saturating_mul(4) / 10)
+        }
+    }
+
+    /// ✅ v3.10.0: 是否以 Invalid 结果结算
+    pub fn is_invalid(&self) -> bool {
+        self.is_completed && self.winner_token_type == crate::types::MarketOutcome::Invalid.to_u8()
+    }
+
+    /// ✅ v3.10.0: Invalid 结果的退款比例（基于最后成交价格快照）
+    ///
+    /// # 返回
+    /// `(yes_ratio, no_ratio)`，两者之和为 10000，保证每个完整集合恰好退还 1 USDC
+    pub fn invalid_refund_ratios(&self) -> (u64, u64) {
+        let yes_ratio = (self.last_trade_yes_price_bps as u64).min(crate::constants::BASIS_POINTS_DIVISOR);
+        (yes_ratio, crate::constants::BASIS_POINTS_DIVISOR - yes_ratio)
+    }
+
+    /// ✅ v3.10.1: Invalid 市场 LP 按本金退出的金额
+    ///
+    /// 退还 min(本金份额, 池内 USDC 份额)：池内 USDC 不足本金时按池子份额退出（亏损按份额分摊），
+    /// 高于本金的做市盈利保留在池中
+    ///
+    /// # 参数
+    /// * `lp_shares` - 本次退出的份额
+    /// * `position_lp_shares` / `position_invested_usdc` - LP 头寸的总份额与累计投入本金
+    ///
+    /// # 返回
+    /// `(退还 USDC, 扣减的本金份额, 池内 USDC 份额)`
+    pub fn invalid_lp_unwind(
+        &self,
+        lp_shares: u64,
+        position_lp_shares: u64,
+        position_invested_usdc: u64,
+    ) -> Result<(u64, u64, u64)> {
+        let pool_share_usdc = crate::utils::calculate_proportional_share(
+            self.pool_collateral_reserve,
+            lp_shares as u128,
+            self.total_lp_shares as u128,
+        )?;
+        let invested_usdc_share = crate::utils::calculate_proportional_share(
+            position_invested_usdc,
+            lp_shares as u128,
+            position_lp_shares as u128,
+        )?;
+        Ok((invested_usdc_share.min(pool_share_usdc), invested_usdc_share, pool_share_usdc))
+    }
+
+    /// ✅ v3.11.1: 校验已超过结算截止（无截止的市场不可按截止作废）
+    pub fn check_resolution_deadline(&self, current_slot: u64) -> Result<()> {
+        require!(
+            self.resolution_deadline_slot > 0,
+            crate::errors::PredictionMarketError::NoResolutionDeadline
+        );
+        require!(
+            current_slot >= self.resolution_deadline_slot,
+            crate::errors::PredictionMarketError::ResolutionDeadlineNotReached
+        );
+        Ok(())
+    }
+
+    /// ✅ v3.9.2: 管理员能否对本市场提交结算提案
+    ///
+    /// 指定结算人 / 价格源市场须按约定方式结算，仅当委员会已记录僵局升级（`escalated`）
+    /// 或已超过结算截止时，才回退由全局管理员结算
+    pub fn admin_resolution_allowed(&self, escalated: bool, current_slot: u64) -> bool {
+        if !self.has_resolver() && self.price_feed_config().is_none() {
+            return true;
+        }
+        escalated || self.check_resolution_deadline(current_slot).is_ok()
+    }
+
+    /// ✅ v3.9.0: 是否指定了结算人（单一密钥或委员会）
+    pub fn has_resolver(&self) -> bool {
+        self.resolver != Pubkey::default()
+    }
+
+    /// ✅ v3.5.0: 是否可加入事件组（未入组、未结算、非标量）
+    /// ✅ v3.6.2: 条件市场不可入组——作废条件市场只按父市场结果结算，不携带事件组账户
+    pub fn is_groupable(&self) -> bool {
+        !self.is_grouped() && !self.is_completed && !self.is_scalar && !self.is_conditional()
+    }
+
+    /// ✅ v3.6.0: 是否为条件市场
+    pub fn is_conditional(&self) -> bool {
+        self.parent_market != Pubkey::default()
+    }
+
+    /// ✅ v3.6.0: 父市场结果是否满足本条件市场的要求
+    ///
+    /// 父市场作废、平局或反向结算均视为条件不满足
+    pub fn parent_condition_met(&self, parent: &Market) -> bool {
+        parent.is_completed
+            && !parent.is_voided
+            && parent.winner_token_type == self.parent_required_outcome
+    }
+
+    /// ✅ v3.6.1: 校验条件市场可被作废（父市场已结算且结果不满足要求）
+    pub fn check_conditional_void(&self, parent: &Market) -> Result<()> {
+        require!(self.is_conditional(), crate::errors::PredictionMarketError::NotConditionalMarket);
+        require!(!self.is_completed, crate::errors::PredictionMarketError::MarketIsCompleted);
+        require!(parent.is_completed, crate::errors::PredictionMarketError::ParentMarketNotResolved);
+        require!(
+            !self.parent_condition_met(parent),
+            crate::errors::PredictionMarketError::ParentConditionMet
+        );
+        Ok(())
+    }
+
+    /// ✅ v3.4.0: 标量市场区间（非标量市场返回 None）
+    pub fn scalar_bounds(&self) -> Option<ScalarBounds> {
+        self.is_scalar.then_some(ScalarBounds {
+            lower: self.scalar_lower_bound,
+            upper: self.scalar_upper_bound,
+        })
+    }
+
     /// 计算池子不平衡比例（放大100倍精度）
     ///
     /// # 返回值
@@ -1526,24 +2534,18 @@ impl Market {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::state::config::Config;
+    use crate::constants::BASIS_POINTS_DIVISOR;
 
-    /// ✅ v2.5 回归测试：验证 swap 失败时状态能正确恢复
-    ///
-    /// 测试场景：
-    /// 1. swap_in_progress 应在失败后自动清除（ReentrancyGuard RAII）
-    /// 2. lmsr_b 应在失败后恢复原值（闭包内 require! 失败）
-    #[test]
-    fn test_swap_state_recovery_on_failure() {
-        // 创建测试市场
-        let mut market = Market {
+    /// 测试市场（二元、未结算、无排期限制）
+    fn test_market() -> Market {
+        Market {
             yes_token_mint: Pubkey::new_unique(),
             no_token_mint: Pubkey::new_unique(),
             creator: Pubkey::new_unique(),
             total_collateral_locked: 0,
             total_yes_minted: 0,
             total_no_minted: 0,
-            pool_collateral_reserve: 100, // 小值，让 max_trade_size 很小
+            pool_collateral_reserve: 0,
             pool_yes_reserve: 0,
             pool_no_reserve: 0,
             total_lp_shares: 0,
@@ -1592,9 +2594,75 @@ mod tests {
             market_paused: false,
             // ✅ v3.1.3: 哨兵代币追踪
             sentinel_no_minted: true,
-        };
+            // ✅ v3.4.0: 标量市场
+            is_scalar: false,
+            scalar_lower_bound: 0,
+            scalar_upper_bound: 0,
+            scalar_resolved_value: 0,
+            // ✅ v3.5.0: 负风险事件组
+            event_group: Pubkey::default(),
+
+            // ✅ v3.6.0: 条件市场
+            parent_market: Pubkey::default(),
+            parent_required_outcome: 0,
+            is_voided: false,
+
+            // ✅ v3.8.0: 价格源自动结算
+            price_feed: Pubkey::default(),
+            price_threshold: 0,
+            price_threshold_expo: 0,
+            price_resolve_after: 0,
+            price_max_staleness_secs: 0,
+            price_max_conf_bps: 0,
+
+            // ✅ v3.9.0: 市场级结算人
+            resolver: Pubkey::default(),
+            resolver_is_committee: false,
+
+            // ✅ v3.10.0: 无效结果退款
+            last_trade_yes_price_bps: 5000,
+
+            // ✅ v3.11.0: 结算截止
+            resolution_deadline_slot: 0,
+
+            // ✅ v3.13.0: 规则元数据
+            has_metadata: false,
+
+            // ✅ v3.14.0: Unix 时间戳排期
+            start_time: 0,
+            end_time: 0,
+
+            // ✅ v3.20.0: TWAP 价格累加器
+            twap_cumulative_yes_price: 0,
+            twap_last_update_ts: 0,
+            twap_observation_index: 0,
+            twap_observation_count: 0,
+            twap_observations: [TwapObservation::default(); TWAP_OBSERVATION_COUNT],
+
+            // ✅ v3.24.0: 动态 LP 费率
+            dynamic_fee_enabled: false,
+            dynamic_fee_min_bps: 0,
+            dynamic_fee_max_bps: 0,
+            dynamic_fee_imbalance_bps: 0,
+            dynamic_fee_volatility_multiplier_bps: 0,
+            volatility_ewma_bps: 0,
+            volatility_last_update_ts: 0,
+            price_feed_id: [0u8; 32],
+        }
+    }
 
-        let original_b = market.lmsr_b;
+    /// ✅ v2.5 回归测试：验证 swap 失败时状态能正确恢复
+    ///
+    /// 测试场景：
+    /// 1. swap_in_progress 应在失败后自动清除（ReentrancyGuard RAII）
+    /// 2. lmsr_b 应在失败后恢复原值（闭包内 require! 失败）
+    #[test]
+    fn test_swap_state_recovery_on_failure() {
+        // 创建测试市场
+        let mut market = Market {
+            pool_collateral_reserve: 100, // 小值，让 max_trade_size 很小
+            ..test_market()
+        };
 
         // 创建 mock 上下文（swap 需要的账户）
         // 注意：这个测试只验证状态恢复逻辑，不执行完整的 CPI 调用
@@ -1682,4 +2750,330 @@ mod tests {
         let guard3 = crate::utils::ReentrancyGuard::new(&mut swap_in_progress);
         assert!(guard3.is_ok(), "Should acquire lock after release");
     }
+
+    /// ✅ v3.6.2: 条件市场不可加入事件组（否则作废时缺少事件组账户而无法结算）
+    #[test]
+    fn test_conditional_market_not_groupable() {
+        assert!(test_market().is_groupable());
+
+        let conditional = Market {
+            parent_market: Pubkey::new_unique(),
+            parent_required_outcome: 1,
+            ..test_market()
+        };
+        assert!(!conditional.is_groupable());
+
+        let grouped = Market { event_group: Pubkey::new_unique(), ..test_market() };
+        assert!(!grouped.is_groupable());
+        assert!(!Market { is_scalar: true, ..test_market() }.is_groupable());
+        assert!(!Market { is_completed: true, ..test_market() }.is_groupable());
+    }
+
+    /// ✅ v3.4.0: 标量市场赔付比例
+    #[test]
+    fn test_scalar_payout_ratios() {
+        let bounds = ScalarBounds { lower: 50_000, upper: 150_000 };
+
+        // 区间内线性插值
+        assert_eq!(bounds.payout_ratios(100_000).unwrap(), (5_000, 5_000));
+        assert_eq!(bounds.payout_ratios(75_000).unwrap(), (2_500, 7_500));
+
+        // 区间外截断
+        assert_eq!(bounds.payout_ratios(10_000).unwrap(), (0, 10_000));
+        assert_eq!(bounds.payout_ratios(200_000).unwrap(), (10_000, 0));
+
+        // 负数区间
+        let bounds = ScalarBounds { lower: -100, upper: 100 };
+        assert_eq!(bounds.payout_ratios(-50).unwrap(), (2_500, 7_500));
+
+        // 极端区间不溢出
+        let bounds = ScalarBounds { lower: i64::MIN, upper: i64::MAX };
+        let (yes, no) = bounds.payout_ratios(0).unwrap();
+        assert_eq!(yes + no, 10_000);
+
+        // 无效区间拒绝
+        assert!(ScalarBounds { lower: 10, upper: 10 }.payout_ratios(10).is_err());
+        assert!(ScalarBounds { lower: 20, upper: 10 }.validate().is_err());
+    }
+
+    /// ✅ v3.22.0: 引荐人分成不超过团队份额
+    #[test]
+    fn test_calculate_referral_fee() {
+        // 平台费 1000，团队份额 800，分成 30% → 300
+        assert_eq!(calculate_referral_fee(1_000, 800, 3_000).unwrap(), 300);
+
+        // 分成超过团队份额时截断（保险池份额不受影响）
+        assert_eq!(calculate_referral_fee(1_000, 800, 10_000).unwrap(), 800);
+
+        // 无分成
+        assert_eq!(calculate_referral_fee(1_000, 800, 0).unwrap(), 0);
+        assert_eq!(calculate_referral_fee(0, 0, 5_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_resolve_fee_bps_and_maker_rebate() {
+        // 无分级：基础费率
+        assert_eq!(resolve_fee_bps(100, 200, None), (100, 200, 0));
+
+        // 分级只降不升
+        let tier = FeeRates { platform_fee_bps: 50, lp_fee_bps: 300 };
+        assert_eq!(resolve_fee_bps(100, 200, Some(tier)), (50, 200, 0));
+
+        // 负 LP 费率 → LP 费为 0，返佣 20 bps
+        let maker = FeeRates { platform_fee_bps: 80, lp_fee_bps: -20 };
+        assert_eq!(resolve_fee_bps(100, 200, Some(maker)), (80, 0, 20));
+
+        // 成交 1000 USDC，平台费 8 USDC，保险池 20% → 团队份额 6.4 USDC，返佣 2 USDC
+        assert_eq!(calculate_maker_rebate(1_000_000_000, 20, 8_000_000, 2_000).unwrap(), 2_000_000);
+
+        // 返佣超过团队份额时截断
+        assert_eq!(calculate_maker_rebate(1_000_000_000, 50, 1_000_000, 2_000).unwrap(), 800_000);
+        assert_eq!(calculate_maker_rebate(1_000_000_000, 0, 1_000_000, 2_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_refund_ratios() {
+        // 价格快照 p → YES=p, NO=1-p，合计恰好 10000（完整集合退 1 USDC）
+        for price in [0u16, 1, 1_000, 5_000, 9_999, 10_000] {
+            let market = Market { last_trade_yes_price_bps: price, ..test_market() };
+            let (yes, no) = market.invalid_refund_ratios();
+            assert_eq!(yes, price as u64);
+            assert_eq!(yes + no, BASIS_POINTS_DIVISOR);
+        }
+
+        // 越界快照截断为 100% YES
+        let market = Market { last_trade_yes_price_bps: 12_000, ..test_market() };
+        assert_eq!(market.invalid_refund_ratios(), (BASIS_POINTS_DIVISOR, 0));
+    }
+
+    #[test]
+    fn test_invalid_lp_unwind_returns_min_of_invested_and_pool_share() {
+        // 池内盈利：2 个 LP 各 500 份额、各投入 1000，池内 3000 → 各退本金 1000，盈利留在池中
+        let market = Market { pool_collateral_reserve: 3_000, total_lp_shares: 1_000, ..test_market() };
+        assert_eq!(market.invalid_lp_unwind(500, 500, 1_000).unwrap(), (1_000, 1_000, 1_500));
+
+        // 池内亏损：池内 1200 → 按池子份额退 600，本金份额全部扣减
+        let market = Market { pool_collateral_reserve: 1_200, total_lp_shares: 1_000, ..test_market() };
+        assert_eq!(market.invalid_lp_unwind(500, 500, 1_000).unwrap(), (600, 1_000, 600));
+
+        // 部分退出：本金按头寸份额比例扣减
+        let market = Market { pool_collateral_reserve: 3_000, total_lp_shares: 1_000, ..test_market() };
+        assert_eq!(market.invalid_lp_unwind(100, 500, 1_000).unwrap(), (200, 200, 300));
+
+        // 依次退出不超过池内余额
+        let mut market = Market { pool_collateral_reserve: 1_999, total_lp_shares: 3, ..test_market() };
+        let mut paid = 0;
+        for _ in 0..3 {
+            let (out, _, _) = market.invalid_lp_unwind(1, 1, 1_000).unwrap();
+            market.pool_collateral_reserve -= out;
+            market.total_lp_shares -= 1;
+            paid += out;
+        }
+        assert!(paid <= 1_999);
+
+        // 无份额时拒绝（除零）
+        let market = Market { pool_collateral_reserve: 100, total_lp_shares: 0, ..test_market() };
+        assert!(market.invalid_lp_unwind(1, 1, 100).is_err());
+    }
+
+    #[test]
+    fn test_resolution_deadline() {
+        // 无 ending_slot / end_time 的市场没有截止
+        assert!(test_market().check_resolution_deadline(u64::MAX).is_err());
+
+        let market = Market { resolution_deadline_slot: 1_000, ..test_market() };
+        assert!(market.check_resolution_deadline(999).is_err());
+        assert!(market.check_resolution_deadline(1_000).is_ok());
+        assert!(market.check_resolution_deadline(1_001).is_ok());
+
+        // 事件组截止 = 所有成员截止均已到达（以最晚成员为准）
+        let members = [
+            Market { resolution_deadline_slot: 1_000, ..test_market() },
+            Market { resolution_deadline_slot: 2_000, ..test_market() },
+        ];
+        let group_passed = |slot| members.iter().all(|m| m.check_resolution_deadline(slot).is_ok());
+        assert!(!group_passed(1_500));
+        assert!(group_passed(2_000));
+
+        // 任一成员无截止 → 事件组无截止，只能由管理员作废
+        let members = [
+            Market { resolution_deadline_slot: 1_000, ..test_market() },
+            test_market(),
+        ];
+        assert!(!members.iter().all(|m| m.check_resolution_deadline(u64::MAX).is_ok()));
+    }
+
+    #[test]
+    fn test_admin_resolution_restricted_on_designated_markets() {
+        // 普通市场：管理员随时可提案
+        assert!(test_market().admin_resolution_allowed(false, 0));
+
+        let resolver_market = Market { resolver: Pubkey::new_unique(), ..test_market() };
+        let feed_market = Market { price_feed: Pubkey::new_unique(), ..test_market() };
+        for market in [resolver_market, feed_market] {
+            // 无僵局、无截止：拒绝
+            assert!(!market.admin_resolution_allowed(false, u64::MAX));
+            // 委员会僵局升级后放行
+            assert!(market.admin_resolution_allowed(true, 0));
+
+            // 截止前拒绝，到达截止后放行
+            let market = Market { resolution_deadline_slot: 1_000, ..market };
+            assert!(!market.admin_resolution_allowed(false, 999));
+            assert!(market.admin_resolution_allowed(false, 1_000));
+        }
+    }
+
+    /// ✅ v3.6.1: 条件市场作废校验 + 按价格快照退款（而非 50/50）
+    #[test]
+    fn test_conditional_void_refunds_at_price_snapshot() {
+        let parent = Market {
+            is_completed: true,
+            winner_token_type: 0,
+            ..test_market()
+        };
+        let mut market = Market {
+            parent_market: Pubkey::new_unique(),
+            parent_required_outcome: 1,
+            last_trade_yes_price_bps: 1_000,
+            ..test_market()
+        };
+
+        // 父市场反向结算 → 可作废；以 10c 买入的 YES 退 10c 而非 50c
+        assert!(market.check_conditional_void(&parent).is_ok());
+        assert_eq!(market.invalid_refund_ratios(), (1_000, 9_000));
+
+        // 父市场按要求结算 → 不可作废
+        let parent_met = Market { winner_token_type: 1, ..parent.clone() };
+        assert!(market.check_conditional_void(&parent_met).is_err());
+
+        // 父市场作废 → 条件不满足，可作废
+        let parent_voided = Market { is_voided: true, ..parent_met.clone() };
+        assert!(market.check_conditional_void(&parent_voided).is_ok());
+
+        // 父市场未结算 / 非条件市场 / 已结算 → 拒绝
+        let parent_open = Market { is_completed: false, ..parent.clone() };
+        assert!(market.check_conditional_void(&parent_open).is_err());
+        assert!(test_market().check_conditional_void(&parent).is_err());
+        market.is_completed = true;
+        assert!(market.check_conditional_void(&parent).is_err());
+    }
+
+    #[test]
+    fn test_zero_padded_legacy_account_deserializes() {
+        let market = Market { lmsr_b: 4_242, ..test_market() };
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+
+        // 模拟旧账户：缺少 v3.20.0 之后追加的字段（TWAP 602 + 动态费率 21 + feed_id 32）
+        let legacy_len = data.len() - 602 - 21 - 32;
+        data.truncate(legacy_len);
+        assert!(Market::try_deserialize(&mut data.as_slice()).is_err());
+
+        // 扩容后新增字节为 0：旧字段保持不变，追加字段为"未设置"
+        data.resize(8 + Market::INIT_SPACE, 0);
+        let migrated = Market::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(migrated.lmsr_b, 4_242);
+        assert_eq!(migrated.yes_token_mint, market.yes_token_mint);
+        assert_eq!(migrated.twap_last_update_ts, 0);
+        assert_eq!(migrated.twap_observation_count, 0);
+        assert!(!migrated.dynamic_fee_enabled);
+        assert_eq!(migrated.price_feed_id, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_legacy_len_ignores_stale_bytes_after_shortened_name() {
+        let long = Market {
+            display_name: "x".repeat(Market::MAX_DISPLAY_NAME_LEN),
+            lmsr_b: 4_242,
+            ..test_market()
+        };
+        let mut data = Vec::new();
+        long.try_serialize(&mut data).unwrap();
+        data.truncate(data.len() - Market::LEGACY_APPENDED_SPACE);
+
+        // update_market_name 原地缩短名称：旧版内容变短，尾部残留旧字节
+        let short = Market { display_name: "BTC".to_string(), ..long };
+        let mut reserialized = Vec::new();
+        short.try_serialize(&mut reserialized).unwrap();
+        let legacy_len = reserialized.len() - Market::LEGACY_APPENDED_SPACE;
+        data[..legacy_len].copy_from_slice(&reserialized[..legacy_len]);
+        assert!(data[legacy_len..].iter().any(|b| *b != 0));
+
+        assert_eq!(Market::legacy_serialized_len(&data).unwrap(), legacy_len);
+
+        // 清零残留字节后扩容：追加字段为"未设置"
+        data[legacy_len..].fill(0);
+        data.resize(8 + Market::INIT_SPACE, 0);
+        let migrated = Market::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(migrated.display_name, "BTC");
+        assert_eq!(migrated.lmsr_b, 4_242);
+        assert_eq!(migrated.twap_cumulative_yes_price, 0);
+        assert_eq!(migrated.twap_observation_count, 0);
+        assert!(!migrated.dynamic_fee_enabled);
+        assert_eq!(migrated.price_feed_id, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_end_time_boundaries() {
+        // 仅 end_time：结束时刻本身即视为已结束
+        let market = Market { start_slot: None, ending_slot: None, end_time: 1_000, ..test_market() };
+        assert!(!market.has_ended(0, 999));
+        assert!(market.has_ended(0, 1_000));
+        assert!(market.check_trading_window(0, 999).is_ok());
+        assert!(market.check_trading_window(0, 1_000).is_err());
+        assert_eq!(market.seconds_to_end(0, 400), Some(600));
+        assert_eq!(market.seconds_to_end(0, 1_200), Some(-200));
+
+        // start_time 边界
+        let market = Market { start_time: 500, ..market };
+        assert!(market.check_trading_window(0, 499).is_err());
+        assert!(market.check_trading_window(0, 500).is_ok());
+
+        // ending_slot 与 end_time 同时设置：结算需两者都到达，交易在任一到达时停止
+        let market = Market { start_slot: None, ending_slot: Some(100), end_time: 1_000, ..test_market() };
+        assert!(!market.has_ended(100, 999));
+        assert!(!market.has_ended(99, 1_000));
+        assert!(market.has_ended(100, 1_000));
+        assert!(market.check_trading_window(99, 999).is_ok());
+        assert!(market.check_trading_window(100, 999).is_err());
+        assert!(market.check_trading_window(99, 1_000).is_err());
+
+        // 剩余时间：end_time 优先于 slot 估算；仅有 ending_slot 时按 400ms/slot 估算
+        let market = Market { ending_slot: Some(1_000), end_time: 5_000, ..test_market() };
+        assert_eq!(market.seconds_to_end(0, 4_000), Some(1_000));
+        let market = Market { ending_slot: Some(1_000), end_time: 0, ..test_market() };
+        assert_eq!(market.seconds_to_end(0, 4_000), Some(400));
+        assert_eq!(market.seconds_to_end(2_000, 4_000), Some(0));
+
+        // 未设置任何结束时间
+        let market = Market { ending_slot: None, end_time: 0, ..test_market() };
+        assert!(market.has_ended(0, 0));
+        assert_eq!(market.seconds_to_end(0, 0), None);
+    }
+
+    #[test]
+    fn test_effective_b_stage_under_end_time() {
+        use crate::constants::{FINAL_STAGE_SECONDS, MID_STAGE_SECONDS};
+
+        // ending_slot 远在未来，阶段完全由 end_time 决定
+        let end_time = 10_000_000;
+        let market = Market { lmsr_b: 1_000, ending_slot: Some(u64::MAX), end_time, ..test_market() };
+        let b_at = |secs_left: i64| market.effective_lmsr_b_at(0, end_time - secs_left).unwrap();
+
+        assert_eq!(b_at(MID_STAGE_SECONDS + 1), 1_000);
+        assert_eq!(b_at(MID_STAGE_SECONDS), 1_000);
+        assert_eq!(b_at(MID_STAGE_SECONDS - 1), 1_200);
+        assert_eq!(b_at(FINAL_STAGE_SECONDS), 1_200);
+        assert_eq!(b_at(FINAL_STAGE_SECONDS - 1), 1_500);
+        assert_eq!(b_at(1), 1_500);
+        // 已结束：回退原始 b 值
+        assert_eq!(b_at(0), 1_000);
+        assert_eq!(b_at(-1), 1_000);
+
+        // 仅 ending_slot：按 slot 估算（2.5 slot/秒）
+        let market = Market { lmsr_b: 1_000, ending_slot: Some(1_000_000), end_time: 0, ..test_market() };
+        let final_slots = (FINAL_STAGE_SECONDS as u64) * 10 / 4;
+        assert_eq!(market.effective_lmsr_b_at(1_000_000 - final_slots, 0).unwrap(), 1_200);
+        assert_eq!(market.effective_lmsr_b_at(1_000_000 - final_slots + 10, 0).unwrap(), 1_500);
+    }
 }
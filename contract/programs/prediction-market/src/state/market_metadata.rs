@@ -0,0 +1,191 @@
+//! ✅ v3.13.0: 市场规则承诺与结算证据
+//!
+//! 市场创建时写入 `MarketMetadata`（每个市场一个）：
+//! - 完整规则文本的哈希（规则原文存放在链下，任何人可重新计算哈希比对）
+//! - 结算来源 URI（如官方数据页面）
+//! - 分类与标签
+//!
+//! 管理员提交结算提案时必须附带证据哈希与 URI（最终确认时写入），
+//! 证据写入本账户并随 ResolutionEvent 发出，供用户与审计方对照已承诺的规则核验结算结果。
+//!
+//! ✅ v3.13.1: 所有结算路径都必须携带有效证据，并在市场有元数据时写入本账户：
+//! - 人工结算（公开提案、争议裁决、指定结算人、委员会投票）由提交者附带证据
+//! - 自动结算（价格源、截止作废、条件市场作废）由链上数据派生证据（`ResolutionEvidence::derived`）
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::{MARKET_METADATA, MAX_CATEGORY_LEN, MAX_MARKET_TAGS, MAX_METADATA_URI_LEN, MAX_TAG_LEN};
+use crate::errors::PredictionMarketError;
+use solana_sha256_hasher::hashv;
+
+/// 市场元数据（规则承诺 + 结算证据）
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MarketMetadata {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 完整规则文本哈希（SHA-256）
+    pub rules_hash: [u8; 32],
+
+    /// 结算来源 URI
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub resolution_source_uri: String,
+
+    /// 分类
+    #[max_len(MAX_CATEGORY_LEN)]
+    pub category: String,
+
+    /// 标签
+    #[max_len(MAX_MARKET_TAGS, MAX_TAG_LEN)]
+    pub tags: Vec<String>,
+
+    /// 结算证据哈希（未结算时全 0）
+    pub evidence_hash: [u8; 32],
+
+    /// 结算证据 URI（未结算时为空）
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub evidence_uri: String,
+
+    /// 提交证据的结算人
+    pub resolved_by: Pubkey,
+
+    /// 证据提交时间（0 = 未结算）
+    pub resolved_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl MarketMetadata {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = MARKET_METADATA;
+
+    /// 记录结算证据
+    pub fn record_evidence(&mut self, evidence: &ResolutionEvidence, resolved_by: Pubkey, now: i64) {
+        self.evidence_hash = evidence.hash;
+        self.evidence_uri = evidence.uri.clone();
+        self.resolved_by = resolved_by;
+        self.resolved_at = now;
+    }
+}
+
+/// 创建市场时提交的规则承诺
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MarketRules {
+    /// 完整规则文本哈希（SHA-256，不可为全 0）
+    pub rules_hash: [u8; 32],
+    /// 结算来源 URI（不可为空）
+    pub resolution_source_uri: String,
+    /// 分类
+    pub category: String,
+    /// 标签（最多 MAX_MARKET_TAGS 个）
+    pub tags: Vec<String>,
+}
+
+impl MarketRules {
+    /// 校验规则参数
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.rules_hash != [0u8; 32]
+                && !self.resolution_source_uri.is_empty()
+                && self.resolution_source_uri.len() <= MAX_METADATA_URI_LEN
+                && self.category.len() <= MAX_CATEGORY_LEN
+                && self.tags.len() <= MAX_MARKET_TAGS
+                && self.tags.iter().all(|tag| !tag.is_empty() && tag.len() <= MAX_TAG_LEN),
+            PredictionMarketError::InvalidMarketRules
+        );
+        Ok(())
+    }
+}
+
+/// 结算证据（证据哈希 + 可公开访问的证据 URI）
+///
+/// ✅ v3.13.1: 所有结算路径都必须提供有效证据（默认值全 0 / 空无法通过校验）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResolutionEvidence {
+    /// 证据哈希（如证据快照的 SHA-256）
+    pub hash: [u8; 32],
+    /// 证据 URI
+    pub uri: String,
+}
+
+impl ResolutionEvidence {
+    /// 校验证据：哈希非全 0，URI 非空且不超长
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.hash != [0u8; 32] && !self.uri.is_empty() && self.uri.len() <= MAX_METADATA_URI_LEN,
+            PredictionMarketError::InvalidResolutionEvidence
+        );
+        Ok(())
+    }
+
+    /// ✅ v3.13.1: 由链上数据派生证据（自动结算路径）
+    ///
+    /// 哈希为各字段拼接后的 SHA-256，URI 描述数据来源，任何人可按相同字段重新计算核验
+    pub fn derived(uri: String, parts: &[&[u8]]) -> Self {
+        Self { hash: hashv(parts).to_bytes(), uri }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> MarketRules {
+        MarketRules {
+            rules_hash: [7u8; 32],
+            resolution_source_uri: "https://example.com/source".to_string(),
+            category: "crypto".to_string(),
+            tags: vec!["btc".to_string(), "price".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_rules_validate() {
+        assert!(rules().validate().is_ok());
+
+        // 空规则哈希
+        let mut r = rules();
+        r.rules_hash = [0u8; 32];
+        assert!(r.validate().is_err());
+
+        // 缺少结算来源
+        let mut r = rules();
+        r.resolution_source_uri.clear();
+        assert!(r.validate().is_err());
+
+        // 标签过多 / 过长
+        let mut r = rules();
+        r.tags = vec!["t".to_string(); MAX_MARKET_TAGS + 1];
+        assert!(r.validate().is_err());
+        let mut r = rules();
+        r.tags = vec!["x".repeat(MAX_TAG_LEN + 1)];
+        assert!(r.validate().is_err());
+    }
+
+    #[test]
+    fn test_evidence_validate() {
+        let evidence = ResolutionEvidence { hash: [1u8; 32], uri: "ipfs://evidence".to_string() };
+        assert!(evidence.validate().is_ok());
+        assert!(ResolutionEvidence::default().validate().is_err());
+        assert!(ResolutionEvidence { hash: [1u8; 32], uri: String::new() }.validate().is_err());
+        assert!(ResolutionEvidence { hash: [1u8; 32], uri: "u".repeat(MAX_METADATA_URI_LEN + 1) }
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_derived_evidence() {
+        let feed = Pubkey::new_unique();
+        let a = ResolutionEvidence::derived(format!("pyth:{}", feed), &[feed.as_ref(), &100i64.to_le_bytes()]);
+        let b = ResolutionEvidence::derived(format!("pyth:{}", feed), &[feed.as_ref(), &101i64.to_le_bytes()]);
+        assert!(a.validate().is_ok());
+        assert_ne!(a.hash, b.hash);
+        assert_eq!(
+            a,
+            ResolutionEvidence::derived(format!("pyth:{}", feed), &[feed.as_ref(), &100i64.to_le_bytes()])
+        );
+    }
+}
@@ -1,3 +1,14 @@
+pub mod categorical_market;
+pub mod conditional_order;
 pub mod config;
+pub mod event_group;
+pub mod fee_tier;
+pub mod intent_nonce;
 pub mod market;
+pub mod market_metadata;
+pub mod oracle;
+pub mod order_book;
+pub mod referrer;
+pub mod resolver;
+pub mod session_key;
 pub mod whitelist;
@@ -0,0 +1,351 @@
+//! ✅ v3.7.0: 乐观预言机（optimistic oracle）结算
+//!
+//! **流程**：
+//! 1. propose：任何人在市场结束后提交结算结果并质押 USDC 保证金
+//! 2. dispute：挑战窗口（liveness）内任何人可质押等额反向保证金发起争议
+//! 3. finalize：
+//!    - 无争议：窗口结束后任何人可按提案结果结算，保证金退还提案人
+//!    - 有争议：升级至全局管理员裁决，败方保证金罚没给胜方
+//!
+//! ✅ v3.7.1: 最终确认后提案账户与保证金托管随即关闭；
+//! 提案未最终确认而市场已经其他路径结算时，通过 refund_resolution_bonds 原路退还保证金。
+//! ✅ v3.8.1: 指定结算人 / 价格源市场不接受公开提案。
+//!
+//! 市场仅在 finalize 时才标记为 is_completed，因此 claim_rewards 在最终确认前不可用。
+//!
+//! ✅ v3.12.0: 管理员两阶段结算复用同一提案账户（保证金为 0）：
+//! 管理员提案后进入 admin_challenge_secs 挑战窗口，窗口内可修改或撤回，
+//! 窗口结束后任何人通过 finalize_resolution 最终确认。
+//!
+//! ✅ v3.12.1: 管理员提案是唯一的管理员结算入口（一步生效的 resolution / resolve_scalar 已移除）：
+//! 标量市场的提案记录结算值，最终确认时按结算值发出 ScalarResolutionEvent。
+//! 状态转换的前置条件集中在 `check_*` 方法中，供各指令与单元测试共用。
+//! 提案账户布局随之变化：升级前须最终确认或撤回所有进行中的提案。
+//!
+//! ✅ v3.13.1: 所有提案须附带结算证据（哈希 + URI），
+//! finalize_resolution 将提案证据写入 MarketMetadata 并随 ResolutionEvent 发出。
+//! 提案账户布局再次变化，升级前同样须清空进行中的提案。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::{
+    MAX_METADATA_URI_LEN, MAX_ORACLE_LIVENESS_SECS, MIN_ADMIN_CHALLENGE_SECS, MIN_ORACLE_LIVENESS_SECS, ORACLE_CONFIG,
+    RESOLUTION_PROPOSAL,
+};
+use crate::errors::PredictionMarketError;
+use crate::state::market_metadata::ResolutionEvidence;
+
+/// 乐观预言机全局参数（由管理员通过 configure_oracle 设置）
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct OracleConfig {
+    /// 挑战窗口（秒）
+    pub liveness_secs: i64,
+
+    /// 最低提案保证金（USDC 最小单位）
+    pub min_bond: u64,
+
+    /// 是否启用乐观预言机
+    pub enabled: bool,
+
+    /// ✅ v3.12.0: 管理员两阶段结算的挑战窗口（秒）
+    pub admin_challenge_secs: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl OracleConfig {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = ORACLE_CONFIG;
+
+    /// 校验参数范围
+    pub fn validate(liveness_secs: i64, min_bond: u64, admin_challenge_secs: i64) -> Result<()> {
+        require!(
+            (MIN_ORACLE_LIVENESS_SECS..=MAX_ORACLE_LIVENESS_SECS).contains(&liveness_secs),
+            PredictionMarketError::InvalidLiveness
+        );
+        require!(
+            (MIN_ADMIN_CHALLENGE_SECS..=MAX_ORACLE_LIVENESS_SECS).contains(&admin_challenge_secs),
+            PredictionMarketError::InvalidLiveness
+        );
+        require!(min_bond > 0, PredictionMarketError::BondTooLow);
+        Ok(())
+    }
+}
+
+/// 结算提案状态
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ProposalStatus {
+    /// 挑战窗口中
+    Proposed,
+    /// 已被挑战，等待管理员裁决
+    Disputed,
+    /// 已最终确认（市场已结算）
+    ///
+    /// ✅ v3.7.1: 最终确认时提案账户随即关闭，该状态不再持久化
+    Finalized,
+}
+
+/// 市场结算提案（每个市场一个）
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ResolutionProposal {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 提案人
+    pub proposer: Pubkey,
+
+    /// 提案结果（0=NO, 1=YES, 2=平局）
+    pub token_type: u8,
+
+    /// 提案 YES 赎回比例（基点）
+    pub yes_ratio: u64,
+
+    /// 提案 NO 赎回比例（基点）
+    pub no_ratio: u64,
+
+    /// 提案保证金
+    pub bond: u64,
+
+    /// 提案时间
+    pub proposed_at: i64,
+
+    /// 挑战窗口结束时间
+    pub liveness_ends_at: i64,
+
+    /// 挑战人（Pubkey::default() = 无）
+    pub disputer: Pubkey,
+
+    /// 挑战保证金（= bond）
+    pub dispute_bond: u64,
+
+    /// 挑战时间
+    pub disputed_at: i64,
+
+    /// 状态
+    pub status: ProposalStatus,
+
+    /// PDA bump（保证金托管签名）
+    pub bump: u8,
+
+    /// ✅ v3.13.1: 结算证据哈希（所有提案必填）
+    pub evidence_hash: [u8; 32],
+
+    /// ✅ v3.13.1: 结算证据 URI
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub evidence_uri: String,
+
+    /// ✅ v3.12.1: 标量市场的结算值（None = 按比例结算）
+    pub scalar_value: Option<i64>,
+}
+
+impl ResolutionProposal {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = RESOLUTION_PROPOSAL;
+
+    /// 挑战窗口是否仍然开放
+    pub fn in_liveness(&self, now: i64) -> bool {
+        self.status == ProposalStatus::Proposed && now < self.liveness_ends_at
+    }
+
+    /// 裁决结果是否与提案一致
+    pub fn matches(&self, yes_ratio: u64, no_ratio: u64, token_type: u8) -> bool {
+        self.token_type == token_type && self.yes_ratio == yes_ratio && self.no_ratio == no_ratio
+    }
+
+    /// ✅ v3.12.0: 是否为管理员两阶段结算提案（公开提案的保证金必须 ≥ min_bond > 0）
+    pub fn is_admin_proposal(&self) -> bool {
+        self.bond == 0
+    }
+
+    /// 托管中的保证金总额
+    pub fn total_bond(&self) -> Result<u64> {
+        self.bond
+            .checked_add(self.dispute_bond)
+            .ok_or(PredictionMarketError::MathOverflow.into())
+    }
+
+    /// ✅ v3.13.1: 提案附带的结算证据
+    pub fn evidence(&self) -> ResolutionEvidence {
+        ResolutionEvidence { hash: self.evidence_hash, uri: self.evidence_uri.clone() }
+    }
+
+    /// ✅ v3.12.1: 可被公开挑战：非管理员提案、挑战窗口内、挑战人不是提案人
+    pub fn check_disputable(&self, now: i64, disputer: &Pubkey) -> Result<()> {
+        require!(
+            self.status == ProposalStatus::Proposed,
+            PredictionMarketError::InvalidProposalStatus
+        );
+        require!(self.in_liveness(now), PredictionMarketError::LivenessExpired);
+        require!(
+            !self.is_admin_proposal(),
+            PredictionMarketError::AdminProposalNotDisputable
+        );
+        require!(*disputer != self.proposer, PredictionMarketError::SelfDispute);
+        Ok(())
+    }
+
+    /// ✅ v3.12.1: 可被管理员修改或撤回：管理员提案且挑战窗口内
+    pub fn check_admin_revisable(&self, now: i64) -> Result<()> {
+        require!(self.is_admin_proposal(), PredictionMarketError::NotAdminProposal);
+        require!(self.in_liveness(now), PredictionMarketError::LivenessExpired);
+        Ok(())
+    }
+
+    /// ✅ v3.12.1: 可无争议最终确认：未被挑战且挑战窗口已结束
+    pub fn check_finalizable(&self, now: i64) -> Result<()> {
+        require!(
+            self.status == ProposalStatus::Proposed,
+            PredictionMarketError::InvalidProposalStatus
+        );
+        require!(now >= self.liveness_ends_at, PredictionMarketError::LivenessNotExpired);
+        Ok(())
+    }
+
+    /// ✅ v3.12.1: 修改提案结果，挑战窗口从 now 重新计时（✅ v3.13.1: 证据一并更新）
+    pub fn amend(
+        &mut self,
+        yes_ratio: u64,
+        no_ratio: u64,
+        token_type: u8,
+        evidence: ResolutionEvidence,
+        now: i64,
+        challenge_secs: i64,
+    ) -> Result<()> {
+        self.check_admin_revisable(now)?;
+        self.liveness_ends_at = now
+            .checked_add(challenge_secs)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.token_type = token_type;
+        self.yes_ratio = yes_ratio;
+        self.no_ratio = no_ratio;
+        self.proposed_at = now;
+        self.evidence_hash = evidence.hash;
+        self.evidence_uri = evidence.uri;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oracle_config_validate() {
+        assert!(OracleConfig::validate(MIN_ORACLE_LIVENESS_SECS, 1, 3600).is_ok());
+        assert!(OracleConfig::validate(MAX_ORACLE_LIVENESS_SECS, 1, 3600).is_ok());
+        assert!(OracleConfig::validate(MIN_ORACLE_LIVENESS_SECS - 1, 1, 3600).is_err());
+        assert!(OracleConfig::validate(MAX_ORACLE_LIVENESS_SECS + 1, 1, 3600).is_err());
+        assert!(OracleConfig::validate(3600, 0, 3600).is_err());
+        // 管理员挑战窗口范围
+        assert!(OracleConfig::validate(3600, 1, MIN_ADMIN_CHALLENGE_SECS).is_ok());
+        assert!(OracleConfig::validate(3600, 1, MIN_ADMIN_CHALLENGE_SECS - 1).is_err());
+    }
+
+    #[test]
+    fn test_proposal_liveness_and_match() {
+        let proposal = ResolutionProposal {
+            market: Pubkey::new_unique(),
+            proposer: Pubkey::new_unique(),
+            token_type: 1,
+            yes_ratio: 10_000,
+            no_ratio: 0,
+            bond: 100,
+            proposed_at: 1_000,
+            liveness_ends_at: 4_600,
+            disputer: Pubkey::default(),
+            dispute_bond: 0,
+            disputed_at: 0,
+            status: ProposalStatus::Proposed,
+            bump: 255,
+            evidence_hash: [0u8; 32],
+            evidence_uri: String::new(),
+            scalar_value: None,
+        };
+
+        assert!(proposal.in_liveness(4_599));
+        assert!(!proposal.in_liveness(4_600));
+        assert!(proposal.matches(10_000, 0, 1));
+        assert!(!proposal.matches(0, 10_000, 0));
+        assert_eq!(proposal.total_bond().unwrap(), 100);
+        assert!(!proposal.is_admin_proposal());
+    }
+
+    fn admin_proposal() -> ResolutionProposal {
+        ResolutionProposal {
+            market: Pubkey::new_unique(),
+            proposer: Pubkey::new_unique(),
+            token_type: 1,
+            yes_ratio: 10_000,
+            no_ratio: 0,
+            bond: 0,
+            proposed_at: 1_000,
+            liveness_ends_at: 4_600,
+            disputer: Pubkey::default(),
+            dispute_bond: 0,
+            disputed_at: 0,
+            status: ProposalStatus::Proposed,
+            bump: 255,
+            evidence_hash: [1u8; 32],
+            evidence_uri: "ipfs://evidence".to_string(),
+            scalar_value: None,
+        }
+    }
+
+    #[test]
+    fn test_admin_proposal_state_machine() {
+        let mut proposal = admin_proposal();
+        assert!(proposal.is_admin_proposal());
+
+        // 管理员提案不接受公开挑战
+        assert!(proposal.check_disputable(2_000, &Pubkey::new_unique()).is_err());
+
+        // 窗口内可修改：结果与证据更新，窗口重新计时
+        let evidence = ResolutionEvidence { hash: [2u8; 32], uri: "ipfs://amended".to_string() };
+        proposal.amend(0, 10_000, 0, evidence.clone(), 4_000, 3_600).unwrap();
+        assert!(proposal.matches(0, 10_000, 0));
+        assert_eq!(proposal.evidence(), evidence);
+        assert_eq!(proposal.liveness_ends_at, 7_600);
+
+        // 原窗口结束时间已不再生效：仍可撤回 / 不可最终确认
+        assert!(proposal.check_admin_revisable(4_600).is_ok());
+        assert!(proposal.check_finalizable(4_600).is_err());
+
+        // 新窗口结束后：不可修改 / 撤回，可最终确认
+        assert!(proposal.check_admin_revisable(7_600).is_err());
+        assert!(proposal.amend(10_000, 0, 1, evidence, 7_600, 3_600).is_err());
+        assert!(proposal.matches(0, 10_000, 0));
+        assert!(proposal.check_finalizable(7_600).is_ok());
+    }
+
+    #[test]
+    fn test_public_proposal_state_machine() {
+        let disputer = Pubkey::new_unique();
+        let mut proposal = ResolutionProposal { bond: 100, ..admin_proposal() };
+
+        // 公开提案不可由管理员修改 / 撤回
+        assert!(proposal.check_admin_revisable(2_000).is_err());
+
+        // 提案人不能挑战自己；窗口结束后不能挑战
+        let proposer = proposal.proposer;
+        assert!(proposal.check_disputable(2_000, &proposer).is_err());
+        assert!(proposal.check_disputable(4_600, &disputer).is_err());
+
+        // 窗口内被挑战后：不可无争议最终确认，也不可再次挑战
+        assert!(proposal.check_disputable(2_000, &disputer).is_ok());
+        proposal.status = ProposalStatus::Disputed;
+        proposal.disputer = disputer;
+        proposal.dispute_bond = proposal.bond;
+        assert!(proposal.check_finalizable(4_600).is_err());
+        assert!(proposal.check_disputable(2_000, &Pubkey::new_unique()).is_err());
+        assert_eq!(proposal.total_bond().unwrap(), 200);
+
+        // 未被挑战的提案在窗口结束后可最终确认
+        let proposal = ResolutionProposal { bond: 100, ..admin_proposal() };
+        assert!(proposal.check_finalizable(4_599).is_err());
+        assert!(proposal.check_finalizable(4_600).is_ok());
+    }
+}
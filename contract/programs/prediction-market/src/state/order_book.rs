@@ -0,0 +1,513 @@
+//! ✅ v3.17.0: 链上限价订单簿（与 LMSR 池并行的混合流动性）
+//!
+//! 每个市场一个 `OrderBook`，固定 MAX_BOOK_ORDERS 个挂单槽位：
+//! - 买单（bid）：以基点价格买入 YES/NO，挂单时托管 ⌈数量 × 价格⌉ USDC
+//! - 卖单（ask）：以基点价格卖出 YES/NO，挂单时托管代币
+//!
+//! **成交路径**：
+//! 1. match_limit_orders：任何人撮合价格交叉的买卖单（按先挂单的价格成交）
+//! 2. swap：提供订单簿账户时，先吃优于 LMSR 边际价格的挂单，剩余部分进入池子
+//!
+//! 成交所得记入挂单（claimable），资金始终留在订单簿托管账户中，
+//! 挂单人通过 settle_limit_order 领取，或通过 cancel_limit_order 领取并撤单。
+//! 订单簿成交不收取手续费。
+//!
+//! ✅ v3.17.1: 槽位防占满：挂单名义金额（数量 × 限价）须 ≥ MIN_LIMIT_ORDER_NOTIONAL，
+//! 且每个挂单人最多占用 MAX_ORDERS_PER_OWNER 个槽位。
+//!
+//! ✅ v3.17.2: 槽位按价格优先：订单簿已满时，新挂单可挤出相对池子价格最劣的挂单
+//! （`eviction_candidate`），被挤出挂单按撤单处理，托管资金原路退还挂单人。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::{
+    BASIS_POINTS_DIVISOR, MAX_BOOK_ORDERS, MAX_ORDERS_PER_OWNER, MIN_LIMIT_ORDER_NOTIONAL, ORDER_BOOK,
+};
+use crate::errors::PredictionMarketError;
+
+/// 挂单方向：买单
+pub const ORDER_SIDE_BID: u8 = 0;
+
+/// 挂单方向：卖单
+pub const ORDER_SIDE_ASK: u8 = 1;
+
+/// 限价挂单（order_id = 0 表示空槽位）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct LimitOrder {
+    /// 挂单编号（订单簿内递增，从 1 开始）
+    pub order_id: u64,
+
+    /// 挂单人
+    pub owner: Pubkey,
+
+    /// 方向（0=买单, 1=卖单）
+    pub side: u8,
+
+    /// 代币类型（0=NO, 1=YES）
+    pub token_type: u8,
+
+    /// 限价（基点，1-9999）
+    pub price_bps: u16,
+
+    /// 未成交代币数量
+    pub remaining: u64,
+
+    /// 买单剩余托管 USDC
+    pub escrow_usdc: u64,
+
+    /// 卖单成交所得 USDC（待领取）
+    pub claimable_usdc: u64,
+
+    /// 买单成交所得代币（待领取）
+    pub claimable_tokens: u64,
+
+    /// 挂单时间
+    pub created_at: i64,
+}
+
+impl LimitOrder {
+    /// 是否为空槽位
+    pub fn is_empty(&self) -> bool {
+        self.order_id == 0
+    }
+
+    /// 是否仍可成交
+    pub fn is_open(&self) -> bool {
+        !self.is_empty() && self.remaining > 0
+    }
+}
+
+/// 单笔撮合结果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderMatch {
+    /// 成交代币数量
+    pub tokens: u64,
+    /// 成交 USDC
+    pub usdc: u64,
+    /// 成交价格（基点）
+    pub price_bps: u16,
+}
+
+/// 市场订单簿
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct OrderBook {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 下一个挂单编号
+    pub next_order_id: u64,
+
+    /// 挂单槽位
+    pub orders: [LimitOrder; MAX_BOOK_ORDERS],
+
+    /// PDA bump（托管账户签名）
+    pub bump: u8,
+}
+
+/// 按基点价格计算 USDC：tokens × price_bps / 10000
+pub fn quote_usdc(tokens: u64, price_bps: u16, round_up: bool) -> Result<u64> {
+    let numerator = (tokens as u128)
+        .checked_mul(price_bps as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let divisor = BASIS_POINTS_DIVISOR as u128;
+    let usdc = if round_up {
+        numerator
+            .checked_add(divisor - 1)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / divisor
+    } else {
+        numerator / divisor
+    };
+    u64::try_from(usdc).map_err(|_| PredictionMarketError::MathOverflow.into())
+}
+
+impl OrderBook {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = ORDER_BOOK;
+
+    /// 挂单（返回挂单编号）
+    pub fn post(
+        &mut self,
+        owner: Pubkey,
+        side: u8,
+        token_type: u8,
+        price_bps: u16,
+        amount: u64,
+        now: i64,
+    ) -> Result<u64> {
+        require!(side <= ORDER_SIDE_ASK, PredictionMarketError::InvalidParameter);
+        require!(token_type <= 1, PredictionMarketError::InvalidTokenType);
+        require!(
+            price_bps > 0 && (price_bps as u64) < BASIS_POINTS_DIVISOR,
+            PredictionMarketError::InvalidOrderPrice
+        );
+        // ✅ v3.17.1: 按限价计算名义金额，低价粉尘挂单同样被拒绝
+        require!(
+            quote_usdc(amount, price_bps, false)? >= MIN_LIMIT_ORDER_NOTIONAL,
+            PredictionMarketError::LimitOrderTooSmall
+        );
+        require!(
+            self.open_orders_of(&owner) < MAX_ORDERS_PER_OWNER,
+            PredictionMarketError::TooManyOpenOrders
+        );
+
+        let slot = self
+            .orders
+            .iter()
+            .position(LimitOrder::is_empty)
+            .ok_or(PredictionMarketError::OrderBookFull)?;
+
+        let order_id = self.next_order_id.max(1);
+        self.next_order_id = order_id
+            .checked_add(1)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let escrow_usdc = if side == ORDER_SIDE_BID { quote_usdc(amount, price_bps, true)? } else { 0 };
+
+        self.orders[slot] = LimitOrder {
+            order_id,
+            owner,
+            side,
+            token_type,
+            price_bps,
+            remaining: amount,
+            escrow_usdc,
+            claimable_usdc: 0,
+            claimable_tokens: 0,
+            created_at: now,
+        };
+        Ok(order_id)
+    }
+
+    /// ✅ v3.17.1: 挂单人占用的槽位数量
+    pub fn open_orders_of(&self, owner: &Pubkey) -> usize {
+        self.orders.iter().filter(|o| !o.is_empty() && o.owner == *owner).count()
+    }
+
+    /// 查找挂单槽位
+    pub fn index_of(&self, order_id: u64) -> Result<usize> {
+        require!(order_id > 0, PredictionMarketError::OrderNotFound);
+        self.orders
+            .iter()
+            .position(|o| o.order_id == order_id)
+            .ok_or_else(|| PredictionMarketError::OrderNotFound.into())
+    }
+
+    /// ✅ v3.17.2: 挂单相对池子价格的劣势（越大越不具竞争力）
+    ///
+    /// 买单 = 池子价格 - 限价，卖单 = 限价 - 池子价格（按挂单代币类型换算池子价格）
+    fn price_gap(side: u8, token_type: u8, price_bps: u16, pool_yes_price_bps: u16) -> i32 {
+        let pool_bps = if token_type == 1 {
+            pool_yes_price_bps as i32
+        } else {
+            BASIS_POINTS_DIVISOR as i32 - pool_yes_price_bps as i32
+        };
+        if side == ORDER_SIDE_BID {
+            pool_bps - price_bps as i32
+        } else {
+            price_bps as i32 - pool_bps
+        }
+    }
+
+    /// ✅ v3.17.2: 订单簿已满时可被新挂单挤出的槽位
+    ///
+    /// 候选为相对池子价格最劣的挂单（已无剩余数量、仅待领取的挂单最先挤出；同等劣势挤出最新挂单），
+    /// 仅当新挂单严格优于候选时返回
+    pub fn eviction_candidate(&self, side: u8, token_type: u8, price_bps: u16, pool_yes_price_bps: u16) -> Option<usize> {
+        let gap = |o: &LimitOrder| {
+            if o.is_open() {
+                Self::price_gap(o.side, o.token_type, o.price_bps, pool_yes_price_bps)
+            } else {
+                i32::MAX
+            }
+        };
+        let (index, worst) = self
+            .orders
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| !o.is_empty())
+            .max_by_key(|(_, o)| (gap(o), o.order_id))?;
+
+        (Self::price_gap(side, token_type, price_bps, pool_yes_price_bps) < gap(worst)).then_some(index)
+    }
+
+    /// 价格低于 `below_bps` 的最优卖单（价格最低，同价先挂先成交）
+    pub fn best_ask(&self, token_type: u8, below_bps: u16) -> Option<usize> {
+        self.orders
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| {
+                o.is_open() && o.side == ORDER_SIDE_ASK && o.token_type == token_type && o.price_bps < below_bps
+            })
+            .min_by_key(|(_, o)| (o.price_bps, o.order_id))
+            .map(|(i, _)| i)
+    }
+
+    /// 价格高于 `above_bps` 的最优买单（价格最高，同价先挂先成交）
+    pub fn best_bid(&self, token_type: u8, above_bps: u16) -> Option<usize> {
+        self.orders
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| {
+                o.is_open() && o.side == ORDER_SIDE_BID && o.token_type == token_type && o.price_bps > above_bps
+            })
+            .min_by_key(|(_, o)| (u16::MAX - o.price_bps, o.order_id))
+            .map(|(i, _)| i)
+    }
+
+    /// 吃卖单：taker 买入 `tokens` 个代币，按挂单价向上取整支付 USDC（返回应付 USDC）
+    pub fn fill_ask(&mut self, index: usize, tokens: u64) -> Result<u64> {
+        let order = &mut self.orders[index];
+        require!(
+            order.is_open() && order.side == ORDER_SIDE_ASK && tokens <= order.remaining,
+            PredictionMarketError::InvalidAmount
+        );
+
+        let usdc = quote_usdc(tokens, order.price_bps, true)?;
+        order.remaining -= tokens;
+        order.claimable_usdc = order
+            .claimable_usdc
+            .checked_add(usdc)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(usdc)
+    }
+
+    /// 吃买单：taker 卖出 `tokens` 个代币，按挂单价向下取整从托管中获得 USDC（返回应得 USDC）
+    pub fn fill_bid(&mut self, index: usize, tokens: u64) -> Result<u64> {
+        let order = &mut self.orders[index];
+        require!(
+            order.is_open() && order.side == ORDER_SIDE_BID && tokens <= order.remaining,
+            PredictionMarketError::InvalidAmount
+        );
+
+        let usdc = quote_usdc(tokens, order.price_bps, false)?;
+        order.remaining -= tokens;
+        order.escrow_usdc = order
+            .escrow_usdc
+            .checked_sub(usdc)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        order.claimable_tokens = order
+            .claimable_tokens
+            .checked_add(tokens)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(usdc)
+    }
+
+    /// 撮合价格交叉的买单与卖单（同一代币，按先挂单的价格成交）
+    pub fn match_orders(&mut self, bid_index: usize, ask_index: usize) -> Result<OrderMatch> {
+        let bid = self.orders[bid_index];
+        let ask = self.orders[ask_index];
+        require!(
+            bid.is_open()
+                && ask.is_open()
+                && bid.side == ORDER_SIDE_BID
+                && ask.side == ORDER_SIDE_ASK
+                && bid.token_type == ask.token_type
+                && bid.owner != ask.owner,
+            PredictionMarketError::OrdersDoNotCross
+        );
+        require!(bid.price_bps >= ask.price_bps, PredictionMarketError::OrdersDoNotCross);
+
+        let price_bps = if bid.order_id < ask.order_id { bid.price_bps } else { ask.price_bps };
+        let tokens = bid.remaining.min(ask.remaining);
+        let usdc = quote_usdc(tokens, price_bps, false)?;
+
+        let bid = &mut self.orders[bid_index];
+        bid.remaining -= tokens;
+        bid.escrow_usdc = bid
+            .escrow_usdc
+            .checked_sub(usdc)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        bid.claimable_tokens = bid
+            .claimable_tokens
+            .checked_add(tokens)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let ask = &mut self.orders[ask_index];
+        ask.remaining -= tokens;
+        ask.claimable_usdc = ask
+            .claimable_usdc
+            .checked_add(usdc)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        Ok(OrderMatch { tokens, usdc, price_bps })
+    }
+
+    /// 领取挂单：返回应支付给挂单人的 (USDC, 代币)
+    ///
+    /// - `cancel = true`：撤单，额外退还未成交部分（卖单代币 / 买单剩余托管 USDC）并释放槽位
+    /// - `cancel = false`：仅领取成交所得；挂单已完全成交时同时退还取整余量并释放槽位
+    pub fn settle(&mut self, index: usize, cancel: bool) -> Result<(u64, u64)> {
+        let order = self.orders[index];
+        require!(!order.is_empty(), PredictionMarketError::OrderNotFound);
+
+        let close = cancel || order.remaining == 0;
+        let (mut usdc, mut tokens) = (order.claimable_usdc, order.claimable_tokens);
+        if close {
+            if order.side == ORDER_SIDE_BID {
+                usdc = usdc
+                    .checked_add(order.escrow_usdc)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+            } else {
+                tokens = tokens
+                    .checked_add(order.remaining)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+            }
+            self.orders[index] = LimitOrder::default();
+        } else {
+            self.orders[index].claimable_usdc = 0;
+            self.orders[index].claimable_tokens = 0;
+        }
+        Ok((usdc, tokens))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_book() -> OrderBook {
+        OrderBook {
+            market: Pubkey::new_unique(),
+            next_order_id: 1,
+            orders: [LimitOrder::default(); MAX_BOOK_ORDERS],
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_post_and_best_price_selection() {
+        let mut book = empty_book();
+        let maker = Pubkey::new_unique();
+
+        let a1 = book.post(maker, ORDER_SIDE_ASK, 1, 4_500, 20_000_000, 0).unwrap();
+        let a2 = book.post(maker, ORDER_SIDE_ASK, 1, 4_000, 20_000_000, 0).unwrap();
+        let b1 = book.post(maker, ORDER_SIDE_BID, 1, 3_000, 20_000_000, 0).unwrap();
+        assert_eq!((a1, a2, b1), (1, 2, 3));
+
+        // 买单托管 ⌈数量 × 价格⌉
+        assert_eq!(book.orders[book.index_of(b1).unwrap()].escrow_usdc, 6_000_000);
+
+        // 最优卖单：价格最低且低于 LMSR 价格
+        assert_eq!(book.best_ask(1, 5_000), Some(book.index_of(a2).unwrap()));
+        assert_eq!(book.best_ask(1, 4_000), None);
+        assert_eq!(book.best_ask(0, 5_000), None);
+        assert_eq!(book.best_bid(1, 2_500), Some(book.index_of(b1).unwrap()));
+        assert_eq!(book.best_bid(1, 3_000), None);
+
+        // 无效参数
+        assert!(book.post(maker, ORDER_SIDE_BID, 1, 0, 20_000_000, 0).is_err());
+        assert!(book.post(maker, ORDER_SIDE_BID, 1, 10_000, 20_000_000, 0).is_err());
+    }
+
+    #[test]
+    fn test_dust_orders_cannot_fill_book() {
+        let mut book = empty_book();
+        let maker = Pubkey::new_unique();
+
+        // 名义金额按限价计算：代币数量再大，低价挂单也需达到最小金额
+        assert!(book.post(maker, ORDER_SIDE_BID, 1, 5_000, 9_999_999, 0).is_err());
+        assert!(book.post(maker, ORDER_SIDE_ASK, 1, 1, 49_999_999_999, 0).is_err());
+        assert!(book.post(maker, ORDER_SIDE_ASK, 1, 1, 50_000_000_000, 0).is_ok());
+        assert!(book.post(maker, ORDER_SIDE_BID, 1, 5_000, 10_000_000, 0).is_ok());
+
+        // 每个挂单人最多占用 MAX_ORDERS_PER_OWNER 个槽位
+        for _ in 2..MAX_ORDERS_PER_OWNER {
+            book.post(maker, ORDER_SIDE_BID, 1, 5_000, 10_000_000, 0).unwrap();
+        }
+        assert_eq!(book.open_orders_of(&maker), MAX_ORDERS_PER_OWNER);
+        assert!(book.post(maker, ORDER_SIDE_BID, 1, 5_000, 10_000_000, 0).is_err());
+        assert!(book.post(Pubkey::new_unique(), ORDER_SIDE_BID, 1, 5_000, 10_000_000, 0).is_ok());
+
+        // 撤单释放槽位后可再次挂单
+        let first = book.orders.iter().find(|o| o.owner == maker).unwrap().order_id;
+        let idx = book.index_of(first).unwrap();
+        book.settle(idx, true).unwrap();
+        assert!(book.post(maker, ORDER_SIDE_BID, 1, 5_000, 10_000_000, 0).is_ok());
+    }
+
+    #[test]
+    fn test_full_book_evicts_least_competitive_order() {
+        let mut book = empty_book();
+        let spammer_keys: Vec<Pubkey> = (0..MAX_BOOK_ORDERS / MAX_ORDERS_PER_OWNER).map(|_| Pubkey::new_unique()).collect();
+
+        // 占满订单簿：远离池子价格（YES = 50%）的 YES 卖单
+        for (i, key) in spammer_keys.iter().enumerate() {
+            for j in 0..MAX_ORDERS_PER_OWNER {
+                let price = 9_000 + (i * MAX_ORDERS_PER_OWNER + j) as u16 * 10;
+                book.post(*key, ORDER_SIDE_ASK, 1, price, 10_000_000, 0).unwrap();
+            }
+        }
+        let maker = Pubkey::new_unique();
+        assert!(book.post(maker, ORDER_SIDE_BID, 0, 4_900, 20_000_000, 0).is_err());
+
+        // 不优于最劣挂单（9_310bps 卖单，劣势 4_310）的新挂单不能挤出
+        assert_eq!(book.eviction_candidate(ORDER_SIDE_ASK, 1, 9_310, 5_000), None);
+        assert_eq!(book.eviction_candidate(ORDER_SIDE_BID, 0, 500, 5_000), None);
+
+        // 更优的 NO 买单（劣势 100）挤出最劣的卖单
+        let index = book.eviction_candidate(ORDER_SIDE_BID, 0, 4_900, 5_000).unwrap();
+        assert_eq!(book.orders[index].price_bps, 9_310);
+        let evicted = book.orders[index];
+        assert_eq!(book.settle(index, true).unwrap(), (0, evicted.remaining));
+        assert!(book.post(maker, ORDER_SIDE_BID, 0, 4_900, 20_000_000, 0).is_ok());
+
+        // 已完全成交、仅待领取的挂单最先被挤出
+        let filled = book.index_of(1).unwrap();
+        book.fill_ask(filled, 10_000_000).unwrap();
+        assert_eq!(book.eviction_candidate(ORDER_SIDE_ASK, 1, 9_300, 5_000), Some(filled));
+    }
+
+    #[test]
+    fn test_fill_and_settle() {
+        let mut book = empty_book();
+        let maker = Pubkey::new_unique();
+
+        let ask = book.post(maker, ORDER_SIDE_ASK, 1, 4_000, 20_000_000, 0).unwrap();
+        let idx = book.index_of(ask).unwrap();
+        assert_eq!(book.fill_ask(idx, 4_000_000).unwrap(), 1_600_000);
+        assert!(book.fill_ask(idx, 16_000_001).is_err());
+
+        // 部分成交：只领取所得，挂单保留
+        assert_eq!(book.settle(idx, false).unwrap(), (1_600_000, 0));
+        assert_eq!(book.orders[idx].remaining, 16_000_000);
+
+        // 撤单：退还未成交代币并释放槽位
+        assert_eq!(book.settle(idx, true).unwrap(), (0, 16_000_000));
+        assert!(book.index_of(ask).is_err());
+
+        // 买单完全成交后退还取整余量
+        let bid = book.post(maker, ORDER_SIDE_BID, 0, 3_333, 30_000_001, 0).unwrap();
+        let idx = book.index_of(bid).unwrap();
+        let escrow = book.orders[idx].escrow_usdc;
+        let paid = book.fill_bid(idx, 30_000_001).unwrap();
+        assert_eq!(book.settle(idx, false).unwrap(), (escrow - paid, 30_000_001));
+        assert!(book.orders[idx].is_empty());
+    }
+
+    #[test]
+    fn test_match_crossing_orders() {
+        let mut book = empty_book();
+        let (buyer, seller) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let ask = book.post(seller, ORDER_SIDE_ASK, 1, 5_500, 10_000_000, 0).unwrap();
+        let bid = book.post(buyer, ORDER_SIDE_BID, 1, 6_000, 15_000_000, 0).unwrap();
+        let (bi, ai) = (book.index_of(bid).unwrap(), book.index_of(ask).unwrap());
+
+        // 按先挂单（卖单）的价格成交
+        let m = book.match_orders(bi, ai).unwrap();
+        assert_eq!(m, OrderMatch { tokens: 10_000_000, usdc: 5_500_000, price_bps: 5_500 });
+        assert_eq!(book.orders[bi].remaining, 5_000_000);
+        assert_eq!(book.orders[bi].escrow_usdc, 9_000_000 - 5_500_000);
+        assert_eq!(book.orders[ai].claimable_usdc, 5_500_000);
+
+        // 价格不交叉
+        let low_bid = book.post(buyer, ORDER_SIDE_BID, 1, 5_000, 10_000_000, 0).unwrap();
+        let high_ask = book.post(seller, ORDER_SIDE_ASK, 1, 5_100, 10_000_000, 0).unwrap();
+        assert!(book
+            .match_orders(book.index_of(low_bid).unwrap(), book.index_of(high_ask).unwrap())
+            .is_err());
+    }
+}
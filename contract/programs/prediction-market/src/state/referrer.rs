@@ -0,0 +1,67 @@
+//! ✅ v3.22.0: 引荐人统计账户
+//!
+//! 管理员通过 configure_referrer 为引荐人创建账户并设置平台费分成比例。
+//! swap / mint_complete_set 携带引荐人账户时累计引荐成交量，
+//! swap 的平台费分成转入该 PDA 的 USDC ATA，引荐人通过 claim_referral_fees 领取。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::REFERRER;
+use crate::errors::PredictionMarketError;
+
+/// 引荐人统计
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ReferrerStats {
+    /// 引荐人（领取权限）
+    pub referrer: Pubkey,
+
+    /// 平台费分成比例（基点，实际受 Config.max_referral_share_bps 限制）
+    pub share_bps: u16,
+
+    /// 累计引荐成交量（USDC，含手续费）
+    pub referred_volume: u64,
+
+    /// 累计引荐成交笔数
+    pub referred_trades: u64,
+
+    /// 累计分成（USDC）
+    pub total_earned: u64,
+
+    /// 累计已领取（USDC）
+    pub total_claimed: u64,
+
+    /// 创建时间
+    pub created_at: i64,
+
+    /// PDA bump（托管账户签名）
+    pub bump: u8,
+}
+
+impl ReferrerStats {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = REFERRER;
+
+    /// 实际生效的分成比例（受全局上限约束）
+    pub fn effective_share_bps(&self, max_share_bps: u16) -> u16 {
+        self.share_bps.min(max_share_bps)
+    }
+
+    /// 记录一笔引荐成交
+    pub fn record(&mut self, volume: u64, referral_fee: u64) -> Result<()> {
+        self.referred_volume = self
+            .referred_volume
+            .checked_add(volume)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.referred_trades = self
+            .referred_trades
+            .checked_add(1)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.total_earned = self
+            .total_earned
+            .checked_add(referral_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//! ✅ v3.9.0: 市场级结算人与 M-of-N 结算委员会
+//!
+//! 市场创建时可指定结算人（`Market.resolver`）：
+//! - 单一密钥：该密钥可直接调用 resolve_by_resolver 结算
+//! - 委员会：N 个成员各自投票，M 个一致投票后自动结算
+//!
+//! ✅ v3.9.1: 按结果分别计票，任一结果达到 M 票即结算；不一致投票本身不再触发升级，
+//! 仅当剩余未投票成员已不足以让任何结果达到 M 票（僵局）时才升级（escalated），
+//! 之后只能由全局管理员结算。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::{MAX_COMMITTEE_MEMBERS, RESOLUTION_VOTES, RESOLVER_COMMITTEE};
+use crate::errors::PredictionMarketError;
+
+/// 结算委员会
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ResolverCommittee {
+    /// 创建者
+    pub creator: Pubkey,
+
+    /// 委员会编号（PDA 种子）
+    pub committee_id: u64,
+
+    /// 成员数量 N
+    pub member_count: u8,
+
+    /// 结算所需一致票数 M
+    pub threshold: u8,
+
+    /// 成员（仅前 member_count 项有效）
+    pub members: [Pubkey; MAX_COMMITTEE_MEMBERS],
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ResolverCommittee {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = RESOLVER_COMMITTEE;
+
+    /// 校验成员列表与阈值：1 ≤ M ≤ N ≤ MAX，成员不重复且非默认地址
+    pub fn validate(members: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !members.is_empty()
+                && members.len() <= MAX_COMMITTEE_MEMBERS
+                && threshold >= 1
+                && threshold as usize <= members.len(),
+            PredictionMarketError::InvalidCommittee
+        );
+        for (i, member) in members.iter().enumerate() {
+            require!(
+                *member != Pubkey::default() && !members[..i].contains(member),
+                PredictionMarketError::InvalidCommittee
+            );
+        }
+        Ok(())
+    }
+
+    /// 有效成员切片
+    pub fn active_members(&self) -> &[Pubkey] {
+        &self.members[..self.member_count as usize]
+    }
+
+    /// 查找成员索引
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.active_members().iter().position(|m| m == key)
+    }
+}
+
+/// 单张结算投票（与管理员结算提案参数一致）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct ResolverVote {
+    /// 获胜方（0=NO, 1=YES, 2=平局）
+    pub token_type: u8,
+    /// YES 赎回比例（基点）
+    pub yes_ratio: u64,
+    /// NO 赎回比例（基点）
+    pub no_ratio: u64,
+}
+
+/// 市场的委员会投票记录
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ResolutionVotes {
+    /// 市场
+    pub market: Pubkey,
+
+    /// 委员会
+    pub committee: Pubkey,
+
+    /// 已投票成员掩码（第 i 位 = 成员 i）
+    pub voted_mask: u16,
+
+    /// 各成员的投票（按成员索引）
+    pub votes: [ResolverVote; MAX_COMMITTEE_MEMBERS],
+
+    /// 是否因冲突升级至全局管理员
+    pub escalated: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ResolutionVotes {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = RESOLUTION_VOTES;
+
+    /// 记录成员投票
+    ///
+    /// # 返回
+    /// 本票所投结果的累计票数（含本票）
+    pub fn record(&mut self, member_index: usize, vote: ResolverVote) -> Result<u8> {
+        let bit = 1u16 << member_index;
+        require!(self.voted_mask & bit == 0, PredictionMarketError::AlreadyVoted);
+
+        self.voted_mask |= bit;
+        self.votes[member_index] = vote;
+        Ok(self.tally(&vote))
+    }
+
+    /// ✅ v3.9.1: 某一结果的已投票数
+    pub fn tally(&self, vote: &ResolverVote) -> u8 {
+        self.cast_votes().filter(|existing| *existing == vote).count() as u8
+    }
+
+    /// ✅ v3.9.1: 是否仍有结果可能达到阈值
+    ///
+    /// 领先结果票数 + 未投票成员数 ≥ M 时仍可能结算（未投票成员也可能投出新结果，
+    /// 此时领先票数按 0 计，条件同样被覆盖）；否则为僵局，应升级至全局管理员
+    pub fn can_reach_threshold(&self, member_count: u8, threshold: u8) -> bool {
+        let voted = self.voted_mask.count_ones() as u8;
+        let remaining = member_count.saturating_sub(voted);
+        let leading = self.cast_votes().map(|vote| self.tally(vote)).max().unwrap_or(0);
+        leading.saturating_add(remaining) >= threshold
+    }
+
+    /// 已投出的票
+    fn cast_votes(&self) -> impl Iterator<Item = &ResolverVote> {
+        self.votes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.voted_mask & (1u16 << i) != 0)
+            .map(|(_, vote)| vote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YES: ResolverVote = ResolverVote { token_type: 1, yes_ratio: 10_000, no_ratio: 0 };
+    const NO: ResolverVote = ResolverVote { token_type: 0, yes_ratio: 0, no_ratio: 10_000 };
+
+    fn empty_votes() -> ResolutionVotes {
+        ResolutionVotes {
+            market: Pubkey::new_unique(),
+            committee: Pubkey::new_unique(),
+            voted_mask: 0,
+            votes: [ResolverVote::default(); MAX_COMMITTEE_MEMBERS],
+            escalated: false,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_committee_validate() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let c = Pubkey::new_unique();
+
+        assert!(ResolverCommittee::validate(&[a, b, c], 2).is_ok());
+        assert!(ResolverCommittee::validate(&[a], 1).is_ok());
+        // M > N
+        assert!(ResolverCommittee::validate(&[a, b], 3).is_err());
+        // M = 0
+        assert!(ResolverCommittee::validate(&[a, b], 0).is_err());
+        // 重复成员
+        assert!(ResolverCommittee::validate(&[a, b, a], 2).is_err());
+        // 空委员会
+        assert!(ResolverCommittee::validate(&[], 1).is_err());
+    }
+
+    #[test]
+    fn test_record_votes_and_conflicts() {
+        let mut votes = empty_votes();
+
+        assert_eq!(votes.record(0, YES).unwrap(), 1);
+        assert_eq!(votes.record(2, YES).unwrap(), 2);
+        // 重复投票
+        assert!(votes.record(0, YES).is_err());
+        // 不一致投票按结果分别计票
+        assert_eq!(votes.record(1, NO).unwrap(), 1);
+        assert_eq!(votes.voted_mask, 0b111);
+        assert_eq!(votes.tally(&YES), 2);
+        assert_eq!(votes.tally(&NO), 1);
+    }
+
+    #[test]
+    fn test_dissent_does_not_escalate_before_deadlock() {
+        // 3-of-5：一张反对票不影响多数结果
+        let mut votes = empty_votes();
+        votes.record(0, YES).unwrap();
+        votes.record(1, NO).unwrap();
+        assert!(votes.can_reach_threshold(5, 3));
+        votes.record(2, YES).unwrap();
+        assert_eq!(votes.record(3, YES).unwrap(), 3);
+
+        // 3-of-5：2 YES / 2 NO 后剩 1 票，任一结果仍可达 3 票
+        let mut votes = empty_votes();
+        votes.record(0, YES).unwrap();
+        votes.record(1, NO).unwrap();
+        votes.record(2, YES).unwrap();
+        votes.record(3, NO).unwrap();
+        assert!(votes.can_reach_threshold(5, 3));
+
+        // 3-of-4：2 YES / 2 NO 且无剩余成员 → 僵局
+        assert!(!votes.can_reach_threshold(4, 3));
+
+        // 4-of-5：YES / NO / 平局 各一票后剩 2 票，领先 1 + 2 < 4 → 僵局
+        let draw = ResolverVote { token_type: 2, yes_ratio: 5_000, no_ratio: 5_000 };
+        let mut votes = empty_votes();
+        votes.record(0, YES).unwrap();
+        assert!(votes.can_reach_threshold(5, 4));
+        votes.record(1, NO).unwrap();
+        assert!(votes.can_reach_threshold(5, 4));
+        votes.record(2, draw).unwrap();
+        assert!(!votes.can_reach_threshold(5, 4));
+    }
+}
@@ -0,0 +1,206 @@
+//! ✅ v3.26.0: 会话密钥（委托临时热钥交易，免钱包弹窗）
+//!
+//! owner 一次性创建 `SessionKey`（有效期、USDC 支出上限、市场白名单、允许的操作），
+//! 并将 USDC / YES / NO ATA 的 delegate 授权给用户委托权限 PDA。此后会话签名者可代替 owner
+//! 签名 swap / mint_complete_set / redeem_complete_set：
+//!
+//! - 资金仍从 owner 的 ATA 转出，转账由用户委托权限 PDA 以 delegate 身份签名
+//! - 输出只进入 owner 的 ATA（各指令的 `associated_token::authority = user` 约束），
+//!   会话模式禁止代买 recipient，因此会话密钥无法把资金转给他人
+//! - 热钥本身从不持有 delegate 权限，泄露后的损失以支出上限与有效期为界，owner 可随时撤销
+//!
+//! ✅ v3.26.1: SPL Token 账户只有一个 delegate，各会话 PDA 与 IntentNonce PDA 分别作为 delegate
+//! 会互相覆盖授权。现统一以每用户一个的委托权限 PDA（`[INTENT_NONCE, owner]`）作为唯一 delegate，
+//! 会话与意图共用同一份 SPL 授权额度；每个会话的支出上限、作用域与有效期由本账户在程序内约束。
+//! 会话模式下 user 不签名，无法支付租金，ATA 与 user_info 须已存在。
+
+use anchor_lang::prelude::*;
+// 显式使用 Anchor 预导出的 borsh 版本，避免 derive 时与测试环境的 --extern 冲突
+use anchor_lang::prelude::borsh;
+use crate::constants::{MAX_SESSION_KEY_DURATION_SECS, MAX_SESSION_MARKETS, SESSION_ACTIONS_ALL, SESSION_KEY};
+use crate::errors::PredictionMarketError;
+
+/// 会话密钥
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct SessionKey {
+    /// 授权人（资金所有者）
+    pub owner: Pubkey,
+
+    /// 会话签名者（临时热钥）
+    pub session_signer: Pubkey,
+
+    /// 过期时间（Unix 时间戳）
+    pub expires_at: i64,
+
+    /// USDC 支出上限（买入含手续费 + 铸造抵押）
+    pub spend_cap: u64,
+
+    /// 已支出 USDC
+    pub spent: u64,
+
+    /// 允许的操作（SESSION_ACTION_* 位掩码）
+    pub allowed_actions: u8,
+
+    /// 限定市场数量（0 = 不限市场）
+    pub market_count: u8,
+
+    /// 限定市场
+    pub allowed_markets: [Pubkey; MAX_SESSION_MARKETS],
+
+    /// 创建时间
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SessionKey {
+    /// PDA 种子前缀
+    pub const SEED_PREFIX: &'static str = SESSION_KEY;
+
+    /// 校验创建参数
+    pub fn validate(&self, now: i64) -> Result<()> {
+        require!(
+            self.session_signer != Pubkey::default() && self.session_signer != self.owner,
+            PredictionMarketError::InvalidSessionKey
+        );
+        require!(
+            self.expires_at > now && self.expires_at - now <= MAX_SESSION_KEY_DURATION_SECS,
+            PredictionMarketError::InvalidSessionKey
+        );
+        require!(
+            self.allowed_actions != 0 && self.allowed_actions & !SESSION_ACTIONS_ALL == 0,
+            PredictionMarketError::InvalidSessionKey
+        );
+        require!(
+            self.market_count as usize <= MAX_SESSION_MARKETS,
+            PredictionMarketError::InvalidSessionKey
+        );
+        Ok(())
+    }
+
+    /// 校验会话是否可由 `signer` 在 `market` 上执行 `action`
+    pub fn check(&self, signer: &Pubkey, market: &Pubkey, action: u8, now: i64) -> Result<()> {
+        require_keys_eq!(*signer, self.session_signer, PredictionMarketError::InvalidSessionKey);
+        require!(now < self.expires_at, PredictionMarketError::SessionKeyExpired);
+        require!(
+            self.allowed_actions & action == action
+                && (self.market_count == 0
+                    || self.allowed_markets[..self.market_count as usize].contains(market)),
+            PredictionMarketError::SessionKeyNotAllowed
+        );
+        Ok(())
+    }
+
+    /// 累计支出，超过上限时失败（整笔交易回滚）
+    pub fn record_spend(&mut self, amount: u64) -> Result<()> {
+        let spent = self
+            .spent
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(spent <= self.spend_cap, PredictionMarketError::SessionSpendCapExceeded);
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+/// 校验指令的交易授权
+///
+/// - 未提供会话账户：user 必须签名，返回 false
+/// - 提供会话账户与会话签名者：校验会话，返回 true（转账由用户委托权限 PDA 签名）
+pub fn authorize_session(
+    user: &AccountInfo,
+    session_key: Option<&SessionKey>,
+    session_signer: Option<&AccountInfo>,
+    market: &Pubkey,
+    action: u8,
+    now: i64,
+) -> Result<bool> {
+    match (session_key, session_signer) {
+        (None, None) => {
+            require!(user.is_signer, PredictionMarketError::InvalidSessionKey);
+            Ok(false)
+        }
+        (Some(session), Some(signer)) => {
+            require!(signer.is_signer, PredictionMarketError::InvalidSessionKey);
+            require_keys_eq!(session.owner, *user.key, PredictionMarketError::InvalidSessionKey);
+            session.check(signer.key, market, action, now)?;
+            Ok(true)
+        }
+        _ => err!(PredictionMarketError::InvalidSessionKey),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{SESSION_ACTION_MINT, SESSION_ACTION_REDEEM, SESSION_ACTION_SWAP};
+
+    fn session(markets: &[Pubkey]) -> SessionKey {
+        let mut allowed_markets = [Pubkey::default(); MAX_SESSION_MARKETS];
+        allowed_markets[..markets.len()].copy_from_slice(markets);
+        SessionKey {
+            owner: Pubkey::new_unique(),
+            session_signer: Pubkey::new_unique(),
+            expires_at: 10_000,
+            spend_cap: 1_000,
+            spent: 0,
+            allowed_actions: SESSION_ACTION_SWAP | SESSION_ACTION_REDEEM,
+            market_count: markets.len() as u8,
+            allowed_markets,
+            created_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let s = session(&[]);
+        assert!(s.validate(0).is_ok());
+
+        // 已过期 / 超过最长有效期
+        assert!(s.validate(10_000).is_err());
+        assert!(s.validate(10_000 - MAX_SESSION_KEY_DURATION_SECS - 1).is_err());
+
+        // 未知操作位 / 无操作
+        let mut bad = session(&[]);
+        bad.allowed_actions = 1 << 7;
+        assert!(bad.validate(0).is_err());
+        bad.allowed_actions = 0;
+        assert!(bad.validate(0).is_err());
+
+        // 会话签名者不能是 owner
+        let mut same = session(&[]);
+        same.session_signer = same.owner;
+        assert!(same.validate(0).is_err());
+    }
+
+    #[test]
+    fn test_check_scope() {
+        let market = Pubkey::new_unique();
+        let s = session(&[market]);
+        let signer = s.session_signer;
+
+        assert!(s.check(&signer, &market, SESSION_ACTION_SWAP, 0).is_ok());
+        assert!(s.check(&signer, &market, SESSION_ACTION_MINT, 0).is_err());
+        assert!(s.check(&signer, &Pubkey::new_unique(), SESSION_ACTION_SWAP, 0).is_err());
+        assert!(s.check(&Pubkey::new_unique(), &market, SESSION_ACTION_SWAP, 0).is_err());
+        assert!(s.check(&signer, &market, SESSION_ACTION_SWAP, 10_000).is_err());
+
+        // 不限市场
+        let any = session(&[]);
+        assert!(any.check(&any.session_signer, &market, SESSION_ACTION_REDEEM, 0).is_ok());
+    }
+
+    #[test]
+    fn test_spend_cap() {
+        let mut s = session(&[]);
+        s.record_spend(600).unwrap();
+        s.record_spend(400).unwrap();
+        assert_eq!(s.spent, 1_000);
+        assert!(s.record_spend(1).is_err());
+        assert_eq!(s.spent, 1_000);
+        s.record_spend(0).unwrap();
+    }
+}
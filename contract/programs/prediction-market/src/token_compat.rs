@@ -0,0 +1,187 @@
+//! ✅ v3.27.0: SPL Token / Token-2022 兼容层
+//!
+//! 所有指令通过 `token_interface` 同时支持 SPL Token 与 Token-2022：
+//! 代币程序为 `Interface<TokenInterface>`，mint / 代币账户为 `InterfaceAccount`，
+//! 转账统一使用 `transfer_checked`（Token-2022 带转账手续费的 mint 不接受 `transfer`）。
+//!
+//! **代币程序分离**（✅ v3.27.1）：同时涉及抵押品与 YES/NO 的指令分别传入
+//! `collateral_token_program` 与 `outcome_token_program`，抵押品可继续使用 SPL Token，
+//! YES/NO 使用 Token-2022（反之亦然）；只涉及一类代币的指令仍只传入 `token_program`。
+//! create_market 通过 `mint::token_program` 约束校验各 mint 的归属，YES/NO 须同属一个程序。
+//! Token-2022 部署中，YES/NO mint 由
+//! `create_outcome_mint` 创建，元数据写入 mint 自身（metadata-pointer + token-metadata 扩展），
+//! 不再经过 Metaplex。
+//!
+//! **扩展白名单**：
+//! - 抵押品 mint：TransferFeeConfig、MetadataPointer、TokenMetadata
+//! - YES/NO mint：MetadataPointer、TokenMetadata
+//!
+//! 转账钩子、永久委托、不可转让、默认冻结、保密转账等扩展会破坏金库托管与账本假设，一律拒绝。
+//!
+//! **转账手续费记账**：Token-2022 手续费从转账金额中扣留在目标账户，接收方实收 `amount - fee`。
+//! - 转入按账本记账的协议账户（市场金库、订单簿托管、结算保证金）：按 `gross_up_transfer_fee`
+//!   上浮转账金额，由付款方承担手续费，协议账户恰好收到账本记录的金额，
+//!   `total_collateral_locked` / `pool_collateral_reserve` 与金库余额保持一致
+//! - 转出协议账户：账本与金库同按转出金额扣减，`usdc_vault_min_balance` 校验不受影响，
+//!   手续费由接收方承担（团队钱包、引荐托管按实收余额领取）
+//! - 托管账户关闭前以 `harvest_withheld_fees` 归集扣留的手续费
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::{TransferFee, TransferFeeConfig},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::Mint as MintState,
+};
+use crate::errors::PredictionMarketError;
+
+/// 抵押品 mint 允许的 Token-2022 扩展
+pub const COLLATERAL_MINT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+/// YES/NO mint 允许的 Token-2022 扩展
+pub const OUTCOME_MINT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+/// 校验 mint 只含白名单内的扩展（SPL Token mint 无扩展，直接通过）
+fn check_mint_extensions(mint: &AccountInfo, allowed: &[ExtensionType]) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)
+        .map_err(|_| PredictionMarketError::InvalidMint)?;
+    let extensions = state
+        .get_extension_types()
+        .map_err(|_| PredictionMarketError::InvalidMint)?;
+    require!(
+        extensions.iter().all(|extension| allowed.contains(extension)),
+        PredictionMarketError::UnsupportedMintExtension
+    );
+    Ok(())
+}
+
+/// 校验抵押品 mint 扩展
+pub fn validate_collateral_mint(mint: &AccountInfo) -> Result<()> {
+    check_mint_extensions(mint, COLLATERAL_MINT_EXTENSIONS)
+}
+
+/// 校验 YES/NO mint 扩展
+pub fn validate_outcome_mint(mint: &AccountInfo) -> Result<()> {
+    check_mint_extensions(mint, OUTCOME_MINT_EXTENSIONS)
+}
+
+/// 读取当前 epoch 生效的转账手续费参数（无 TransferFeeConfig 扩展时为 None）
+fn current_transfer_fee(mint: &AccountInfo) -> Result<Option<TransferFee>> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)
+        .map_err(|_| PredictionMarketError::InvalidMint)?;
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(Some(*config.get_epoch_fee(Clock::get()?.epoch))),
+        Err(_) => Ok(None),
+    }
+}
+
+/// 接收方恰好收到 `net_amount` 所需的转账金额
+fn gross_up(fee: &TransferFee, net_amount: u64) -> Option<u64> {
+    if net_amount == 0 {
+        return Some(0);
+    }
+    net_amount.checked_add(fee.calculate_inverse_fee(net_amount)?)
+}
+
+/// 转入协议账户的转账金额：使协议账户恰好收到 `net_amount`（手续费由付款方承担）
+pub fn gross_up_transfer_fee(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    match current_transfer_fee(mint)? {
+        Some(fee) => Ok(gross_up(&fee, net_amount).ok_or(PredictionMarketError::MathOverflow)?),
+        None => Ok(net_amount),
+    }
+}
+
+/// 转账 `amount` 时扣留的手续费（无 TransferFeeConfig 扩展时为 0）
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match current_transfer_fee(mint)? {
+        Some(fee) => Ok(fee.calculate_fee(amount).ok_or(PredictionMarketError::MathOverflow)?),
+        None => Ok(0),
+    }
+}
+
+/// 将代币账户中扣留的转账手续费归集到 mint（需可写）
+///
+/// Token-2022 拒绝关闭仍有扣留手续费的账户，托管账户关闭前须先归集（无 TransferFeeConfig 时跳过）
+pub fn harvest_withheld_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+) -> Result<()> {
+    if current_transfer_fee(mint)?.is_none() {
+        return Ok(());
+    }
+    token_interface::harvest_withheld_tokens_to_mint(
+        CpiContext::new(
+            token_program.clone(),
+            token_interface::HarvestWithheldTokensToMint {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        vec![account.clone()],
+    )
+}
+
+/// TokenMetadata 扩展所需空间：TLV 头 4 + update_authority 32 + mint 32
+/// + name / symbol / uri（各 4 字节长度前缀）+ 空的附加字段 4
+pub fn token_metadata_space(name: &str, symbol: &str, uri: &str) -> usize {
+    4 + 32 + 32 + (4 + name.len()) + (4 + symbol.len()) + (4 + uri.len()) + 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee(bps: u16, maximum_fee: u64) -> TransferFee {
+        TransferFee {
+            epoch: 0u64.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: bps.into(),
+        }
+    }
+
+    #[test]
+    fn test_gross_up_delivers_net_amount() {
+        let fee = fee(100, u64::MAX);
+        for net in [1u64, 99, 1_000_000, 123_456_789] {
+            let gross = gross_up(&fee, net).unwrap();
+            assert!(gross > net);
+            assert_eq!(gross - fee.calculate_fee(gross).unwrap(), net);
+        }
+        assert_eq!(gross_up(&fee, 0), Some(0));
+    }
+
+    #[test]
+    fn test_gross_up_respects_maximum_fee() {
+        let fee = fee(500, 1_000);
+        let gross = gross_up(&fee, 10_000_000).unwrap();
+        assert_eq!(gross, 10_001_000);
+        assert_eq!(gross - fee.calculate_fee(gross).unwrap(), 10_000_000);
+
+        // 零费率
+        assert_eq!(gross_up(&self::fee(0, 0), 42), Some(42));
+    }
+
+    #[test]
+    fn test_token_metadata_space() {
+        assert_eq!(token_metadata_space("agree", "YES", "https://x"), 4 + 64 + 9 + 7 + 13 + 4);
+    }
+}
@@ -95,7 +95,7 @@ impl TokenType {
 
 /// 市场结算结果枚举
 ///
-/// 用于 resolution 指令，表示市场最终结果
+/// 用于结算提案与结算指令，表示市场最终结果
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MarketOutcome {
     /// NO 方获胜（100% NO, 0% YES）
@@ -104,13 +104,18 @@ pub enum MarketOutcome {
     YesWins = 1,
     /// 平局（50% NO, 50% YES）
     Draw = 2,
+    /// ✅ v3.10.0: 无效/作废（问题含糊或取消）
+    ///
+    /// 与 Draw 不同，按最后成交价格快照退款：YES 按 p、NO 按 1-p 赎回，
+    /// 使低价买入 YES 的用户不会与高价买入 NO 的用户获得相同赔付
+    Invalid = 3,
 }
 
 impl MarketOutcome {
     /// 从 u8 转换为 MarketOutcome
     ///
     /// # 参数
-    /// * `value` - u8 值（0=NoWins, 1=YesWins, 2=Draw）
+    /// * `value` - u8 值（0=NoWins, 1=YesWins, 2=Draw, 3=Invalid）
     ///
     /// # 返回
     /// * `Option<Self>` - 转换成功返回Some，失败返回None
@@ -119,6 +124,7 @@ impl MarketOutcome {
             0 => Some(MarketOutcome::NoWins),
             1 => Some(MarketOutcome::YesWins),
             2 => Some(MarketOutcome::Draw),
+            3 => Some(MarketOutcome::Invalid),
             _ => None,
         }
     }
@@ -130,13 +136,15 @@ impl MarketOutcome {
 
     /// 获取 YES 代币赎回比例（基点）
     ///
+    /// Invalid 的实际比例由价格快照决定（见 `Market::invalid_refund_ratios`），此处返回中性值
+    ///
     /// # 返回
     /// * `u64` - YES 代币赎回比例（10000 = 100%）
     pub fn yes_ratio(self) -> u64 {
         match self {
             MarketOutcome::NoWins => 0,
             MarketOutcome::YesWins => crate::constants::BASIS_POINTS_DIVISOR,
-            MarketOutcome::Draw => crate::constants::BASIS_POINTS_DIVISOR / 2,
+            MarketOutcome::Draw | MarketOutcome::Invalid => crate::constants::BASIS_POINTS_DIVISOR / 2,
         }
     }
 
@@ -148,11 +156,31 @@ impl MarketOutcome {
         match self {
             MarketOutcome::NoWins => crate::constants::BASIS_POINTS_DIVISOR,
             MarketOutcome::YesWins => 0,
-            MarketOutcome::Draw => crate::constants::BASIS_POINTS_DIVISOR / 2,
+            MarketOutcome::Draw | MarketOutcome::Invalid => crate::constants::BASIS_POINTS_DIVISOR / 2,
         }
     }
 }
 
+/// ✅ v3.19.0: 交易路由
+///
+/// 用于 routed_swap，表示实际执行的成交路径（在 SwapExecuted 事件中报告）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapRoute {
+    /// 直接与 LMSR 池子成交
+    Direct = 0,
+    /// 买入：铸造完整集合后将另一侧代币卖回池子
+    MintAndSell = 1,
+    /// 卖出：从池子买入另一侧代币后赎回完整集合
+    BuyAndRedeem = 2,
+}
+
+impl SwapRoute {
+    /// 转换为 u8
+    pub fn to_u8(self) -> u8 {
+        self as u8
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,5 +215,15 @@ mod tests {
 
         assert_eq!(MarketOutcome::Draw.yes_ratio(), 5000);
         assert_eq!(MarketOutcome::Draw.no_ratio(), 5000);
+
+        assert_eq!(MarketOutcome::from_u8(3), Some(MarketOutcome::Invalid));
+        assert_eq!(MarketOutcome::from_u8(4), None);
+    }
+
+    #[test]
+    fn test_swap_route_conversion() {
+        assert_eq!(SwapRoute::Direct.to_u8(), 0);
+        assert_eq!(SwapRoute::MintAndSell.to_u8(), 1);
+        assert_eq!(SwapRoute::BuyAndRedeem.to_u8(), 2);
     }
 }
@@ -5,7 +5,7 @@
 
 use crate::*;
 use crate::errors::PredictionMarketError;
-use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{self, Mint, TokenInterface};
 use anchor_lang::system_program::{transfer, Transfer};
 use std::ops::{Div, Mul};
 
@@ -100,36 +100,39 @@ pub fn sol_transfer_from_user<'info>(
 
 /// 从用户账户转账代币
 /// 
-/// 使用SPL代币程序从用户账户转账代币
+/// 使用代币程序（SPL Token / Token-2022）从用户账户转账代币
 /// 
 /// # 参数
 /// * `from` - 源代币账户
+/// * `mint` - 代币 mint（transfer_checked 校验精度）
 /// * `authority` - 授权签名者
 /// * `to` - 目标代币账户
-/// * `token_program` - SPL代币程序
+/// * `token_program` - 代币程序
 /// * `amount` - 转账数量
 /// 
 /// # 返回
 /// * `Result<()>` - 操作结果
 pub fn token_transfer_user<'info>(
     from: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
     authority: &Signer<'info>,
     to: AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    token_program: &Interface<'info, TokenInterface>,
     amount: u64,
 ) -> Result<()> {
     // 创建CPI上下文
     let cpi_ctx: CpiContext<_> = CpiContext::new(
         token_program.to_account_info(),
-        token::Transfer {
+        token_interface::TransferChecked {
             from,
+            mint: mint.to_account_info(),
             authority: authority.to_account_info(),
             to,
         },
     );
     
-    // 执行代币转账
-    token::transfer(cpi_ctx, amount)?;
+    // 执行代币转账（✅ v3.27.0: transfer_checked，兼容 Token-2022）
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
     Ok(())
 }
 
@@ -140,9 +143,10 @@ pub fn token_transfer_user<'info>(
 /// 
 /// # 参数
 /// * `from` - 源代币账户
+/// * `mint` - 代币 mint（transfer_checked 校验精度）
 /// * `authority` - PDA授权账户
 /// * `to` - 目标代币账户
-/// * `token_program` - SPL代币程序
+/// * `token_program` - 代币程序
 /// * `signer_seeds` - PDA签名种子
 /// * `amount` - 转账数量
 /// 
@@ -150,25 +154,27 @@ pub fn token_transfer_user<'info>(
 /// * `Result<()>` - 操作结果
 pub fn token_transfer_with_signer<'info>(
     from: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
     authority: AccountInfo<'info>,
     to: AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    token_program: &Interface<'info, TokenInterface>,
     signer_seeds: &[&[&[u8]]],
     amount: u64,
 ) -> Result<()> {
     // 创建带签名的CPI上下文
     let cpi_ctx: CpiContext<_> = CpiContext::new_with_signer(
         token_program.to_account_info(),
-        token::Transfer {
+        token_interface::TransferChecked {
             from,
+            mint: mint.to_account_info(),
             to,
             authority,
         },
         signer_seeds,
     );
     
-    // 执行代币转账
-    token::transfer(cpi_ctx, amount)?;
+    // 执行代币转账（✅ v3.27.0: transfer_checked，兼容 Token-2022）
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
     Ok(())
 }
 
@@ -237,14 +243,14 @@ pub fn token_burn_with_signer<'info>(
     mint: AccountInfo<'info>, // ✅ FIX: 新增 mint 参数
     from: AccountInfo<'info>, // 要销毁代币的账户
     authority: AccountInfo<'info>, // PDA授权账户
-    token_program: &Program<'info, Token>, // SPL代币程序
+    token_program: &Interface<'info, TokenInterface>, // SPL代币程序
     signer_seeds: &[&[&[u8]]], // PDA签名种子
     amount: u64, // 销毁数量
 ) -> Result<()> {
     // 创建带签名的CPI上下文
     let cpi_ctx: CpiContext<_> = CpiContext::new_with_signer(
         token_program.to_account_info(),
-        token::Burn {
+        token_interface::Burn {
             mint, // ✅ FIX: 使用正确的 mint 参数
             from, // 要销毁的账户
             authority, // PDA授权账户
@@ -253,7 +259,7 @@ pub fn token_burn_with_signer<'info>(
     );
 
     // 执行代币销毁
-    token::burn(cpi_ctx, amount)?;
+    token_interface::burn(cpi_ctx, amount)?;
     Ok(())
 }
 
@@ -271,7 +277,7 @@ pub fn token_burn_with_signer<'info>(
 /// * `Option<u64>` - 计算结果，如果溢出则返回None
 ///
 /// # 注意
-/// ⚠️ 此函数目前未被项目使用，如需启用请确保参数有效
+/// ✅ v3.3.0: 分类市场交易（categorical_swap）使用此函数计算手续费
 pub fn bps_mul(bps: u64, value: u64, divisor: u64) -> Option<u64> {
     // ✅ FIX: 移除 unwrap()，使用 ? 链式调用
     bps_mul_raw(bps, value, divisor)?.try_into().ok()
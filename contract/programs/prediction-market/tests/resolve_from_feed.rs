@@ -0,0 +1,210 @@
+//! ✅ v3.8.2: resolve_from_feed 集成测试（solana-program-test + mock Pyth PriceUpdateV2）
+//!
+//! 价格源账户不再按地址固定：调用者提交的任意 Receiver 拥有、feed_id 一致的历史价格账户均可结算。
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator, InstructionData, ToAccountMetas};
+use prediction_market::{
+    constants::{GLOBAL, MARKET},
+    price_feed::{encode_price_update_v2, PYTH_RECEIVER_PROGRAM_ID},
+    state::market::Market,
+};
+use solana_program::program_pack::Pack;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::Transaction,
+};
+
+const FEED_ID: [u8; 32] = [7u8; 32];
+const RESOLVE_AFTER: i64 = 1_700_000_000;
+
+/// Anchor 生成的 entry 要求 accounts 与 AccountInfo 生命周期一致
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    prediction_market::entry(program_id, accounts, data)
+}
+
+struct Fixture {
+    market: Pubkey,
+    yes_token: Pubkey,
+    no_token: Pubkey,
+    global_vault: Pubkey,
+}
+
+fn mint_account() -> Account {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        is_initialized: true,
+        decimals: 6,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: 1_000_000_000, data, owner: spl_token::id(), executable: false, rent_epoch: 0 }
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey) -> Account {
+    let mut data = vec![0u8; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint,
+        owner,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: 1_000_000_000, data, owner: spl_token::id(), executable: false, rent_epoch: 0 }
+}
+
+fn price_update_account(feed_id: [u8; 32], price: i64, publish_time: i64) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data: encode_price_update_v2(feed_id, price, price as u64 / 1_000, -8, publish_time),
+        owner: PYTH_RECEIVER_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// 价格源市场："价格是否高于 150"，登记的参考价格源账户为 `registered_feed`
+fn feed_market_account(yes_token: Pubkey, no_token: Pubkey, registered_feed: Pubkey) -> Account {
+    // 全 0 布局可直接反序列化（同旧账户扩容后的零填充），仅设置本测试需要的字段
+    let mut zeroed = Market::DISCRIMINATOR.to_vec();
+    zeroed.resize(8 + Market::INIT_SPACE, 0);
+    let mut market = Market::try_deserialize(&mut zeroed.as_slice()).unwrap();
+    market.yes_token_mint = yes_token;
+    market.no_token_mint = no_token;
+    market.price_feed = registered_feed;
+    market.price_feed_id = FEED_ID;
+    market.price_threshold = 150;
+    market.price_threshold_expo = 0;
+    market.price_resolve_after = RESOLVE_AFTER;
+    market.price_max_staleness_secs = 600;
+    market.price_max_conf_bps = 100;
+
+    let mut data = Vec::new();
+    market.try_serialize(&mut data).unwrap();
+    data.resize(8 + Market::INIT_SPACE, 0);
+    Account { lamports: 1_000_000_000, data, owner: prediction_market::ID, executable: false, rent_epoch: 0 }
+}
+
+fn setup(feeds: &[(Pubkey, Account)]) -> (ProgramTest, Fixture) {
+    let mut program_test = ProgramTest::new("prediction_market", prediction_market::ID, processor!(process_instruction));
+    program_test.prefer_bpf(false);
+
+    let yes_token = Pubkey::new_unique();
+    let no_token = Pubkey::new_unique();
+    let (market, _) = Pubkey::find_program_address(
+        &[MARKET.as_bytes(), yes_token.as_ref(), no_token.as_ref()],
+        &prediction_market::ID,
+    );
+    let (global_vault, _) = Pubkey::find_program_address(&[GLOBAL.as_bytes()], &prediction_market::ID);
+
+    program_test.add_account(yes_token, mint_account());
+    program_test.add_account(no_token, mint_account());
+    for mint in [yes_token, no_token] {
+        program_test.add_account(
+            spl_associated_token_account::get_associated_token_address(&global_vault, &mint),
+            token_account(mint, global_vault),
+        );
+    }
+    program_test.add_account(market, feed_market_account(yes_token, no_token, Pubkey::new_unique()));
+    for (key, account) in feeds {
+        program_test.add_account(*key, account.clone());
+    }
+
+    (program_test, Fixture { market, yes_token, no_token, global_vault })
+}
+
+fn resolve_ix(fixture: &Fixture, price_feed: Pubkey, caller: Pubkey) -> Instruction {
+    Instruction {
+        program_id: prediction_market::ID,
+        accounts: prediction_market::accounts::ResolveFromFeed {
+            market: fixture.market,
+            price_feed,
+            global_vault: fixture.global_vault,
+            yes_token: fixture.yes_token,
+            no_token: fixture.no_token,
+            global_yes_ata: spl_associated_token_account::get_associated_token_address(
+                &fixture.global_vault,
+                &fixture.yes_token,
+            ),
+            global_no_ata: spl_associated_token_account::get_associated_token_address(
+                &fixture.global_vault,
+                &fixture.no_token,
+            ),
+            event_group: None,
+            parent_market: None,
+            market_metadata: None,
+            caller,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: prediction_market::instruction::ResolveFromFeed {}.data(),
+    }
+}
+
+async fn warp_to(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+async fn send(context: &mut ProgramTestContext, ix: Instruction) -> bool {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+    context.banks_client.process_transaction(tx).await.is_ok()
+}
+
+async fn fetch_market(context: &mut ProgramTestContext, market: Pubkey) -> Market {
+    let account = context.banks_client.get_account(market).await.unwrap().unwrap();
+    Market::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn test_resolve_from_caller_posted_feed_with_matching_feed_id() {
+    // 调用者提交的历史价格账户（地址与市场登记的参考账户不同）
+    let posted_feed = Pubkey::new_unique();
+    let (program_test, fixture) = setup(&[(
+        posted_feed,
+        price_update_account(FEED_ID, 16_000_000_000, RESOLVE_AFTER + 60),
+    )]);
+    let mut context = program_test.start_with_context().await;
+    warp_to(&mut context, RESOLVE_AFTER + 3_600).await;
+
+    let ix = resolve_ix(&fixture, posted_feed, context.payer.pubkey());
+    assert!(send(&mut context, ix).await);
+
+    let market = fetch_market(&mut context, fixture.market).await;
+    assert!(market.is_completed);
+    assert_eq!(market.winner_token_type, 1);
+}
+
+#[tokio::test]
+async fn test_resolve_from_feed_rejects_foreign_feeds() {
+    let wrong_feed_id = Pubkey::new_unique();
+    let wrong_owner = Pubkey::new_unique();
+    let late_publish = Pubkey::new_unique();
+    let mut foreign_owner = price_update_account(FEED_ID, 16_000_000_000, RESOLVE_AFTER + 60);
+    foreign_owner.owner = Pubkey::new_unique();
+    let (program_test, fixture) = setup(&[
+        (wrong_feed_id, price_update_account([8u8; 32], 16_000_000_000, RESOLVE_AFTER + 60)),
+        (wrong_owner, foreign_owner),
+        // 窗口（resolve_after + 600 秒）之后发布的价格
+        (late_publish, price_update_account(FEED_ID, 16_000_000_000, RESOLVE_AFTER + 601)),
+    ]);
+    let mut context = program_test.start_with_context().await;
+    warp_to(&mut context, RESOLVE_AFTER + 3_600).await;
+
+    for feed in [wrong_feed_id, wrong_owner, late_publish] {
+        let ix = resolve_ix(&fixture, feed, context.payer.pubkey());
+        assert!(!send(&mut context, ix).await);
+    }
+
+    let market = fetch_market(&mut context, fixture.market).await;
+    assert!(!market.is_completed);
+}
@@ -95,7 +95,8 @@ async function main() {
       lpInsuranceAllocationBps: 2000,  // 20%
       insuranceLossThresholdBps: 1000,  // 10%
       insuranceMaxCompensationBps: 5000,  // 50%
-      insurancePoolEnabled: false
+      insurancePoolEnabled: false,
+      maxReferralShareBps: 0
     };
 
     console.log("📝 配置参数:");
@@ -92,6 +92,7 @@ async function main() {
     insuranceLossThresholdBps: 1000,  // 10%
     insuranceMaxCompensationBps: 5000,  // 50%
     insurancePoolEnabled: false,
+    maxReferralShareBps: 0,
   };
 
   console.log('📝 配置参数:');
@@ -81,7 +81,8 @@ async function main() {
       lpInsuranceAllocationBps: 2000,  // 20%
       insuranceLossThresholdBps: 1000,  // 10%
       insuranceMaxCompensationBps: 5000,  // 50%
-      insurancePoolEnabled: false  // 初期禁用
+      insurancePoolEnabled: false,  // 初期禁用
+      maxReferralShareBps: 0
     };
 
     const tx = await program.methods
@@ -109,6 +109,7 @@ async function initialize() {
     insuranceLossThresholdBps: 1000,
     insuranceMaxCompensationBps: 5000,
     insurancePoolEnabled: false,
+    maxReferralShareBps: 0,
   };
 
   console.log('📤 发送交易...\n');
@@ -128,6 +128,7 @@ async function testAllFunctions() {
           insurance_loss_threshold_bps: 1000,
           insurance_max_compensation_bps: 5000,
           insurance_pool_enabled: false,
+          max_referral_share_bps: 0,
         })
         .accounts({
           payer: keypair.publicKey,
@@ -19,6 +19,7 @@ import {
   getAccount,
 } from '@solana/spl-token';
 import * as fs from 'fs';
+import { createHash } from 'crypto';
 import * as path from 'path';
 
 // 配置
@@ -178,6 +179,12 @@ async function main() {
   );
   console.log('📊 Market PDA:', marketPda.toString());
 
+  // 派生 Market Metadata PDA（规则承诺与结算证据）
+  const [marketMetadataPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('market_metadata'), marketPda.toBuffer()],
+    PROGRAM_ID
+  );
+
   // 派生 Market USDC Vault
   const [marketUsdcVault] = PublicKey.findProgramAddressSync(
     [Buffer.from('market_usdc_vault'), marketId.toBuffer()],
@@ -192,6 +199,24 @@ async function main() {
     endingSlot: null,
     displayName: '测试市场：BTC 会在2024年底突破10万美元吗？',
     initialYesProb: 5000, // 50%
+    scalarBounds: null, // 二元市场
+    parentRequiredOutcome: null, // 非条件市场
+    priceFeed: null, // 非价格源市场
+    resolver: null, // 由全局管理员结算
+    resolutionGraceSlots: null, // 默认结算宽限期（结束后约 7 天）
+    rules: {
+      // 完整规则文本的 SHA-256（规则原文存放在链下）
+      rulesHash: Array.from(
+        createHash('sha256')
+          .update('若 2024-12-31 23:59 UTC 前 Coinbase BTC-USD 成交价达到 100,000 美元则 YES 胜出，否则 NO 胜出。')
+          .digest()
+      ),
+      resolutionSourceUri: 'https://www.coinbase.com/price/bitcoin',
+      category: 'crypto',
+      tags: ['btc', 'price'],
+    },
+    startTime: null, // 不限制开始时间（Unix 时间戳）
+    endTime: null, // 不限制结束时间（Unix 时间戳）
   };
 
   console.log('\n📝 市场参数:');
@@ -237,6 +262,7 @@ async function main() {
         yesToken: yesTokenMint.publicKey,
         noToken: noTokenMint.publicKey, // 需要 mut 权限
         market: marketPda,
+        marketMetadata: marketMetadataPda,
         yesTokenMetadataAccount: yesMetadata,
         noTokenMetadataAccount: noMetadata,
         globalYesTokenAccount: globalYesAta,
@@ -247,6 +273,8 @@ async function main() {
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         mplTokenMetadataProgram: METADATA_PROGRAM_ID,
         teamWallet: teamWallet,
+        parentMarket: null,
+        resolverCommittee: null,
       })
       .signers([yesTokenMint])
       .rpc();
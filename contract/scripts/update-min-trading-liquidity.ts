@@ -90,6 +90,7 @@ async function main() {
     insuranceLossThresholdBps: currentConfig.insuranceLossThresholdBps,
     insuranceMaxCompensationBps: currentConfig.insuranceMaxCompensationBps,
     insurancePoolEnabled: currentConfig.insurancePoolEnabled,
+    maxReferralShareBps: currentConfig.maxReferralShareBps ?? 0,
   };
 
   console.log('\n📝 新配置:');
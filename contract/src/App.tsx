@@ -387,8 +387,13 @@ export function ExampleUsage() {
 
     try {
       const tx = await client.createMarket({
+        displayName: 'Example market',
         yesSymbol: 'YES',
         yesUri: 'https://example.com/metadata.json',
+        noSymbol: 'NO',
+        noUri: 'https://example.com/metadata.json',
+        rulesText: 'Resolves YES if the example event happens before the end time.',
+        resolutionSourceUri: 'https://example.com/source',
         startSlot: undefined,
         endingSlot: undefined
       });
@@ -29,7 +29,11 @@ export const SEEDS = {
   GLOBAL: "global",
   MARKET: "market", 
   USERINFO: "userinfo",
-  METADATA: "metadata"
+  METADATA: "metadata",
+  ORACLE_CONFIG: "oracle_config",
+  RESOLUTION_PROPOSAL: "resolution_proposal",
+  MARKET_METADATA: "market_metadata",
+  WHITELIST: "wl-seed"
 } as const;
 
 // 代币类型枚举
@@ -78,8 +82,18 @@ export interface UserInfo {
 export interface CreateMarketParams {
   yesSymbol: string;
   yesUri: string;
+  noSymbol: string;
+  noUri: string;
   startSlot?: number;
   endingSlot?: number;
+  displayName: string;
+  initialYesProb?: number; // 2000-8000 基点，默认 5000
+  rulesText: string; // 结算规则原文（链上只记录 SHA-256）
+  resolutionSourceUri: string;
+  category?: string;
+  tags?: string[];
+  startTime?: number;
+  endTime?: number;
 }
 
 export interface SwapParams {
@@ -202,11 +216,12 @@ export class PredictionMarketClient {
     return tx;
   }
 
-  // 创建市场
+  // 创建市场（先铸造NO代币，再创建市场与YES代币）
   async createMarket(params: CreateMarketParams): Promise<string> {
     const yesTokenKeypair = Keypair.generate();
     const noTokenKeypair = Keypair.generate();
-    
+    await this.mintNoToken(noTokenKeypair, params.noSymbol, params.noUri);
+
     const globalConfigPDA = this.getGlobalConfigPDA();
     const globalVaultPDA = this.getGlobalVaultPDA();
     const marketPDA = this.getMarketPDA(yesTokenKeypair.publicKey, noTokenKeypair.publicKey);
@@ -217,35 +232,66 @@ export class PredictionMarketClient {
     const globalYesTokenAccountPDA = this.getGlobalTokenAccountPDA(yesTokenKeypair.publicKey);
     const globalNoTokenAccountPDA = this.getGlobalTokenAccountPDA(noTokenKeypair.publicKey);
 
-    // 获取团队钱包地址（需要从全局配置中获取）
+    // 团队钱包与抵押品 mint 从全局配置中获取
     const globalConfig = await (this.program.account as any).config.fetch(globalConfigPDA);
     const teamWallet = globalConfig.teamWallet;
+    const usdcMint: PublicKey = globalConfig.usdcMint;
+
+    // 白名单账户仅在存在时传入（可选账户）
+    const whitelistPDA = this.getPDA([Buffer.from(SEEDS.WHITELIST), this.wallet.publicKey.toBuffer()]);
+    const creatorWhitelist = (await this.connection.getAccountInfo(whitelistPDA)) ? whitelistPDA : null;
+
+    const rulesHash = Array.from(
+      new Uint8Array(await crypto.subtle.digest('SHA-256', new TextEncoder().encode(params.rulesText)))
+    );
 
     const tx = await (this.program as any).methods
       .createMarket({
         yesSymbol: params.yesSymbol,
         yesUri: params.yesUri,
-        startSlot: params.startSlot || null,
-        endingSlot: params.endingSlot || null
+        startSlot: params.startSlot ? new BN(params.startSlot) : null,
+        endingSlot: params.endingSlot ? new BN(params.endingSlot) : null,
+        displayName: params.displayName.slice(0, 64),
+        initialYesProb: params.initialYesProb ?? 5000,
+        scalarBounds: null, // 二元市场
+        parentRequiredOutcome: null, // 非条件市场
+        priceFeed: null, // 人工结算
+        resolver: null, // 全局管理员结算
+        resolutionGraceSlots: null, // 默认结算截止
+        rules: {
+          rulesHash,
+          resolutionSourceUri: params.resolutionSourceUri,
+          category: params.category ?? '',
+          tags: params.tags ?? []
+        },
+        startTime: params.startTime ? new BN(params.startTime) : null,
+        endTime: params.endTime ? new BN(params.endTime) : null
       })
       .accounts({
         globalConfig: globalConfigPDA,
         globalVault: globalVaultPDA,
         creator: this.wallet.publicKey,
+        creatorWhitelist,
         yesToken: yesTokenKeypair.publicKey,
         noToken: noTokenKeypair.publicKey,
+        usdcMint,
         market: marketPDA,
+        marketMetadata: this.getMarketMetadataPDA(marketPDA),
         yesTokenMetadataAccount: yesTokenMetadataPDA,
         noTokenMetadataAccount: noTokenMetadataPDA,
         globalYesTokenAccount: globalYesTokenAccountPDA,
+        globalNoTokenAccount: globalNoTokenAccountPDA,
         teamWallet: teamWallet,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        collateralTokenProgram: TOKEN_PROGRAM_ID,
+        outcomeTokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        mplTokenMetadataProgram: new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
+        mplTokenMetadataProgram: new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"),
+        parentMarket: null,
+        resolverCommittee: null
       })
-      .signers([yesTokenKeypair, noTokenKeypair])
+      .signers([yesTokenKeypair])
       .rpc();
 
     return tx;
@@ -325,7 +371,8 @@ export class PredictionMarketClient {
         userInfo: userInfoPDA,
         user: this.wallet.publicKey,
         systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        collateralTokenProgram: TOKEN_PROGRAM_ID,
+        outcomeTokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID
       })
       .rpc();
@@ -360,7 +407,8 @@ export class PredictionMarketClient {
         userInfo: userInfoPDA,
         user: this.wallet.publicKey,
         systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        collateralTokenProgram: TOKEN_PROGRAM_ID,
+        outcomeTokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID
       })
       .rpc();
@@ -395,7 +443,8 @@ export class PredictionMarketClient {
         userInfo: userInfoPDA,
         user: this.wallet.publicKey,
         systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        collateralTokenProgram: TOKEN_PROGRAM_ID,
+        outcomeTokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID
       })
       .rpc();
@@ -403,35 +452,46 @@ export class PredictionMarketClient {
     return tx;
   }
 
-  // 市场结算
-  async resolveMarket(
+  // 获取结算提案PDA
+  getResolutionProposalPDA(marketPDA: PublicKey): PublicKey {
+    return this.getPDA([Buffer.from(SEEDS.RESOLUTION_PROPOSAL), marketPDA.toBuffer()]);
+  }
+
+  // 获取市场元数据PDA
+  getMarketMetadataPDA(marketPDA: PublicKey): PublicKey {
+    return this.getPDA([Buffer.from(SEEDS.MARKET_METADATA), marketPDA.toBuffer()]);
+  }
+
+  // 管理员结算提案（两阶段结算第一步，挑战窗口结束后调用 finalizeResolution）
+  async proposeResolution(
     marketPDA: PublicKey,
-    yesTokenMint: PublicKey,
-    noTokenMint: PublicKey,
     yesAmount: number,
     noAmount: number,
     tokenType: TokenType,
-    isCompleted: boolean
+    evidenceHash: number[],
+    evidenceUri: string
   ): Promise<string> {
     const globalConfigPDA = this.getGlobalConfigPDA();
-    const globalVaultPDA = this.getGlobalVaultPDA();
-    const userInfoPDA = this.getUserInfoPDA(marketPDA);
+    const proposalPDA = this.getResolutionProposalPDA(marketPDA);
+    const config = await (this.program.account as any).config.fetch(globalConfigPDA);
+    const usdcMint = config.usdcMint as PublicKey;
 
     const tx = await (this.program as any).methods
-      .resolution(
+      .proposeAdminResolution(
         new BN(yesAmount),
         new BN(noAmount),
         tokenType,
-        isCompleted
+        evidenceHash,
+        evidenceUri
       )
       .accounts({
         globalConfig: globalConfigPDA,
+        oracleConfig: this.getPDA([Buffer.from(SEEDS.ORACLE_CONFIG)]),
         market: marketPDA,
-        globalVault: globalVaultPDA,
-        yesToken: yesTokenMint,
-        noToken: noTokenMint,
-        userInfo: userInfoPDA,
-        user: this.wallet.publicKey,
+        proposal: proposalPDA,
+        resolutionVotes: null,
+        usdcMint,
+        bondVault: await getAssociatedTokenAddress(usdcMint, proposalPDA, true),
         authority: this.wallet.publicKey,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -442,6 +502,47 @@ export class PredictionMarketClient {
     return tx;
   }
 
+  // 最终确认结算（挑战窗口结束且无争议，任何人可调用，保证金退还提案人）
+  async finalizeResolution(
+    marketPDA: PublicKey,
+    yesTokenMint: PublicKey,
+    noTokenMint: PublicKey
+  ): Promise<string> {
+    const globalConfigPDA = this.getGlobalConfigPDA();
+    const globalVaultPDA = this.getGlobalVaultPDA();
+    const proposalPDA = this.getResolutionProposalPDA(marketPDA);
+    const config = await (this.program.account as any).config.fetch(globalConfigPDA);
+    const usdcMint = config.usdcMint as PublicKey;
+    const proposal = await (this.program.account as any).resolutionProposal.fetch(proposalPDA);
+    const proposer = proposal.proposer as PublicKey;
+
+    const tx = await (this.program as any).methods
+      .finalizeResolution()
+      .accounts({
+        globalConfig: globalConfigPDA,
+        market: marketPDA,
+        proposal: proposalPDA,
+        proposer,
+        globalVault: globalVaultPDA,
+        yesToken: yesTokenMint,
+        noToken: noTokenMint,
+        globalYesAta: this.getGlobalTokenAccountPDA(yesTokenMint),
+        globalNoAta: this.getGlobalTokenAccountPDA(noTokenMint),
+        usdcMint,
+        bondVault: await getAssociatedTokenAddress(usdcMint, proposalPDA, true),
+        bondRecipientUsdcAta: await getAssociatedTokenAddress(usdcMint, proposer, true),
+        eventGroup: null,
+        parentMarket: null,
+        marketMetadata: this.getMarketMetadataPDA(marketPDA),
+        caller: this.wallet.publicKey,
+        collateralTokenProgram: TOKEN_PROGRAM_ID,
+        outcomeTokenProgram: TOKEN_PROGRAM_ID
+      })
+      .rpc();
+
+    return tx;
+  }
+
   // 查询市场信息
   async getMarketInfo(marketPDA: PublicKey): Promise<MarketInfo> {
     const marketAccount = await (this.program.account as any).market.fetch(marketPDA);
@@ -316,8 +316,11 @@ export function TradingInterface({ marketPDA }: TradingInterfaceProps) {
 export function CreateMarketForm() {
   const { createMarket, loading, error } = usePredictionMarket({});
   const [formData, setFormData] = useState({
+    displayName: '',
     yesSymbol: '',
     yesUri: '',
+    rulesText: '',
+    resolutionSourceUri: '',
     startSlot: '',
     endingSlot: ''
   });
@@ -327,16 +330,24 @@ export function CreateMarketForm() {
     
     try {
       await createMarket({
+        displayName: formData.displayName,
         yesSymbol: formData.yesSymbol,
         yesUri: formData.yesUri,
+        noSymbol: formData.yesSymbol.replace('YES', 'NO'),
+        noUri: formData.yesUri,
+        rulesText: formData.rulesText,
+        resolutionSourceUri: formData.resolutionSourceUri,
         startSlot: formData.startSlot ? parseInt(formData.startSlot) : undefined,
         endingSlot: formData.endingSlot ? parseInt(formData.endingSlot) : undefined
       });
       
       // 重置表单
       setFormData({
+        displayName: '',
         yesSymbol: '',
         yesUri: '',
+        rulesText: '',
+        resolutionSourceUri: '',
         startSlot: '',
         endingSlot: ''
       });
@@ -356,6 +367,18 @@ export function CreateMarketForm() {
       )}
       
       <form onSubmit={handleSubmit}>
+        <div className="form-group">
+          <label>市场名称:</label>
+          <input
+            type="text"
+            value={formData.displayName}
+            onChange={(e) => setFormData({ ...formData, displayName: e.target.value })}
+            placeholder="例如: BTC 年底是否突破 10 万美元"
+            maxLength={64}
+            required
+          />
+        </div>
+
         <div className="form-group">
           <label>YES代币符号:</label>
           <input
@@ -378,6 +401,28 @@ export function CreateMarketForm() {
           />
         </div>
 
+        <div className="form-group">
+          <label>结算规则:</label>
+          <textarea
+            value={formData.rulesText}
+            onChange={(e) => setFormData({ ...formData, rulesText: e.target.value })}
+            placeholder="结算条件与数据来源说明"
+            required
+          />
+        </div>
+
+        <div className="form-group">
+          <label>结算数据源URI:</label>
+          <input
+            type="url"
+            value={formData.resolutionSourceUri}
+            onChange={(e) => setFormData({ ...formData, resolutionSourceUri: e.target.value })}
+            placeholder="https://example.com/source"
+            maxLength={200}
+            required
+          />
+        </div>
+
         <div className="form-group">
           <label>开始槽位 (可选):</label>
           <input
@@ -55,8 +55,18 @@ interface UsePredictionMarketReturn {
 interface CreateMarketParams {
   yesSymbol: string;
   yesUri: string;
+  noSymbol: string;
+  noUri: string;
   startSlot?: number;
   endingSlot?: number;
+  displayName: string;
+  initialYesProb?: number;
+  rulesText: string;
+  resolutionSourceUri: string;
+  category?: string;
+  tags?: string[];
+  startTime?: number;
+  endTime?: number;
 }
 
 interface SwapParams {
@@ -70,7 +80,8 @@ interface ResolutionParams {
   yesAmount: number;
   noAmount: number;
   tokenType: TokenType;
-  isCompleted: boolean;
+  evidenceHash: number[];
+  evidenceUri: string;
 }
 
 interface ConfigParams {
@@ -293,16 +304,14 @@ export function usePredictionMarket({
       setLoading(true);
       setError(null);
       
-      const marketInfo = await client.getMarketInfo(marketPDA);
-      
-      const tx = await client.resolveMarket(
+      // 仅提交结算提案；挑战窗口结束后由 client.finalizeResolution 最终确认
+      const tx = await client.proposeResolution(
         marketPDA,
-        marketInfo.yesTokenMint,
-        marketInfo.noTokenMint,
         params.yesAmount,
         params.noAmount,
         params.tokenType,
-        params.isCompleted
+        params.evidenceHash,
+        params.evidenceUri
       );
       
       await refreshMarkets();
@@ -219,6 +219,10 @@ async function processPublish(message: MarketPublishMessage): Promise<void> {
       noSymbol,
       noUri,
       initialYesProb: MARKET_DEFAULTS.INITIAL_YES_PROB,
+      // Commit the full resolution rules on-chain (hash) so resolutions can be audited against them
+      rulesText: JSON.stringify(market.resolution ?? {}),
+      resolutionSourceUri: market.resolution?.criteria?.allowed_sources?.[0]?.url ?? yesUri,
+      category: market.category ?? '',
     });
 
     logger.info(
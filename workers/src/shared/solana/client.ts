@@ -9,6 +9,7 @@ import anchor from '@coral-xyz/anchor';
 const { Program, AnchorProvider, Wallet, BN } = anchor;
 import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from '@solana/spl-token';
 import bs58 from 'bs58';
+import { createHash } from 'crypto';
 import { env } from '../env.js';
 import { createWorkerLogger } from '../logger.js';
 import idl from './prediction_market.json' with { type: 'json' };
@@ -22,6 +23,7 @@ const SEEDS = {
   MARKET: 'market',
   WHITELIST: 'wl-seed',
   MARKET_USDC_VAULT: 'market_usdc_vault',
+  MARKET_METADATA: 'market_metadata',
 } as const;
 
 // Metaplex Token Metadata Program ID
@@ -82,6 +84,12 @@ export interface CreateMarketParams {
   initialYesProb?: number; // 0-10000 basis points, default 5000 (50%)
   startSlot?: number;
   endingSlot?: number;
+  startTime?: number; // Unix seconds
+  endTime?: number; // Unix seconds
+  rulesText: string; // Full resolution rules; its SHA-256 is committed on-chain
+  resolutionSourceUri: string; // Max 200 bytes
+  category?: string; // Max 32 bytes
+  tags?: string[]; // Up to 5 tags, max 24 bytes each
 }
 
 export class SolanaClient {
@@ -144,6 +152,13 @@ export class SolanaClient {
     );
   }
 
+  private getMarketMetadataPDA(market: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.MARKET_METADATA), market.toBuffer()],
+      new PublicKey(env.PROGRAM_ID)
+    );
+  }
+
   /**
    * Get Creator Whitelist PDA
    */
@@ -168,6 +183,20 @@ export class SolanaClient {
     );
   }
 
+  /**
+   * Get the collateral (USDC) mint and its token program from the global config
+   */
+  private async getCollateralMint(): Promise<{ usdcMint: PublicKey; collateralTokenProgram: PublicKey }> {
+    const [globalConfig] = this.getGlobalConfigPDA();
+    const config = await (this.program.account as any).config.fetch(globalConfig);
+    const usdcMint: PublicKey = config.usdcMint;
+    const mintInfo = await this.connection.getAccountInfo(usdcMint);
+    if (!mintInfo) {
+      throw new Error(`Collateral mint ${usdcMint.toBase58()} not found`);
+    }
+    return { usdcMint, collateralTokenProgram: mintInfo.owner };
+  }
+
   /**
    * Check if the publisher wallet is whitelisted
    */
@@ -250,7 +279,9 @@ export class SolanaClient {
     // Step 2: Create the market with YES token
     logger.info({ yesToken: yesTokenKeypair.publicKey.toBase58() }, 'Creating market');
 
+    const { usdcMint, collateralTokenProgram } = await this.getCollateralMint();
     const [market] = this.getMarketPDA(yesTokenKeypair.publicKey, noTokenKeypair.publicKey);
+    const [marketMetadata] = this.getMarketMetadataPDA(market);
     const [yesMetadata] = this.getMetadataPDA(yesTokenKeypair.publicKey);
     const [creatorWhitelist] = this.getWhitelistPDA(this.keypair.publicKey);
 
@@ -273,6 +304,19 @@ export class SolanaClient {
       endingSlot: params.endingSlot ? new BN(params.endingSlot) : null,
       displayName: params.displayName.substring(0, 64), // Max 64 chars
       initialYesProb: params.initialYesProb ?? 5000, // Default 50%
+      scalarBounds: null, // Binary market
+      parentRequiredOutcome: null, // Not a conditional market
+      priceFeed: null, // Manually resolved
+      resolver: null, // Global authority resolves
+      resolutionGraceSlots: null, // Default resolution deadline (~7 days after end)
+      rules: {
+        rulesHash: Array.from(createHash('sha256').update(params.rulesText).digest()),
+        resolutionSourceUri: params.resolutionSourceUri.substring(0, 200),
+        category: (params.category ?? '').substring(0, 32),
+        tags: (params.tags ?? []).slice(0, 5).map((tag) => tag.substring(0, 24)),
+      },
+      startTime: params.startTime ? new BN(params.startTime) : null,
+      endTime: params.endTime ? new BN(params.endTime) : null,
     };
 
     try {
@@ -285,17 +329,22 @@ export class SolanaClient {
           creatorWhitelist, // Will be ignored if whitelist is disabled
           yesToken: yesTokenKeypair.publicKey,
           noToken: noTokenKeypair.publicKey,
+          usdcMint,
           market,
+          marketMetadata,
           yesTokenMetadataAccount: yesMetadata,
           noTokenMetadataAccount: noMetadata,
           globalYesTokenAccount: globalYesAta,
           globalNoTokenAccount: globalNoAta,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          collateralTokenProgram,
+          outcomeTokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           mplTokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID,
           teamWallet,
+          parentMarket: null,
+          resolverCommittee: null,
         })
         .signers([yesTokenKeypair])
         .rpc();